@@ -7,14 +7,20 @@ use stakenet_sdk::{
         submit_stats::SubmitStats,
     },
     utils::{
-        instructions::{compute_coinbase_targets, compute_directed_stake_meta},
+        accounts::get_directed_stake_meta,
+        instructions::{
+            compute_coinbase_targets, compute_directed_stake_ticket_cranks,
+            compute_scheduled_directed_stake_preference_applications,
+            compute_underfunded_directed_stake_ticket_closures,
+        },
         transactions::{package_instructions, submit_packaged_transactions},
     },
 };
 
 use crate::state::keeper_config::KeeperConfig;
 
-/// Copy directed stake targets to [`DirectedStakeMeta`] account
+/// Apply due scheduled preferences, close underfunded permissionless tickets, then crank directed stake tickets
+/// once targets are migrated to tickets, or copy the Coinbase target to [`DirectedStakeMeta`] account until then
 pub async fn crank_copy_directed_stake_targets(
     keeper_config: &KeeperConfig,
     keypair: Arc<Keypair>,
@@ -23,7 +29,6 @@ pub async fn crank_copy_directed_stake_targets(
     let KeeperConfig {
         client,
         steward_program_id: program_id,
-        token_mint,
         priority_fee_in_microlamports: priority_fee,
         kobe_client,
         coinbase_vote_pubkey,
@@ -31,60 +36,127 @@ pub async fn crank_copy_directed_stake_targets(
     } = keeper_config;
     let mut stats = SubmitStats::default();
 
-    let normal_ixs = compute_directed_stake_meta(
+    // Due scheduled preferences are applied first so tickets are cranked with them
+    let application_ixs = compute_scheduled_directed_stake_preference_applications(
         client.clone(),
-        token_mint,
-        &all_steward_accounts.stake_pool_address,
         &all_steward_accounts.config_address,
-        &keypair.pubkey(),
         program_id,
     )
     .await
     .map_err(|e| JitoTransactionError::Custom(e.to_string()))?;
 
     log::info!(
-        "Copying directed stake targets kind=normal instructions={}",
-        normal_ixs.len()
+        "Applying scheduled directed stake preferences instructions={}",
+        application_ixs.len()
     );
 
-    let normal_txs_to_run =
-        package_instructions(&normal_ixs, 8, Some(*priority_fee), Some(1_400_000), None);
-    let normal_stats =
-        submit_packaged_transactions(client, normal_txs_to_run, &keypair, Some(50), None).await?;
-    stats.combine(&normal_stats);
+    let application_txs_to_run = package_instructions(
+        &application_ixs,
+        4,
+        Some(*priority_fee),
+        Some(1_400_000),
+        None,
+    );
+    let application_stats =
+        submit_packaged_transactions(client, application_txs_to_run, &keypair, Some(50), None)
+            .await?;
+    stats.combine(&application_stats);
 
-    let coinbase_delegation_ixs = compute_coinbase_targets(
+    // Permissionless tickets whose holders fell below the minimum balance are closed first
+    let closure_ixs = compute_underfunded_directed_stake_ticket_closures(
         client.clone(),
-        kobe_client,
         &all_steward_accounts.config_address,
         &keypair.pubkey(),
         program_id,
-        coinbase_vote_pubkey,
     )
     .await
     .map_err(|e| JitoTransactionError::Custom(e.to_string()))?;
 
     log::info!(
-        "Copying directed stake targets kind=coinbase_delegation instructions={}",
-        coinbase_delegation_ixs.len()
+        "Closing underfunded directed stake tickets instructions={}",
+        closure_ixs.len()
     );
 
-    let coinbase_delegation_txs_to_run = package_instructions(
-        &coinbase_delegation_ixs,
-        8,
-        Some(*priority_fee),
-        Some(1_400_000),
-        None,
-    );
-    let coinbase_delegation_stats = submit_packaged_transactions(
-        client,
-        coinbase_delegation_txs_to_run,
-        &keypair,
-        Some(50),
-        None,
+    let closure_txs_to_run =
+        package_instructions(&closure_ixs, 4, Some(*priority_fee), Some(1_400_000), None);
+    let closure_stats =
+        submit_packaged_transactions(client, closure_txs_to_run, &keypair, Some(50), None).await?;
+    stats.combine(&closure_stats);
+
+    // Ticket cranks only succeed after `migrate_directed_targets_to_tickets`, which ends uploads
+    let directed_stake_meta = get_directed_stake_meta(
+        client.clone(),
+        &all_steward_accounts.config_address,
+        program_id,
     )
     .await?;
-    stats.combine(&coinbase_delegation_stats);
+
+    if directed_stake_meta.ticket_targets_only() {
+        // Ticket cranks fail while balance sampling is disabled
+        if all_steward_accounts
+            .config_account
+            .parameters
+            .directed_stake_sample_interval_slots
+            == 0
+        {
+            log::warn!("Skipping directed stake ticket cranks, balance sampling is disabled");
+            return Ok(stats);
+        }
+
+        let normal_ixs = compute_directed_stake_ticket_cranks(
+            client.clone(),
+            &all_steward_accounts.config_address,
+            &keypair.pubkey(),
+            program_id,
+        )
+        .await
+        .map_err(|e| JitoTransactionError::Custom(e.to_string()))?;
+
+        log::info!(
+            "Cranking directed stake tickets kind=normal instructions={}",
+            normal_ixs.len()
+        );
+
+        let normal_txs_to_run =
+            package_instructions(&normal_ixs, 4, Some(*priority_fee), Some(1_400_000), None);
+        let normal_stats =
+            submit_packaged_transactions(client, normal_txs_to_run, &keypair, Some(50), None)
+                .await?;
+        stats.combine(&normal_stats);
+    } else {
+        let coinbase_delegation_ixs = compute_coinbase_targets(
+            client.clone(),
+            kobe_client,
+            &all_steward_accounts.config_address,
+            &keypair.pubkey(),
+            program_id,
+            coinbase_vote_pubkey,
+        )
+        .await
+        .map_err(|e| JitoTransactionError::Custom(e.to_string()))?;
+
+        log::info!(
+            "Copying directed stake targets kind=coinbase_delegation instructions={}",
+            coinbase_delegation_ixs.len()
+        );
+
+        let coinbase_delegation_txs_to_run = package_instructions(
+            &coinbase_delegation_ixs,
+            8,
+            Some(*priority_fee),
+            Some(1_400_000),
+            None,
+        );
+        let coinbase_delegation_stats = submit_packaged_transactions(
+            client,
+            coinbase_delegation_txs_to_run,
+            &keypair,
+            Some(50),
+            None,
+        )
+        .await?;
+        stats.combine(&coinbase_delegation_stats);
+    }
 
     Ok(stats)
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     ops::{Mul, Sub},
     sync::Arc,
 };
@@ -6,7 +7,7 @@ use std::{
 use anchor_lang::{AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas};
 use jito_steward::{
     stake_pool_utils::{StakePool, ValidatorList},
-    StewardStateEnum,
+    StewardStateEnum, COMPUTE_SCORE,
 };
 use log::{error, info};
 use solana_client::nonblocking::rpc_client::RpcClient;
@@ -20,12 +21,13 @@ use solana_sdk::{
     stake,
     stake::{instruction::deactivate_delinquent_stake, state::StakeStateV2},
     system_program,
+    vote::program::id as get_vote_program_id,
 };
 use solana_vote_interface::state::VoteStateV4;
 use spl_associated_token_account::get_associated_token_address;
 #[allow(deprecated)]
 use spl_stake_pool::{
-    find_transient_stake_program_address, find_withdraw_authority_program_address,
+    find_withdraw_authority_program_address,
     instruction::deposit_sol,
     instruction::{
         cleanup_removed_validator_entries, update_stake_pool_balance, update_validator_list_balance,
@@ -41,18 +43,30 @@ use stakenet_sdk::{
     },
     utils::{
         accounts::{
-            get_cluster_history_address, get_directed_stake_meta, get_directed_stake_meta_address,
-            get_stake_address, get_steward_state_account, get_transient_stake_address,
-            get_validator_history_address,
+            get_cluster_history_address, get_commission_pledge_address, get_cycle_report_address,
+            get_directed_stake_meta, get_directed_stake_meta_address,
+            get_instant_unstake_fractions_address, get_rebalance_journal_address,
+            get_score_warnings_address, get_stake_address, get_steward_state_account,
+            get_transient_stake_address, get_validator_exit_address, get_validator_history_address,
         },
         helpers::{
             check_stake_accounts, get_unprogressed_validators, DirectedRebalanceProgressionInfo,
         },
-        transactions::{configure_instruction, package_instructions, submit_packaged_transactions},
+        instructions::{
+            initialize_instant_unstake_fractions, initialize_rebalance_journal,
+            initialize_score_warnings, rebalance_many, update_preferred_validators,
+        },
+        transactions::{
+            configure_instruction, get_multiple_accounts_batched, package_instructions,
+            submit_packaged_transactions,
+        },
     },
 };
 use validator_history::ValidatorHistory;
 
+/// Validators rebalanced by each `rebalance_many` transaction, limited by the transaction size
+const REBALANCE_MANY_BATCH_SIZE: usize = 3;
+
 pub fn _get_update_stake_pool_ixs(
     program_id: &Pubkey,
     stake_pool: &StakePool,
@@ -513,6 +527,11 @@ async fn _handle_adding_validators(
                     clock: solana_sdk::sysvar::clock::id(),
                     stake_history: solana_sdk::sysvar::stake_history::id(),
                     stake_config: stake::config::ID,
+                    validator_exit: get_validator_exit_address(
+                        &all_steward_accounts.config_address,
+                        vote_account,
+                        program_id,
+                    ),
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::AutoAddValidatorToPool {}.data(),
@@ -534,6 +553,104 @@ async fn _handle_adding_validators(
     Ok(stats)
 }
 
+/// Scores validators added to the pool after ComputeScores finished, so they can be delegated
+/// to in the current cycle. These must be scored in validator list order, so instructions are
+/// submitted one at a time.
+async fn _handle_score_added_validators(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    priority_fee: Option<u64>,
+) -> Result<SubmitStats, JitoTransactionError> {
+    let state = &all_steward_accounts.state_account.state;
+    let mut stats = SubmitStats::default();
+
+    if state.validators_added == 0
+        || !state.has_flag(COMPUTE_SCORE)
+        || matches!(
+            state.state_tag,
+            StewardStateEnum::ComputeScores | StewardStateEnum::RebalanceDirected
+        )
+    {
+        return Ok(stats);
+    }
+
+    let validator_history_program_id = validator_history::id();
+    let cluster_history = get_cluster_history_address(&validator_history_program_id);
+
+    let cycle_report = _get_cycle_report(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+        false,
+    )
+    .await?;
+
+    let score_warnings = _get_score_warnings(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+    )
+    .await?;
+
+    let first_added_index = state.num_pool_validators as usize;
+    let last_added_index = first_added_index + state.validators_added as usize;
+
+    for validator_index in first_added_index..last_added_index {
+        let Some(validator) = all_steward_accounts
+            .validator_list_account
+            .validators
+            .get(validator_index)
+        else {
+            break;
+        };
+
+        let ix = Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::ComputeScoreAddedValidator {
+                config: all_steward_accounts.config_address,
+                state_account: all_steward_accounts.state_address,
+                validator_history: get_validator_history_address(
+                    &validator.vote_account_address,
+                    &validator_history_program_id,
+                ),
+                validator_list: all_steward_accounts.validator_list_address,
+                cluster_history,
+                commission_pledge: get_commission_pledge_address(
+                    &all_steward_accounts.config_address,
+                    &validator.vote_account_address,
+                    program_id,
+                ),
+                cycle_report,
+                score_warnings,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::ComputeScoreAddedValidator {
+                validator_list_index: validator_index as u64,
+            }
+            .data(),
+        };
+
+        let configured_ix = configure_instruction(&[ix], priority_fee, Some(1_400_000), None);
+
+        let ix_stats =
+            submit_packaged_transactions(client, vec![configured_ix], payer, Some(50), None)
+                .await?;
+        stats.combine(&ix_stats);
+
+        if ix_stats.errors > 0 {
+            break;
+        }
+    }
+
+    Ok(stats)
+}
+
 async fn _handle_delinquent_validators(
     payer: &Arc<Keypair>,
     client: &Arc<RpcClient>,
@@ -545,6 +662,32 @@ async fn _handle_delinquent_validators(
 ) -> Result<SubmitStats, JitoTransactionError> {
     let checks = check_stake_accounts(all_steward_validator_accounts, epoch);
 
+    let validators_to_remove = &all_steward_accounts
+        .state_account
+        .state
+        .validators_to_remove;
+
+    let is_active = |validator: &ValidatorStakeInfo| {
+        matches!(
+            StakeStatus::try_from(validator.status),
+            Ok(StakeStatus::Active)
+        )
+    };
+
+    // Validators that requested an exit are marked for removal while still active in the pool
+    let exiting_vote_accounts = all_steward_accounts
+        .validator_list_account
+        .validators
+        .iter()
+        .enumerate()
+        .filter_map(|(index, validator)| {
+            if validators_to_remove.get(index).unwrap_or(false) && is_active(validator) {
+                Some(validator.vote_account_address)
+            } else {
+                None
+            }
+        });
+
     let bad_vote_accounts = checks
         .iter()
         .filter_map(|(vote_account, check)| {
@@ -554,7 +697,8 @@ async fn _handle_delinquent_validators(
                 None
             }
         })
-        .collect::<Vec<Pubkey>>();
+        .chain(exiting_vote_accounts)
+        .collect::<HashSet<Pubkey>>();
 
     let ixs_to_run = bad_vote_accounts
         .iter()
@@ -579,12 +723,12 @@ async fn _handle_delinquent_validators(
                 validator_index,
             )?;
 
-            if all_steward_accounts
-                .state_account
-                .state
-                .validators_to_remove
+            if validators_to_remove
                 .get(validator_index)
                 .expect("Could not find validator index in validators_to_remove")
+                && !is_active(
+                    &all_steward_accounts.validator_list_account.validators[validator_index],
+                )
             {
                 return None;
             }
@@ -720,6 +864,208 @@ async fn _handle_epoch_maintenance(
     Ok(stats)
 }
 
+/// Returns the [`jito_steward::CycleReport`] address for the cycle being cranked if the report exists.
+/// When `create_if_missing` is set or cycle reports are required, the report is initialized first so
+/// that every cycle gets one.
+async fn _get_cycle_report(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    priority_fee: Option<u64>,
+    create_if_missing: bool,
+) -> Result<Option<Pubkey>, JitoTransactionError> {
+    let state = &all_steward_accounts.state_account.state;
+    let current_epoch = client.get_epoch_info().await?.epoch;
+
+    // A new cycle starts with the next ComputeScore instruction once next_cycle_epoch is reached
+    let cycle_start_epoch = if current_epoch >= state.next_cycle_epoch {
+        current_epoch
+    } else {
+        client
+            .get_epoch_schedule()
+            .await?
+            .get_epoch(state.start_computing_scores_slot)
+    };
+
+    let cycle_report = get_cycle_report_address(
+        &all_steward_accounts.config_address,
+        cycle_start_epoch,
+        program_id,
+    );
+
+    if client.get_account(&cycle_report).await.is_ok() {
+        return Ok(Some(cycle_report));
+    }
+
+    if !create_if_missing
+        && !all_steward_accounts
+            .config_account
+            .parameters
+            .cycle_reports_enabled()
+    {
+        return Ok(None);
+    }
+
+    let ix = Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializeCycleReport {
+            config: all_steward_accounts.config_address,
+            state_account: all_steward_accounts.state_address,
+            cycle_report,
+            system_program: system_program::id(),
+            payer: payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeCycleReport { cycle_start_epoch }.data(),
+    };
+
+    let configured_ix = configure_instruction(&[ix], priority_fee, None, None);
+
+    let stats =
+        submit_packaged_transactions(client, vec![configured_ix], payer, Some(50), None).await?;
+
+    info!("Initialized cycle report cycle_start_epoch={cycle_start_epoch}");
+
+    Ok((stats.successes > 0).then_some(cycle_report))
+}
+
+/// Returns the InstantUnstakeFractions account when graded instant unstake is enabled, creating it if missing
+async fn _get_instant_unstake_fractions(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    priority_fee: Option<u64>,
+) -> Result<Option<Pubkey>, JitoTransactionError> {
+    if !all_steward_accounts
+        .config_account
+        .parameters
+        .graded_instant_unstake_enabled()
+    {
+        return Ok(None);
+    }
+
+    let instant_unstake_fractions =
+        get_instant_unstake_fractions_address(&all_steward_accounts.config_address, program_id);
+
+    if client.get_account(&instant_unstake_fractions).await.is_ok() {
+        return Ok(Some(instant_unstake_fractions));
+    }
+
+    let ix = initialize_instant_unstake_fractions(
+        program_id,
+        &all_steward_accounts.config_address,
+        &payer.pubkey(),
+    );
+
+    let configured_ix = configure_instruction(&[ix], priority_fee, None, None);
+
+    let stats =
+        submit_packaged_transactions(client, vec![configured_ix], payer, Some(50), None).await?;
+
+    info!("Initialized instant unstake fractions");
+
+    Ok((stats.successes > 0).then_some(instant_unstake_fractions))
+}
+
+/// Returns the ScoreWarnings account when score warnings are enabled, creating it if missing
+async fn _get_score_warnings(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    priority_fee: Option<u64>,
+) -> Result<Option<Pubkey>, JitoTransactionError> {
+    if !all_steward_accounts
+        .config_account
+        .parameters
+        .score_warnings_enabled()
+    {
+        return Ok(None);
+    }
+
+    let score_warnings =
+        get_score_warnings_address(&all_steward_accounts.config_address, program_id);
+
+    if client.get_account(&score_warnings).await.is_ok() {
+        return Ok(Some(score_warnings));
+    }
+
+    let ixs = initialize_score_warnings(
+        program_id,
+        &all_steward_accounts.config_address,
+        &payer.pubkey(),
+    );
+
+    let configured_ix = configure_instruction(&ixs, priority_fee, None, None);
+
+    let stats =
+        submit_packaged_transactions(client, vec![configured_ix], payer, Some(50), None).await?;
+
+    info!("Initialized score warnings");
+
+    Ok((stats.successes > 0).then_some(score_warnings))
+}
+
+/// Creates the RebalanceJournal of each of `vote_accounts` that does not have one yet, since
+/// rebalancing a validator fails without it
+async fn _initialize_rebalance_journals(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    vote_accounts: &[Pubkey],
+    priority_fee: Option<u64>,
+) -> Result<SubmitStats, JitoTransactionError> {
+    let journal_addresses: Vec<Pubkey> = vote_accounts
+        .iter()
+        .map(|vote_account| {
+            get_rebalance_journal_address(
+                &all_steward_accounts.config_address,
+                vote_account,
+                program_id,
+            )
+        })
+        .collect();
+    let journal_accounts = get_multiple_accounts_batched(&journal_addresses, client).await?;
+    let vote_account_accounts = get_multiple_accounts_batched(vote_accounts, client).await?;
+
+    // Journals can't be created for closed vote accounts, which rebalance without one
+    let ixs: Vec<Instruction> = vote_accounts
+        .iter()
+        .zip(journal_accounts)
+        .zip(vote_account_accounts)
+        .filter(|((_, journal_account), vote_account_account)| {
+            journal_account.is_none()
+                && vote_account_account
+                    .as_ref()
+                    .is_some_and(|account| account.owner == get_vote_program_id())
+        })
+        .map(|((vote_account, _), _)| {
+            initialize_rebalance_journal(
+                program_id,
+                &all_steward_accounts.config_address,
+                vote_account,
+                &payer.pubkey(),
+            )
+        })
+        .collect();
+    if ixs.is_empty() {
+        return Ok(SubmitStats::default());
+    }
+
+    let txs_to_run = package_instructions(&ixs, 10, priority_fee, None, None);
+
+    info!(
+        "Submitting transactions step=initialize_rebalance_journals instructions={} transactions={}",
+        ixs.len(),
+        txs_to_run.len()
+    );
+
+    submit_packaged_transactions(client, txs_to_run, payer, Some(50), None).await
+}
+
 async fn _handle_compute_score(
     payer: &Arc<Keypair>,
     client: &Arc<RpcClient>,
@@ -733,6 +1079,25 @@ async fn _handle_compute_score(
     let validators_to_run =
         get_unprogressed_validators(all_steward_accounts, &validator_history_program_id);
 
+    let cycle_report = _get_cycle_report(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+        true,
+    )
+    .await?;
+
+    let score_warnings = _get_score_warnings(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+    )
+    .await?;
+
     let ixs_to_run = validators_to_run
         .iter()
         .map(|validator_info| Instruction {
@@ -743,6 +1108,13 @@ async fn _handle_compute_score(
                 validator_history: validator_info.history_account,
                 validator_list: all_steward_accounts.validator_list_address,
                 cluster_history,
+                commission_pledge: get_commission_pledge_address(
+                    &all_steward_accounts.config_address,
+                    &validator_info.vote_account,
+                    program_id,
+                ),
+                cycle_report,
+                score_warnings,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::ComputeScore {
@@ -772,12 +1144,23 @@ async fn _handle_compute_delegations(
     all_steward_accounts: &AllStewardAccounts,
     priority_fee: Option<u64>,
 ) -> Result<SubmitStats, JitoTransactionError> {
+    let cycle_report = _get_cycle_report(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+        false,
+    )
+    .await?;
+
     let ix = Instruction {
         program_id: *program_id,
         accounts: jito_steward::accounts::ComputeDelegations {
             config: all_steward_accounts.config_address,
             state_account: all_steward_accounts.state_address,
             validator_list: all_steward_accounts.validator_list_address,
+            cycle_report,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::ComputeDelegations {}.data(),
@@ -817,6 +1200,28 @@ async fn _handle_idle(
     Ok(stats)
 }
 
+async fn _handle_update_preferred_validators(
+    payer: &Arc<Keypair>,
+    client: &Arc<RpcClient>,
+    program_id: &Pubkey,
+    all_steward_accounts: &AllStewardAccounts,
+    priority_fee: Option<u64>,
+) -> Result<SubmitStats, JitoTransactionError> {
+    let ix = update_preferred_validators(
+        program_id,
+        &all_steward_accounts.config_address,
+        &all_steward_accounts.stake_pool_address,
+        &all_steward_accounts.validator_list_address,
+    );
+
+    let configured_ix = configure_instruction(&[ix], priority_fee, None, None);
+
+    let stats =
+        submit_packaged_transactions(client, vec![configured_ix], payer, Some(50), None).await?;
+
+    Ok(stats)
+}
+
 async fn _handle_compute_instant_unstake(
     payer: &Arc<Keypair>,
     client: &Arc<RpcClient>,
@@ -830,6 +1235,34 @@ async fn _handle_compute_instant_unstake(
     let validators_to_run =
         get_unprogressed_validators(all_steward_accounts, &validator_history_program_id);
 
+    let cycle_report = _get_cycle_report(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+        false,
+    )
+    .await?;
+
+    let instant_unstake_fractions = _get_instant_unstake_fractions(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+    )
+    .await?;
+
+    let score_warnings = _get_score_warnings(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+    )
+    .await?;
+
     let ixs_to_run = validators_to_run
         .iter()
         .map(|validator_info| Instruction {
@@ -840,6 +1273,14 @@ async fn _handle_compute_instant_unstake(
                 validator_history: validator_info.history_account,
                 validator_list: all_steward_accounts.validator_list_address,
                 cluster_history,
+                commission_pledge: get_commission_pledge_address(
+                    &all_steward_accounts.config_address,
+                    &validator_info.vote_account,
+                    program_id,
+                ),
+                cycle_report,
+                instant_unstake_fractions,
+                score_warnings,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::ComputeInstantUnstake {
@@ -866,7 +1307,8 @@ async fn _handle_compute_instant_unstake(
 ///
 /// This function is responsible for redistributing stake across validators in a stake pool
 /// according to the steward's rebalancing algorithm. It identifies validators that need
-/// rebalancing, constructs the necessary instructions, and submits them as transactions.
+/// rebalancing, and submits one `rebalance_many` instruction per `REBALANCE_MANY_BATCH_SIZE`
+/// validators.
 ///
 /// # Note
 ///
@@ -895,101 +1337,97 @@ async fn _handle_rebalance(
         .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
         .await?;
 
-    let directed_stake_meta =
-        get_directed_stake_meta_address(&all_steward_accounts.config_address, program_id);
+    let cycle_report = _get_cycle_report(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+        false,
+    )
+    .await?;
+
+    let instant_unstake_fractions = _get_instant_unstake_fractions(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        priority_fee,
+    )
+    .await?;
+
+    let mut stats = _initialize_rebalance_journals(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        &validators_to_run
+            .iter()
+            .map(|validator_info| validator_info.vote_account)
+            .collect::<Vec<Pubkey>>(),
+        priority_fee,
+    )
+    .await?;
 
     let needs_deposit = reserve_stake_acc
         .lamports
         .lt(&stake_rent.mul(validators_to_run.len() as u64));
 
-    let deposit_sol_ix = if needs_deposit {
-        let acc_token_address = get_associated_token_address(
-            &payer.pubkey(),
-            &all_steward_accounts.stake_pool_account.pool_mint,
-        );
-
-        Some(deposit_sol(
-            &spl_stake_pool::id(),
-            &all_steward_accounts.stake_pool_address,
-            &all_steward_accounts.stake_pool_withdraw_authority,
-            &all_steward_accounts.stake_pool_account.reserve_stake,
-            &payer.pubkey(),
-            &acc_token_address,
-            &all_steward_accounts.stake_pool_account.manager_fee_account,
-            &acc_token_address,
-            &all_steward_accounts.stake_pool_account.pool_mint,
-            &spl_token::id(),
-            stake_rent,
-        ))
+    // The deposit's accounts take up room in the transaction otherwise used by another validator
+    let batch_size = if needs_deposit {
+        REBALANCE_MANY_BATCH_SIZE - 1
     } else {
-        None
+        REBALANCE_MANY_BATCH_SIZE
     };
 
-    let ixs_to_run: Vec<Instruction> = validators_to_run
-        .iter()
-        .flat_map(|validator_info| {
-            let validator_index = validator_info.index;
-            let vote_account = &validator_info.vote_account;
-            let history_account = validator_info.history_account;
+    let txs_to_run: Vec<Vec<Instruction>> = validators_to_run
+        .chunks(batch_size)
+        .map(|validators| {
+            let deposit_sol_ix = needs_deposit.then(|| {
+                let acc_token_address = get_associated_token_address(
+                    &payer.pubkey(),
+                    &all_steward_accounts.stake_pool_account.pool_mint,
+                );
 
-            let stake_address =
-                get_stake_address(vote_account, &all_steward_accounts.stake_pool_address);
+                deposit_sol(
+                    &spl_stake_pool::id(),
+                    &all_steward_accounts.stake_pool_address,
+                    &all_steward_accounts.stake_pool_withdraw_authority,
+                    &all_steward_accounts.stake_pool_account.reserve_stake,
+                    &payer.pubkey(),
+                    &acc_token_address,
+                    &all_steward_accounts.stake_pool_account.manager_fee_account,
+                    &acc_token_address,
+                    &all_steward_accounts.stake_pool_account.pool_mint,
+                    &spl_token::id(),
+                    stake_rent.mul(validators.len() as u64),
+                )
+            });
 
-            let (transient_stake_address, _) = find_transient_stake_program_address(
-                &spl_stake_pool::id(),
-                vote_account,
-                &all_steward_accounts.stake_pool_address,
-                all_steward_accounts.validator_list_account.validators[validator_index]
-                    .transient_seed_suffix
-                    .into(),
+            let rebalance_many_ix = rebalance_many(
+                program_id,
+                all_steward_accounts,
+                validators,
+                cycle_report,
+                instant_unstake_fractions,
             );
 
-            let rebalance_ix = Instruction {
-                program_id: *program_id,
-                accounts: jito_steward::accounts::Rebalance {
-                    config: all_steward_accounts.config_address,
-                    state_account: all_steward_accounts.state_address,
-                    validator_history: history_account,
-                    stake_pool_program: spl_stake_pool::id(),
-                    stake_pool: all_steward_accounts.stake_pool_address,
-                    withdraw_authority: all_steward_accounts.stake_pool_withdraw_authority,
-                    validator_list: all_steward_accounts.validator_list_address,
-                    reserve_stake: all_steward_accounts.stake_pool_account.reserve_stake,
-                    stake_account: stake_address,
-                    transient_stake_account: transient_stake_address,
-                    vote_account: *vote_account,
-                    system_program: system_program::id(),
-                    stake_program: stake::program::id(),
-                    rent: solana_sdk::sysvar::rent::id(),
-                    clock: solana_sdk::sysvar::clock::id(),
-                    stake_history: solana_sdk::sysvar::stake_history::id(),
-                    stake_config: stake::config::ID,
-                    directed_stake_meta,
-                }
-                .to_account_metas(None),
-                data: jito_steward::instruction::Rebalance {
-                    validator_list_index: validator_index as u64,
-                }
-                .data(),
-            };
+            let ixs = deposit_sol_ix
+                .into_iter()
+                .chain(std::iter::once(rebalance_many_ix))
+                .collect::<Vec<Instruction>>();
 
-            deposit_sol_ix
-                .iter()
-                .cloned()
-                .chain(std::iter::once(rebalance_ix))
-                .collect::<Vec<Instruction>>()
+            configure_instruction(&ixs, priority_fee, Some(1_400_000), None)
         })
         .collect();
 
-    let txs_to_run = package_instructions(&ixs_to_run, 2, priority_fee, Some(1_400_000), None);
-
     info!(
-        "Submitting transactions step=rebalance instructions={} transactions={}",
-        ixs_to_run.len(),
+        "Submitting transactions step=rebalance validators={} transactions={}",
+        validators_to_run.len(),
         txs_to_run.len()
     );
 
-    let stats = submit_packaged_transactions(client, txs_to_run, payer, Some(30), None).await?;
+    stats.combine(&submit_packaged_transactions(client, txs_to_run, payer, Some(30), None).await?);
 
     Ok(stats)
 }
@@ -1041,6 +1479,20 @@ async fn _handle_directed_rebalance(
         .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
         .await?;
 
+    let stats = _initialize_rebalance_journals(
+        payer,
+        client,
+        program_id,
+        all_steward_accounts,
+        &validators_to_run
+            .iter()
+            .map(|validator_info| validator_info.vote_account)
+            .collect::<Vec<Pubkey>>(),
+        priority_fee,
+    )
+    .await?;
+    submit_stats.combine(&stats);
+
     if reserve_stake_acc
         .lamports
         .lt(&stake_rent.mul(validators_to_run.len() as u64))
@@ -1110,6 +1562,11 @@ async fn _handle_directed_rebalance(
                 stake_history: solana_sdk::sysvar::stake_history::id(),
                 stake_config: stake::config::ID,
                 directed_stake_meta: directed_stake_meta_address,
+                rebalance_journal: get_rebalance_journal_address(
+                    &all_steward_accounts.config_address,
+                    vote_account,
+                    program_id,
+                ),
             }
             .to_account_metas(None),
             data: jito_steward::instruction::RebalanceDirected {
@@ -1254,6 +1711,22 @@ pub async fn crank_steward(
         return_stats.combine(&stats);
     }
 
+    {
+        // --------- SCORE VALIDATORS ADDED MID-CYCLE -----------
+        info!("Cranking steward step=score_added_validators");
+
+        let stats = _handle_score_added_validators(
+            payer,
+            client,
+            program_id,
+            all_steward_accounts,
+            priority_fee,
+        )
+        .await?;
+
+        return_stats.combine(&stats);
+    }
+
     {
         // --------- CHECK AND HANDLE STATE -----------
         if should_crank_state {
@@ -1283,6 +1756,26 @@ pub async fn crank_steward(
                     .await?
                 }
                 StewardStateEnum::Idle => {
+                    // Rebalancing is done for the epoch, point deposits and withdrawals at the
+                    // validators furthest from their targets
+                    if all_steward_accounts
+                        .config_account
+                        .parameters
+                        .auto_preferred_validators_enabled()
+                    {
+                        info!("Cranking steward step=update_preferred_validators");
+
+                        let stats = _handle_update_preferred_validators(
+                            payer,
+                            client,
+                            program_id,
+                            all_steward_accounts,
+                            priority_fee,
+                        )
+                        .await?;
+                        return_stats.combine(&stats);
+                    }
+
                     info!("Cranking steward state=idle");
 
                     _handle_idle(
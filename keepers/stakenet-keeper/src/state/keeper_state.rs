@@ -1,6 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use anchor_lang::prelude::{EpochSchedule, SlotHistory};
@@ -16,7 +16,9 @@ use stakenet_sdk::{
         aggregate_accounts::{AllStewardAccounts, AllValidatorAccounts},
         errors::JitoTransactionError,
     },
-    utils::accounts::{get_directed_stake_meta, get_validator_history_address},
+    utils::accounts::{
+        get_directed_stake_contributions, get_directed_stake_meta, get_validator_history_address,
+    },
 };
 use validator_history::{ClusterHistory, ValidatorHistory};
 
@@ -127,6 +129,10 @@ pub struct KeeperState {
     pub all_active_validator_accounts: Option<Box<AllValidatorAccounts>>,
     pub steward_progress_flags: StewardProgressFlags,
     pub cluster_name: String,
+
+    // (epoch, bucket) in which all directed stake contributions were last seen sampled, so the
+    // contributions are only fetched again once a new sample bucket starts
+    pub directed_stake_sampled_bucket: Mutex<Option<(u64, u64)>>,
 }
 
 impl KeeperState {
@@ -345,6 +351,41 @@ impl KeeperState {
                 ))
             })?;
 
+        // Ticket cranks also sample balances, so they run once per sample interval
+        let sample_interval_slots = steward_state
+            .config_account
+            .parameters
+            .directed_stake_sample_interval_slots;
+        if sample_interval_slots > 0 {
+            let current_bucket = slot_index / sample_interval_slots;
+            let already_sampled = self
+                .directed_stake_sampled_bucket
+                .lock()
+                .map(|sampled_bucket| *sampled_bucket == Some((current_epoch, current_bucket)))
+                .unwrap_or(false);
+            let sample_due = !already_sampled && {
+                let contributions =
+                    get_directed_stake_contributions(client.clone(), program_id).await?;
+                contributions
+                    .values()
+                    .filter(|contribution| contribution.config == steward_state.config_address)
+                    .any(|contribution| {
+                        contribution.sample_epoch != current_epoch
+                            || contribution.sample_bucket != current_bucket
+                    })
+            };
+            if !sample_due {
+                if let Ok(mut sampled_bucket) = self.directed_stake_sampled_bucket.lock() {
+                    *sampled_bucket = Some((current_epoch, current_bucket));
+                }
+            } else {
+                log::info!(
+                    "Directed stake balance sample due epoch={current_epoch} bucket={current_bucket}"
+                );
+                return Ok(true);
+            }
+        }
+
         let epoch_progress = slot_index as f64 / self.epoch_schedule.slots_per_epoch as f64;
 
         if epoch_progress <= 0.5 {
@@ -421,6 +462,7 @@ impl Default for KeeperState {
             all_active_validator_accounts: None,
             steward_progress_flags: StewardProgressFlags { flags: 0 },
             cluster_name: String::new(),
+            directed_stake_sampled_bucket: Mutex::new(None),
         }
     }
 }
@@ -1,3 +1,5 @@
+use anchor_lang::solana_program::{pubkey, pubkey::Pubkey};
+
 pub const MAX_ALLOC_BYTES: usize = 10_240;
 pub const VEC_SIZE_BYTES: usize = 4;
 pub const U64_SIZE: usize = 8;
@@ -8,6 +10,10 @@ pub const STAKE_STATUS_OFFSET: usize = 40;
 pub const VOTE_ADDRESS_OFFSET: usize = 41;
 pub const STAKE_POOL_WITHDRAW_SEED: &[u8] = b"withdraw";
 pub const STAKE_POOL_TRANSIENT_SEED: &[u8] = b"transient";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
 pub const MAX_VALIDATORS: usize = 5_000;
 pub const BASIS_POINTS_MAX: u16 = 10_000;
 pub const COMMISSION_MAX: u8 = 100;
@@ -21,6 +27,8 @@ pub const EPOCH_PROGRESS_MAX: f64 = 0.99;
 pub const NUM_EPOCHS_BETWEEN_SCORING_MAX: u64 = 100;
 // Cannot score validators in under 100 slots, to submit 1 instruction per validator
 pub const COMPUTE_SCORE_SLOT_RANGE_MIN: u64 = 100;
+// Bucket minimums of sampled ticket balances are summed in a u64, which bounds the buckets per epoch
+pub const MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH: u64 = 500;
 #[cfg(feature = "mainnet-beta")]
 pub const VALIDATOR_HISTORY_FIRST_RELIABLE_EPOCH: u64 = 520;
 #[cfg(not(feature = "mainnet-beta"))]
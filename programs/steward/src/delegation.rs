@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
 use spl_stake_pool::big_vec::BigVec;
+use spl_stake_pool::state::StakeStatus;
 
-use crate::constants::LAMPORT_BALANCE_DEFAULT;
+use crate::constants::{BASIS_POINTS_MAX, LAMPORT_BALANCE_DEFAULT};
 use crate::events::DecreaseComponents;
 use crate::state::directed_stake::DirectedStakeMeta;
 use crate::{
     errors::StewardError,
-    utils::{get_target_lamports, stake_lamports_at_validator_list_index},
-    StewardStateV2,
+    utils::{
+        get_target_lamports, stake_lamports_at_validator_list_index,
+        stake_status_at_validator_list_index, vote_pubkey_at_validator_list_index,
+    },
+    InstantUnstakeFractions, Parameters, StewardStateV2,
 };
 
 #[derive(Debug, Clone)]
@@ -35,6 +39,7 @@ pub fn decrease_stake_calculation(
     validator_list: &BigVec<'_>,
     minimum_delegation: u64,
     stake_rent: u64,
+    instant_unstake_fractions: Option<&InstantUnstakeFractions>,
 ) -> Result<RebalanceType> {
     if target_index >= state.num_pool_validators as usize {
         return Err(StewardError::ValidatorIndexOutOfBounds.into());
@@ -49,11 +54,6 @@ pub fn decrease_stake_calculation(
         .rev()
     {
         let temp_index = *idx as usize;
-        let temp_target_lamports = if state.instant_unstake.get_unsafe(temp_index) {
-            0
-        } else {
-            get_target_lamports(&state.delegations[temp_index], stake_pool_lamports)?
-        };
 
         let (mut temp_current_lamports, some_transient_stake) =
             stake_lamports_at_validator_list_index(validator_list, temp_index)?;
@@ -77,6 +77,23 @@ pub fn decrease_stake_calculation(
             temp_current_lamports = current_lamports;
         }
 
+        let temp_instant_unstake_fraction_bps = if state.instant_unstake.get_unsafe(temp_index) {
+            instant_unstake_fraction_bps(instant_unstake_fractions, validator_list, temp_index)?
+        } else {
+            0
+        };
+        let temp_target_lamports = if temp_instant_unstake_fraction_bps >= BASIS_POINTS_MAX {
+            0
+        } else {
+            let temp_target_lamports =
+                get_target_lamports(&state.delegations[temp_index], stake_pool_lamports)?;
+            instant_unstake_target_lamports(
+                temp_target_lamports,
+                temp_current_lamports,
+                temp_instant_unstake_fraction_bps,
+            )
+        };
+
         // For the current `temp` validator, calculate how much we can remove and what category it's coming from
         let unstake_amounts =
             if !some_transient_stake && temp_target_lamports < temp_current_lamports {
@@ -85,6 +102,7 @@ pub fn decrease_stake_calculation(
                     temp_index,
                     temp_current_lamports,
                     temp_target_lamports,
+                    temp_instant_unstake_fraction_bps,
                 )?
             } else {
                 // If the validator has transient lamports, some rebalancing has already taken place so we skip
@@ -200,6 +218,242 @@ pub fn increase_stake_calculation(
     Err(StewardError::ValidatorIndexOutOfBounds.into())
 }
 
+/// Share of the stake of the validator at `index` to remove when it is flagged for instant unstake (in basis points).
+/// Validators without a graded instant unstake entry are fully unstaked.
+pub fn instant_unstake_fraction_bps(
+    instant_unstake_fractions: Option<&InstantUnstakeFractions>,
+    validator_list: &BigVec<'_>,
+    index: usize,
+) -> Result<u16> {
+    match instant_unstake_fractions {
+        Some(instant_unstake_fractions) => {
+            let vote_account = vote_pubkey_at_validator_list_index(validator_list, index)?;
+            Ok(instant_unstake_fractions
+                .get(&vote_account)
+                .unwrap_or(BASIS_POINTS_MAX))
+        }
+        None => Ok(BASIS_POINTS_MAX),
+    }
+}
+
+/// Lamports removed from a validator with `current_lamports` by a graded instant unstake
+pub fn graded_instant_unstake_lamports(
+    current_lamports: u64,
+    instant_unstake_fraction_bps: u16,
+) -> u64 {
+    (current_lamports as u128)
+        .saturating_mul(instant_unstake_fraction_bps.min(BASIS_POINTS_MAX) as u128)
+        .saturating_div(BASIS_POINTS_MAX as u128) as u64
+}
+
+/// Target of a validator flagged for instant unstake with `instant_unstake_fraction_bps` below `BASIS_POINTS_MAX`:
+/// the fraction of its current lamports is removed, or more if its delegation target is lower.
+pub fn instant_unstake_target_lamports(
+    target_lamports: u64,
+    current_lamports: u64,
+    instant_unstake_fraction_bps: u16,
+) -> u64 {
+    target_lamports.min(
+        current_lamports.saturating_sub(graded_instant_unstake_lamports(
+            current_lamports,
+            instant_unstake_fraction_bps,
+        )),
+    )
+}
+
+/// Drops increases and decreases smaller than `minimum_rebalance_lamports`.
+/// Decreases that include instant unstake lamports are always kept. A minimum of 0 keeps every move.
+pub fn skip_dust_rebalance(
+    rebalance: RebalanceType,
+    minimum_rebalance_lamports: u64,
+) -> RebalanceType {
+    match rebalance {
+        RebalanceType::Increase(amount) if amount < minimum_rebalance_lamports => {
+            RebalanceType::None
+        }
+        RebalanceType::Decrease(DecreaseComponents {
+            instant_unstake_lamports: 0,
+            total_unstake_lamports,
+            ..
+        }) if total_unstake_lamports < minimum_rebalance_lamports => RebalanceType::None,
+        rebalance => rebalance,
+    }
+}
+
+/// Split of the pool's lamports that undirected validator targets are computed on
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UndirectedPoolLamports {
+    /// Pool lamports not staked to directed stake targets
+    pub undirected_pool_lamports: u64,
+    /// Lamports held back in the reserve for SOL withdrawals
+    pub reserve_liquidity_target: u64,
+    /// Rent and minimum delegation of every validator stake account in the pool
+    pub base_lamports: u64,
+}
+
+impl UndirectedPoolLamports {
+    /// The liquidity buffer is held back from the reserve and excluded from the pool that validator targets are
+    /// computed on, so decreases refill the reserve when it is short. It never eats into `base_lamports`.
+    pub fn new(
+        parameters: &Parameters,
+        total_pool_lamports: u64,
+        directed_staked_lamports: u64,
+        num_validators: usize,
+        minimum_delegation: u64,
+        stake_rent: u64,
+    ) -> Self {
+        let undirected_pool_lamports = total_pool_lamports.saturating_sub(directed_staked_lamports);
+        let base_lamports = minimum_delegation
+            .saturating_add(stake_rent)
+            .saturating_mul(num_validators as u64);
+        let reserve_liquidity_target = parameters
+            .reserve_liquidity_target(total_pool_lamports)
+            .min(undirected_pool_lamports.saturating_sub(base_lamports));
+
+        Self {
+            undirected_pool_lamports,
+            reserve_liquidity_target,
+            base_lamports,
+        }
+    }
+
+    /// Lamports validator targets are computed on, including `base_lamports`
+    pub fn stake_pool_lamports(&self) -> u64 {
+        self.undirected_pool_lamports
+            .saturating_sub(self.reserve_liquidity_target)
+    }
+
+    /// Lamports validator targets are computed on, beyond `base_lamports`
+    pub fn delegable_lamports(&self) -> u64 {
+        self.stake_pool_lamports()
+            .saturating_sub(self.base_lamports)
+    }
+}
+
+/// Validator list indices of the pool's preferred deposit and withdraw validators
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredValidators {
+    pub deposit: Option<usize>,
+    pub withdraw: Option<usize>,
+}
+
+/// Picks the validators that stake deposits and withdrawals should go through, so that user flows move
+/// stake toward targets instead of spending rebalance budget.
+///
+/// The deposit validator is the delegated validator furthest below its target, ties going to the higher score.
+/// The withdraw validator is the validator furthest above its target, ties going to the lower raw score, where
+/// validators marked for instant unstake have a target of 0. Validators marked for removal or not `Active` in the
+/// validator list are never picked. Current lamports are computed the same way as in `increase_stake_calculation`.
+pub fn preferred_validators_calculation(
+    state: &StewardStateV2,
+    directed_stake_meta: &DirectedStakeMeta,
+    stake_pool_lamports: u64,
+    validator_list: &BigVec<'_>,
+    minimum_delegation: u64,
+    stake_rent: u64,
+) -> Result<PreferredValidators> {
+    let base_lamport_balance = minimum_delegation
+        .checked_add(stake_rent)
+        .ok_or(StewardError::ArithmeticError)?;
+    let num_pool_validators = state.num_pool_validators as usize;
+
+    let mut preferred = PreferredValidators::default();
+    let mut max_lamports_below_target = 0;
+    let mut max_lamports_above_target = 0;
+
+    for idx in state.sorted_score_indices[..num_pool_validators].iter() {
+        let index = *idx as usize;
+        if state.delegations[index].numerator == 0
+            || state.instant_unstake.get(index)?
+            || !is_preferred_validator_candidate(state, validator_list, index)?
+        {
+            continue;
+        }
+
+        let target_lamports = get_target_lamports(&state.delegations[index], stake_pool_lamports)?;
+        let current_lamports = undirected_lamports_at_index(
+            state,
+            directed_stake_meta,
+            validator_list,
+            index,
+            base_lamport_balance,
+        )?;
+
+        let lamports_below_target = target_lamports.saturating_sub(current_lamports);
+        if lamports_below_target > max_lamports_below_target {
+            max_lamports_below_target = lamports_below_target;
+            preferred.deposit = Some(index);
+        }
+    }
+
+    for idx in state.sorted_raw_score_indices[..num_pool_validators]
+        .iter()
+        .rev()
+    {
+        let index = *idx as usize;
+        if !is_preferred_validator_candidate(state, validator_list, index)? {
+            continue;
+        }
+
+        let target_lamports = if state.instant_unstake.get(index)? {
+            0
+        } else {
+            get_target_lamports(&state.delegations[index], stake_pool_lamports)?
+        };
+        let current_lamports = undirected_lamports_at_index(
+            state,
+            directed_stake_meta,
+            validator_list,
+            index,
+            base_lamport_balance,
+        )?;
+
+        let lamports_above_target = current_lamports.saturating_sub(target_lamports);
+        if lamports_above_target > max_lamports_above_target {
+            max_lamports_above_target = lamports_above_target;
+            preferred.withdraw = Some(index);
+        }
+    }
+
+    Ok(preferred)
+}
+
+fn is_preferred_validator_candidate(
+    state: &StewardStateV2,
+    validator_list: &BigVec<'_>,
+    index: usize,
+) -> Result<bool> {
+    Ok(!state.validators_to_remove.get(index)?
+        && !state.validators_for_immediate_removal.get(index)?
+        && stake_status_at_validator_list_index(validator_list, index)?
+            == StakeStatus::Active as u8)
+}
+
+/// Active undirected lamports of a validator beyond its base lamport balance
+fn undirected_lamports_at_index(
+    state: &StewardStateV2,
+    directed_stake_meta: &DirectedStakeMeta,
+    validator_list: &BigVec<'_>,
+    index: usize,
+    base_lamport_balance: u64,
+) -> Result<u64> {
+    let (current_lamports, _) = stake_lamports_at_validator_list_index(validator_list, index)?;
+
+    let directed_stake_meta_index = directed_stake_meta.directed_stake_meta_indices[index];
+    let (new_directed_stake_lamports, new_total_stake_lamports) = state
+        .simulate_adjust_directed_stake_for_deposits_and_withdrawals(
+            current_lamports,
+            index,
+            directed_stake_meta_index as usize,
+            directed_stake_meta,
+        )?;
+
+    // ValidatorList includes base lamports in active_stake_lamports
+    Ok(new_total_stake_lamports
+        .saturating_sub(new_directed_stake_lamports)
+        .saturating_sub(base_lamport_balance))
+}
+
 #[derive(Default)]
 pub struct UnstakeAmounts {
     pub stake_deposit_unstake_lamports: u64,
@@ -237,6 +491,7 @@ impl UnstakeState {
         index: usize,
         mut current_lamports: u64,
         target_lamports: u64,
+        instant_unstake_fraction_bps: u16,
     ) -> Result<UnstakeAmounts> {
         // Stake deposit
         let stake_deposit_unstake_lamports =
@@ -248,8 +503,13 @@ impl UnstakeState {
             .ok_or(StewardError::ArithmeticError)?;
 
         // Instant unstake
-        let instant_unstake_lamports =
-            self.instant_unstake(state, index, current_lamports, target_lamports)?;
+        let instant_unstake_lamports = self.instant_unstake(
+            state,
+            index,
+            current_lamports,
+            target_lamports,
+            instant_unstake_fraction_bps,
+        )?;
         current_lamports = current_lamports.saturating_sub(instant_unstake_lamports);
         self.instant_unstake_total = self
             .instant_unstake_total
@@ -311,15 +571,24 @@ impl UnstakeState {
         index: usize,
         current_lamports: u64,
         target_lamports: u64,
+        instant_unstake_fraction_bps: u16,
     ) -> Result<u64> {
         // If this validator is marked for instant unstake, destake to the target
+        // With a graded instant unstake, only the fraction is instant unstake and the rest is scoring unstake
         if state.instant_unstake.get_unsafe(index)
             && self.instant_unstake_total < self.instant_unstake_cap
         {
-            let lamports_above_target = current_lamports
+            let mut lamports_above_target = current_lamports
                 .checked_sub(target_lamports)
                 .ok_or(StewardError::ArithmeticError)?;
 
+            if instant_unstake_fraction_bps < BASIS_POINTS_MAX {
+                lamports_above_target = lamports_above_target.min(graded_instant_unstake_lamports(
+                    current_lamports,
+                    instant_unstake_fraction_bps,
+                ));
+            }
+
             let cap_limit = self
                 .instant_unstake_cap
                 .checked_sub(self.instant_unstake_total)
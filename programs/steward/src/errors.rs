@@ -87,4 +87,80 @@ pub enum StewardError {
     DirectedStakeVoteAccountMismatch,
     #[msg("This validator has already been rebalanced for this epoch for this stake category.")]
     ValidatorAlreadyRebalanced,
+    #[msg("Cycle report does not match the current cycle")]
+    InvalidCycleReport,
+    #[msg("Token account is not the ticket holder's associated token account for the pool mint")]
+    InvalidTokenAccount,
+    #[msg("Validator list indices do not match the ticket preferences")]
+    InvalidValidatorListIndices,
+    #[msg("Directed stake balance sampling is disabled")]
+    DirectedStakeSamplingDisabled,
+    #[msg("Ticket has the maximum number of balance sources")]
+    BalanceSourcesFull,
+    #[msg("Balance source not found")]
+    BalanceSourceNotFound,
+    #[msg("Balance source already added")]
+    BalanceSourceAlreadyAdded,
+    #[msg("Directed stake ticket is not closed")]
+    DirectedStakeTicketNotClosed,
+    #[msg("Directed stake ticket has not been cranked this epoch")]
+    DirectedStakeTicketNotCranked,
+    #[msg("Validator has opted out of directed stake")]
+    ValidatorOptedOutOfDirectedStake,
+    #[msg("Validator consent account does not match the validator")]
+    InvalidValidatorConsent,
+    #[msg("Permissionless directed stake tickets are disabled")]
+    PermissionlessDirectedStakeTicketsDisabled,
+    #[msg("JitoSOL balance is below the permissionless ticket minimum")]
+    DirectedStakeBalanceBelowMinimum,
+    #[msg("JitoSOL balance is not below the permissionless ticket minimum")]
+    DirectedStakeBalanceAboveMinimum,
+    #[msg("Directed stake basket is invalid or belongs to another config")]
+    InvalidDirectedStakeBasket,
+    #[msg("Ticket has the maximum number of scheduled preference sets")]
+    DirectedStakePreferenceScheduleFull,
+    #[msg("No preferences are scheduled for the activation epoch")]
+    ScheduledDirectedStakePreferencesNotFound,
+    #[msg("No scheduled preferences are due")]
+    NoScheduledDirectedStakePreferencesDue,
+    #[msg("Automatic preferred validators are disabled")]
+    AutoPreferredValidatorsDisabled,
+    #[msg("Graded instant unstake requires the instant unstake fractions account")]
+    InstantUnstakeFractionsRequired,
+    #[msg("Score warnings require the score warnings account")]
+    ScoreWarningsRequired,
+    #[msg("Validator requested to exit the pool and cannot be added back yet")]
+    ValidatorExitRequested,
+    #[msg("Commission pledge account does not belong to this validator and config")]
+    InvalidCommissionPledge,
+    #[msg("Validator broke a commission pledge and cannot pledge again yet")]
+    CommissionPledgeCooldown,
+    #[msg("Commission pledge terms are invalid or loosen an active pledge")]
+    InvalidCommissionPledgeTerms,
+    #[msg("Remaining accounts do not match the validators to rebalance")]
+    InvalidRebalanceManyAccounts,
+    #[msg("Unstake circuit breaker tripped")]
+    UnstakeCircuitBreakerTripped,
+    #[msg("Directed stake targets have already been migrated to tickets")]
+    DirectedStakeTargetsAlreadyMigrated,
+    #[msg("Directed stake target is managed by tickets and cannot be uploaded")]
+    DirectedStakeTargetManagedByTickets,
+    #[msg("Balance source accounts do not match the ticket's balance sources")]
+    InvalidBalanceSources,
+    #[msg("Directed stake ticket still points at the basket")]
+    DirectedStakeBasketStillReferenced,
+    #[msg("Rebalance journal account does not belong to this validator and config")]
+    InvalidRebalanceJournal,
+    #[msg("Instant unstake circuit breaker tripped")]
+    InstantUnstakeCircuitBreakerTripped,
+    #[msg("Cycle reports require the cycle report account")]
+    CycleReportRequired,
+    #[msg("Directed stake targets have not been migrated to tickets")]
+    DirectedStakeTargetsNotMigrated,
+    #[msg("Validator has no rebalance journal, it must be initialized before rebalancing")]
+    RebalanceJournalNotInitialized,
+    #[msg("Directed stake balances can only be read before other programs run in the transaction")]
+    DirectedStakeBalanceNotAtTransactionStart,
+    #[msg("Validator exits can only be requested before the instant unstake epoch progress")]
+    ValidatorExitWindowClosed,
 }
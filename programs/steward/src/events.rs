@@ -7,6 +7,13 @@ use anchor_lang::prelude::{event, AnchorDeserialize, AnchorSerialize};
 use anchor_lang::{solana_program::pubkey::Pubkey, Discriminator};
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use crate::{
+    instructions::AuthorityType,
+    score::{InstantUnstakeComponentsV4, InstantUnstakeDetails},
+    state::directed_stake::{DirectedStakePreference, DirectedStakeRecordType},
+    Parameters,
+};
+
 #[event]
 #[derive(Debug, Clone)]
 
@@ -15,6 +22,7 @@ pub struct AutoRemoveValidatorEvent {
     pub vote_account: Pubkey,
     pub vote_account_closed: bool,
     pub stake_account_deactivated: bool,
+    pub exit_requested: bool,
     pub marked_for_immediate_removal: bool,
 }
 
@@ -45,6 +53,99 @@ pub struct StateTransition {
     pub new_state: String,
 }
 
+/// Emitted when the steward pauses itself because unstaking activity in the current cycle
+/// exceeded one of the circuit breaker parameters
+#[event]
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerEvent {
+    pub epoch: u64,
+    pub slot: u64,
+    pub instant_unstake_count: u64,
+    pub unstake_total_lamports: u64,
+    pub circuit_breaker_instant_unstake_count: u16,
+    pub circuit_breaker_unstake_bps: u16,
+}
+
+/// Emitted when a validator first fails only filters in `Parameters::score_warning_filters`. It keeps
+/// its score until the cycle ends at `grace_end_epoch`, and is unscored if it still fails them after
+#[event]
+#[derive(Debug, Clone)]
+pub struct ScoreWarningEvent {
+    pub vote_account: Pubkey,
+    pub epoch: u16,
+    pub failed_filters: u16,
+    pub grace_end_epoch: u64,
+}
+
+/// Emitted when a validator marks itself for removal from the pool with `request_validator_exit`.
+/// It will not be re-added before `rejoin_epoch` unless it calls `request_validator_rejoin`.
+#[event]
+#[derive(Debug, Clone)]
+pub struct ValidatorExitEvent {
+    pub vote_account: Pubkey,
+    pub validator_list_index: u64,
+    pub marked_for_removal: bool,
+    pub rejoin_epoch: u64,
+}
+
+/// Emitted when a validator lifts its exit block with `request_validator_rejoin`
+#[event]
+#[derive(Debug, Clone)]
+pub struct ValidatorRejoinEvent {
+    pub vote_account: Pubkey,
+    pub exit_epoch: u64,
+    pub rejoin_epoch: u64,
+    pub epoch: u64,
+}
+
+/// Emitted when a validator makes or tightens a `CommissionPledge`
+#[event]
+#[derive(Debug, Clone)]
+pub struct CommissionPledgeEvent {
+    pub vote_account: Pubkey,
+    pub max_commission: u8,
+    pub max_mev_commission_bps: u16,
+    pub max_priority_fee_commission_bps: u16,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+}
+
+/// Emitted when `compute_instant_unstake` finds a commission above a validator's pledged maximum.
+/// The validator is instantly unstaked and cannot pledge again before `cooldown_end_epoch`.
+#[event]
+#[derive(Debug, Clone)]
+pub struct CommissionPledgeBrokenEvent {
+    pub vote_account: Pubkey,
+    pub epoch: u64,
+    pub cooldown_end_epoch: u64,
+}
+
+/// Emitted by `verify_invariants` for each validator whose tracked lamports disagree.
+/// `drift_flags` is a bitmask of `ACCOUNTING_DRIFT_*` flags.
+#[event]
+#[derive(Debug, Clone)]
+pub struct AccountingDriftEvent {
+    pub epoch: u64,
+    pub validator_list_index: u64,
+    pub vote_account: Pubkey,
+    pub drift_flags: u8,
+    pub validator_lamport_balance: u64,
+    pub validator_list_active_lamports: u64,
+    pub directed_stake_lamports: u64,
+    pub target_total_staked_lamports: u64,
+}
+
+/// Emitted once per `verify_invariants` call, summarizing the validator list indices checked
+#[event]
+#[derive(Debug, Clone)]
+pub struct VerifyInvariantsEvent {
+    pub epoch: u64,
+    pub start_index: u64,
+    pub end_index: u64,
+    pub validators_with_drift: u64,
+    pub paused: bool,
+}
+
 #[event]
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct DecreaseComponents {
@@ -75,6 +176,155 @@ pub struct DirectedRebalanceEvent {
     pub decrease_lamports: u64,
 }
 
+#[event]
+#[derive(Debug, Clone)]
+pub struct UpdatePreferredValidatorsEvent {
+    pub epoch: u16,
+    pub preferred_deposit_validator: Option<Pubkey>,
+    pub preferred_withdraw_validator: Option<Pubkey>,
+}
+
+/////// Admin and configuration events ///////
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct UpdateParametersEvent {
+    pub authority: Pubkey,
+    pub previous_parameters: Parameters,
+    pub new_parameters: Parameters,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct UpdatePriorityFeeParametersEvent {
+    pub authority: Pubkey,
+    pub previous_parameters: Parameters,
+    pub new_parameters: Parameters,
+}
+
+/// Emitted by both `add_validators_to_blacklist` and `remove_validators_from_blacklist`.
+/// `previous_values[i]` is the blacklist value of `validator_history_indices[i]` before the update.
+#[event]
+#[derive(Debug, Clone)]
+pub struct UpdateBlacklistEvent {
+    pub authority: Pubkey,
+    pub validator_history_indices: Vec<u32>,
+    pub previous_values: Vec<bool>,
+    pub blacklisted: bool,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct SetNewAuthorityEvent {
+    pub authority_type: AuthorityType,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Emitted by both `pause_steward` and `resume_steward`
+#[event]
+#[derive(Debug, Clone)]
+pub struct SetPausedEvent {
+    pub authority: Pubkey,
+    pub previous_paused: bool,
+    pub paused: bool,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct AdminMarkForRemovalEvent {
+    pub authority: Pubkey,
+    pub validator_list_index: u64,
+    pub immediate: bool,
+    pub previous_value: bool,
+    pub mark_for_removal: bool,
+}
+
+/// Emitted by both `add_to_directed_stake_whitelist` and `remove_from_directed_stake_whitelist`.
+/// Counts are the number of whitelisted records of `record_type` before and after the update.
+#[event]
+#[derive(Debug, Clone)]
+pub struct UpdateDirectedStakeWhitelistEvent {
+    pub authority: Pubkey,
+    pub record_type: DirectedStakeRecordType,
+    pub record: Pubkey,
+    pub added: bool,
+    pub previous_count: u16,
+    pub new_count: u16,
+}
+
+/////// Directed stake lifecycle events ///////
+
+/// Emitted by both `initialize_directed_stake_ticket` and `initialize_permissionless_directed_stake_ticket`
+#[event]
+#[derive(Debug, Clone)]
+pub struct DirectedStakeTicketInitializedEvent {
+    pub ticket: Pubkey,
+    pub signer: Pubkey,
+    pub ticket_update_authority: Pubkey,
+    pub ticket_holder_is_protocol: bool,
+    pub is_permissionless: bool,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct DirectedStakeTicketUpdatedEvent {
+    pub ticket: Pubkey,
+    pub signer: Pubkey,
+    pub previous_preferences: Vec<DirectedStakePreference>,
+    pub new_preferences: Vec<DirectedStakePreference>,
+}
+
+/// Emitted by both `close_directed_stake_ticket` and `close_underfunded_directed_stake_ticket`.
+/// `signer` is the default pubkey when the ticket was closed permissionlessly for being underfunded.
+#[event]
+#[derive(Debug, Clone)]
+pub struct DirectedStakeTicketClosedEvent {
+    pub ticket: Pubkey,
+    pub signer: Pubkey,
+    pub ticket_update_authority: Pubkey,
+    pub preferences: Vec<DirectedStakePreference>,
+    pub underfunded: bool,
+}
+
+/// `requested_target_lamports` is the uploaded target before the validator's consent is applied,
+/// `total_target_lamports` includes the ticket contributions.
+#[event]
+#[derive(Debug, Clone)]
+pub struct CopyDirectedStakeTargetsEvent {
+    pub signer: Pubkey,
+    pub vote_pubkey: Pubkey,
+    pub validator_list_index: u64,
+    pub epoch: u64,
+    pub requested_target_lamports: u64,
+    pub previous_total_target_lamports: u64,
+    pub total_target_lamports: u64,
+}
+
+/// Emitted by the ticket cranks and `apply_scheduled_directed_stake_preferences` when they change the
+/// portion of directed stake targets contributed by tickets. Only targets that changed are included.
+/// `previous_ticket_target_lamports[i]` and `ticket_target_lamports[i]` belong to `vote_pubkeys[i]`.
+#[event]
+#[derive(Debug, Clone)]
+pub struct DirectedStakeTicketTargetsChangedEvent {
+    pub ticket: Pubkey,
+    pub epoch: u64,
+    pub vote_pubkeys: Vec<Pubkey>,
+    pub previous_ticket_target_lamports: Vec<u64>,
+    pub ticket_target_lamports: Vec<u64>,
+}
+
+/// Only validators whose directed stake lamports changed are included.
+/// `previous_lamports[i]` and `new_lamports[i]` belong to `validator_list_indices[i]`.
+#[event]
+#[derive(Debug, Clone)]
+pub struct SyncDirectedStakeLamportsEvent {
+    pub signer: Pubkey,
+    pub validator_list_indices: Vec<u32>,
+    pub previous_lamports: Vec<u64>,
+    pub new_lamports: Vec<u64>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum RebalanceTypeTag {
     None,
@@ -151,3 +401,39 @@ pub struct InstantUnstakeComponents {
     pub vote_account: Pubkey,
     pub epoch: u16,
 }
+
+/// Still emitted next to `InstantUnstakeComponentsV4`, which adds `instant_unstake_fraction_bps`, so
+/// existing consumers keep parsing instant unstake results. Because the event discriminator is based on
+/// struct name, it's important to rename the struct if fields are changed.
+#[event]
+#[derive(Debug, PartialEq, Eq)]
+pub struct InstantUnstakeComponentsV3 {
+    pub instant_unstake: bool,
+    pub delinquency_check: bool,
+    pub commission_check: bool,
+    pub mev_commission_check: bool,
+    pub is_blacklisted: bool,
+    pub is_bad_merkle_root_upload_authority: bool,
+    pub is_bad_priority_fee_merkle_root_upload_authority: bool,
+    pub vote_account: Pubkey,
+    pub epoch: u16,
+    pub details: InstantUnstakeDetails,
+}
+
+impl From<&InstantUnstakeComponentsV4> for InstantUnstakeComponentsV3 {
+    fn from(components: &InstantUnstakeComponentsV4) -> Self {
+        Self {
+            instant_unstake: components.instant_unstake,
+            delinquency_check: components.delinquency_check,
+            commission_check: components.commission_check,
+            mev_commission_check: components.mev_commission_check,
+            is_blacklisted: components.is_blacklisted,
+            is_bad_merkle_root_upload_authority: components.is_bad_merkle_root_upload_authority,
+            is_bad_priority_fee_merkle_root_upload_authority: components
+                .is_bad_priority_fee_merkle_root_upload_authority,
+            vote_account: components.vote_account,
+            epoch: components.epoch,
+            details: components.details.clone(),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakeBalanceSources, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct AddDirectedStakeBalanceSource<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeBalanceSources::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub balance_sources: AccountLoader<'info, DirectedStakeBalanceSources>,
+
+    /// CHECK: Layout is interpreted off-chain according to `source_type`
+    pub source_account: AccountInfo<'info>,
+
+    /// Owner of the position in `source_account`
+    pub source_owner: Signer<'info>,
+
+    #[account(
+        address = ticket_account.load()?.ticket_update_authority @ StewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+/*
+Attributes the JitoSOL held in `source_account` to a ticket. Both the ticket update authority and the
+owner of the position sign, so positions cannot be claimed by a ticket without the owner's consent.
+*/
+pub fn handler(ctx: Context<AddDirectedStakeBalanceSource>, source_type: u16) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut balance_sources = ctx.accounts.balance_sources.load_mut()?;
+    balance_sources.add_source(
+        ctx.accounts.source_account.key(),
+        ctx.accounts.source_owner.key(),
+        source_type,
+    )?;
+
+    Ok(())
+}
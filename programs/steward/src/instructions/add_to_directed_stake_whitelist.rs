@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::{
     errors::StewardError,
+    events::UpdateDirectedStakeWhitelistEvent,
     stake_pool_utils::deserialize_stake_pool,
     state::directed_stake::DirectedStakeRecordType,
     utils::{get_stake_pool_address, validator_exists_in_list},
@@ -49,6 +50,7 @@ pub fn handler(
     record: Pubkey,
 ) -> Result<()> {
     let mut whitelist = ctx.accounts.directed_stake_whitelist.load_mut()?;
+    let previous_count = whitelist.total_permissioned(record_type);
 
     match record_type {
         DirectedStakeRecordType::Validator => {
@@ -64,5 +66,15 @@ pub fn handler(
             whitelist.add_protocol_staker(record)?;
         }
     }
+
+    emit!(UpdateDirectedStakeWhitelistEvent {
+        authority: ctx.accounts.authority.key(),
+        record_type,
+        record,
+        added: true,
+        previous_count,
+        new_count: whitelist.total_permissioned(record_type),
+    });
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::{utils::get_config_blacklist_authority, Config};
+use crate::{events::UpdateBlacklistEvent, utils::get_config_blacklist_authority, Config};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -17,10 +17,20 @@ pub fn handler(
     validator_history_indices: &[u32],
 ) -> Result<()> {
     let mut config = ctx.accounts.config.load_mut()?;
+    let mut previous_values = Vec::with_capacity(validator_history_indices.len());
     for index in validator_history_indices {
+        previous_values.push(config.validator_history_blacklist.get(*index as usize)?);
         config
             .validator_history_blacklist
             .set(*index as usize, true)?;
     }
+
+    emit!(UpdateBlacklistEvent {
+        authority: ctx.accounts.authority.key(),
+        validator_history_indices: validator_history_indices.to_vec(),
+        previous_values,
+        blacklisted: true,
+    });
+
     Ok(())
 }
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{utils::get_config_admin, Config, StewardStateAccount, StewardStateAccountV2};
+use crate::{
+    events::AdminMarkForRemovalEvent, utils::get_config_admin, Config, StewardStateAccount,
+    StewardStateAccountV2,
+};
 
 #[derive(Accounts)]
 pub struct AdminMarkForRemoval<'info> {
@@ -29,17 +32,32 @@ pub fn handler(
 ) -> Result<()> {
     let mut state = ctx.accounts.state_account.load_mut()?;
 
-    if immediate {
+    let previous_value = if immediate {
+        let previous_value = state
+            .state
+            .validators_for_immediate_removal
+            .get(validator_list_index)?;
         state
             .state
             .validators_for_immediate_removal
             .set(validator_list_index, mark_for_removal)?;
+        previous_value
     } else {
+        let previous_value = state.state.validators_to_remove.get(validator_list_index)?;
         state
             .state
             .validators_to_remove
             .set(validator_list_index, mark_for_removal)?;
-    }
+        previous_value
+    };
+
+    emit!(AdminMarkForRemovalEvent {
+        authority: ctx.accounts.authority.key(),
+        validator_list_index: validator_list_index as u64,
+        immediate,
+        previous_value,
+        mark_for_removal,
+    });
 
     Ok(())
 }
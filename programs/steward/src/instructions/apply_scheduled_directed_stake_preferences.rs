@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    events::DirectedStakeTicketUpdatedEvent,
+    instructions::crank_directed_stake_ticket::{
+        add_preference_contributions, TicketTargetChanges,
+    },
+    stake_pool_utils::deserialize_stake_pool,
+    state::directed_stake::DirectedStakePreference,
+    utils::{get_stake_pool_address, get_validator_list, load_zero_copy_mut},
+    Config, DirectedStakeContribution, DirectedStakeMeta, DirectedStakePreferenceSchedule,
+    DirectedStakeTicket, DirectedStakeWhitelist,
+};
+
+#[derive(Accounts)]
+pub struct ApplyScheduledDirectedStakePreferences<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [DirectedStakeWhitelist::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub whitelist_account: AccountLoader<'info, DirectedStakeWhitelist>,
+
+    #[account(mut)]
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakePreferenceSchedule::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub preference_schedule: AccountLoader<'info, DirectedStakePreferenceSchedule>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: The ticket's `DirectedStakeContribution` PDA, which does not exist until the ticket is first cranked
+    #[account(
+        mut,
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountInfo<'info>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: Used to verify the validator list index of each preference
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Replaces the ticket's preferences with the latest scheduled set whose activation epoch has been
+reached and removes every due set from the schedule. A due set that is no longer valid, because a validator was
+removed from the whitelist or the ticket extension grew, is dropped without being applied.
+
+The targets the ticket contributed under its previous preferences are removed when a set is applied. If the ticket
+was already cranked in the current epoch, the targets of the new preferences are added from the balance of that
+crank, otherwise the next `crank_directed_stake_ticket` adds them. Validators that opted out receive nothing and
+targets are capped at the validator's maximum.
+
+`validator_list_indices[i]` is the validator list index of the i-th preference of the latest due set, or u32::MAX
+if the validator is not in the pool, and `remaining_accounts[i]` is the `DirectedStakeValidatorConsent` PDA of its
+validator, or the `DirectedStakeBasket` it points at.
+*/
+pub fn handler(
+    ctx: Context<ApplyScheduledDirectedStakePreferences>,
+    validator_list_indices: Vec<u32>,
+) -> Result<()> {
+    let whitelist = ctx.accounts.whitelist_account.load()?;
+    let mut ticket = ctx.accounts.ticket_account.load_mut()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    let due = ctx
+        .accounts
+        .preference_schedule
+        .load_mut()?
+        .take_due(clock.epoch)
+        .ok_or(StewardError::NoScheduledDirectedStakePreferencesDue)?;
+    let preferences = due.preferences();
+
+    let total_bps: u32 = preferences
+        .iter()
+        .map(|pref| pref.stake_share_bps as u32)
+        .sum();
+    if total_bps + ticket.extension_stake_share_bps as u32 > 10_000 {
+        msg!(
+            "Scheduled preferences for epoch {} exceed 10_000 bps, dropping",
+            due.activation_epoch
+        );
+        return Ok(());
+    }
+    if let Some(preference) = preferences.iter().find(|pref| {
+        !bool::from(pref.is_basket) && !whitelist.is_validator_permissioned(&pref.vote_pubkey)
+    }) {
+        msg!(
+            "Validator {} of the scheduled preferences for epoch {} is not whitelisted, dropping",
+            preference.vote_pubkey,
+            due.activation_epoch
+        );
+        return Ok(());
+    }
+
+    require!(
+        validator_list_indices.len() == preferences.len(),
+        StewardError::InvalidValidatorListIndices
+    );
+    require!(
+        ctx.remaining_accounts.len() >= preferences.len(),
+        StewardError::InvalidValidatorConsent
+    );
+
+    let previous_preferences = ticket.preferences().to_vec();
+    ticket.num_preferences = preferences.len() as u16;
+    ticket.staker_preferences =
+        [DirectedStakePreference::empty(); crate::MAX_PREFERENCES_PER_TICKET];
+    ticket.staker_preferences[..preferences.len()].copy_from_slice(preferences);
+
+    if !ctx.accounts.contribution_account.data_is_empty() {
+        let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+        let mut contribution =
+            load_zero_copy_mut::<DirectedStakeContribution>(&ctx.accounts.contribution_account)?;
+        let mut target_changes = TicketTargetChanges::default();
+        target_changes.track_entries(
+            &stake_meta,
+            &contribution.contributions[..contribution.num_contributions as usize],
+        );
+        stake_meta.remove_ticket_contributions(&mut contribution, clock.epoch);
+        if contribution.last_updated_epoch == clock.epoch {
+            let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+            let token_balance = contribution.token_balance;
+            add_preference_contributions(
+                &mut stake_meta,
+                &mut contribution,
+                &mut target_changes,
+                preferences,
+                &validator_list_indices,
+                &ctx.remaining_accounts[..preferences.len()],
+                &ctx.accounts.config.key(),
+                token_balance,
+                &stake_pool,
+                &ctx.accounts.validator_list,
+                clock.epoch,
+            )?;
+        }
+        target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+    }
+
+    emit!(DirectedStakeTicketUpdatedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: Pubkey::default(),
+        previous_preferences,
+        new_preferences: preferences.to_vec(),
+    });
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use crate::constants::{MAX_VALIDATORS, STAKE_POOL_WITHDRAW_SEED};
 use crate::errors::StewardError;
 use crate::events::AutoAddValidatorEvent;
-use crate::state::{Config, StewardStateAccount, StewardStateAccountV2};
+use crate::state::{Config, StewardStateAccount, StewardStateAccountV2, ValidatorExit};
 use crate::{
     stake_pool_utils::deserialize_stake_pool,
-    utils::{add_validator_check, get_stake_pool_address, get_validator_list_length},
+    utils::{
+        add_validator_check, get_stake_pool_address, get_validator_list_length, load_validator_exit,
+    },
 };
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke_signed, stake, sysvar, vote};
@@ -96,6 +98,13 @@ pub struct AutoAddValidator<'info> {
     pub rent: Sysvar<'info, Rent>,
 
     pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Only exists if the validator requested to exit, loaded in the handler
+    #[account(
+        seeds = [ValidatorExit::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_exit: AccountInfo<'info>,
 }
 
 /*
@@ -114,6 +123,12 @@ pub fn handler(ctx: Context<AutoAddValidator>) -> Result<()> {
 
         add_validator_check(&clock, &config, &state_account, validator_list)?;
 
+        if let Some(validator_exit) = load_validator_exit(&ctx.accounts.validator_exit)? {
+            if validator_exit.is_blocked(epoch) {
+                return Err(StewardError::ValidatorExitRequested.into());
+            }
+        }
+
         let validator_list_len = get_validator_list_length(&ctx.accounts.validator_list)?;
         if validator_list_len.checked_add(1).unwrap() > MAX_VALIDATORS {
             return Err(StewardError::MaxValidatorsReached.into());
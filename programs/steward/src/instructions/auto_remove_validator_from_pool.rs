@@ -130,6 +130,7 @@ pub struct AutoRemoveValidator<'info> {
 pub fn handler(ctx: Context<AutoRemoveValidator>, validator_list_index: usize) -> Result<()> {
     let stake_account_deactivated;
     let vote_account_closed;
+    let exit_requested;
     let clock = Clock::get()?;
     let epoch = clock.epoch;
 
@@ -165,8 +166,15 @@ pub fn handler(ctx: Context<AutoRemoveValidator>, validator_list_index: usize) -
         // Check if vote account closed
         vote_account_closed = *ctx.accounts.vote_account.owner != vote::program::ID;
 
+        // Marked by `request_validator_exit` while still active in the pool
+        exit_requested = state_account
+            .state
+            .validators_to_remove
+            .get(validator_list_index)?
+            && StakeStatus::try_from(validator_stake_info.status)? == StakeStatus::Active;
+
         require!(
-            stake_account_deactivated || vote_account_closed,
+            stake_account_deactivated || vote_account_closed || exit_requested,
             StewardError::ValidatorNotRemovable
         );
     }
@@ -269,6 +277,7 @@ pub fn handler(ctx: Context<AutoRemoveValidator>, validator_list_index: usize) -
             validator_list_index: validator_list_index as u64,
             stake_account_deactivated,
             vote_account_closed,
+            exit_requested,
             marked_for_immediate_removal,
         });
     }
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakePreferenceSchedule, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct CancelScheduledDirectedStakePreferences<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakePreferenceSchedule::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub preference_schedule: AccountLoader<'info, DirectedStakePreferenceSchedule>,
+
+    pub signer: Signer<'info>,
+}
+
+/*
+Removes the preference set scheduled for `activation_epoch`.
+Signed by the ticket update authority or the ticket override authority.
+*/
+pub fn handler(
+    ctx: Context<CancelScheduledDirectedStakePreferences>,
+    activation_epoch: u64,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let signer = ctx.accounts.signer.key;
+    if signer != &ticket.ticket_update_authority
+        && signer != &config.directed_stake_ticket_override_authority
+    {
+        msg!("Error: Only a valid ticket authority can cancel scheduled preferences.");
+        return Err(error!(StewardError::Unauthorized));
+    }
+
+    let mut preference_schedule = ctx.accounts.preference_schedule.load_mut()?;
+    preference_schedule.cancel(activation_epoch)
+}
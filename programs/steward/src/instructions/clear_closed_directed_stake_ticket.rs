@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, instructions::crank_directed_stake_ticket::TicketTargetChanges, Config,
+    DirectedStakeContribution, DirectedStakeMeta,
+};
+
+#[derive(Accounts)]
+pub struct ClearClosedDirectedStakeTicket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: Must be closed, checked in the handler
+    pub ticket_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Permissionless. Removes the directed stake targets contributed by a ticket that has been closed and
+closes its contribution account, returning the rent to the signer.
+*/
+pub fn handler(ctx: Context<ClearClosedDirectedStakeTicket>) -> Result<()> {
+    require!(
+        ctx.accounts.ticket_account.data_is_empty() && ctx.accounts.ticket_account.lamports() == 0,
+        StewardError::DirectedStakeTicketNotClosed
+    );
+
+    let clock = Clock::get()?;
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let mut contribution = ctx.accounts.contribution_account.load_mut()?;
+    require_keys_eq!(
+        contribution.config,
+        ctx.accounts.config.key(),
+        StewardError::Unauthorized
+    );
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(
+        &stake_meta,
+        &contribution.contributions[..contribution.num_contributions as usize],
+    );
+    stake_meta.remove_ticket_contributions(&mut contribution, clock.epoch);
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
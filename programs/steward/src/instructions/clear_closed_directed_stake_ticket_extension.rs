@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, instructions::crank_directed_stake_ticket::TicketTargetChanges, Config,
+    DirectedStakeExtensionContribution, DirectedStakeMeta, DirectedStakeTicketExtension,
+};
+
+#[derive(Accounts)]
+pub struct ClearClosedDirectedStakeTicketExtension<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: Must be closed, checked in the handler
+    pub ticket_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [DirectedStakeTicketExtension::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub ticket_extension: AccountLoader<'info, DirectedStakeTicketExtension>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [DirectedStakeExtensionContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub extension_contribution: AccountLoader<'info, DirectedStakeExtensionContribution>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Permissionless. Removes the directed stake targets contributed by the extension of a ticket that has been closed
+and closes the extension accounts, returning the rent to the signer.
+*/
+pub fn handler(ctx: Context<ClearClosedDirectedStakeTicketExtension>) -> Result<()> {
+    require!(
+        ctx.accounts.ticket_account.data_is_empty() && ctx.accounts.ticket_account.lamports() == 0,
+        StewardError::DirectedStakeTicketNotClosed
+    );
+
+    let clock = Clock::get()?;
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let extension_contribution = ctx.accounts.extension_contribution.load()?;
+    require_keys_eq!(
+        extension_contribution.config,
+        ctx.accounts.config.key(),
+        StewardError::Unauthorized
+    );
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(&stake_meta, &extension_contribution.contributions);
+    for entry in extension_contribution.contributions.iter() {
+        stake_meta.remove_contribution_entry(entry, clock.epoch);
+    }
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
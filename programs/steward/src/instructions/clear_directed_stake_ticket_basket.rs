@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    instructions::crank_directed_stake_ticket::TicketTargetChanges,
+    utils::{load_ticket_extension, load_zero_copy},
+    Config, DirectedStakeBasket, DirectedStakeBasketContribution, DirectedStakeMeta,
+    DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct ClearDirectedStakeTicketBasket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: Closed, or checked to no longer point at the basket in the handler
+    pub ticket_account: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeTicketExtension` PDA, which may not exist
+    pub ticket_extension: AccountInfo<'info>,
+
+    /// CHECK: Only used for its address
+    pub basket: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            DirectedStakeBasketContribution::SEED,
+            ticket_account.key().as_ref(),
+            basket.key().as_ref()
+        ],
+        bump
+    )]
+    pub basket_contribution: AccountLoader<'info, DirectedStakeBasketContribution>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Permissionless. Removes the directed stake targets a ticket contributes through a basket once the ticket has been
+closed or none of the preferences of the ticket and its extension point at the basket anymore, and closes the
+basket contribution account, returning the rent to the signer.
+*/
+pub fn handler(ctx: Context<ClearDirectedStakeTicketBasket>) -> Result<()> {
+    let ticket_closed =
+        ctx.accounts.ticket_account.data_is_empty() && ctx.accounts.ticket_account.lamports() == 0;
+    if !ticket_closed {
+        let ticket = load_zero_copy::<DirectedStakeTicket>(&ctx.accounts.ticket_account)?;
+        let ticket_extension = load_ticket_extension(
+            &ctx.accounts.ticket_extension,
+            &ctx.accounts.ticket_account.key(),
+        )?;
+
+        require!(
+            DirectedStakeBasket::ticket_stake_share_bps(
+                &ctx.accounts.basket.key(),
+                &ticket,
+                ticket_extension.as_deref(),
+            ) == 0,
+            StewardError::DirectedStakeBasketStillReferenced
+        );
+    }
+
+    let clock = Clock::get()?;
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let basket_contribution = ctx.accounts.basket_contribution.load()?;
+    require_keys_eq!(
+        basket_contribution.config,
+        ctx.accounts.config.key(),
+        StewardError::Unauthorized
+    );
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(&stake_meta, &basket_contribution.contributions);
+    for entry in basket_contribution.contributions.iter() {
+        stake_meta.remove_contribution_entry(entry, clock.epoch);
+    }
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, DirectedStakePreferenceSchedule, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct CloseDirectedStakePreferenceSchedule<'info> {
+    /// CHECK: Closed, or a ticket whose update authority is the signer, checked in the handler
+    pub ticket_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [DirectedStakePreferenceSchedule::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub preference_schedule: AccountLoader<'info, DirectedStakePreferenceSchedule>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Closes a ticket's preference schedule, returning the rent to the signer. Signed by the ticket update authority, or
+permissionless once the ticket has been closed.
+*/
+pub fn handler(ctx: Context<CloseDirectedStakePreferenceSchedule>) -> Result<()> {
+    let ticket_account = &ctx.accounts.ticket_account;
+    if ticket_account.data_is_empty() && ticket_account.lamports() == 0 {
+        return Ok(());
+    }
+
+    require_keys_eq!(
+        *ticket_account.owner,
+        crate::id(),
+        StewardError::Unauthorized
+    );
+    let data = ticket_account.try_borrow_data()?;
+    let ticket = DirectedStakeTicket::try_deserialize(&mut data.as_ref())?;
+    require_keys_eq!(
+        ticket.ticket_update_authority,
+        ctx.accounts.signer.key(),
+        StewardError::Unauthorized
+    );
+
+    Ok(())
+}
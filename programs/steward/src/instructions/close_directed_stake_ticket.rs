@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::StewardError, Config, DirectedStakePreference, DirectedStakeTicket,
-    DirectedStakeWhitelist,
+    errors::StewardError, events::DirectedStakeTicketClosedEvent, Config, DirectedStakePreference,
+    DirectedStakeTicket, DirectedStakeWhitelist,
 };
 use std::mem::size_of;
 
@@ -38,6 +38,7 @@ impl CloseDirectedStakeTicket<'_> {
         ticket_override_authority: &Pubkey,
     ) -> Result<()> {
         if !whitelist.is_staker_permissioned(signer_pubkey)
+            && !bool::from(ticket.is_permissionless)
             && signer_pubkey != ticket_override_authority
         {
             return Err(error!(StewardError::Unauthorized));
@@ -90,5 +91,14 @@ pub fn handler(ctx: Context<CloseDirectedStakeTicket>) -> Result<()> {
         &[],
         &config.directed_stake_ticket_override_authority,
     )?;
+
+    emit!(DirectedStakeTicketClosedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: ctx.accounts.authority.key(),
+        ticket_update_authority: ticket.ticket_update_authority,
+        preferences: ticket.preferences().to_vec(),
+        underfunded: false,
+    });
+
     Ok(())
 }
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    events::DirectedStakeTicketClosedEvent,
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{get_stake_pool_address, ticket_holder_token_balance},
+    Config, DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct CloseUnderfundedDirectedStakeTicket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        close = ticket_holder
+    )]
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    /// CHECK: Receives the ticket rent, checked against the ticket update authority
+    #[account(
+        mut,
+        address = ticket_account.load()?.ticket_update_authority @ StewardError::Unauthorized
+    )]
+    pub ticket_holder: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket holder's associated token account for the pool mint
+    pub holder_token_account: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeBalanceSources` PDA, which may not exist
+    pub balance_sources: AccountInfo<'info>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Closes a permissionless ticket whose holder's JitoSOL balance fell below
+`directed_stake_permissionless_ticket_min_balance`, returning the rent to the holder. The balance is counted like
+in `crank_directed_stake_ticket`, with `remaining_accounts` being the accounts of the ticket's balance sources, in
+order. While permissionless tickets are disabled (a minimum of 0), all permissionless tickets can be closed
+without checking the balance.
+
+The targets the ticket contributed are removed afterwards with `clear_closed_directed_stake_ticket`.
+*/
+pub fn handler(ctx: Context<CloseUnderfundedDirectedStakeTicket>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+    require!(
+        bool::from(ticket.is_permissionless),
+        StewardError::Unauthorized
+    );
+
+    let min_balance = ctx
+        .accounts
+        .config
+        .load()?
+        .parameters
+        .directed_stake_permissionless_ticket_min_balance;
+    if min_balance > 0 {
+        let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+        let balance = ticket_holder_token_balance(
+            &ctx.accounts.holder_token_account,
+            &ctx.accounts.balance_sources,
+            ctx.remaining_accounts,
+            &ctx.accounts.ticket_account.key(),
+            &ticket.ticket_update_authority,
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+        )?;
+        require!(
+            balance < min_balance,
+            StewardError::DirectedStakeBalanceAboveMinimum
+        );
+    }
+
+    emit!(DirectedStakeTicketClosedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: Pubkey::default(),
+        ticket_update_authority: ticket.ticket_update_authority,
+        preferences: ticket.preferences().to_vec(),
+        underfunded: true,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::utils::{get_validator_list, state_checks};
 use crate::{
-    maybe_transition, Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
+    errors::StewardError, maybe_transition, Config, CycleReport, StewardStateAccount,
+    StewardStateAccountV2, StewardStateEnum,
 };
 use anchor_lang::prelude::*;
 
@@ -18,6 +19,10 @@ pub struct ComputeDelegations<'info> {
     /// CHECK: Account owner checked, account type checked in get_validator_stake_info_at_index
     #[account(address = get_validator_list(&config)?)]
     pub validator_list: AccountInfo<'info>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
 }
 
 /*
@@ -38,10 +43,27 @@ pub fn handler(ctx: Context<ComputeDelegations>) -> Result<()> {
         Some(StewardStateEnum::ComputeDelegations),
     )?;
 
+    if config.parameters.cycle_reports_enabled() {
+        require!(
+            ctx.accounts.cycle_report.is_some(),
+            StewardError::CycleReportRequired
+        );
+    }
+
     state_account
         .state
         .compute_delegations(clock.epoch, &config)?;
 
+    if let Some(cycle_report) = &ctx.accounts.cycle_report {
+        let mut cycle_report = cycle_report.load_mut()?;
+        cycle_report.check_cycle(
+            &ctx.accounts.config.key(),
+            &state_account.state,
+            &epoch_schedule,
+        )?;
+        cycle_report.record_delegations(&state_account.state);
+    }
+
     if let Some(event) = maybe_transition(
         &mut state_account.state,
         &clock,
@@ -1,14 +1,21 @@
 use crate::{
+    constants::BASIS_POINTS_MAX,
     errors::StewardError,
+    events::{CommissionPledgeBrokenEvent, InstantUnstakeComponentsV3},
     maybe_transition,
-    utils::{get_validator_list, get_validator_stake_info_at_index, state_checks},
-    Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
+    utils::{
+        get_validator_list, get_validator_stake_info_at_index, load_commission_pledge,
+        state_checks, store_commission_pledge,
+    },
+    Config, CycleReport, InstantUnstakeFractions, ScoreWarnings, StewardStateAccount,
+    StewardStateAccountV2, StewardStateEnum,
 };
 use anchor_lang::prelude::*;
 use validator_history::{ClusterHistory, ValidatorHistory};
 
 #[derive(Accounts)]
 pub struct ComputeInstantUnstake<'info> {
+    #[account(mut)]
     pub config: AccountLoader<'info, Config>,
 
     #[account(
@@ -31,10 +38,33 @@ pub struct ComputeInstantUnstake<'info> {
         bump
     )]
     pub cluster_history: AccountLoader<'info, ClusterHistory>,
+
+    /// CHECK: Address checked in the handler. Empty if the validator has not created a commission pledge
+    #[account(mut)]
+    pub commission_pledge: AccountInfo<'info>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
+
+    /// Required when graded instant unstake is enabled
+    #[account(
+        mut,
+        seeds = [InstantUnstakeFractions::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub instant_unstake_fractions: Option<AccountLoader<'info, InstantUnstakeFractions>>,
+
+    /// Required when score warnings are enabled
+    #[account(
+        seeds = [ScoreWarnings::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub score_warnings: Option<AccountLoader<'info, ScoreWarnings>>,
 }
 
 pub fn handler(ctx: Context<ComputeInstantUnstake>, validator_list_index: usize) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
+    let mut config = ctx.accounts.config.load_mut()?;
     let mut state_account = ctx.accounts.state_account.load_mut()?;
     let validator_history = ctx.accounts.validator_history.load()?;
     let cluster = ctx.accounts.cluster_history.load()?;
@@ -68,14 +98,117 @@ pub fn handler(ctx: Context<ComputeInstantUnstake>, validator_list_index: usize)
         StewardError::ValidatorNotInList
     );
 
-    if let Some(instant_unstake) = state_account.state.compute_instant_unstake(
+    if config.parameters.graded_instant_unstake_enabled() {
+        require!(
+            ctx.accounts.instant_unstake_fractions.is_some(),
+            StewardError::InstantUnstakeFractionsRequired
+        );
+    }
+
+    if config.parameters.score_warnings_enabled() {
+        require!(
+            ctx.accounts.score_warnings.is_some(),
+            StewardError::ScoreWarningsRequired
+        );
+    }
+
+    if config.parameters.cycle_reports_enabled() {
+        require!(
+            ctx.accounts.cycle_report.is_some(),
+            StewardError::CycleReportRequired
+        );
+    }
+    let score_warnings = ctx
+        .accounts
+        .score_warnings
+        .as_ref()
+        .map(|score_warnings| score_warnings.load())
+        .transpose()?;
+
+    // Too many validators flagged in one cycle points to bad inputs rather than bad validators,
+    // so the validator that would go over the limit is not marked. The steward pauses until the
+    // admin resumes it, which acknowledges the validators flagged so far.
+    config.circuit_breaker_baseline.sync(&state_account.state)?;
+
+    let instant_unstake = match state_account.state.compute_instant_unstake(
         &clock,
         &epoch_schedule,
         &validator_history,
         validator_list_index,
         &cluster,
         &config,
-    )? {
+        score_warnings.as_deref(),
+    ) {
+        Err(error) if error == StewardError::InstantUnstakeCircuitBreakerTripped.into() => {
+            config.set_paused(true);
+            emit!(state_account
+                .state
+                .circuit_breaker_event(&clock, &config.parameters)?);
+            return Ok(());
+        }
+        instant_unstake => instant_unstake?,
+    };
+
+    if let Some(mut instant_unstake) = instant_unstake {
+        // Breaking a commission pledge fully unstakes the validator and starts the pledge cooldown
+        if let Some(mut commission_pledge) = load_commission_pledge(
+            &ctx.accounts.commission_pledge,
+            &ctx.accounts.config.key(),
+            &validator_history.vote_account,
+        )? {
+            if commission_pledge.is_breached(&validator_history, clock.epoch) {
+                if !instant_unstake.instant_unstake
+                    && state_account.state.instant_unstake_circuit_breaker_tripped(
+                        &config.parameters,
+                        &config.circuit_breaker_baseline,
+                        1,
+                    )
+                {
+                    // Left unprocessed so the validator is checked again once the steward is resumed
+                    state_account
+                        .state
+                        .progress
+                        .set(validator_list_index, false)?;
+                    config.set_paused(true);
+                    emit!(state_account
+                        .state
+                        .circuit_breaker_event(&clock, &config.parameters)?);
+                    return Ok(());
+                }
+
+                commission_pledge.mark_broken(
+                    clock.epoch,
+                    config.parameters.commission_pledge_cooldown_epochs,
+                );
+                store_commission_pledge(&ctx.accounts.commission_pledge, &commission_pledge)?;
+                state_account
+                    .state
+                    .instant_unstake
+                    .set(validator_list_index, true)?;
+                instant_unstake.instant_unstake = true;
+                instant_unstake.instant_unstake_fraction_bps = BASIS_POINTS_MAX;
+
+                emit!(CommissionPledgeBrokenEvent {
+                    vote_account: validator_history.vote_account,
+                    epoch: clock.epoch,
+                    cooldown_end_epoch: commission_pledge.cooldown_end_epoch,
+                });
+            }
+        }
+
+        if let Some(instant_unstake_fractions) = &ctx.accounts.instant_unstake_fractions {
+            let mut instant_unstake_fractions = instant_unstake_fractions.load_mut()?;
+            // Validators without a recorded fraction are fully unstaked
+            if !instant_unstake_fractions.record(
+                clock.epoch,
+                &instant_unstake.vote_account,
+                instant_unstake.instant_unstake_fraction_bps,
+            ) {
+                msg!("Instant unstake fractions full, fully unstaking validator");
+                instant_unstake.instant_unstake_fraction_bps = BASIS_POINTS_MAX;
+            }
+        }
+        emit!(InstantUnstakeComponentsV3::from(&instant_unstake));
         emit!(instant_unstake);
     }
 
@@ -88,5 +221,17 @@ pub fn handler(ctx: Context<ComputeInstantUnstake>, validator_list_index: usize)
         emit!(event);
     }
 
+    // Recorded after the transition, which recomputes delegations for validators added during Rebalance
+    if let Some(cycle_report) = &ctx.accounts.cycle_report {
+        let mut cycle_report = cycle_report.load_mut()?;
+        cycle_report.check_cycle(
+            &ctx.accounts.config.key(),
+            &state_account.state,
+            &epoch_schedule,
+        )?;
+        cycle_report.record_instant_unstake(&state_account.state);
+        cycle_report.record_delegations(&state_account.state);
+    }
+
     Ok(())
 }
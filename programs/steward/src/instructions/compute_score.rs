@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 use crate::{
     errors::StewardError,
+    events::ScoreWarningEvent,
     maybe_transition,
     utils::{
         get_validator_list, get_validator_list_length, get_validator_stake_info_at_index,
-        state_checks,
+        load_commission_pledge, state_checks,
     },
-    Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
+    Config, CycleReport, ScoreWarnings, StewardStateAccount, StewardStateAccountV2,
+    StewardStateEnum,
 };
 use validator_history::{ClusterHistory, ValidatorHistory};
 
@@ -34,6 +36,21 @@ pub struct ComputeScore<'info> {
         bump
     )]
     pub cluster_history: AccountLoader<'info, ClusterHistory>,
+
+    /// CHECK: Address checked in the handler. Empty if the validator has not created a commission pledge
+    pub commission_pledge: AccountInfo<'info>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
+
+    /// Required when score warnings are enabled
+    #[account(
+        mut,
+        seeds = [ScoreWarnings::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub score_warnings: Option<AccountLoader<'info, ScoreWarnings>>,
 }
 
 pub fn handler(ctx: Context<ComputeScore>, validator_list_index: usize) -> Result<()> {
@@ -84,8 +101,41 @@ pub fn handler(ctx: Context<ComputeScore>, validator_list_index: usize) -> Resul
         StewardError::InvalidState
     );
 
+    if config.parameters.score_warnings_enabled() {
+        require!(
+            ctx.accounts.score_warnings.is_some(),
+            StewardError::ScoreWarningsRequired
+        );
+    }
+
+    // Also required by the instruction starting the cycle, so no cycle begins without its report
+    if config.parameters.cycle_reports_enabled() {
+        require!(
+            ctx.accounts.cycle_report.is_some(),
+            StewardError::CycleReportRequired
+        );
+    }
+
+    let mut score_warnings = ctx
+        .accounts
+        .score_warnings
+        .as_ref()
+        .map(|score_warnings| score_warnings.load_mut())
+        .transpose()?;
+    let previously_warned = score_warnings
+        .as_ref()
+        .map(|score_warnings| score_warnings.warned.get(validator_history.index as usize))
+        .transpose()?
+        .unwrap_or(false);
+
+    let commission_pledge = load_commission_pledge(
+        &ctx.accounts.commission_pledge,
+        &ctx.accounts.config.key(),
+        &validator_history.vote_account,
+    )?;
+
     let num_pool_validators = get_validator_list_length(validator_list)?;
-    if let Some(score) = state_account.state.compute_score(
+    let score = state_account.state.compute_score(
         &clock,
         &epoch_schedule,
         &validator_history,
@@ -93,7 +143,44 @@ pub fn handler(ctx: Context<ComputeScore>, validator_list_index: usize) -> Resul
         &cluster_history,
         &config,
         num_pool_validators as u64,
-    )? {
+        score_warnings.as_deref_mut(),
+        commission_pledge.as_ref(),
+    )?;
+
+    let mut cycle_report = ctx
+        .accounts
+        .cycle_report
+        .as_ref()
+        .map(|cycle_report| cycle_report.load_mut())
+        .transpose()?;
+    if let Some(cycle_report) = &cycle_report {
+        cycle_report.check_cycle(
+            &ctx.accounts.config.key(),
+            &state_account.state,
+            &epoch_schedule,
+        )?;
+    }
+
+    if let Some(score) = score {
+        if let Some(score_warnings) = &score_warnings {
+            if !previously_warned
+                && score_warnings
+                    .warned
+                    .get(validator_history.index as usize)?
+            {
+                emit!(ScoreWarningEvent {
+                    vote_account: validator_history.vote_account,
+                    epoch: clock.epoch as u16,
+                    failed_filters: score.failed_filters(),
+                    grace_end_epoch: state_account.state.next_cycle_epoch,
+                });
+            }
+        }
+
+        if let Some(cycle_report) = &mut cycle_report {
+            cycle_report.record_score(&state_account.state, &score);
+        }
+
         emit!(score);
     }
 
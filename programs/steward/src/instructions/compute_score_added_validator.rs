@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    events::ScoreWarningEvent,
+    utils::{
+        get_validator_list, get_validator_stake_info_at_index, load_commission_pledge, state_checks,
+    },
+    Config, CycleReport, ScoreWarnings, StewardStateAccount, StewardStateAccountV2,
+    COMPUTE_DELEGATIONS,
+};
+use validator_history::{ClusterHistory, ValidatorHistory};
+
+#[derive(Accounts)]
+pub struct ComputeScoreAddedValidator<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    pub validator_history: AccountLoader<'info, ValidatorHistory>,
+
+    /// CHECK: Account owner checked, account type checked in get_validator_stake_info_at_index
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+
+    #[account(
+        seeds = [ClusterHistory::SEED],
+        seeds::program = validator_history::id(),
+        bump
+    )]
+    pub cluster_history: AccountLoader<'info, ClusterHistory>,
+
+    /// CHECK: Address checked in the handler. Empty if the validator has not created a commission pledge
+    pub commission_pledge: AccountInfo<'info>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
+
+    /// Required when score warnings are enabled
+    #[account(
+        mut,
+        seeds = [ScoreWarnings::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub score_warnings: Option<AccountLoader<'info, ScoreWarnings>>,
+}
+
+/*
+Permissionless. Scores a validator that was added to the pool after ComputeScores finished,
+so that it can be delegated to in the current cycle. Does not change the state machine state.
+*/
+pub fn handler(
+    ctx: Context<ComputeScoreAddedValidator>,
+    validator_list_index: usize,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let mut state_account = ctx.accounts.state_account.load_mut()?;
+    let validator_history = ctx.accounts.validator_history.load()?;
+    let cluster_history = ctx.accounts.cluster_history.load()?;
+    let validator_list = &ctx.accounts.validator_list;
+    let clock: Clock = Clock::get()?;
+    let epoch_schedule = EpochSchedule::get()?;
+
+    // Allowed in any state, the state method checks that scoring for the cycle is complete
+    state_checks(&clock, &config, &state_account, validator_list, None)?;
+
+    let validator_stake_info =
+        get_validator_stake_info_at_index(validator_list, validator_list_index)?;
+    require!(
+        validator_stake_info.vote_account_address == validator_history.vote_account,
+        StewardError::ValidatorNotInList
+    );
+
+    if config.parameters.score_warnings_enabled() {
+        require!(
+            ctx.accounts.score_warnings.is_some(),
+            StewardError::ScoreWarningsRequired
+        );
+    }
+
+    if config.parameters.cycle_reports_enabled() {
+        require!(
+            ctx.accounts.cycle_report.is_some(),
+            StewardError::CycleReportRequired
+        );
+    }
+
+    let mut score_warnings = ctx
+        .accounts
+        .score_warnings
+        .as_ref()
+        .map(|score_warnings| score_warnings.load_mut())
+        .transpose()?;
+    let previously_warned = score_warnings
+        .as_ref()
+        .map(|score_warnings| score_warnings.warned.get(validator_history.index as usize))
+        .transpose()?
+        .unwrap_or(false);
+
+    let commission_pledge = load_commission_pledge(
+        &ctx.accounts.commission_pledge,
+        &ctx.accounts.config.key(),
+        &validator_history.vote_account,
+    )?;
+
+    let score = state_account.state.compute_score_added_validator(
+        &clock,
+        &epoch_schedule,
+        &validator_history,
+        validator_list_index,
+        &cluster_history,
+        &config,
+        score_warnings.as_deref_mut(),
+        commission_pledge.as_ref(),
+    )?;
+
+    if let Some(score_warnings) = &score_warnings {
+        if !previously_warned
+            && score_warnings
+                .warned
+                .get(validator_history.index as usize)?
+        {
+            emit!(ScoreWarningEvent {
+                vote_account: validator_history.vote_account,
+                epoch: clock.epoch as u16,
+                failed_filters: score.failed_filters(),
+                grace_end_epoch: state_account.state.next_cycle_epoch,
+            });
+        }
+    }
+
+    if let Some(cycle_report) = &ctx.accounts.cycle_report {
+        let mut cycle_report = cycle_report.load_mut()?;
+        cycle_report.check_cycle(
+            &ctx.accounts.config.key(),
+            &state_account.state,
+            &epoch_schedule,
+        )?;
+        cycle_report.record_score(&state_account.state, &score);
+        if state_account.state.has_flag(COMPUTE_DELEGATIONS) {
+            cycle_report.record_delegations(&state_account.state);
+        }
+    }
+
+    emit!(score);
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::directed_stake::{DirectedStakeMeta, DirectedStakeTarget};
+use crate::events::CopyDirectedStakeTargetsEvent;
+use crate::state::directed_stake::DirectedStakeMeta;
 use crate::utils::get_validator_list;
+use crate::utils::load_validator_consent;
 use crate::utils::vote_pubkey_at_validator_list_index;
 use crate::{errors::StewardError, Config};
 use spl_stake_pool::state::ValidatorListHeader;
@@ -32,6 +34,9 @@ pub struct CopyDirectedStakeTargets<'info> {
         address = config.load()?.directed_stake_meta_upload_authority @ StewardError::Unauthorized
     )]
     pub authority: Signer<'info>,
+
+    /// CHECK: The validator's `DirectedStakeValidatorConsent` PDA, checked in the handler. May not exist.
+    pub validator_consent: AccountInfo<'info>,
 }
 
 impl CopyDirectedStakeTargets<'_> {
@@ -60,28 +65,44 @@ pub fn handler(
         return Err(error!(StewardError::Unauthorized));
     }
 
-    let clock = Clock::get()?;
-    match stake_meta.get_target_index(&vote_pubkey) {
-        Some(target_index) => {
-            msg!("Updating target index: {}", target_index);
-            stake_meta.targets[target_index].total_target_lamports = target_lamports;
-            stake_meta.targets[target_index].target_last_updated_epoch = clock.epoch;
-        }
-        None => {
-            let new_target = DirectedStakeTarget {
-                vote_pubkey,
-                total_target_lamports: target_lamports,
-                total_staked_lamports: 0,
-                target_last_updated_epoch: clock.epoch,
-                staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
-            };
-            let target_index = stake_meta.total_stake_targets as usize;
-            stake_meta.targets[target_index] = new_target;
-            stake_meta.total_stake_targets += 1;
-            stake_meta.directed_stake_lamports[validator_list_index] = 0;
-            stake_meta.directed_stake_meta_indices[validator_list_index] = target_index as u64;
-        }
+    // After `migrate_directed_targets_to_tickets`, targets are computed from tickets alone
+    if stake_meta.ticket_targets_only() {
+        msg!("Targets are managed by directed stake tickets");
+        return Err(error!(StewardError::DirectedStakeTargetManagedByTickets));
     }
+
+    // Tickets are not cranked before the migration, so this sets the whole target
+    let clock = Clock::get()?;
+    let target_index =
+        stake_meta.get_or_create_target_index(&vote_pubkey, validator_list_index, clock.epoch)?;
+    msg!("Updating target index: {}", target_index);
+    let target = &mut stake_meta.targets[target_index];
+
+    let requested_target_lamports = target_lamports;
+    let previous_total_target_lamports = target.total_target_lamports;
+
+    // The upload is limited by the validator's consent
+    let target_lamports = match load_validator_consent(
+        &ctx.accounts.validator_consent,
+        &ctx.accounts.config.key(),
+        &vote_pubkey,
+    )? {
+        Some(consent) => consent.accepted_lamports(target_lamports, 0),
+        None => target_lamports,
+    };
+    target.total_target_lamports = target_lamports;
+    target.unattributed_target_lamports = 0;
+    target.target_last_updated_epoch = clock.epoch;
+
+    emit!(CopyDirectedStakeTargetsEvent {
+        signer: ctx.accounts.authority.key(),
+        vote_pubkey,
+        validator_list_index: validator_list_index as u64,
+        epoch: clock.epoch,
+        requested_target_lamports,
+        previous_total_target_lamports,
+        total_target_lamports: target.total_target_lamports,
+    });
+
     Ok(())
 }
@@ -0,0 +1,463 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use spl_stake_pool::state::{StakePool, ValidatorListHeader};
+
+use crate::{
+    errors::StewardError,
+    events::DirectedStakeTicketTargetsChangedEvent,
+    stake_pool_utils::deserialize_stake_pool,
+    state::directed_stake::DirectedStakePreference,
+    utils::{
+        get_stake_pool_address, get_validator_list, load_validator_consent,
+        require_transaction_start_balances, ticket_holder_token_balance, validator_exists_in_list,
+        vote_pubkey_at_validator_list_index, U8Bool,
+    },
+    Config, DirectedStakeContribution, DirectedStakeContributionEntry, DirectedStakeMeta,
+    DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct CrankDirectedStakeTicket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    /// CHECK: Verified to be the ticket holder's associated token account for the pool mint
+    pub holder_token_account: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeBalanceSources` PDA, which may not exist
+    pub balance_sources: AccountInfo<'info>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: Used to verify the validator list index of each preference
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+    /// CHECK: Instructions sysvar, used to verify that the balance was not borrowed for the transaction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+/// Converts `stake_share_bps` of `token_balance` pool tokens to lamports at the pool's current exchange rate
+pub fn ticket_allocation_lamports(
+    token_balance: u64,
+    stake_share_bps: u16,
+    total_lamports: u64,
+    pool_token_supply: u64,
+) -> Result<u64> {
+    if pool_token_supply == 0 {
+        return Ok(0);
+    }
+    let allocated_tokens = (token_balance as u128)
+        .checked_mul(stake_share_bps as u128)
+        .ok_or(StewardError::ArithmeticError)?
+        / 10_000;
+    let lamports = allocated_tokens
+        .checked_mul(total_lamports as u128)
+        .ok_or(StewardError::ArithmeticError)?
+        / pool_token_supply as u128;
+    u64::try_from(lamports).map_err(|_| error!(StewardError::ArithmeticCastError))
+}
+
+/// Ticket portions of the directed stake targets touched by a crank, as they were before it
+#[derive(Default)]
+pub struct TicketTargetChanges {
+    previous: Vec<(usize, u64)>,
+}
+
+impl TicketTargetChanges {
+    /// Records the ticket portion of the target at `target_index` the first time it is touched
+    pub fn track(&mut self, stake_meta: &DirectedStakeMeta, target_index: usize) {
+        if target_index < stake_meta.targets.len()
+            && !self
+                .previous
+                .iter()
+                .any(|(index, _)| *index == target_index)
+        {
+            self.previous.push((
+                target_index,
+                stake_meta.targets[target_index].ticket_target_lamports,
+            ));
+        }
+    }
+
+    /// Records the targets of the `entries` that contribute lamports
+    pub fn track_entries(
+        &mut self,
+        stake_meta: &DirectedStakeMeta,
+        entries: &[DirectedStakeContributionEntry],
+    ) {
+        for entry in entries.iter().filter(|entry| entry.lamports > 0) {
+            self.track(stake_meta, entry.target_index as usize);
+        }
+    }
+
+    /// `(vote_pubkey, previous_ticket_target_lamports, ticket_target_lamports)` of the tracked targets that changed
+    pub fn changes(&self, stake_meta: &DirectedStakeMeta) -> Vec<(Pubkey, u64, u64)> {
+        self.previous
+            .iter()
+            .filter_map(|(target_index, previous)| {
+                let target = &stake_meta.targets[*target_index];
+                (target.ticket_target_lamports != *previous).then_some((
+                    target.vote_pubkey,
+                    *previous,
+                    target.ticket_target_lamports,
+                ))
+            })
+            .collect()
+    }
+
+    /// Emits `DirectedStakeTicketTargetsChangedEvent` for the tracked targets that changed, if any
+    pub fn emit(&self, stake_meta: &DirectedStakeMeta, ticket: Pubkey, epoch: u64) {
+        let changes = self.changes(stake_meta);
+        if changes.is_empty() {
+            return;
+        }
+        emit!(DirectedStakeTicketTargetsChangedEvent {
+            ticket,
+            epoch,
+            vote_pubkeys: changes.iter().map(|change| change.0).collect(),
+            previous_ticket_target_lamports: changes.iter().map(|change| change.1).collect(),
+            ticket_target_lamports: changes.iter().map(|change| change.2).collect(),
+        });
+    }
+}
+
+/// Adds the contributions of the non-basket `preferences` of a ticket holding `token_balance` pool tokens.
+/// `validator_list_indices[i]` is the validator list index of the i-th preference, or u32::MAX if the
+/// validator is not in the pool, and `consent_accounts[i]` is the `DirectedStakeValidatorConsent` PDA of its
+/// validator.
+#[allow(clippy::too_many_arguments)]
+pub fn add_preference_contributions(
+    stake_meta: &mut DirectedStakeMeta,
+    contribution: &mut DirectedStakeContribution,
+    target_changes: &mut TicketTargetChanges,
+    preferences: &[DirectedStakePreference],
+    validator_list_indices: &[u32],
+    consent_accounts: &[AccountInfo],
+    config: &Pubkey,
+    token_balance: u64,
+    stake_pool: &StakePool,
+    validator_list_info: &AccountInfo,
+    epoch: u64,
+) -> Result<()> {
+    // Validators claimed to be outside of the pool are checked after the validator list borrow is released
+    let mut validators_not_in_pool: Vec<Pubkey> = Vec::new();
+    let replaces_migrated = !bool::from(contribution.migrated_target_replaced);
+
+    {
+        let mut validator_list_data = validator_list_info.try_borrow_mut_data()?;
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        require!(
+            header.account_type == spl_stake_pool::state::AccountType::ValidatorList,
+            StewardError::ValidatorListTypeMismatch
+        );
+
+        for (i, (preference, validator_list_index)) in preferences
+            .iter()
+            .zip(validator_list_indices.iter())
+            .enumerate()
+        {
+            // Baskets are expanded by crank_directed_stake_ticket_basket
+            if preference.is_basket.into() {
+                continue;
+            }
+            if *validator_list_index == u32::MAX {
+                validators_not_in_pool.push(preference.vote_pubkey);
+                continue;
+            }
+
+            let validator_list_index = *validator_list_index as usize;
+            require!(
+                validator_list_index < validator_list.len() as usize,
+                StewardError::ValidatorIndexOutOfBounds
+            );
+            let validator_list_vote_pubkey =
+                vote_pubkey_at_validator_list_index(&validator_list, validator_list_index)?;
+            require_keys_eq!(
+                validator_list_vote_pubkey,
+                preference.vote_pubkey,
+                StewardError::DirectedStakeVoteAccountMismatch
+            );
+
+            let lamports = ticket_allocation_lamports(
+                token_balance,
+                preference.stake_share_bps,
+                stake_pool.total_lamports,
+                stake_pool.pool_token_supply,
+            )?;
+            if lamports == 0 {
+                continue;
+            }
+
+            let target_index = stake_meta.get_or_create_target_index(
+                &preference.vote_pubkey,
+                validator_list_index,
+                epoch,
+            )?;
+            let lamports = match load_validator_consent(
+                &consent_accounts[i],
+                config,
+                &preference.vote_pubkey,
+            )? {
+                Some(consent) => consent.accepted_lamports(
+                    lamports,
+                    stake_meta.targets[target_index].total_target_lamports,
+                ),
+                None => lamports,
+            };
+            if lamports == 0 {
+                continue;
+            }
+            target_changes.track(stake_meta, target_index);
+            stake_meta.add_ticket_contribution(target_index, lamports, replaces_migrated, epoch);
+
+            let entry_index = contribution.num_contributions as usize;
+            contribution.contributions[entry_index] = DirectedStakeContributionEntry {
+                vote_pubkey: preference.vote_pubkey,
+                target_index: target_index as u64,
+                lamports,
+                applied_epoch: epoch,
+            };
+            contribution.num_contributions += 1;
+        }
+    }
+
+    for vote_pubkey in validators_not_in_pool.iter() {
+        require!(
+            !validator_exists_in_list(validator_list_info, vote_pubkey)?,
+            StewardError::InvalidValidatorListIndices
+        );
+    }
+    contribution.migrated_target_replaced = U8Bool::from(true);
+
+    Ok(())
+}
+
+/*
+Permissionless. Recomputes a ticket's directed stake targets from the ticket holder's pool token balance,
+replacing whatever the ticket contributed on its previous crank.
+
+Each crank also samples the balance, and the targets are computed from the time-weighted average balance
+of the previous epoch, so `directed_stake_sample_interval_slots` must be set. Only steward and compute
+budget instructions may precede the crank in its transaction, so a sampled balance cannot be flash loaned.
+
+`validator_list_indices[i]` is the validator list index of the i-th preference, or u32::MAX if the
+validator is not in the pool, and `remaining_accounts[i]` is the `DirectedStakeValidatorConsent` PDA of its
+validator. Validators that opted out receive nothing and targets are capped at the validator's maximum.
+The following `remaining_accounts` are the accounts of the ticket's balance sources, in order, whose
+token accounts held by the ticket holder count toward the holder's balance.
+
+Preferences pointing at a `DirectedStakeBasket` are skipped here and expanded into the basket validators by
+`crank_directed_stake_ticket_basket`, which uses the balance of this crank.
+*/
+pub fn handler(
+    ctx: Context<CrankDirectedStakeTicket>,
+    validator_list_indices: Vec<u32>,
+) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    // Uploaded targets already include ticket holders' balances until the migration sets them aside
+    require!(
+        ctx.accounts
+            .directed_stake_meta
+            .load()?
+            .ticket_targets_only(),
+        StewardError::DirectedStakeTargetsNotMigrated
+    );
+
+    // A spot balance could be borrowed for the duration of the crank
+    let sample_interval_slots = ctx
+        .accounts
+        .config
+        .load()?
+        .parameters
+        .directed_stake_sample_interval_slots;
+    require!(
+        sample_interval_slots > 0,
+        StewardError::DirectedStakeSamplingDisabled
+    );
+
+    let num_preferences = ticket.num_preferences as usize;
+    require!(
+        validator_list_indices.len() == num_preferences,
+        StewardError::InvalidValidatorListIndices
+    );
+    require!(
+        ctx.remaining_accounts.len() >= num_preferences,
+        StewardError::InvalidValidatorConsent
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+    require_transaction_start_balances(&ctx.accounts.instructions)?;
+    let current_balance = ticket_holder_token_balance(
+        &ctx.accounts.holder_token_account,
+        &ctx.accounts.balance_sources,
+        &ctx.remaining_accounts[num_preferences..],
+        &ctx.accounts.ticket_account.key(),
+        &ticket.ticket_update_authority,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+    )?;
+
+    let clock = Clock::get()?;
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let mut contribution = ctx.accounts.contribution_account.load_mut()?;
+
+    contribution.record_balance_sample(
+        current_balance,
+        clock.slot,
+        sample_interval_slots,
+        &EpochSchedule::get()?,
+    );
+    let token_balance = contribution.average_balance;
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(
+        &stake_meta,
+        &contribution.contributions[..contribution.num_contributions as usize],
+    );
+    stake_meta.remove_ticket_contributions(&mut contribution, clock.epoch);
+
+    add_preference_contributions(
+        &mut stake_meta,
+        &mut contribution,
+        &mut target_changes,
+        &ticket.staker_preferences[..num_preferences],
+        &validator_list_indices,
+        &ctx.remaining_accounts[..num_preferences],
+        &ctx.accounts.config.key(),
+        token_balance,
+        &stake_pool,
+        &ctx.accounts.validator_list,
+        clock.epoch,
+    )?;
+
+    contribution.token_balance = token_balance;
+    contribution.last_updated_epoch = clock.epoch;
+
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_contributions_are_replaced() {
+        let mut meta: Box<DirectedStakeMeta> = Box::new(bytemuck::Zeroable::zeroed());
+        meta.directed_stake_meta_indices = [u64::MAX; crate::constants::MAX_VALIDATORS];
+        let mut contribution: Box<DirectedStakeContribution> =
+            Box::new(bytemuck::Zeroable::zeroed());
+        let vote_pubkey = Pubkey::new_unique();
+
+        // 10 pool tokens at 2 SOL per token, 60% directed to the validator
+        let lamports = ticket_allocation_lamports(10_000, 6_000, 200, 100).unwrap();
+        assert_eq!(lamports, 12_000);
+        assert_eq!(
+            ticket_allocation_lamports(10_000, 6_000, 200, 0).unwrap(),
+            0
+        );
+
+        let target_index = meta.get_or_create_target_index(&vote_pubkey, 3, 1).unwrap();
+        assert_eq!(meta.directed_stake_meta_indices[3], target_index as u64);
+        meta.add_ticket_contribution(target_index, lamports, false, 1);
+        contribution.contributions[0] = DirectedStakeContributionEntry {
+            vote_pubkey,
+            target_index: target_index as u64,
+            lamports,
+            applied_epoch: 1,
+        };
+        contribution.num_contributions = 1;
+
+        // Uploaded portion on top of the ticket contribution
+        meta.targets[target_index].total_target_lamports += 5_000;
+
+        let mut target_changes = TicketTargetChanges::default();
+        target_changes.track_entries(&meta, &contribution.contributions[..1]);
+        meta.remove_ticket_contributions(&mut contribution, 2);
+        assert_eq!(
+            target_changes.changes(&meta),
+            vec![(vote_pubkey, lamports, 0)]
+        );
+        assert_eq!(contribution.num_contributions, 0);
+        assert_eq!(meta.targets[target_index].total_target_lamports, 5_000);
+        assert_eq!(meta.targets[target_index].ticket_target_lamports, 0);
+        assert_eq!(
+            meta.get_or_create_target_index(&vote_pubkey, 3, 2).unwrap(),
+            target_index
+        );
+        assert_eq!(meta.total_stake_targets, 1);
+    }
+
+    #[test]
+    fn test_time_weighted_balance_samples() {
+        // 4 buckets of 25 slots per epoch
+        let epoch_schedule = EpochSchedule::custom(100, 100, false);
+        let mut contribution: Box<DirectedStakeContribution> =
+            Box::new(bytemuck::Zeroable::zeroed());
+
+        // A balance spike held for a single crank starts sampling epoch 5 and earns nothing
+        contribution.record_balance_sample(1_000_000, 590, 25, &epoch_schedule);
+        assert_eq!(contribution.sample_epoch, 5);
+        assert_eq!(contribution.average_balance_epoch, 4);
+        assert_eq!(contribution.average_balance, 0);
+
+        // Epoch 5 was only sampled from the spike on, so it is not credited either
+        contribution.record_balance_sample(1_000, 605, 25, &epoch_schedule);
+        assert_eq!(contribution.average_balance_epoch, 5);
+        assert_eq!(contribution.average_balance, 0);
+
+        // Borrowed balance in the same bucket only counts the lowest sample
+        contribution.record_balance_sample(1_000_000, 620, 25, &epoch_schedule);
+        assert_eq!(contribution.sample_bucket_min_balance, 1_000);
+
+        // Bucket 1 is never sampled, buckets 2 and 3 are
+        contribution.record_balance_sample(2_000, 660, 25, &epoch_schedule);
+        contribution.record_balance_sample(3_000, 690, 25, &epoch_schedule);
+
+        // Epoch 6 was sampled from its start, the next epoch finalizes (1_000 + 0 + 2_000 + 3_000) / 4
+        contribution.record_balance_sample(5_000, 705, 25, &epoch_schedule);
+        assert_eq!(contribution.average_balance_epoch, 6);
+        assert_eq!(contribution.average_balance, 1_500);
+
+        // Skipping an epoch entirely restarts sampling, with nothing credited until a full epoch is sampled again
+        contribution.record_balance_sample(4_000, 905, 25, &epoch_schedule);
+        assert_eq!(contribution.average_balance_epoch, 8);
+        assert_eq!(contribution.average_balance, 0);
+        assert_eq!(contribution.sample_start_epoch, 9);
+    }
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use spl_stake_pool::state::ValidatorListHeader;
+
+use crate::{
+    errors::StewardError,
+    instructions::crank_directed_stake_ticket::{ticket_allocation_lamports, TicketTargetChanges},
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{
+        get_stake_pool_address, get_validator_list, load_ticket_extension, load_validator_consent,
+        validator_exists_in_list, vote_pubkey_at_validator_list_index, U8Bool,
+    },
+    Config, DirectedStakeBasket, DirectedStakeBasketContribution, DirectedStakeContribution,
+    DirectedStakeContributionEntry, DirectedStakeMeta, DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct CrankDirectedStakeTicketBasket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeTicketExtension` PDA, which may not exist
+    pub ticket_extension: AccountInfo<'info>,
+
+    #[account(has_one = config)]
+    pub basket: AccountLoader<'info, DirectedStakeBasket>,
+
+    #[account(
+        mut,
+        seeds = [
+            DirectedStakeBasketContribution::SEED,
+            ticket_account.key().as_ref(),
+            basket.key().as_ref()
+        ],
+        bump
+    )]
+    pub basket_contribution: AccountLoader<'info, DirectedStakeBasketContribution>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: Used to verify the validator list index of each basket validator
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Recomputes the directed stake targets a ticket contributes through a basket for the basket
+validators in [start_index, start_index + validator_list_indices.len()), replacing what they contributed on their
+previous crank. The stake shares of every preference of the ticket and its extension pointing at the basket are
+split across the basket validators by weight. Contributions past the basket's current number of validators are
+removed on every crank.
+
+Targets are computed from the balance used by the ticket's last `crank_directed_stake_ticket`, which must have
+run in the current epoch.
+
+`validator_list_indices[i]` is the validator list index of the (start_index + i)-th basket validator, or
+u32::MAX if the validator is not in the pool, and `remaining_accounts[i]` is the `DirectedStakeValidatorConsent`
+PDA of its validator.
+*/
+pub fn handler(
+    ctx: Context<CrankDirectedStakeTicketBasket>,
+    start_index: u16,
+    validator_list_indices: Vec<u32>,
+) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let ticket_extension = load_ticket_extension(
+        &ctx.accounts.ticket_extension,
+        &ctx.accounts.ticket_account.key(),
+    )?;
+
+    let clock = Clock::get()?;
+    let contribution = ctx.accounts.contribution_account.load()?;
+    require!(
+        contribution.last_updated_epoch == clock.epoch,
+        StewardError::DirectedStakeTicketNotCranked
+    );
+
+    let basket = ctx.accounts.basket.load()?;
+    let num_validators = basket.num_validators as usize;
+    let start_index = start_index as usize;
+    require!(
+        start_index + validator_list_indices.len() <= num_validators,
+        StewardError::InvalidValidatorListIndices
+    );
+    require!(
+        ctx.remaining_accounts.len() == validator_list_indices.len(),
+        StewardError::InvalidValidatorConsent
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+    // Stake shares of a ticket and its extension never sum past 10_000 bps
+    let stake_share_bps = DirectedStakeBasket::ticket_stake_share_bps(
+        &ctx.accounts.basket.key(),
+        &ticket,
+        ticket_extension.as_deref(),
+    );
+    let basket_lamports = ticket_allocation_lamports(
+        contribution.token_balance,
+        stake_share_bps.min(10_000) as u16,
+        stake_pool.total_lamports,
+        stake_pool.pool_token_supply,
+    )?;
+
+    // Validators claimed to be outside of the pool are checked after the validator list borrow is released
+    let mut validators_not_in_pool: Vec<Pubkey> = Vec::new();
+
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let mut basket_contribution = ctx.accounts.basket_contribution.load_mut()?;
+    let replaces_migrated = !bool::from(basket_contribution.migrated_target_replaced);
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(
+        &stake_meta,
+        &basket_contribution.contributions[num_validators..],
+    );
+    for entry in basket_contribution.contributions[num_validators..].iter_mut() {
+        stake_meta.remove_contribution_entry(entry, clock.epoch);
+        *entry = DirectedStakeContributionEntry::default();
+    }
+
+    {
+        let mut validator_list_data = ctx.accounts.validator_list.try_borrow_mut_data()?;
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        require!(
+            header.account_type == spl_stake_pool::state::AccountType::ValidatorList,
+            StewardError::ValidatorListTypeMismatch
+        );
+
+        for (i, validator_list_index) in validator_list_indices.iter().enumerate() {
+            let basket_index = start_index + i;
+            let basket_entry = basket.validators[basket_index];
+
+            let entry = basket_contribution.contributions[basket_index];
+            target_changes.track_entries(&stake_meta, std::slice::from_ref(&entry));
+            stake_meta.remove_contribution_entry(&entry, clock.epoch);
+            basket_contribution.contributions[basket_index] =
+                DirectedStakeContributionEntry::default();
+
+            if *validator_list_index == u32::MAX {
+                validators_not_in_pool.push(basket_entry.vote_pubkey);
+                continue;
+            }
+
+            let validator_list_index = *validator_list_index as usize;
+            require!(
+                validator_list_index < validator_list.len() as usize,
+                StewardError::ValidatorIndexOutOfBounds
+            );
+            let validator_list_vote_pubkey =
+                vote_pubkey_at_validator_list_index(&validator_list, validator_list_index)?;
+            require_keys_eq!(
+                validator_list_vote_pubkey,
+                basket_entry.vote_pubkey,
+                StewardError::DirectedStakeVoteAccountMismatch
+            );
+
+            let lamports = (basket_lamports as u128)
+                .saturating_mul(basket_entry.weight_bps as u128)
+                .saturating_div(10_000) as u64;
+            if lamports == 0 {
+                continue;
+            }
+
+            let target_index = stake_meta.get_or_create_target_index(
+                &basket_entry.vote_pubkey,
+                validator_list_index,
+                clock.epoch,
+            )?;
+            let lamports = match load_validator_consent(
+                &ctx.remaining_accounts[i],
+                &ctx.accounts.config.key(),
+                &basket_entry.vote_pubkey,
+            )? {
+                Some(consent) => consent.accepted_lamports(
+                    lamports,
+                    stake_meta.targets[target_index].total_target_lamports,
+                ),
+                None => lamports,
+            };
+            if lamports == 0 {
+                continue;
+            }
+            target_changes.track(&stake_meta, target_index);
+            stake_meta.add_ticket_contribution(
+                target_index,
+                lamports,
+                replaces_migrated,
+                clock.epoch,
+            );
+
+            basket_contribution.contributions[basket_index] = DirectedStakeContributionEntry {
+                vote_pubkey: basket_entry.vote_pubkey,
+                target_index: target_index as u64,
+                lamports,
+                applied_epoch: clock.epoch,
+            };
+        }
+    }
+
+    for vote_pubkey in validators_not_in_pool.iter() {
+        require!(
+            !validator_exists_in_list(&ctx.accounts.validator_list, vote_pubkey)?,
+            StewardError::InvalidValidatorListIndices
+        );
+    }
+
+    basket_contribution.migrated_target_replaced = U8Bool::from(true);
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
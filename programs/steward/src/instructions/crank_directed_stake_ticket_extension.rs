@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use spl_stake_pool::state::ValidatorListHeader;
+
+use crate::{
+    errors::StewardError,
+    instructions::crank_directed_stake_ticket::{ticket_allocation_lamports, TicketTargetChanges},
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{
+        get_stake_pool_address, get_validator_list, load_validator_consent,
+        validator_exists_in_list, vote_pubkey_at_validator_list_index, U8Bool,
+    },
+    Config, DirectedStakeContribution, DirectedStakeContributionEntry,
+    DirectedStakeExtensionContribution, DirectedStakeMeta, DirectedStakeTicket,
+    DirectedStakeTicketExtension,
+};
+
+#[derive(Accounts)]
+pub struct CrankDirectedStakeTicketExtension<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    #[account(
+        seeds = [DirectedStakeTicketExtension::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub ticket_extension: AccountLoader<'info, DirectedStakeTicketExtension>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeExtensionContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub extension_contribution: AccountLoader<'info, DirectedStakeExtensionContribution>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: Used to verify the validator list index of each preference
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Recomputes the directed stake targets of the extension preferences in
+[start_index, start_index + validator_list_indices.len()), replacing what they contributed on their previous crank.
+Contributions of preferences past the extension's current number of preferences are removed on every crank.
+
+Targets are computed from the balance used by the ticket's last `crank_directed_stake_ticket`, which must have
+run in the current epoch.
+
+`validator_list_indices[i]` is the validator list index of the (start_index + i)-th extension preference, or
+u32::MAX if the validator is not in the pool, and `remaining_accounts[i]` is the `DirectedStakeValidatorConsent`
+PDA of its validator. Preferences pointing at a `DirectedStakeBasket` are skipped, they are expanded by
+`crank_directed_stake_ticket_basket`.
+*/
+pub fn handler(
+    ctx: Context<CrankDirectedStakeTicketExtension>,
+    start_index: u16,
+    validator_list_indices: Vec<u32>,
+) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    let contribution = ctx.accounts.contribution_account.load()?;
+    require!(
+        contribution.last_updated_epoch == clock.epoch,
+        StewardError::DirectedStakeTicketNotCranked
+    );
+    let token_balance = contribution.token_balance;
+
+    let ticket_extension = ctx.accounts.ticket_extension.load()?;
+    let num_preferences = ticket_extension.num_preferences as usize;
+    let start_index = start_index as usize;
+    require!(
+        start_index + validator_list_indices.len() <= num_preferences,
+        StewardError::InvalidValidatorListIndices
+    );
+    require!(
+        ctx.remaining_accounts.len() == validator_list_indices.len(),
+        StewardError::InvalidValidatorConsent
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+
+    // Validators claimed to be outside of the pool are checked after the validator list borrow is released
+    let mut validators_not_in_pool: Vec<Pubkey> = Vec::new();
+
+    let mut stake_meta = ctx.accounts.directed_stake_meta.load_mut()?;
+    let mut extension_contribution = ctx.accounts.extension_contribution.load_mut()?;
+    let replaces_migrated = !bool::from(extension_contribution.migrated_target_replaced);
+
+    let mut target_changes = TicketTargetChanges::default();
+    target_changes.track_entries(
+        &stake_meta,
+        &extension_contribution.contributions[num_preferences..],
+    );
+    for entry in extension_contribution.contributions[num_preferences..].iter_mut() {
+        stake_meta.remove_contribution_entry(entry, clock.epoch);
+        *entry = DirectedStakeContributionEntry::default();
+    }
+
+    {
+        let mut validator_list_data = ctx.accounts.validator_list.try_borrow_mut_data()?;
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        require!(
+            header.account_type == spl_stake_pool::state::AccountType::ValidatorList,
+            StewardError::ValidatorListTypeMismatch
+        );
+
+        for (i, validator_list_index) in validator_list_indices.iter().enumerate() {
+            let preference_index = start_index + i;
+            let preference = ticket_extension.staker_preferences[preference_index];
+
+            let entry = extension_contribution.contributions[preference_index];
+            target_changes.track_entries(&stake_meta, std::slice::from_ref(&entry));
+            stake_meta.remove_contribution_entry(&entry, clock.epoch);
+            extension_contribution.contributions[preference_index] =
+                DirectedStakeContributionEntry::default();
+
+            // Baskets are expanded by crank_directed_stake_ticket_basket
+            if preference.is_basket.into() {
+                continue;
+            }
+            if *validator_list_index == u32::MAX {
+                validators_not_in_pool.push(preference.vote_pubkey);
+                continue;
+            }
+
+            let validator_list_index = *validator_list_index as usize;
+            require!(
+                validator_list_index < validator_list.len() as usize,
+                StewardError::ValidatorIndexOutOfBounds
+            );
+            let validator_list_vote_pubkey =
+                vote_pubkey_at_validator_list_index(&validator_list, validator_list_index)?;
+            require_keys_eq!(
+                validator_list_vote_pubkey,
+                preference.vote_pubkey,
+                StewardError::DirectedStakeVoteAccountMismatch
+            );
+
+            let lamports = ticket_allocation_lamports(
+                token_balance,
+                preference.stake_share_bps,
+                stake_pool.total_lamports,
+                stake_pool.pool_token_supply,
+            )?;
+            if lamports == 0 {
+                continue;
+            }
+
+            let target_index = stake_meta.get_or_create_target_index(
+                &preference.vote_pubkey,
+                validator_list_index,
+                clock.epoch,
+            )?;
+            let lamports = match load_validator_consent(
+                &ctx.remaining_accounts[i],
+                &ctx.accounts.config.key(),
+                &preference.vote_pubkey,
+            )? {
+                Some(consent) => consent.accepted_lamports(
+                    lamports,
+                    stake_meta.targets[target_index].total_target_lamports,
+                ),
+                None => lamports,
+            };
+            if lamports == 0 {
+                continue;
+            }
+            target_changes.track(&stake_meta, target_index);
+            stake_meta.add_ticket_contribution(
+                target_index,
+                lamports,
+                replaces_migrated,
+                clock.epoch,
+            );
+
+            extension_contribution.contributions[preference_index] =
+                DirectedStakeContributionEntry {
+                    vote_pubkey: preference.vote_pubkey,
+                    target_index: target_index as u64,
+                    lamports,
+                    applied_epoch: clock.epoch,
+                };
+        }
+    }
+
+    for vote_pubkey in validators_not_in_pool.iter() {
+        require!(
+            !validator_exists_in_list(&ctx.accounts.validator_list, vote_pubkey)?,
+            StewardError::InvalidValidatorListIndices
+        );
+    }
+
+    extension_contribution.migrated_target_replaced = U8Bool::from(true);
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use validator_history_vote_state::VoteStateVersions;
+
+use crate::{errors::StewardError, CommissionPledge, Config};
+
+#[derive(Accounts)]
+pub struct InitializeCommissionPledge<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: Authorized withdrawer checked in the handler
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authorized_withdrawer,
+        space = CommissionPledge::SIZE,
+        seeds = [CommissionPledge::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub commission_pledge: AccountLoader<'info, CommissionPledge>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authorized_withdrawer: Signer<'info>,
+}
+
+/// Checks that `signer` is the authorized withdrawer of `vote_account`
+pub fn authorized_withdrawer_check(vote_account: &AccountInfo, signer: &Pubkey) -> Result<()> {
+    let authorized_withdrawer = VoteStateVersions::deserialize_authorized_withdrawer(vote_account)?;
+    require_keys_eq!(authorized_withdrawer, *signer, StewardError::Unauthorized);
+    Ok(())
+}
+
+/*
+Creates the account holding a validator's commission pledge, with no pledge made yet.
+Signed by the vote account's authorized withdrawer.
+*/
+pub fn handler(ctx: Context<InitializeCommissionPledge>) -> Result<()> {
+    authorized_withdrawer_check(
+        &ctx.accounts.vote_account,
+        ctx.accounts.authorized_withdrawer.key,
+    )?;
+
+    let mut commission_pledge = ctx.accounts.commission_pledge.load_init()?;
+    commission_pledge.config = ctx.accounts.config.key();
+    commission_pledge.vote_account = ctx.accounts.vote_account.key();
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, Config, CycleReport, StewardStateAccount, StewardStateAccountV2,
+};
+
+#[derive(Accounts)]
+#[instruction(cycle_start_epoch: u64)]
+pub struct InitializeCycleReport<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = CycleReport::SIZE,
+        seeds = [CycleReport::SEED, config.key().as_ref(), &cycle_start_epoch.to_le_bytes()],
+        bump
+    )]
+    pub cycle_report: AccountLoader<'info, CycleReport>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the report for the cycle currently in progress, or for a cycle starting this epoch.
+*/
+pub fn handler(ctx: Context<InitializeCycleReport>, cycle_start_epoch: u64) -> Result<()> {
+    let state_account = ctx.accounts.state_account.load()?;
+    let clock = Clock::get()?;
+    let epoch_schedule = EpochSchedule::get()?;
+
+    let current_cycle_start_epoch =
+        epoch_schedule.get_epoch(state_account.state.start_computing_scores_slot);
+    require!(
+        cycle_start_epoch == clock.epoch || cycle_start_epoch == current_cycle_start_epoch,
+        StewardError::InvalidCycleReport
+    );
+
+    let mut cycle_report = ctx.accounts.cycle_report.load_init()?;
+    cycle_report.config = ctx.accounts.config.key();
+    cycle_report.cycle_start_epoch = cycle_start_epoch;
+
+    if cycle_start_epoch == current_cycle_start_epoch {
+        cycle_report.start_computing_scores_slot = state_account.state.start_computing_scores_slot;
+        cycle_report.num_pool_validators = state_account.state.num_pool_validators;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakeBalanceSources, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakeBalanceSources<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DirectedStakeBalanceSources::SIZE,
+        seeds = [DirectedStakeBalanceSources::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub balance_sources: AccountLoader<'info, DirectedStakeBalanceSources>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        address = ticket_account.load()?.ticket_update_authority @ StewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+/*
+Creates the account listing a ticket's additional balance sources. Signed by the ticket update authority.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakeBalanceSources>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut balance_sources = ctx.accounts.balance_sources.load_init()?;
+    balance_sources.ticket = ctx.accounts.ticket_account.key();
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakeBasket};
+
+#[derive(Accounts)]
+#[instruction(name: [u8; 32])]
+pub struct InitializeDirectedStakeBasket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DirectedStakeBasket::SIZE,
+        seeds = [DirectedStakeBasket::SEED, config.key().as_ref(), name.as_ref()],
+        bump
+    )]
+    pub basket: AccountLoader<'info, DirectedStakeBasket>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        address = config.load()?.directed_stake_whitelist_authority @ StewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+/*
+Creates an empty basket named `name` whose validators are managed by `curator`.
+*/
+pub fn handler(
+    ctx: Context<InitializeDirectedStakeBasket>,
+    name: [u8; 32],
+    curator: Pubkey,
+) -> Result<()> {
+    let mut basket = ctx.accounts.basket.load_init()?;
+    basket.config = ctx.accounts.config.key();
+    basket.curator = curator;
+    basket.name = name;
+    basket.num_validators = 0;
+
+    Ok(())
+}
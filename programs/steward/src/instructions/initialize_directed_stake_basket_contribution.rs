@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, Config, DirectedStakeBasket, DirectedStakeBasketContribution,
+    DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakeBasketContribution<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(has_one = config)]
+    pub basket: AccountLoader<'info, DirectedStakeBasket>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DirectedStakeBasketContribution::SIZE,
+        seeds = [
+            DirectedStakeBasketContribution::SEED,
+            ticket_account.key().as_ref(),
+            basket.key().as_ref()
+        ],
+        bump
+    )]
+    pub basket_contribution: AccountLoader<'info, DirectedStakeBasketContribution>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the account tracking what a ticket contributes to directed stake targets through a basket.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakeBasketContribution>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut basket_contribution = ctx.accounts.basket_contribution.load_init()?;
+    basket_contribution.config = ctx.accounts.config.key();
+    basket_contribution.ticket = ctx.accounts.ticket_account.key();
+    basket_contribution.basket = ctx.accounts.basket.key();
+
+    Ok(())
+}
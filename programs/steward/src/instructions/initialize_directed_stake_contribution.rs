@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakeContribution, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakeContribution<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DirectedStakeContribution::SIZE,
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the account tracking a ticket's contribution to directed stake targets.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakeContribution>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut contribution = ctx.accounts.contribution_account.load_init()?;
+    contribution.config = ctx.accounts.config.key();
+    contribution.ticket = ctx.accounts.ticket_account.key();
+
+    Ok(())
+}
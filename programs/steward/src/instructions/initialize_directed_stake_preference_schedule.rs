@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, Config, DirectedStakePreferenceSchedule, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakePreferenceSchedule<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DirectedStakePreferenceSchedule::SIZE,
+        seeds = [DirectedStakePreferenceSchedule::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub preference_schedule: AccountLoader<'info, DirectedStakePreferenceSchedule>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        address = ticket_account.load()?.ticket_update_authority @ StewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+/*
+Creates the account holding a ticket's scheduled preference changes.
+Signed by the ticket update authority.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakePreferenceSchedule>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut preference_schedule = ctx.accounts.preference_schedule.load_init()?;
+    preference_schedule.ticket = ctx.accounts.ticket_account.key();
+
+    Ok(())
+}
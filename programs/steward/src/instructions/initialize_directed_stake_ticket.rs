@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::StewardError, state::directed_stake::DirectedStakePreference, utils::U8Bool, Config,
-    DirectedStakeTicket, DirectedStakeWhitelist,
+    errors::StewardError, events::DirectedStakeTicketInitializedEvent,
+    state::directed_stake::DirectedStakePreference, utils::U8Bool, Config, DirectedStakeTicket,
+    DirectedStakeWhitelist,
 };
 use std::mem::size_of;
 
@@ -79,7 +80,17 @@ pub fn handler(
         [DirectedStakePreference::empty(); crate::MAX_PREFERENCES_PER_TICKET];
     ticket.ticket_update_authority = ticket_update_authority;
     ticket.ticket_holder_is_protocol = U8Bool::from(ticket_holder_is_protocol);
-    ticket._padding0 = [0; 125];
+    ticket.is_permissionless = U8Bool::from(false);
+    ticket.extension_stake_share_bps = 0;
+    ticket._padding0 = [0; 122];
+
+    emit!(DirectedStakeTicketInitializedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: ctx.accounts.signer.key(),
+        ticket_update_authority,
+        ticket_holder_is_protocol,
+        is_permissionless: false,
+    });
 
     Ok(())
 }
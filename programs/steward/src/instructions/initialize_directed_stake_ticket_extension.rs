@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, Config, DirectedStakeExtensionContribution, DirectedStakeTicket,
+    DirectedStakeTicketExtension,
+};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakeTicketExtension<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DirectedStakeTicketExtension::SIZE,
+        seeds = [DirectedStakeTicketExtension::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub ticket_extension: AccountLoader<'info, DirectedStakeTicketExtension>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DirectedStakeExtensionContribution::SIZE,
+        seeds = [DirectedStakeExtensionContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub extension_contribution: AccountLoader<'info, DirectedStakeExtensionContribution>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        address = ticket_account.load()?.ticket_update_authority @ StewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+/*
+Creates the accounts holding a ticket's additional preferences and what they contribute to directed stake targets.
+Signed by the ticket update authority.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakeTicketExtension>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let mut ticket_extension = ctx.accounts.ticket_extension.load_init()?;
+    ticket_extension.ticket = ctx.accounts.ticket_account.key();
+
+    let mut extension_contribution = ctx.accounts.extension_contribution.load_init()?;
+    extension_contribution.config = ctx.accounts.config.key();
+    extension_contribution.ticket = ctx.accounts.ticket_account.key();
+
+    Ok(())
+}
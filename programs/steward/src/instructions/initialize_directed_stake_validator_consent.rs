@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::update_directed_stake_validator_consent::validator_authority_check, Config,
+    DirectedStakeValidatorConsent,
+};
+
+#[derive(Accounts)]
+pub struct InitializeDirectedStakeValidatorConsent<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: Owner and signer authority checked in the handler
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DirectedStakeValidatorConsent::SIZE,
+        seeds = [DirectedStakeValidatorConsent::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_consent: AccountLoader<'info, DirectedStakeValidatorConsent>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Creates the account through which a validator controls the directed stake it receives. Signed by the
+validator identity or the vote account's authorized withdrawer. Validators without one accept directed stake
+with no maximum.
+*/
+pub fn handler(ctx: Context<InitializeDirectedStakeValidatorConsent>) -> Result<()> {
+    validator_authority_check(&ctx.accounts.vote_account, ctx.accounts.signer.key)?;
+
+    let mut validator_consent = ctx.accounts.validator_consent.load_init()?;
+    validator_consent.config = ctx.accounts.config.key();
+    validator_consent.vote_account = ctx.accounts.vote_account.key();
+
+    Ok(())
+}
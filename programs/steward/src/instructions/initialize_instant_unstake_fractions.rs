@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, InstantUnstakeFractions};
+
+#[derive(Accounts)]
+pub struct InitializeInstantUnstakeFractions<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = InstantUnstakeFractions::SIZE,
+        seeds = [InstantUnstakeFractions::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub instant_unstake_fractions: AccountLoader<'info, InstantUnstakeFractions>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the account holding graded instant unstake fractions, required once
+`instant_unstake_max_fraction_bps` is set.
+*/
+pub fn handler(ctx: Context<InitializeInstantUnstakeFractions>) -> Result<()> {
+    let mut instant_unstake_fractions = ctx.accounts.instant_unstake_fractions.load_init()?;
+    instant_unstake_fractions.config = ctx.accounts.config.key();
+    instant_unstake_fractions.bump = ctx.bumps.instant_unstake_fractions;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    events::DirectedStakeTicketInitializedEvent,
+    stake_pool_utils::deserialize_stake_pool,
+    state::directed_stake::DirectedStakePreference,
+    utils::{associated_token_account_balance, get_stake_pool_address, U8Bool},
+    Config, DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct InitializePermissionlessDirectedStakeTicket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DirectedStakeTicket::SIZE,
+        seeds = [DirectedStakeTicket::SEED, config.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    /// CHECK: Verified to be the signer's associated token account for the pool mint
+    pub holder_token_account: AccountInfo<'info>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/*
+Opens a ticket for a holder without a whitelist entry. The holder pays the rent and becomes the ticket update
+authority, and must hold at least `directed_stake_permissionless_ticket_min_balance` JitoSOL.
+*/
+pub fn handler(ctx: Context<InitializePermissionlessDirectedStakeTicket>) -> Result<()> {
+    let min_balance = ctx
+        .accounts
+        .config
+        .load()?
+        .parameters
+        .directed_stake_permissionless_ticket_min_balance;
+    require!(
+        min_balance > 0,
+        StewardError::PermissionlessDirectedStakeTicketsDisabled
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+    let balance = associated_token_account_balance(
+        &ctx.accounts.holder_token_account,
+        ctx.accounts.signer.key,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+    )?;
+    require!(
+        balance >= min_balance,
+        StewardError::DirectedStakeBalanceBelowMinimum
+    );
+
+    let mut ticket = ctx.accounts.ticket_account.load_init()?;
+    ticket.num_preferences = 0;
+    ticket.staker_preferences =
+        [DirectedStakePreference::empty(); crate::MAX_PREFERENCES_PER_TICKET];
+    ticket.ticket_update_authority = ctx.accounts.signer.key();
+    ticket.ticket_holder_is_protocol = U8Bool::from(false);
+    ticket.is_permissionless = U8Bool::from(true);
+    ticket.extension_stake_share_bps = 0;
+    ticket._padding0 = [0; 122];
+
+    emit!(DirectedStakeTicketInitializedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: ctx.accounts.signer.key(),
+        ticket_update_authority: ctx.accounts.signer.key(),
+        ticket_holder_is_protocol: false,
+        is_permissionless: true,
+    });
+
+    Ok(())
+}
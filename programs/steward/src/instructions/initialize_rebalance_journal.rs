@@ -0,0 +1,39 @@
+use anchor_lang::{prelude::*, solana_program::vote};
+
+use crate::{Config, RebalanceJournal};
+
+#[derive(Accounts)]
+pub struct InitializeRebalanceJournal<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RebalanceJournal::SIZE,
+        seeds = [RebalanceJournal::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub rebalance_journal: AccountLoader<'info, RebalanceJournal>,
+
+    /// CHECK: Only used as a seed, must be owned by the vote program
+    #[account(owner = vote::program::ID)]
+    pub vote_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the journal of stake moved to and from `vote_account`, filled in by
+`rebalance` and `rebalance_directed`, which fail for validators without one unless their vote account
+was closed.
+*/
+pub fn handler(ctx: Context<InitializeRebalanceJournal>) -> Result<()> {
+    let mut rebalance_journal = ctx.accounts.rebalance_journal.load_init()?;
+    rebalance_journal.config = ctx.accounts.config.key();
+    rebalance_journal.vote_account = ctx.accounts.vote_account.key();
+
+    Ok(())
+}
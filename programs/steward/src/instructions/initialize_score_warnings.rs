@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_ALLOC_BYTES, Config, ScoreWarnings};
+
+#[derive(Accounts)]
+pub struct InitializeScoreWarnings<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MAX_ALLOC_BYTES,
+        seeds = [ScoreWarnings::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub score_warnings: AccountLoader<'info, ScoreWarnings>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Creates the account tracking validators warned before being unscored, required once
+`score_warning_filters` is set. It is filled in by `realloc_score_warnings` once grown to ScoreWarnings::SIZE.
+*/
+pub fn handler(_ctx: Context<InitializeScoreWarnings>) -> Result<()> {
+    Ok(())
+}
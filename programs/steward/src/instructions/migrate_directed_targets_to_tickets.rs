@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, state::directed_stake::DirectedStakeMeta, utils::get_config_admin, Config,
+};
+
+#[derive(Accounts)]
+pub struct MigrateDirectedTargetsToTickets<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    #[account(address = get_config_admin(&config)?)]
+    pub authority: Signer<'info>,
+}
+
+/// Makes ticket contributions authoritative for every directed stake target.
+/// Uploaded totals already include ticket holders' balances, so the uploaded portion of each target
+/// is kept as `migrated_target_lamports` and replaced by the contributions of tickets as they are
+/// cranked, instead of being counted twice. Tickets cannot be cranked before this, and targets cannot
+/// be uploaded after it. Requires `directed_stake_sample_interval_slots` to be set, as ticket cranks
+/// only credit time-weighted balances.
+pub fn handler(ctx: Context<MigrateDirectedTargetsToTickets>) -> Result<()> {
+    let clock = Clock::get()?;
+    let directed_stake_meta = &mut ctx.accounts.directed_stake_meta.load_mut()?;
+
+    require!(
+        !directed_stake_meta.ticket_targets_only(),
+        StewardError::DirectedStakeTargetsAlreadyMigrated
+    );
+    // Ticket cranks only credit time-weighted balances
+    require!(
+        ctx.accounts
+            .config
+            .load()?
+            .parameters
+            .directed_stake_sample_interval_slots
+            > 0,
+        StewardError::DirectedStakeSamplingDisabled
+    );
+
+    for target in directed_stake_meta.targets.iter_mut() {
+        if target.vote_pubkey.ne(&Pubkey::default()) {
+            target.migrated_target_lamports = target
+                .total_target_lamports
+                .saturating_sub(target.ticket_target_lamports)
+                .saturating_sub(target.unattributed_target_lamports);
+            target.target_last_updated_epoch = clock.epoch;
+        }
+    }
+
+    directed_stake_meta.ticket_targets_only = true.into();
+
+    Ok(())
+}
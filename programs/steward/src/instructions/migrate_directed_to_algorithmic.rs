@@ -32,6 +32,9 @@ pub fn handler(ctx: Context<MigrateDirectedToAlgorithmic>) -> Result<()> {
         if target.vote_pubkey.ne(&Pubkey::default()) {
             target.total_target_lamports = 0;
             target.total_staked_lamports = 0;
+            target.ticket_target_lamports = 0;
+            target.unattributed_target_lamports = 0;
+            target.migrated_target_lamports = 0;
             target.target_last_updated_epoch = clock.epoch;
             target.staked_last_updated_epoch = clock.epoch;
         }
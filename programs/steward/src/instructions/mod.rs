@@ -1,80 +1,158 @@
 #![allow(ambiguous_glob_reexports)]
+pub mod add_directed_stake_balance_source;
 pub mod add_to_directed_stake_whitelist;
 pub mod add_validators_to_blacklist;
 pub mod admin_mark_for_removal;
+pub mod apply_scheduled_directed_stake_preferences;
 pub mod auto_add_validator_to_pool;
 pub mod auto_remove_validator_from_pool;
+pub mod cancel_scheduled_directed_stake_preferences;
+pub mod clear_closed_directed_stake_ticket;
+pub mod clear_closed_directed_stake_ticket_extension;
+pub mod clear_directed_stake_ticket_basket;
 pub mod close_directed_stake_meta;
+pub mod close_directed_stake_preference_schedule;
 pub mod close_directed_stake_ticket;
 pub mod close_directed_stake_whitelist;
 pub mod close_steward_accounts;
+pub mod close_underfunded_directed_stake_ticket;
 pub mod compute_delegations;
 pub mod compute_instant_unstake;
 pub mod compute_score;
+pub mod compute_score_added_validator;
 pub mod copy_directed_stake_targets;
+pub mod crank_directed_stake_ticket;
+pub mod crank_directed_stake_ticket_basket;
+pub mod crank_directed_stake_ticket_extension;
 pub mod epoch_maintenance;
 pub mod idle;
+pub mod initialize_commission_pledge;
+pub mod initialize_cycle_report;
+pub mod initialize_directed_stake_balance_sources;
+pub mod initialize_directed_stake_basket;
+pub mod initialize_directed_stake_basket_contribution;
+pub mod initialize_directed_stake_contribution;
 pub mod initialize_directed_stake_meta;
+pub mod initialize_directed_stake_preference_schedule;
 pub mod initialize_directed_stake_ticket;
+pub mod initialize_directed_stake_ticket_extension;
+pub mod initialize_directed_stake_validator_consent;
 pub mod initialize_directed_stake_whitelist;
+pub mod initialize_instant_unstake_fractions;
+pub mod initialize_permissionless_directed_stake_ticket;
+pub mod initialize_rebalance_journal;
+pub mod initialize_score_warnings;
 pub mod initialize_steward;
 pub mod instant_remove_validator;
+pub mod migrate_directed_targets_to_tickets;
 pub mod migrate_directed_to_algorithmic;
 pub mod migrate_state_to_v2;
 pub mod pause_steward;
+pub mod pledge_commission;
 pub mod realloc_directed_stake_meta;
 pub mod realloc_directed_stake_whitelist;
+pub mod realloc_score_warnings;
 pub mod realloc_state;
 pub mod rebalance;
 pub mod rebalance_directed;
+pub mod rebalance_many;
+pub mod remove_directed_stake_balance_source;
 pub mod remove_from_directed_stake_whitelist;
 pub mod remove_validators_from_blacklist;
+pub mod request_validator_exit;
+pub mod request_validator_rejoin;
 pub mod reset_steward_state;
 pub mod reset_validator_lamport_balances;
 pub mod resume_steward;
+pub mod sample_directed_stake_balance;
+pub mod schedule_directed_stake_preferences;
 pub mod set_new_authority;
 pub mod spl_passthrough;
 pub mod sync_directed_stake_lamports;
+pub mod update_directed_stake_basket;
 pub mod update_directed_stake_ticket;
+pub mod update_directed_stake_ticket_extension;
+pub mod update_directed_stake_validator_consent;
 pub mod update_parameters;
+pub mod update_preferred_validators;
 pub mod update_priority_fee_parameters;
+pub mod verify_invariants;
 
+pub use add_directed_stake_balance_source::*;
 pub use add_to_directed_stake_whitelist::*;
 pub use add_validators_to_blacklist::*;
 pub use admin_mark_for_removal::*;
+pub use apply_scheduled_directed_stake_preferences::*;
 pub use auto_add_validator_to_pool::*;
 pub use auto_remove_validator_from_pool::*;
+pub use cancel_scheduled_directed_stake_preferences::*;
+pub use clear_closed_directed_stake_ticket::*;
+pub use clear_closed_directed_stake_ticket_extension::*;
+pub use clear_directed_stake_ticket_basket::*;
 pub use close_directed_stake_meta::*;
+pub use close_directed_stake_preference_schedule::*;
 pub use close_directed_stake_ticket::*;
 pub use close_directed_stake_whitelist::*;
 pub use close_steward_accounts::*;
+pub use close_underfunded_directed_stake_ticket::*;
 pub use compute_delegations::*;
 pub use compute_instant_unstake::*;
 pub use compute_score::*;
+pub use compute_score_added_validator::*;
 pub use copy_directed_stake_targets::*;
+pub use crank_directed_stake_ticket::*;
+pub use crank_directed_stake_ticket_basket::*;
+pub use crank_directed_stake_ticket_extension::*;
 pub use epoch_maintenance::*;
 pub use idle::*;
+pub use initialize_commission_pledge::*;
+pub use initialize_cycle_report::*;
+pub use initialize_directed_stake_balance_sources::*;
+pub use initialize_directed_stake_basket::*;
+pub use initialize_directed_stake_basket_contribution::*;
+pub use initialize_directed_stake_contribution::*;
 pub use initialize_directed_stake_meta::*;
+pub use initialize_directed_stake_preference_schedule::*;
 pub use initialize_directed_stake_ticket::*;
+pub use initialize_directed_stake_ticket_extension::*;
+pub use initialize_directed_stake_validator_consent::*;
 pub use initialize_directed_stake_whitelist::*;
+pub use initialize_instant_unstake_fractions::*;
+pub use initialize_permissionless_directed_stake_ticket::*;
+pub use initialize_rebalance_journal::*;
+pub use initialize_score_warnings::*;
 pub use initialize_steward::*;
 pub use instant_remove_validator::*;
+pub use migrate_directed_targets_to_tickets::*;
 pub use migrate_directed_to_algorithmic::*;
 pub use migrate_state_to_v2::*;
 pub use pause_steward::*;
+pub use pledge_commission::*;
 pub use realloc_directed_stake_meta::*;
 pub use realloc_directed_stake_whitelist::*;
+pub use realloc_score_warnings::*;
 pub use realloc_state::*;
 pub use rebalance::*;
 pub use rebalance_directed::*;
+pub use rebalance_many::*;
+pub use remove_directed_stake_balance_source::*;
 pub use remove_from_directed_stake_whitelist::*;
 pub use remove_validators_from_blacklist::*;
+pub use request_validator_exit::*;
+pub use request_validator_rejoin::*;
 pub use reset_steward_state::*;
 pub use reset_validator_lamport_balances::*;
 pub use resume_steward::*;
+pub use sample_directed_stake_balance::*;
+pub use schedule_directed_stake_preferences::*;
 pub use set_new_authority::*;
 pub use spl_passthrough::*;
 pub use sync_directed_stake_lamports::*;
+pub use update_directed_stake_basket::*;
 pub use update_directed_stake_ticket::*;
+pub use update_directed_stake_ticket_extension::*;
+pub use update_directed_stake_validator_consent::*;
 pub use update_parameters::*;
+pub use update_preferred_validators::*;
 pub use update_priority_fee_parameters::*;
+pub use verify_invariants::*;
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{utils::get_config_admin, Config};
+use crate::{events::SetPausedEvent, utils::get_config_admin, Config};
 
 #[derive(Accounts)]
 pub struct PauseSteward<'info> {
@@ -16,6 +16,13 @@ Prevents state machine from making progress. All state machine instructions must
 */
 pub fn handler(ctx: Context<PauseSteward>) -> Result<()> {
     let mut config = ctx.accounts.config.load_mut()?;
+
+    emit!(SetPausedEvent {
+        authority: ctx.accounts.authority.key(),
+        previous_paused: config.is_paused(),
+        paused: true,
+    });
+
     config.set_paused(true);
     Ok(())
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BASIS_POINTS_MAX, COMMISSION_MAX},
+    errors::StewardError,
+    events::CommissionPledgeEvent,
+    instructions::initialize_commission_pledge::authorized_withdrawer_check,
+    utils::U8Bool,
+    CommissionPledge, Config, PledgeCommissionArgs,
+};
+
+#[derive(Accounts)]
+pub struct PledgeCommission<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: Authorized withdrawer checked in the handler
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CommissionPledge::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub commission_pledge: AccountLoader<'info, CommissionPledge>,
+
+    pub authorized_withdrawer: Signer<'info>,
+}
+
+/*
+Pledges maximum inflation, MEV and priority fee commissions through `end_epoch`. Signed by the vote
+account's authorized withdrawer.
+
+While a pledge is active it can only be tightened: lower or equal maxima, and an equal or later end epoch.
+Once it expires a new pledge can be made. A validator that broke its pledge cannot pledge again until
+`cooldown_end_epoch`.
+*/
+pub fn handler(ctx: Context<PledgeCommission>, args: PledgeCommissionArgs) -> Result<()> {
+    authorized_withdrawer_check(
+        &ctx.accounts.vote_account,
+        ctx.accounts.authorized_withdrawer.key,
+    )?;
+
+    let epoch = Clock::get()?.epoch;
+    let mut commission_pledge = ctx.accounts.commission_pledge.load_mut()?;
+
+    require!(
+        !commission_pledge.is_cooling_down(epoch),
+        StewardError::CommissionPledgeCooldown
+    );
+    require!(
+        args.end_epoch > epoch
+            && args.max_commission <= COMMISSION_MAX
+            && args.max_mev_commission_bps <= BASIS_POINTS_MAX
+            && args.max_priority_fee_commission_bps <= BASIS_POINTS_MAX,
+        StewardError::InvalidCommissionPledgeTerms
+    );
+
+    if commission_pledge.is_active(epoch) {
+        require!(
+            args.end_epoch >= commission_pledge.end_epoch
+                && args.max_commission <= commission_pledge.max_commission
+                && args.max_mev_commission_bps <= commission_pledge.max_mev_commission_bps
+                && args.max_priority_fee_commission_bps
+                    <= commission_pledge.max_priority_fee_commission_bps,
+            StewardError::InvalidCommissionPledgeTerms
+        );
+    } else {
+        commission_pledge.start_epoch = epoch;
+        commission_pledge.broken = U8Bool::from(false);
+        commission_pledge.broken_epoch = 0;
+    }
+
+    commission_pledge.end_epoch = args.end_epoch;
+    commission_pledge.max_commission = args.max_commission;
+    commission_pledge.max_mev_commission_bps = args.max_mev_commission_bps;
+    commission_pledge.max_priority_fee_commission_bps = args.max_priority_fee_commission_bps;
+
+    emit!(CommissionPledgeEvent {
+        vote_account: ctx.accounts.vote_account.key(),
+        max_commission: commission_pledge.max_commission,
+        max_mev_commission_bps: commission_pledge.max_mev_commission_bps,
+        max_priority_fee_commission_bps: commission_pledge.max_priority_fee_commission_bps,
+        start_epoch: commission_pledge.start_epoch,
+        end_epoch: commission_pledge.end_epoch,
+    });
+
+    Ok(())
+}
@@ -64,7 +64,10 @@ pub fn handler(ctx: Context<ReallocDirectedStakeMeta>) -> Result<()> {
             total_staked_lamports: 0,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
         stake_meta.targets = [default_target; MAX_VALIDATORS];
         stake_meta.directed_stake_lamports = [0; MAX_VALIDATORS];
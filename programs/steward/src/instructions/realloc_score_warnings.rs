@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_ALLOC_BYTES, errors::StewardError, Config, ScoreWarnings};
+
+fn get_realloc_size(account_info: &AccountInfo) -> Result<usize> {
+    let account_size = account_info.data_len();
+
+    if account_size < ScoreWarnings::SIZE {
+        Ok(ScoreWarnings::SIZE.min(
+            account_size
+                .checked_add(MAX_ALLOC_BYTES)
+                .ok_or(StewardError::ArithmeticError)?,
+        ))
+    } else {
+        Ok(account_size)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReallocScoreWarnings<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        realloc = get_realloc_size(score_warnings.as_ref())?,
+        realloc::payer = payer,
+        realloc::zero = false,
+        seeds = [ScoreWarnings::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub score_warnings: AccountLoader<'info, ScoreWarnings>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/*
+Permissionless. Grows the ScoreWarnings account by up to 10KiB, and fills in its config once it reaches
+ScoreWarnings::SIZE. All other fields start zeroed.
+*/
+pub fn handler(ctx: Context<ReallocScoreWarnings>) -> Result<()> {
+    if ctx.accounts.score_warnings.as_ref().data_len() >= ScoreWarnings::SIZE {
+        let mut score_warnings = ctx.accounts.score_warnings.load_mut()?;
+        if score_warnings.config == Pubkey::default() {
+            score_warnings.config = ctx.accounts.config.key();
+            score_warnings.bump = ctx.bumps.score_warnings;
+        }
+    }
+    Ok(())
+}
@@ -18,19 +18,24 @@ use validator_history::ValidatorHistory;
 
 use crate::{
     constants::STAKE_POOL_WITHDRAW_SEED,
-    delegation::RebalanceType,
+    delegation::{RebalanceType, UndirectedPoolLamports},
     directed_stake::DirectedStakeMeta,
     errors::StewardError,
     events::{DecreaseComponents, RebalanceEvent, RebalanceTypeTag},
     maybe_transition,
     stake_pool_utils::deserialize_stake_pool,
-    utils::{get_stake_pool_address, get_validator_stake_info_at_index, state_checks},
-    Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
+    utils::{
+        get_stake_pool_address, get_validator_stake_info_at_index, load_rebalance_journal_mut,
+        state_checks,
+    },
+    Config, CycleReport, InstantUnstakeFractions, StewardStateAccount, StewardStateAccountV2,
+    StewardStateEnum,
 };
 
 #[derive(Accounts)]
 #[instruction(validator_list_index: u64)]
 pub struct Rebalance<'info> {
+    #[account(mut)]
     pub config: AccountLoader<'info, Config>,
 
     #[account(
@@ -144,6 +149,22 @@ pub struct Rebalance<'info> {
         bump
     )]
     pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
+
+    /// Required when graded instant unstake is enabled
+    #[account(
+        seeds = [InstantUnstakeFractions::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub instant_unstake_fractions: Option<AccountLoader<'info, InstantUnstakeFractions>>,
+
+    /// CHECK: Address checked in the handler. The validator's RebalanceJournal records the stake moved, and
+    /// must be initialized unless the vote account was closed
+    #[account(mut)]
+    pub rebalance_journal: AccountInfo<'info>,
 }
 
 /// Rebalances stake for a validator based on computed delegations and current stake state.
@@ -161,18 +182,124 @@ pub struct Rebalance<'info> {
 /// - If current undirected stake TVL < ceiling: reserve capped to (ceiling - current undirected stake)
 ///
 /// This ensures total undirected stake never exceeds the configured ceiling.
+///
+/// # Reserve Liquidity Target
+///
+/// `Parameters::reserve_liquidity_target` lamports are kept in the reserve for SOL withdrawals:
+/// - They are subtracted from the reserve available for stake increases
+/// - Validator targets are computed on the undirected pool minus the target, so when the reserve
+///   drops below it, validators end up above target and are decreased to refill it
+///
+/// # Graded Instant Unstake
+///
+/// When `instant_unstake_max_fraction_bps` is set, validators flagged for instant unstake with a
+/// fraction recorded in `InstantUnstakeFractions` this epoch only have that share of their current
+/// stake removed, and keep their delegation. Flagged validators without a recorded fraction are fully unstaked.
+///
+/// # Minimum Rebalance
+///
+/// Increases and decreases smaller than `minimum_rebalance_lamports` are skipped and the validator
+/// is marked as rebalanced. Decreases that include instant unstake lamports are never skipped.
+///
+/// # Unstake Carry-over
+///
+/// When `unstake_carryover_ceiling_bps` is set, the scoring, instant and stake deposit unstake cap
+/// budget left unused at the end of a cycle is added to the same cap in the next cycle, up to the
+/// ceiling. The carried and unused lamports are tracked in `Config::unstake_carryover`.
+///
+/// # Circuit Breaker
+///
+/// If a decrease would take the lamports unstaked this cycle above `circuit_breaker_unstake_bps` of
+/// the stake pool lamports the unstake caps are taken from, it is not issued and the steward is
+/// paused until the admin resumes it. Resuming acknowledges the unstaking so far, and the limit applies on top of it for the rest
+/// of the cycle.
 pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<()> {
-    let validator_history = ctx.accounts.validator_history.load()?;
-    let validator_list = &ctx.accounts.validator_list;
+    {
+        let validator_history = ctx.accounts.validator_history.load()?;
+        let validator_stake_info =
+            get_validator_stake_info_at_index(&ctx.accounts.validator_list, validator_list_index)?;
+        require!(
+            validator_stake_info.vote_account_address == validator_history.vote_account,
+            StewardError::ValidatorNotInList
+        );
+    }
+
+    let pool_accounts = RebalancePoolAccounts {
+        config: &ctx.accounts.config,
+        state_account: &ctx.accounts.state_account,
+        state_account_bump: ctx.bumps.state_account,
+        stake_pool_program: &ctx.accounts.stake_pool_program,
+        stake_pool: &ctx.accounts.stake_pool,
+        withdraw_authority: &ctx.accounts.withdraw_authority,
+        validator_list: &ctx.accounts.validator_list,
+        reserve_stake: &ctx.accounts.reserve_stake,
+        clock: &ctx.accounts.clock,
+        rent: ctx.accounts.rent.to_account_info(),
+        stake_history: &ctx.accounts.stake_history,
+        stake_config: &ctx.accounts.stake_config,
+        system_program: &ctx.accounts.system_program,
+        stake_program: &ctx.accounts.stake_program,
+        directed_stake_meta: &ctx.accounts.directed_stake_meta,
+        cycle_report: ctx.accounts.cycle_report.as_ref(),
+        instant_unstake_fractions: ctx.accounts.instant_unstake_fractions.as_ref(),
+    };
+
+    let validator_accounts = RebalanceValidatorAccounts {
+        vote_account: &ctx.accounts.vote_account,
+        stake_account: &ctx.accounts.stake_account,
+        transient_stake_account: &ctx.accounts.transient_stake_account,
+        rebalance_journal: &ctx.accounts.rebalance_journal,
+    };
+
+    rebalance_validator(&pool_accounts, &validator_accounts, validator_list_index)
+}
+
+/// Accounts shared by every validator rebalanced in a `rebalance` or `rebalance_many` instruction
+pub struct RebalancePoolAccounts<'a, 'info> {
+    pub config: &'a AccountLoader<'info, Config>,
+    pub state_account: &'a AccountLoader<'info, StewardStateAccountV2>,
+    pub state_account_bump: u8,
+    pub stake_pool_program: &'a AccountInfo<'info>,
+    pub stake_pool: &'a AccountInfo<'info>,
+    pub withdraw_authority: &'a AccountInfo<'info>,
+    pub validator_list: &'a AccountInfo<'info>,
+    pub reserve_stake: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub stake_history: &'a AccountInfo<'info>,
+    pub stake_config: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub stake_program: &'a AccountInfo<'info>,
+    pub directed_stake_meta: &'a AccountLoader<'info, DirectedStakeMeta>,
+    pub cycle_report: Option<&'a AccountLoader<'info, CycleReport>>,
+    pub instant_unstake_fractions: Option<&'a AccountLoader<'info, InstantUnstakeFractions>>,
+}
+
+/// Accounts of the validator being rebalanced. The stake and transient stake accounts must
+/// already be checked against the validator list entry of the validator.
+pub struct RebalanceValidatorAccounts<'a, 'info> {
+    pub vote_account: &'a AccountInfo<'info>,
+    pub stake_account: &'a AccountInfo<'info>,
+    pub transient_stake_account: &'a AccountInfo<'info>,
+    pub rebalance_journal: &'a AccountInfo<'info>,
+}
+
+/// Rebalances the validator at `validator_list_index`, see [`handler`].
+pub fn rebalance_validator<'info>(
+    accounts: &RebalancePoolAccounts<'_, 'info>,
+    validator_accounts: &RebalanceValidatorAccounts<'_, 'info>,
+    validator_list_index: usize,
+) -> Result<()> {
+    let validator_list = accounts.validator_list;
     let clock = Clock::get()?;
     let epoch_schedule = EpochSchedule::get()?;
-    let config = ctx.accounts.config.load()?;
+    let mut config = accounts.config.load_mut()?;
 
     let rebalance_type: RebalanceType;
     let transient_seed: u64;
 
     {
-        let mut state_account = ctx.accounts.state_account.load_mut()?;
+        let mut state_account = accounts.state_account.load_mut()?;
 
         // Transitions to Idle before doing rebalance if RESET_TO_IDLE is set
         if let Some(event) = maybe_transition(
@@ -189,18 +316,25 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
             &clock,
             &config,
             &state_account,
-            &ctx.accounts.validator_list,
+            accounts.validator_list,
             Some(StewardStateEnum::Rebalance),
         )?;
 
+        if config.parameters.cycle_reports_enabled() {
+            require!(
+                accounts.cycle_report.is_some(),
+                StewardError::CycleReportRequired
+            );
+        }
+
         let validator_stake_info =
             get_validator_stake_info_at_index(validator_list, validator_list_index)?;
         require!(
-            validator_stake_info.vote_account_address == validator_history.vote_account,
+            validator_stake_info.vote_account_address == validator_accounts.vote_account.key(),
             StewardError::ValidatorNotInList
         );
 
-        if ctx.accounts.vote_account.owner != &vote::program::ID
+        if validator_accounts.vote_account.owner != &vote::program::ID
             && !state_account
                 .state
                 .validators_to_remove
@@ -211,13 +345,28 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
 
         transient_seed = u64::from(validator_stake_info.transient_seed_suffix);
 
-        let stake_account_data = &mut ctx.accounts.stake_account.data.borrow();
+        let stake_account_data = &mut validator_accounts.stake_account.data.borrow();
         let stake_state = try_from_slice_unchecked::<StakeStateV2>(stake_account_data)?;
         if !matches!(stake_state, StakeStateV2::Stake(_, _, _)) {
             return Err(StewardError::InvalidStakeState.into());
         }
 
-        let directed_stake_meta = ctx.accounts.directed_stake_meta.load()?;
+        let directed_stake_meta = accounts.directed_stake_meta.load()?;
+
+        if config.parameters.graded_instant_unstake_enabled() {
+            require!(
+                accounts.instant_unstake_fractions.is_some(),
+                StewardError::InstantUnstakeFractionsRequired
+            );
+        }
+        let instant_unstake_fractions = accounts
+            .instant_unstake_fractions
+            .map(|instant_unstake_fractions| instant_unstake_fractions.load())
+            .transpose()?;
+        // Fractions from an earlier epoch are stale
+        let instant_unstake_fractions = instant_unstake_fractions
+            .as_deref()
+            .filter(|instant_unstake_fractions| instant_unstake_fractions.epoch == clock.epoch);
 
         // Do not count directed stake against the pool stake delegation
         let stake_account_active_lamports = match stake_state {
@@ -229,7 +378,8 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
         };
 
         // If and only if there is a valid StakeStateV2 for the transient stake account we should assign that balance
-        let transient_stake_account_data = &mut ctx.accounts.transient_stake_account.data.borrow();
+        let transient_stake_account_data =
+            &mut validator_accounts.transient_stake_account.data.borrow();
         let transient_stake_state =
             try_from_slice_unchecked::<StakeStateV2>(transient_stake_account_data);
         let transient_stake_account_lamports = match transient_stake_state {
@@ -250,38 +400,84 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
         let stake_rent = Rent::get()?.minimum_balance(StakeStateV2::size_of());
 
         rebalance_type = {
-            let validator_list_data = &mut ctx.accounts.validator_list.try_borrow_mut_data()?;
+            let validator_list_data = &mut accounts.validator_list.try_borrow_mut_data()?;
             let (_, validator_list) = ValidatorListHeader::deserialize_vec(validator_list_data)?;
 
-            let total_pool_lamports =
-                deserialize_stake_pool(&ctx.accounts.stake_pool)?.total_lamports;
+            let total_pool_lamports = deserialize_stake_pool(accounts.stake_pool)?.total_lamports;
 
-            let undirected_pool_lamports =
-                total_pool_lamports.saturating_sub(directed_stake_meta.total_staked_lamports());
+            let pool_lamports = UndirectedPoolLamports::new(
+                &config.parameters,
+                total_pool_lamports,
+                directed_stake_meta.total_staked_lamports(),
+                validator_list.len() as usize,
+                minimum_delegation,
+                stake_rent,
+            );
+            let undirected_pool_lamports = pool_lamports.undirected_pool_lamports;
+            let reserve_liquidity_target = pool_lamports.reserve_liquidity_target;
 
             let stake_ceiling = config.parameters.undirected_stake_ceiling_lamports();
 
             let capped_reserve = if undirected_pool_lamports >= stake_ceiling {
                 0
             } else {
-                ctx.accounts
+                accounts
                     .reserve_stake
                     .lamports()
+                    .saturating_sub(reserve_liquidity_target)
                     .min(stake_ceiling.saturating_sub(undirected_pool_lamports))
             };
 
-            state_account.state.rebalance(
+            // Fixed costs are subtracted by `StewardStateV2::rebalance`
+            let stake_pool_lamports = pool_lamports.stake_pool_lamports();
+
+            let carryover_ceiling_bps = config.parameters.unstake_carryover_ceiling_bps;
+            config.unstake_carryover.start_cycle(
+                state_account.state.next_cycle_epoch,
+                carryover_ceiling_bps,
+                stake_pool_lamports,
+            )?;
+
+            config.circuit_breaker_baseline.sync(&state_account.state)?;
+
+            let rebalance_result = state_account.state.rebalance(
                 &directed_stake_meta,
                 clock.epoch,
                 validator_list_index,
                 &validator_list,
-                undirected_pool_lamports,
+                stake_pool_lamports,
                 capped_reserve,
                 stake_account_active_lamports.saturating_sub(minimum_delegation),
                 minimum_delegation,
                 stake_rent,
                 &config.parameters,
-            )?
+                instant_unstake_fractions,
+                &config.unstake_carryover,
+                Some(&config.circuit_breaker_baseline),
+            );
+
+            // Unstaking more of the pool in one cycle than the circuit breaker allows suggests
+            // corrupted inputs. Pause before the decrease is issued so nothing further is moved
+            // until the admin resumes the steward.
+            let rebalance_type = match rebalance_result {
+                Err(error) if error == StewardError::UnstakeCircuitBreakerTripped.into() => {
+                    config.set_paused(true);
+                    emit!(state_account
+                        .state
+                        .circuit_breaker_event(&clock, &config.parameters)?);
+                    return Ok(());
+                }
+                rebalance_result => rebalance_result?,
+            };
+
+            let unstake_state = state_account.state.unstake_state(
+                &config.parameters,
+                stake_pool_lamports,
+                &config.unstake_carryover,
+            )?;
+            config.unstake_carryover.record_unused(&unstake_state);
+
+            rebalance_type
         };
     }
 
@@ -289,73 +485,73 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
         RebalanceType::Decrease(decrease_components) => {
             invoke_signed(
                 &spl_stake_pool::instruction::decrease_validator_stake_with_reserve(
-                    &ctx.accounts.stake_pool_program.key(),
-                    &ctx.accounts.stake_pool.key(),
-                    &ctx.accounts.state_account.key(),
-                    &ctx.accounts.withdraw_authority.key(),
-                    &ctx.accounts.validator_list.key(),
-                    &ctx.accounts.reserve_stake.key(),
-                    &ctx.accounts.stake_account.key(),
-                    &ctx.accounts.transient_stake_account.key(),
+                    &accounts.stake_pool_program.key(),
+                    &accounts.stake_pool.key(),
+                    &accounts.state_account.key(),
+                    &accounts.withdraw_authority.key(),
+                    &accounts.validator_list.key(),
+                    &accounts.reserve_stake.key(),
+                    &validator_accounts.stake_account.key(),
+                    &validator_accounts.transient_stake_account.key(),
                     decrease_components.total_unstake_lamports,
                     transient_seed,
                 ),
                 &[
-                    ctx.accounts.stake_pool.to_account_info(),
-                    ctx.accounts.state_account.to_account_info(),
-                    ctx.accounts.withdraw_authority.to_owned(),
-                    ctx.accounts.validator_list.to_account_info(),
-                    ctx.accounts.reserve_stake.to_account_info(),
-                    ctx.accounts.stake_account.to_account_info(),
-                    ctx.accounts.transient_stake_account.to_account_info(),
-                    ctx.accounts.clock.to_account_info(),
-                    ctx.accounts.rent.to_account_info(),
-                    ctx.accounts.stake_history.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                    ctx.accounts.stake_program.to_account_info(),
+                    accounts.stake_pool.to_account_info(),
+                    accounts.state_account.to_account_info(),
+                    accounts.withdraw_authority.to_owned(),
+                    accounts.validator_list.to_account_info(),
+                    accounts.reserve_stake.to_account_info(),
+                    validator_accounts.stake_account.to_account_info(),
+                    validator_accounts.transient_stake_account.to_account_info(),
+                    accounts.clock.to_account_info(),
+                    accounts.rent.to_account_info(),
+                    accounts.stake_history.to_account_info(),
+                    accounts.system_program.to_account_info(),
+                    accounts.stake_program.to_account_info(),
                 ],
                 &[&[
                     StewardStateAccount::SEED,
-                    &ctx.accounts.config.key().to_bytes(),
-                    &[ctx.bumps.state_account],
+                    &accounts.config.key().to_bytes(),
+                    &[accounts.state_account_bump],
                 ]],
             )?;
         }
         RebalanceType::Increase(lamports) => {
             invoke_signed(
                 &spl_stake_pool::instruction::increase_validator_stake(
-                    &ctx.accounts.stake_pool_program.key(),
-                    &ctx.accounts.stake_pool.key(),
-                    &ctx.accounts.state_account.key(),
-                    &ctx.accounts.withdraw_authority.key(),
-                    &ctx.accounts.validator_list.key(),
-                    &ctx.accounts.reserve_stake.key(),
-                    &ctx.accounts.transient_stake_account.key(),
-                    &ctx.accounts.stake_account.key(),
-                    &ctx.accounts.vote_account.key(),
+                    &accounts.stake_pool_program.key(),
+                    &accounts.stake_pool.key(),
+                    &accounts.state_account.key(),
+                    &accounts.withdraw_authority.key(),
+                    &accounts.validator_list.key(),
+                    &accounts.reserve_stake.key(),
+                    &validator_accounts.transient_stake_account.key(),
+                    &validator_accounts.stake_account.key(),
+                    &validator_accounts.vote_account.key(),
                     lamports,
                     transient_seed,
                 ),
                 &[
-                    ctx.accounts.stake_pool.to_account_info(),
-                    ctx.accounts.state_account.to_account_info(),
-                    ctx.accounts.withdraw_authority.to_owned(),
-                    ctx.accounts.validator_list.to_account_info(),
-                    ctx.accounts.reserve_stake.to_account_info(),
-                    ctx.accounts.transient_stake_account.to_account_info(),
-                    ctx.accounts.stake_account.to_account_info(),
-                    ctx.accounts.vote_account.to_owned(),
-                    ctx.accounts.clock.to_account_info(),
-                    ctx.accounts.rent.to_account_info(),
-                    ctx.accounts.stake_history.to_account_info(),
-                    ctx.accounts.stake_config.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                    ctx.accounts.stake_program.to_account_info(),
+                    accounts.stake_pool.to_account_info(),
+                    accounts.state_account.to_account_info(),
+                    accounts.withdraw_authority.to_owned(),
+                    accounts.validator_list.to_account_info(),
+                    accounts.reserve_stake.to_account_info(),
+                    validator_accounts.transient_stake_account.to_account_info(),
+                    validator_accounts.stake_account.to_account_info(),
+                    validator_accounts.vote_account.to_owned(),
+                    accounts.clock.to_account_info(),
+                    accounts.rent.to_account_info(),
+                    accounts.stake_history.to_account_info(),
+                    accounts.stake_config.to_account_info(),
+                    accounts.system_program.to_account_info(),
+                    accounts.stake_program.to_account_info(),
                 ],
                 &[&[
                     StewardStateAccount::SEED,
-                    &ctx.accounts.config.key().to_bytes(),
-                    &[ctx.bumps.state_account],
+                    &accounts.config.key().to_bytes(),
+                    &[accounts.state_account_bump],
                 ]],
             )?;
         }
@@ -363,10 +559,28 @@ pub fn handler(ctx: Context<Rebalance>, validator_list_index: usize) -> Result<(
     }
 
     {
-        let mut state_account = ctx.accounts.state_account.load_mut()?;
+        let mut state_account = accounts.state_account.load_mut()?;
+
+        if let Some(cycle_report) = accounts.cycle_report {
+            let mut cycle_report = cycle_report.load_mut()?;
+            cycle_report.check_cycle(
+                &accounts.config.key(),
+                &state_account.state,
+                &epoch_schedule,
+            )?;
+            cycle_report.record_rebalance(&state_account.state, &rebalance_type)?;
+        }
+
+        if let Some(mut rebalance_journal) = load_rebalance_journal_mut(
+            validator_accounts.rebalance_journal,
+            &accounts.config.key(),
+            &validator_accounts.vote_account,
+        )? {
+            rebalance_journal.record_rebalance(clock.epoch, &rebalance_type);
+        }
 
         emit!(rebalance_to_event(
-            ctx.accounts.vote_account.key(),
+            validator_accounts.vote_account.key(),
             clock.epoch as u16,
             rebalance_type
         ));
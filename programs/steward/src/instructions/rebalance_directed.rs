@@ -20,7 +20,8 @@ use crate::{
     state::directed_stake::DirectedStakeMeta,
     utils::{
         get_stake_pool_address, get_transient_stake_seed_at_index_from_big_vec,
-        stake_lamports_at_validator_list_index, state_checks, vote_pubkey_at_validator_list_index,
+        load_rebalance_journal_mut, stake_lamports_at_validator_list_index, state_checks,
+        vote_pubkey_at_validator_list_index,
     },
     Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
     REBALANCE_DIRECTED_COMPLETE,
@@ -114,6 +115,11 @@ pub struct RebalanceDirected<'info> {
     /// CHECK: passing through, checks are done by spl-stake-pool
     #[account(address = stake::program::ID)]
     pub stake_program: AccountInfo<'info>,
+
+    /// CHECK: Address checked in the handler. The validator's RebalanceJournal records the stake moved, and
+    /// must be initialized unless the vote account was closed
+    #[account(mut)]
+    pub rebalance_journal: AccountInfo<'info>,
 }
 
 /// Reconciles directed stake accounting after external deposits or withdrawals.
@@ -324,6 +330,24 @@ pub fn handler(ctx: Context<RebalanceDirected>, directed_stake_meta_index: usize
             return Err(StewardError::ValidatorAlreadyRebalanced.into());
         }
 
+        // Targets that have not been refreshed in a while, and what is left of them, decay once per epoch
+        if directed_stake_meta.targets[directed_stake_meta_index].staked_last_updated_epoch
+            != clock.epoch
+        {
+            let decayed_lamports = directed_stake_meta.decay_stale_target(
+                directed_stake_meta_index,
+                clock.epoch,
+                config.parameters.directed_stake_target_expiry_epochs,
+                config.parameters.directed_stake_target_decay_bps,
+            );
+            if decayed_lamports > 0 {
+                msg!(
+                    "Decayed stale directed stake target by {} lamports",
+                    decayed_lamports
+                );
+            }
+        }
+
         let minimum_delegation = minimum_delegation(get_minimum_delegation()?);
         let stake_rent = Rent::get()?.minimum_balance(StakeStateV2::size_of());
 
@@ -494,6 +518,14 @@ pub fn handler(ctx: Context<RebalanceDirected>, directed_stake_meta_index: usize
         state_account.state.set_flag(REBALANCE_DIRECTED_COMPLETE);
     }
 
+    if let Some(mut rebalance_journal) = load_rebalance_journal_mut(
+        &ctx.accounts.rebalance_journal,
+        &ctx.accounts.config.key(),
+        &ctx.accounts.vote_account,
+    )? {
+        rebalance_journal.record_directed_rebalance(clock.epoch, &rebalance_type);
+    }
+
     {
         emit!(rebalance_to_event(
             ctx.accounts.vote_account.key(),
@@ -548,7 +580,9 @@ fn rebalance_to_event(
 mod tests {
     use super::*;
     use crate::{
-        constants::MAX_VALIDATORS, state::directed_stake::DirectedStakeTarget, utils::U8Bool,
+        constants::MAX_VALIDATORS,
+        state::directed_stake::{DirectedStakeContributionEntry, DirectedStakeTarget},
+        utils::U8Bool,
         BitMask,
     };
     use anchor_lang::prelude::Pubkey;
@@ -557,7 +591,8 @@ mod tests {
         DirectedStakeMeta {
             total_stake_targets: 0,
             directed_unstake_total: 0,
-            padding0: [0; 63],
+            ticket_targets_only: U8Bool::from(false),
+            padding0: [0; 62],
             is_initialized: U8Bool::from(true),
             targets: [DirectedStakeTarget {
                 vote_pubkey: Pubkey::default(),
@@ -565,7 +600,10 @@ mod tests {
                 total_staked_lamports: 0,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             }; MAX_VALIDATORS],
             directed_stake_lamports: [0; MAX_VALIDATORS],
             directed_stake_meta_indices: [u64::MAX; MAX_VALIDATORS],
@@ -595,13 +633,140 @@ mod tests {
                 stake_deposit_unstake_total: 0,
                 validators_added: 0,
                 status_flags: 0,
-                _padding0: [0; 2],
+                validators_removed: 0,
             },
             bump: 0,
             _padding0: [0; 7],
         }
     }
 
+    #[test]
+    fn test_decay_stale_target() {
+        let mut directed_stake_meta = create_default_directed_stake_meta();
+        directed_stake_meta.targets[0].total_target_lamports = 10_000;
+        directed_stake_meta.targets[0].ticket_target_lamports = 4_000;
+        directed_stake_meta.targets[0].target_last_updated_epoch = 100;
+
+        // Expiry disabled
+        assert_eq!(directed_stake_meta.decay_stale_target(0, 200, 0, 0), 0);
+        // Not stale yet
+        assert_eq!(directed_stake_meta.decay_stale_target(0, 104, 5, 2_500), 0);
+
+        // 25% per epoch once stale
+        assert_eq!(
+            directed_stake_meta.decay_stale_target(0, 105, 5, 2_500),
+            2_500
+        );
+        assert_eq!(directed_stake_meta.targets[0].total_target_lamports, 7_500);
+        // Ticket contributions are reset, the remainder is no longer attributed
+        assert_eq!(directed_stake_meta.targets[0].ticket_target_lamports, 0);
+        assert_eq!(directed_stake_meta.targets[0].ticket_decay_epoch, 105);
+        assert_eq!(
+            directed_stake_meta.targets[0].unattributed_target_lamports,
+            7_500
+        );
+        // Decaying does not refresh the target
+        assert_eq!(
+            directed_stake_meta.targets[0].target_last_updated_epoch,
+            100
+        );
+
+        // 0 bps resets the target
+        assert_eq!(directed_stake_meta.decay_stale_target(0, 106, 5, 0), 7_500);
+        assert_eq!(directed_stake_meta.targets[0].total_target_lamports, 0);
+        assert_eq!(directed_stake_meta.targets[0].ticket_target_lamports, 0);
+        assert_eq!(
+            directed_stake_meta.targets[0].unattributed_target_lamports,
+            0
+        );
+    }
+
+    #[test]
+    fn test_decay_then_remove_ticket_contributions() {
+        let mut directed_stake_meta = create_default_directed_stake_meta();
+        let vote_pubkey = Pubkey::new_unique();
+        let target_index = directed_stake_meta
+            .get_or_create_target_index(&vote_pubkey, 0, 100)
+            .unwrap();
+        let entry = |lamports: u64, applied_epoch: u64| DirectedStakeContributionEntry {
+            vote_pubkey,
+            target_index: target_index as u64,
+            lamports,
+            applied_epoch,
+        };
+
+        // Two tickets contribute 4_000 each on top of a 2_000 upload
+        directed_stake_meta.add_ticket_contribution(target_index, 4_000, false, 100);
+        directed_stake_meta.add_ticket_contribution(target_index, 4_000, false, 100);
+        directed_stake_meta.targets[target_index].total_target_lamports += 2_000;
+        let ticket_a = entry(4_000, 100);
+        let ticket_b = entry(4_000, 100);
+
+        // The stale target decays by 25%
+        assert_eq!(
+            directed_stake_meta.decay_stale_target(target_index, 105, 5, 2_500),
+            2_500
+        );
+
+        // Ticket B is cranked again, its entry was reset by the decay
+        directed_stake_meta.remove_contribution_entry(&ticket_b, 106);
+        assert_eq!(
+            directed_stake_meta.targets[target_index].total_target_lamports,
+            7_500
+        );
+        directed_stake_meta.add_ticket_contribution(target_index, 4_000, false, 106);
+        let ticket_b = entry(4_000, 106);
+        assert_eq!(
+            directed_stake_meta.targets[target_index].ticket_target_lamports,
+            4_000
+        );
+
+        // Removing ticket A does not eat into ticket B's contribution
+        directed_stake_meta.remove_contribution_entry(&ticket_a, 107);
+        assert_eq!(
+            directed_stake_meta.targets[target_index].ticket_target_lamports,
+            4_000
+        );
+        assert_eq!(
+            directed_stake_meta.targets[target_index].total_target_lamports,
+            11_500
+        );
+
+        // Ticket B's new entry is removed in full
+        directed_stake_meta.remove_contribution_entry(&ticket_b, 108);
+        assert_eq!(
+            directed_stake_meta.targets[target_index].ticket_target_lamports,
+            0
+        );
+        assert_eq!(
+            directed_stake_meta.targets[target_index].total_target_lamports,
+            7_500
+        );
+
+        // The unattributed remainder decays although the removal refreshed the target
+        assert_eq!(
+            directed_stake_meta.decay_stale_target(target_index, 109, 5, 2_500),
+            1_875
+        );
+        assert_eq!(
+            directed_stake_meta.targets[target_index].total_target_lamports,
+            5_625
+        );
+
+        // And keeps decaying until nothing is left
+        for epoch in 110..150 {
+            directed_stake_meta.decay_stale_target(target_index, epoch, 5, 2_500);
+        }
+        assert_eq!(
+            directed_stake_meta.targets[target_index].unattributed_target_lamports,
+            0
+        );
+        assert_eq!(
+            directed_stake_meta.targets[target_index].total_target_lamports,
+            0
+        );
+    }
+
     #[test]
     fn test_adjust_directed_stake_no_adjustment_needed() {
         // Scenario: Everything is in sync - no deposits or withdrawals detected
@@ -629,7 +794,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
 
         let initial_staked_lamports =
@@ -673,7 +841,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
 
         // Call the function
@@ -713,7 +884,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
 
         let initial_staked_lamports =
@@ -757,7 +931,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
 
         // Call the function
@@ -797,7 +974,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
 
         // Call the function
@@ -847,7 +1027,10 @@ mod tests {
             total_staked_lamports: directed_stake_applied_lamports,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         };
         directed_stake_meta.directed_stake_lamports[validator_list_index] =
             directed_stake_applied_lamports;
@@ -0,0 +1,207 @@
+use std::num::NonZeroU32;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{stake, system_program, sysvar},
+};
+use spl_stake_pool::{find_stake_program_address, find_transient_stake_program_address};
+
+use crate::{
+    constants::STAKE_POOL_WITHDRAW_SEED,
+    directed_stake::DirectedStakeMeta,
+    errors::StewardError,
+    instructions::rebalance::{
+        rebalance_validator, RebalancePoolAccounts, RebalanceValidatorAccounts,
+    },
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{get_stake_pool_address, get_validator_stake_info_at_index},
+    Config, CycleReport, InstantUnstakeFractions, StewardStateAccount, StewardStateAccountV2,
+    StewardStateEnum,
+};
+
+/// Number of remaining accounts passed for each validator to `rebalance_many`
+pub const REBALANCE_MANY_ACCOUNTS_PER_VALIDATOR: usize = 4;
+
+#[derive(Accounts)]
+pub struct RebalanceMany<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    /// CHECK: CPI program
+    #[account(address = spl_stake_pool::ID)]
+    pub stake_pool_program: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(
+        seeds = [
+            stake_pool.key().as_ref(),
+            STAKE_POOL_WITHDRAW_SEED
+        ],
+        seeds::program = spl_stake_pool::ID,
+        bump = deserialize_stake_pool(&stake_pool)?.stake_withdraw_bump_seed
+    )]
+    pub withdraw_authority: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(
+        mut,
+        address = deserialize_stake_pool(&stake_pool)?.validator_list
+    )]
+    pub validator_list: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(
+        mut,
+        address = deserialize_stake_pool(&stake_pool)?.reserve_stake
+    )]
+    pub reserve_stake: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = sysvar::clock::ID)]
+    pub clock: AccountInfo<'info>,
+
+    #[account(address = sysvar::rent::ID)]
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = stake::config::ID)]
+    pub stake_config: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = stake::program::ID)]
+    pub stake_program: AccountInfo<'info>,
+
+    /// Directed stake meta account may be required when increasing stake
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CycleReport for the current cycle, required when cycle reports are enabled
+    #[account(mut)]
+    pub cycle_report: Option<AccountLoader<'info, CycleReport>>,
+
+    /// Required when graded instant unstake is enabled
+    #[account(
+        seeds = [InstantUnstakeFractions::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub instant_unstake_fractions: Option<AccountLoader<'info, InstantUnstakeFractions>>,
+}
+
+/// Rebalances each validator at `validator_list_indices` in order, the same way `rebalance` does.
+///
+/// For each validator, `remaining_accounts` holds `REBALANCE_MANY_ACCOUNTS_PER_VALIDATOR` accounts:
+/// its vote account, stake account, transient stake account and `RebalanceJournal`.
+///
+/// Stops early without error once the state machine leaves the Rebalance state or the circuit
+/// breaker pauses the steward; the remaining validators are left for the next instruction.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RebalanceMany<'info>>,
+    validator_list_indices: Vec<u64>,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len()
+            == validator_list_indices.len() * REBALANCE_MANY_ACCOUNTS_PER_VALIDATOR,
+        StewardError::InvalidRebalanceManyAccounts
+    );
+
+    let pool_accounts = RebalancePoolAccounts {
+        config: &ctx.accounts.config,
+        state_account: &ctx.accounts.state_account,
+        state_account_bump: ctx.bumps.state_account,
+        stake_pool_program: &ctx.accounts.stake_pool_program,
+        stake_pool: &ctx.accounts.stake_pool,
+        withdraw_authority: &ctx.accounts.withdraw_authority,
+        validator_list: &ctx.accounts.validator_list,
+        reserve_stake: &ctx.accounts.reserve_stake,
+        clock: &ctx.accounts.clock,
+        rent: ctx.accounts.rent.to_account_info(),
+        stake_history: &ctx.accounts.stake_history,
+        stake_config: &ctx.accounts.stake_config,
+        system_program: &ctx.accounts.system_program,
+        stake_program: &ctx.accounts.stake_program,
+        directed_stake_meta: &ctx.accounts.directed_stake_meta,
+        cycle_report: ctx.accounts.cycle_report.as_ref(),
+        instant_unstake_fractions: ctx.accounts.instant_unstake_fractions.as_ref(),
+    };
+
+    let validator_account_infos = ctx
+        .remaining_accounts
+        .chunks(REBALANCE_MANY_ACCOUNTS_PER_VALIDATOR);
+
+    for (validator_list_index, validator_account_infos) in validator_list_indices
+        .into_iter()
+        .zip(validator_account_infos)
+    {
+        {
+            let config = ctx.accounts.config.load()?;
+            let state_account = ctx.accounts.state_account.load()?;
+            if state_account.state.state_tag != StewardStateEnum::Rebalance || config.is_paused() {
+                break;
+            }
+        }
+
+        let validator_list_index = validator_list_index as usize;
+        let [vote_account, stake_account, transient_stake_account, rebalance_journal] =
+            validator_account_infos
+        else {
+            return Err(StewardError::InvalidRebalanceManyAccounts.into());
+        };
+
+        let validator_stake_info =
+            get_validator_stake_info_at_index(&ctx.accounts.validator_list, validator_list_index)?;
+        let (expected_stake_account, _) = find_stake_program_address(
+            &spl_stake_pool::id(),
+            vote_account.key,
+            ctx.accounts.stake_pool.key,
+            NonZeroU32::new(u32::from(validator_stake_info.validator_seed_suffix)),
+        );
+        let (expected_transient_stake_account, _) = find_transient_stake_program_address(
+            &spl_stake_pool::id(),
+            vote_account.key,
+            ctx.accounts.stake_pool.key,
+            validator_stake_info.transient_seed_suffix.into(),
+        );
+        require!(
+            *stake_account.key == expected_stake_account
+                && *stake_account.owner == stake::program::ID
+                && *transient_stake_account.key == expected_transient_stake_account,
+            StewardError::InvalidRebalanceManyAccounts
+        );
+
+        rebalance_validator(
+            &pool_accounts,
+            &RebalanceValidatorAccounts {
+                vote_account,
+                stake_account,
+                transient_stake_account,
+                rebalance_journal,
+            },
+            validator_list_index,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StewardError, DirectedStakeBalanceSources, DirectedStakeTicket};
+
+#[derive(Accounts)]
+pub struct RemoveDirectedStakeBalanceSource<'info> {
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeBalanceSources::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub balance_sources: AccountLoader<'info, DirectedStakeBalanceSources>,
+
+    /// Either the ticket update authority or the owner of the source
+    pub signer: Signer<'info>,
+}
+
+/*
+Removes a balance source from a ticket. Either the ticket update authority or the source owner can remove it.
+*/
+pub fn handler(
+    ctx: Context<RemoveDirectedStakeBalanceSource>,
+    source_account: Pubkey,
+) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+    let mut balance_sources = ctx.accounts.balance_sources.load_mut()?;
+
+    let removed = balance_sources.remove_source(&source_account)?;
+    require!(
+        ctx.accounts.signer.key() == ticket.ticket_update_authority
+            || ctx.accounts.signer.key() == removed.owner,
+        StewardError::Unauthorized
+    );
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::StewardError, state::directed_stake::DirectedStakeRecordType, Config,
-    DirectedStakeWhitelist,
+    errors::StewardError, events::UpdateDirectedStakeWhitelistEvent,
+    state::directed_stake::DirectedStakeRecordType, Config, DirectedStakeWhitelist,
 };
 use std::mem::size_of;
 
@@ -35,6 +35,7 @@ pub fn handler(
     record: Pubkey,
 ) -> Result<()> {
     let mut whitelist = ctx.accounts.directed_stake_whitelist.load_mut()?;
+    let previous_count = whitelist.total_permissioned(record_type);
 
     match record_type {
         DirectedStakeRecordType::Validator => {
@@ -48,5 +49,14 @@ pub fn handler(
         }
     }
 
+    emit!(UpdateDirectedStakeWhitelistEvent {
+        authority: ctx.accounts.authority.key(),
+        record_type,
+        record,
+        added: false,
+        previous_count,
+        new_count: whitelist.total_permissioned(record_type),
+    });
+
     Ok(())
 }
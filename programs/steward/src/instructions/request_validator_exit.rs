@@ -0,0 +1,132 @@
+use crate::errors::StewardError;
+use crate::events::ValidatorExitEvent;
+use crate::state::{Config, ValidatorExit};
+use crate::utils::{
+    epoch_progress, get_validator_list, get_validator_stake_info_at_index, remove_validator_check,
+};
+use crate::{StewardStateAccount, StewardStateAccountV2};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::vote;
+use spl_stake_pool::state::StakeStatus;
+use validator_history_vote_state::VoteStateVersions;
+
+#[derive(Accounts)]
+#[instruction(validator_list_index: u64)]
+pub struct RequestValidatorExit<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    /// CHECK: Correct account guaranteed if address is correct
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+
+    /// CHECK: Authorized withdrawer checked in the handler
+    #[account(owner = vote::program::ID)]
+    pub vote_account: AccountInfo<'info>,
+
+    /// Left in place when `auto_add_validator_to_pool` re-adds the validator after the cooldown,
+    /// so a later exit overwrites it
+    #[account(
+        init_if_needed,
+        payer = authorized_withdrawer,
+        space = ValidatorExit::SIZE,
+        seeds = [ValidatorExit::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_exit: AccountLoader<'info, ValidatorExit>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    pub authorized_withdrawer: Signer<'info>,
+}
+
+/*
+Lets a validator leave the pool. Signed by the vote account's authorized withdrawer.
+
+Marks the validator in `validators_to_remove` so `auto_remove_validator_from_pool` removes it from the
+stake pool, and blocks `auto_add_validator_to_pool` from re-adding it for `validator_exit_cooldown_epochs`
+epochs, or until it calls `request_validator_rejoin`. Exiting again after being re-added restarts the cooldown.
+
+The mark has to be acted on within the epoch: once the stake pool is updated in the next epoch the steward
+state no longer lines up with the validator list. Exits are therefore only accepted before
+`instant_unstake_epoch_progress`, leaving the rest of the epoch to remove the validator.
+*/
+pub fn handler(ctx: Context<RequestValidatorExit>, validator_list_index: usize) -> Result<()> {
+    let clock = Clock::get()?;
+    let epoch_schedule = EpochSchedule::get()?;
+    let epoch = clock.epoch;
+    let rejoin_epoch;
+    let marked_for_removal;
+
+    {
+        let config = ctx.accounts.config.load()?;
+        let mut state_account = ctx.accounts.state_account.load_mut()?;
+        let validator_list = &ctx.accounts.validator_list;
+
+        let authorized_withdrawer =
+            VoteStateVersions::deserialize_authorized_withdrawer(&ctx.accounts.vote_account)?;
+        require_keys_eq!(
+            authorized_withdrawer,
+            ctx.accounts.authorized_withdrawer.key(),
+            StewardError::Unauthorized
+        );
+
+        remove_validator_check(&clock, &config, &state_account, validator_list)?;
+
+        require!(
+            epoch_progress(&clock, &epoch_schedule)?
+                < config.parameters.instant_unstake_epoch_progress,
+            StewardError::ValidatorExitWindowClosed
+        );
+
+        let validator_stake_info =
+            get_validator_stake_info_at_index(validator_list, validator_list_index)?;
+        require!(
+            validator_stake_info.vote_account_address == ctx.accounts.vote_account.key(),
+            StewardError::ValidatorNotInList
+        );
+
+        // A validator already on its way out of the pool is marked by whichever instruction removed it
+        marked_for_removal =
+            StakeStatus::try_from(validator_stake_info.status)? == StakeStatus::Active;
+        if marked_for_removal {
+            state_account
+                .state
+                .mark_validator_for_removal(validator_list_index)?;
+        }
+
+        rejoin_epoch = epoch.saturating_add(config.parameters.validator_exit_cooldown_epochs);
+    }
+
+    let initialized = ctx
+        .accounts
+        .validator_exit
+        .as_ref()
+        .try_borrow_data()?
+        .starts_with(ValidatorExit::DISCRIMINATOR);
+    let mut validator_exit = if initialized {
+        ctx.accounts.validator_exit.load_mut()?
+    } else {
+        ctx.accounts.validator_exit.load_init()?
+    };
+    validator_exit.config = ctx.accounts.config.key();
+    validator_exit.vote_account = ctx.accounts.vote_account.key();
+    validator_exit.exit_epoch = epoch;
+    validator_exit.rejoin_epoch = rejoin_epoch;
+
+    emit!(ValidatorExitEvent {
+        vote_account: ctx.accounts.vote_account.key(),
+        validator_list_index: validator_list_index as u64,
+        marked_for_removal,
+        rejoin_epoch,
+    });
+
+    Ok(())
+}
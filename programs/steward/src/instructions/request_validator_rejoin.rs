@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use validator_history_vote_state::VoteStateVersions;
+
+use crate::{errors::StewardError, events::ValidatorRejoinEvent, Config, ValidatorExit};
+
+#[derive(Accounts)]
+pub struct RequestValidatorRejoin<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: Authorized withdrawer checked in the handler
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = authorized_withdrawer,
+        seeds = [ValidatorExit::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_exit: AccountLoader<'info, ValidatorExit>,
+
+    #[account(mut)]
+    pub authorized_withdrawer: Signer<'info>,
+}
+
+/*
+Closes the validator's `ValidatorExit`, so that `auto_add_validator_to_pool` can add it again.
+Signed by the vote account's authorized withdrawer.
+*/
+pub fn handler(ctx: Context<RequestValidatorRejoin>) -> Result<()> {
+    let authorized_withdrawer =
+        VoteStateVersions::deserialize_authorized_withdrawer(&ctx.accounts.vote_account)?;
+    require_keys_eq!(
+        authorized_withdrawer,
+        ctx.accounts.authorized_withdrawer.key(),
+        StewardError::Unauthorized
+    );
+
+    let validator_exit = ctx.accounts.validator_exit.load()?;
+
+    emit!(ValidatorRejoinEvent {
+        vote_account: ctx.accounts.vote_account.key(),
+        exit_epoch: validator_exit.exit_epoch,
+        rejoin_epoch: validator_exit.rejoin_epoch,
+        epoch: Clock::get()?.epoch,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{utils::get_config_admin, Config};
+use crate::{events::SetPausedEvent, utils::get_config_admin, Config};
 
 #[derive(Accounts)]
 pub struct ResumeSteward<'info> {
@@ -16,6 +16,15 @@ Resumes ability to make progress in state machine
 */
 pub fn handler(ctx: Context<ResumeSteward>) -> Result<()> {
     let mut config = ctx.accounts.config.load_mut()?;
+
+    emit!(SetPausedEvent {
+        authority: ctx.accounts.authority.key(),
+        previous_paused: config.is_paused(),
+        paused: false,
+    });
+
     config.set_paused(false);
+    // Activity that tripped the circuit breaker this cycle is acknowledged by resuming
+    config.circuit_breaker_baseline.set_resumed();
     Ok(())
 }
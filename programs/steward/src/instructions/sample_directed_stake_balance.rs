@@ -0,0 +1,97 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{
+    errors::StewardError,
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{
+        get_stake_pool_address, require_transaction_start_balances, ticket_holder_token_balance,
+    },
+    Config, DirectedStakeContribution, DirectedStakeTicket,
+};
+
+#[derive(Accounts)]
+pub struct SampleDirectedStakeBalance<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeContribution::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub contribution_account: AccountLoader<'info, DirectedStakeContribution>,
+
+    /// CHECK: Verified to be the ticket holder's associated token account for the pool mint
+    pub holder_token_account: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeBalanceSources` PDA, which may not exist
+    pub balance_sources: AccountInfo<'info>,
+
+    /// CHECK: Address checked against config
+    #[account(address = get_stake_pool_address(&config)?)]
+    pub stake_pool: AccountInfo<'info>,
+    /// CHECK: Instructions sysvar, used to verify that the balance was not borrowed for the transaction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Records a sample of the ticket holder's pool token balance without updating targets,
+so that targets uploaded with `copy_directed_stake_targets` can use the time-weighted average balance.
+`remaining_accounts` are the accounts of the ticket's balance sources, in order, counted like in
+`crank_directed_stake_ticket`. Like the crank, it must not follow instructions of other programs in its
+transaction.
+*/
+pub fn handler(ctx: Context<SampleDirectedStakeBalance>) -> Result<()> {
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    let sample_interval_slots = ctx
+        .accounts
+        .config
+        .load()?
+        .parameters
+        .directed_stake_sample_interval_slots;
+    require!(
+        sample_interval_slots > 0,
+        StewardError::DirectedStakeSamplingDisabled
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+    require_transaction_start_balances(&ctx.accounts.instructions)?;
+    let current_balance = ticket_holder_token_balance(
+        &ctx.accounts.holder_token_account,
+        &ctx.accounts.balance_sources,
+        ctx.remaining_accounts,
+        &ctx.accounts.ticket_account.key(),
+        &ticket.ticket_update_authority,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+    )?;
+
+    let clock = Clock::get()?;
+    let mut contribution = ctx.accounts.contribution_account.load_mut()?;
+    contribution.record_balance_sample(
+        current_balance,
+        clock.slot,
+        sample_interval_slots,
+        &EpochSchedule::get()?,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, instructions::UpdateDirectedStakeTicket,
+    state::directed_stake::DirectedStakePreference, Config, DirectedStakePreferenceSchedule,
+    DirectedStakeTicket, DirectedStakeWhitelist,
+};
+
+#[derive(Accounts)]
+pub struct ScheduleDirectedStakePreferences<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [DirectedStakeWhitelist::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub whitelist_account: AccountLoader<'info, DirectedStakeWhitelist>,
+
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakePreferenceSchedule::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub preference_schedule: AccountLoader<'info, DirectedStakePreferenceSchedule>,
+
+    pub signer: Signer<'info>,
+}
+
+/*
+Schedules `preferences` to replace the ticket's preferences at `activation_epoch`, replacing any set already
+scheduled for that epoch. Authorized and validated like `update_directed_stake_ticket`, `remaining_accounts` are
+the `DirectedStakeValidatorConsent` PDA or `DirectedStakeBasket` of each preference, in order.
+*/
+pub fn handler(
+    ctx: Context<ScheduleDirectedStakePreferences>,
+    activation_epoch: u64,
+    preferences: Vec<DirectedStakePreference>,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let whitelist = ctx.accounts.whitelist_account.load()?;
+    let ticket = ctx.accounts.ticket_account.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    UpdateDirectedStakeTicket::auth(
+        &ticket,
+        &whitelist,
+        ctx.accounts.signer.key,
+        &preferences,
+        &config.directed_stake_ticket_override_authority,
+        &ctx.accounts.config.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    let clock = Clock::get()?;
+    if activation_epoch <= clock.epoch {
+        msg!("Error: Activation epoch must be in the future");
+        return Err(error!(StewardError::InvalidParameterValue));
+    }
+
+    if preferences.len() > crate::MAX_PREFERENCES_PER_TICKET {
+        msg!("Error: Too many preferences provided");
+        return Err(error!(StewardError::InvalidParameterValue));
+    }
+
+    let total_bps: u32 = preferences
+        .iter()
+        .map(|pref| pref.stake_share_bps as u32)
+        .sum();
+    if total_bps + ticket.extension_stake_share_bps as u32 > 10_000 {
+        msg!("Error: Total stake share basis points cannot exceed 10_000");
+        return Err(error!(StewardError::InvalidParameterValue));
+    }
+
+    let mut preference_schedule = ctx.accounts.preference_schedule.load_mut()?;
+    preference_schedule.schedule(activation_epoch, &preferences)
+}
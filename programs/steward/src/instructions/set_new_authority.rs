@@ -5,7 +5,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::IdlBuild;
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::{errors::StewardError, state::Config};
+use crate::{errors::StewardError, events::SetNewAuthorityEvent, state::Config};
 
 #[repr(u8)]
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
@@ -123,29 +123,32 @@ pub fn handler(ctx: Context<SetNewAuthority>, authority_type: AuthorityType) ->
         return Err(StewardError::Unauthorized.into());
     }
 
-    match authority_type {
-        AuthorityType::SetAdmin => {
-            config.admin = ctx.accounts.new_authority.key();
-        }
-        AuthorityType::SetBlacklistAuthority => {
-            config.blacklist_authority = ctx.accounts.new_authority.key();
-        }
-        AuthorityType::SetParametersAuthority => {
-            config.parameters_authority = ctx.accounts.new_authority.key();
-        }
+    let authority = match authority_type {
+        AuthorityType::SetAdmin => &mut config.admin,
+        AuthorityType::SetBlacklistAuthority => &mut config.blacklist_authority,
+        AuthorityType::SetParametersAuthority => &mut config.parameters_authority,
         AuthorityType::SetPriorityFeeParameterAuthority => {
-            config.priority_fee_parameters_authority = ctx.accounts.new_authority.key();
+            &mut config.priority_fee_parameters_authority
         }
         AuthorityType::SetDirectedStakeWhitelistAuthority => {
-            config.directed_stake_whitelist_authority = ctx.accounts.new_authority.key();
+            &mut config.directed_stake_whitelist_authority
         }
         AuthorityType::SetDirectedStakeMetaUploadAuthority => {
-            config.directed_stake_meta_upload_authority = ctx.accounts.new_authority.key();
+            &mut config.directed_stake_meta_upload_authority
         }
         AuthorityType::SetDirectedStakeTicketOverrideAuthority => {
-            config.directed_stake_ticket_override_authority = ctx.accounts.new_authority.key();
+            &mut config.directed_stake_ticket_override_authority
         }
-    }
+    };
+
+    let previous_authority = *authority;
+    *authority = ctx.accounts.new_authority.key();
+
+    emit!(SetNewAuthorityEvent {
+        authority_type,
+        previous_authority,
+        new_authority: ctx.accounts.new_authority.key(),
+    });
 
     Ok(())
 }
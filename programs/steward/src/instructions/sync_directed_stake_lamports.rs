@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::events::SyncDirectedStakeLamportsEvent;
 use crate::state::directed_stake::DirectedStakeMeta;
 use crate::utils::get_validator_list;
 use crate::utils::vote_pubkey_at_validator_list_index;
@@ -40,10 +41,13 @@ impl SyncDirectedStakeLamports<'_> {
     pub const SIZE: usize = 8 + size_of::<Self>();
 }
 
+/// Copies each target's staked lamports into `directed_stake_lamports`, returning
+/// `(validator_list_index, previous_lamports, new_lamports)` for every entry that changed
 pub fn sync_directed_stake_lamports(
     stake_meta: &mut DirectedStakeMeta,
     validator_list: &BigVec<'_>,
-) -> Result<()> {
+) -> Result<Vec<(u32, u64, u64)>> {
+    let mut changes = Vec::new();
     for validator_list_index in 0..validator_list.len() as usize {
         let validator_list_vote_pubkey =
             vote_pubkey_at_validator_list_index(validator_list, validator_list_index)?;
@@ -63,9 +67,17 @@ pub fn sync_directed_stake_lamports(
 
         let target_total_staked_lamports =
             stake_meta.targets[directed_stake_meta_index].total_staked_lamports;
+        let previous_lamports = stake_meta.directed_stake_lamports[validator_list_index];
+        if previous_lamports != target_total_staked_lamports {
+            changes.push((
+                validator_list_index as u32,
+                previous_lamports,
+                target_total_staked_lamports,
+            ));
+        }
         stake_meta.directed_stake_lamports[validator_list_index] = target_total_staked_lamports;
     }
-    Ok(())
+    Ok(changes)
 }
 
 pub fn handler(ctx: Context<SyncDirectedStakeLamports>) -> Result<()> {
@@ -77,6 +89,16 @@ pub fn handler(ctx: Context<SyncDirectedStakeLamports>) -> Result<()> {
         header.account_type == spl_stake_pool::state::AccountType::ValidatorList,
         StewardError::ValidatorListTypeMismatch
     );
-    sync_directed_stake_lamports(&mut stake_meta, &validator_list)?;
+    let changes = sync_directed_stake_lamports(&mut stake_meta, &validator_list)?;
+
+    if !changes.is_empty() {
+        emit!(SyncDirectedStakeLamportsEvent {
+            signer: ctx.accounts.authority.key(),
+            validator_list_indices: changes.iter().map(|change| change.0).collect(),
+            previous_lamports: changes.iter().map(|change| change.1).collect(),
+            new_lamports: changes.iter().map(|change| change.2).collect(),
+        });
+    }
+
     Ok(())
 }
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError, Config, DirectedStakeBasket, DirectedStakeBasketEntry,
+    DirectedStakeWhitelist, MAX_VALIDATORS_PER_BASKET,
+};
+
+#[derive(Accounts)]
+pub struct UpdateDirectedStakeBasket<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [DirectedStakeWhitelist::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub whitelist_account: AccountLoader<'info, DirectedStakeWhitelist>,
+
+    #[account(
+        mut,
+        has_one = config,
+        has_one = curator @ StewardError::Unauthorized
+    )]
+    pub basket: AccountLoader<'info, DirectedStakeBasket>,
+
+    pub curator: Signer<'info>,
+}
+
+/*
+Replaces the basket validators. Signed by the curator. Every validator must be on the directed stake whitelist
+and the weights must sum to 10_000 bps, unless the basket is emptied.
+
+Tickets pointing at the basket are re-targeted the next time `crank_directed_stake_ticket_basket` runs for them.
+*/
+pub fn handler(
+    ctx: Context<UpdateDirectedStakeBasket>,
+    validators: Vec<DirectedStakeBasketEntry>,
+) -> Result<()> {
+    let whitelist = ctx.accounts.whitelist_account.load()?;
+
+    require!(
+        validators.len() <= MAX_VALIDATORS_PER_BASKET,
+        StewardError::InvalidParameterValue
+    );
+    let total_weight_bps: u32 = validators.iter().map(|entry| entry.weight_bps as u32).sum();
+    require!(
+        validators.is_empty() || total_weight_bps == 10_000,
+        StewardError::InvalidParameterValue
+    );
+
+    for (i, entry) in validators.iter().enumerate() {
+        if !whitelist.is_validator_permissioned(&entry.vote_pubkey) {
+            msg!(
+                "Error: Validator {} is not on the directed stake whitelist",
+                entry.vote_pubkey
+            );
+            return Err(error!(StewardError::Unauthorized));
+        }
+        require!(
+            !validators[..i]
+                .iter()
+                .any(|other| other.vote_pubkey == entry.vote_pubkey),
+            StewardError::InvalidParameterValue
+        );
+    }
+
+    let mut basket = ctx.accounts.basket.load_mut()?;
+    for (i, entry) in validators.iter().enumerate() {
+        basket.validators[i] = *entry;
+    }
+    for entry in basket.validators.iter_mut().skip(validators.len()) {
+        *entry = DirectedStakeBasketEntry::new(Pubkey::default(), 0);
+    }
+    basket.num_validators = validators.len() as u64;
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::StewardError, state::directed_stake::DirectedStakePreference, Config,
-    DirectedStakeTicket, DirectedStakeWhitelist,
+    errors::StewardError,
+    events::DirectedStakeTicketUpdatedEvent,
+    instructions::crank_directed_stake_ticket::TicketTargetChanges,
+    state::directed_stake::DirectedStakePreference,
+    utils::{load_ticket_extension, load_validator_consent, load_zero_copy_mut},
+    Config, DirectedStakeBasket, DirectedStakeBasketContribution, DirectedStakeContributionEntry,
+    DirectedStakeMeta, DirectedStakeTicket, DirectedStakeWhitelist,
 };
 use std::mem::size_of;
 
@@ -20,6 +25,17 @@ pub struct UpdateDirectedStakeTicket<'info> {
     #[account(mut)]
     pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
 
+    /// CHECK: Only loaded when basket contributions are removed, it may not exist yet
+    #[account(
+        mut,
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountInfo<'info>,
+
+    /// CHECK: Verified to be the ticket's `DirectedStakeTicketExtension` PDA, which may not exist
+    pub ticket_extension: AccountInfo<'info>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 }
@@ -33,8 +49,11 @@ impl UpdateDirectedStakeTicket<'_> {
         signer_pubkey: &Pubkey,
         preferences: &[DirectedStakePreference],
         ticket_override_authority: &Pubkey,
+        config: &Pubkey,
+        validator_consents: &[AccountInfo],
     ) -> Result<()> {
         if !whitelist.is_staker_permissioned(signer_pubkey)
+            && !bool::from(ticket.is_permissionless)
             && signer_pubkey != ticket_override_authority
         {
             return Err(error!(StewardError::Unauthorized));
@@ -47,7 +66,15 @@ impl UpdateDirectedStakeTicket<'_> {
             return Err(error!(StewardError::Unauthorized));
         }
 
-        for preference in preferences {
+        require!(
+            preferences.len() == validator_consents.len(),
+            StewardError::InvalidValidatorConsent
+        );
+        for (preference, account) in preferences.iter().zip(validator_consents.iter()) {
+            if preference.is_basket.into() {
+                basket_check(config, &preference.vote_pubkey, account)?;
+                continue;
+            }
             if !whitelist.is_validator_permissioned(&preference.vote_pubkey) {
                 msg!(
                     "Error: Validator {} is not on the directed stake whitelist",
@@ -55,11 +82,75 @@ impl UpdateDirectedStakeTicket<'_> {
                 );
                 return Err(error!(StewardError::Unauthorized));
             }
+            validator_consent_check(
+                config,
+                std::iter::once(&preference.vote_pubkey),
+                std::slice::from_ref(account),
+            )?;
         }
         Ok(())
     }
 }
 
+/// Checks that `basket_account` is the `DirectedStakeBasket` at `basket` belonging to `config`
+pub fn basket_check(config: &Pubkey, basket: &Pubkey, basket_account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        *basket_account.key,
+        *basket,
+        StewardError::InvalidDirectedStakeBasket
+    );
+    require_keys_eq!(
+        *basket_account.owner,
+        crate::id(),
+        StewardError::InvalidDirectedStakeBasket
+    );
+    let data = basket_account.try_borrow_data()?;
+    let basket = DirectedStakeBasket::try_deserialize(&mut data.as_ref())
+        .map_err(|_| error!(StewardError::InvalidDirectedStakeBasket))?;
+    require_keys_eq!(
+        basket.config,
+        *config,
+        StewardError::InvalidDirectedStakeBasket
+    );
+    Ok(())
+}
+
+/// Checks that none of the validators of `vote_pubkeys` have opted out of directed stake.
+/// `validator_consents[i]` must be the `DirectedStakeValidatorConsent` PDA of the i-th validator,
+/// which may not exist.
+pub fn validator_consent_check<'a>(
+    config: &Pubkey,
+    vote_pubkeys: impl ExactSizeIterator<Item = &'a Pubkey>,
+    validator_consents: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        vote_pubkeys.len() == validator_consents.len(),
+        StewardError::InvalidValidatorConsent
+    );
+    for (vote_pubkey, consent_account) in vote_pubkeys.zip(validator_consents.iter()) {
+        if let Some(consent) = load_validator_consent(consent_account, config, vote_pubkey)? {
+            if consent.opted_out.into() {
+                msg!(
+                    "Error: Validator {} has opted out of directed stake",
+                    vote_pubkey
+                );
+                return Err(error!(StewardError::ValidatorOptedOutOfDirectedStake));
+            }
+        }
+    }
+    Ok(())
+}
+
+/*
+`remaining_accounts` are the `DirectedStakeValidatorConsent` PDAs of each preference's validator, in order. For
+preferences pointing at a basket, the `DirectedStakeBasket` account itself is passed instead.
+
+They are followed by the `DirectedStakeBasketContribution` PDA of each basket the previous preferences pointed at
+that neither the new preferences nor the ticket extension point at anymore, in the order of the previous
+preferences. The targets those baskets contributed are removed, so a ticket never keeps contributing through a
+basket it left. Basket contribution accounts that do not exist yet are skipped, and emptied ones are closed by
+`clear_directed_stake_ticket_basket`.
+*/
 pub fn handler(
     ctx: Context<UpdateDirectedStakeTicket>,
     preferences: Vec<DirectedStakePreference>,
@@ -83,12 +174,20 @@ pub fn handler(
         StewardError::Unauthorized
     );
 
+    require!(
+        ctx.remaining_accounts.len() >= preferences.len(),
+        StewardError::InvalidValidatorConsent
+    );
+    let (validator_consents, basket_contribution_accounts) =
+        ctx.remaining_accounts.split_at(preferences.len());
     UpdateDirectedStakeTicket::auth(
         &ticket,
         &whitelist,
         ctx.accounts.signer.key,
         &preferences,
         &config.directed_stake_ticket_override_authority,
+        &ctx.accounts.config.key(),
+        validator_consents,
     )?;
 
     if preferences.len() > crate::MAX_PREFERENCES_PER_TICKET {
@@ -101,11 +200,13 @@ pub fn handler(
         .map(|pref| pref.stake_share_bps as u32)
         .sum();
 
-    if total_bps > 10_000 {
+    if total_bps + ticket.extension_stake_share_bps as u32 > 10_000 {
         msg!("Error: Total stake share basis points cannot exceed 10_000");
         return Err(error!(StewardError::InvalidParameterValue));
     }
 
+    let previous_preferences = ticket.preferences().to_vec();
+
     ticket.num_preferences = preferences.len() as u16;
     ticket.staker_preferences =
         [DirectedStakePreference::empty(); crate::MAX_PREFERENCES_PER_TICKET];
@@ -116,5 +217,75 @@ pub fn handler(
         }
     }
 
+    let ticket_extension = load_ticket_extension(
+        &ctx.accounts.ticket_extension,
+        &ctx.accounts.ticket_account.key(),
+    )?;
+    let mut left_baskets: Vec<Pubkey> = Vec::new();
+    for preference in previous_preferences.iter() {
+        if bool::from(preference.is_basket)
+            && !left_baskets.contains(&preference.vote_pubkey)
+            && DirectedStakeBasket::ticket_stake_share_bps(
+                &preference.vote_pubkey,
+                &ticket,
+                ticket_extension.as_deref(),
+            ) == 0
+        {
+            left_baskets.push(preference.vote_pubkey);
+        }
+    }
+    require!(
+        basket_contribution_accounts.len() == left_baskets.len(),
+        StewardError::InvalidDirectedStakeBasket
+    );
+
+    for (basket, basket_contribution_account) in
+        left_baskets.iter().zip(basket_contribution_accounts.iter())
+    {
+        let (expected_address, _) = Pubkey::find_program_address(
+            &[
+                DirectedStakeBasketContribution::SEED,
+                ctx.accounts.ticket_account.key().as_ref(),
+                basket.as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            *basket_contribution_account.key,
+            expected_address,
+            StewardError::InvalidDirectedStakeBasket
+        );
+    }
+
+    emit!(DirectedStakeTicketUpdatedEvent {
+        ticket: ctx.accounts.ticket_account.key(),
+        signer: ctx.accounts.signer.key(),
+        previous_preferences,
+        new_preferences: preferences,
+    });
+
+    let basket_contribution_accounts: Vec<&AccountInfo> = basket_contribution_accounts
+        .iter()
+        .filter(|account| !account.data_is_empty())
+        .collect();
+    if basket_contribution_accounts.is_empty() {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    let mut stake_meta =
+        load_zero_copy_mut::<DirectedStakeMeta>(&ctx.accounts.directed_stake_meta)?;
+    let mut target_changes = TicketTargetChanges::default();
+    for basket_contribution_account in basket_contribution_accounts {
+        let mut basket_contribution =
+            load_zero_copy_mut::<DirectedStakeBasketContribution>(basket_contribution_account)?;
+        target_changes.track_entries(&stake_meta, &basket_contribution.contributions);
+        for entry in basket_contribution.contributions.iter_mut() {
+            stake_meta.remove_contribution_entry(entry, clock.epoch);
+            *entry = DirectedStakeContributionEntry::default();
+        }
+    }
+    target_changes.emit(&stake_meta, ctx.accounts.ticket_account.key(), clock.epoch);
+
     Ok(())
 }
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StewardError,
+    instructions::update_directed_stake_ticket::{basket_check, validator_consent_check},
+    Config, DirectedStakeExtensionPreference, DirectedStakeTicket, DirectedStakeTicketExtension,
+    DirectedStakeWhitelist, UpdateDirectedStakeTicket, MAX_PREFERENCES_PER_TICKET_EXTENSION,
+};
+
+#[derive(Accounts)]
+pub struct UpdateDirectedStakeTicketExtension<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [DirectedStakeWhitelist::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub whitelist_account: AccountLoader<'info, DirectedStakeWhitelist>,
+
+    #[account(mut)]
+    pub ticket_account: AccountLoader<'info, DirectedStakeTicket>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeTicketExtension::SEED, ticket_account.key().as_ref()],
+        bump
+    )]
+    pub ticket_extension: AccountLoader<'info, DirectedStakeTicketExtension>,
+
+    pub signer: Signer<'info>,
+}
+
+/*
+Writes `preferences` to the extension starting at `start_index` and sets the number of extension preferences
+to `num_preferences`, clearing any preference past it. Large extensions are written over several transactions.
+
+The stake shares of the ticket and its extension together cannot exceed 10_000 bps.
+
+`remaining_accounts` are the `DirectedStakeValidatorConsent` PDAs of each of `preferences`' validators, in order.
+For preferences pointing at a basket, the `DirectedStakeBasket` account itself is passed instead.
+*/
+pub fn handler(
+    ctx: Context<UpdateDirectedStakeTicketExtension>,
+    start_index: u16,
+    num_preferences: u16,
+    preferences: Vec<DirectedStakeExtensionPreference>,
+) -> Result<()> {
+    let whitelist = ctx.accounts.whitelist_account.load()?;
+    let mut ticket = ctx.accounts.ticket_account.load_mut()?;
+    let mut ticket_extension = ctx.accounts.ticket_extension.load_mut()?;
+    let config = ctx.accounts.config.load()?;
+
+    // Verify the PDA: seeds should be [SEED, config.key(), ticket_update_authority]
+    let (expected_ticket_address, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeTicket::SEED,
+            ctx.accounts.config.key().as_ref(),
+            ticket.ticket_update_authority.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        ctx.accounts.ticket_account.key(),
+        expected_ticket_address,
+        StewardError::Unauthorized
+    );
+
+    UpdateDirectedStakeTicket::auth(
+        &ticket,
+        &whitelist,
+        ctx.accounts.signer.key,
+        &[],
+        &config.directed_stake_ticket_override_authority,
+        &ctx.accounts.config.key(),
+        &[],
+    )?;
+
+    require!(
+        preferences.len() == ctx.remaining_accounts.len(),
+        StewardError::InvalidValidatorConsent
+    );
+    for (preference, account) in preferences.iter().zip(ctx.remaining_accounts.iter()) {
+        if preference.is_basket.into() {
+            basket_check(&ctx.accounts.config.key(), &preference.vote_pubkey, account)?;
+            continue;
+        }
+        if !whitelist.is_validator_permissioned(&preference.vote_pubkey) {
+            msg!(
+                "Error: Validator {} is not on the directed stake whitelist",
+                preference.vote_pubkey
+            );
+            return Err(error!(StewardError::Unauthorized));
+        }
+        validator_consent_check(
+            &ctx.accounts.config.key(),
+            std::iter::once(&preference.vote_pubkey),
+            std::slice::from_ref(account),
+        )?;
+    }
+
+    let start_index = start_index as usize;
+    let num_preferences = num_preferences as usize;
+    if num_preferences > MAX_PREFERENCES_PER_TICKET_EXTENSION
+        || start_index + preferences.len() > num_preferences
+    {
+        msg!("Error: Too many preferences provided");
+        return Err(error!(StewardError::InvalidParameterValue));
+    }
+
+    for (i, preference) in preferences.iter().enumerate() {
+        ticket_extension.staker_preferences[start_index + i] = *preference;
+    }
+    for preference in ticket_extension.staker_preferences[num_preferences..].iter_mut() {
+        *preference = DirectedStakeExtensionPreference::default();
+    }
+    ticket_extension.num_preferences = num_preferences as u64;
+
+    let extension_bps = ticket_extension.stake_share_bps();
+    if ticket.stake_share_bps() + extension_bps > 10_000 {
+        msg!("Error: Total stake share basis points cannot exceed 10_000");
+        return Err(error!(StewardError::InvalidParameterValue));
+    }
+    ticket.extension_stake_share_bps = extension_bps as u16;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use validator_history_vote_state::VoteStateVersions;
+
+use crate::{errors::StewardError, utils::U8Bool, Config, DirectedStakeValidatorConsent};
+
+#[derive(Accounts)]
+pub struct UpdateDirectedStakeValidatorConsent<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: Owner and signer authority checked in the handler
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [DirectedStakeValidatorConsent::SEED, config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_consent: AccountLoader<'info, DirectedStakeValidatorConsent>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Checks that `signer` is the validator identity or the authorized withdrawer of `vote_account`
+pub fn validator_authority_check(vote_account: &AccountInfo, signer: &Pubkey) -> Result<()> {
+    let node_pubkey = VoteStateVersions::deserialize_node_pubkey(vote_account)?;
+    let authorized_withdrawer = VoteStateVersions::deserialize_authorized_withdrawer(vote_account)?;
+    if *signer != node_pubkey && *signer != authorized_withdrawer {
+        msg!("Error: Signer is not the validator identity or authorized withdrawer");
+        return Err(error!(StewardError::Unauthorized));
+    }
+    Ok(())
+}
+
+/*
+Sets whether the validator accepts directed stake, and the maximum directed stake target it accepts
+(0 for no maximum). Signed by the validator identity or the vote account's authorized withdrawer.
+
+Opting out prevents tickets from adding the validator, and both opting out and the maximum are applied
+whenever its targets are next cranked or copied.
+*/
+pub fn handler(
+    ctx: Context<UpdateDirectedStakeValidatorConsent>,
+    opted_out: bool,
+    max_directed_stake_lamports: u64,
+) -> Result<()> {
+    validator_authority_check(&ctx.accounts.vote_account, ctx.accounts.signer.key)?;
+
+    let mut validator_consent = ctx.accounts.validator_consent.load_mut()?;
+    validator_consent.opted_out = U8Bool::from(opted_out);
+    validator_consent.max_directed_stake_lamports = max_directed_stake_lamports;
+
+    Ok(())
+}
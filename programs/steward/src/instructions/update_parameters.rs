@@ -1,4 +1,7 @@
-use crate::{utils::get_config_parameter_authority, Config, UpdateParametersArgs};
+use crate::{
+    events::UpdateParametersEvent, utils::get_config_parameter_authority, Config,
+    UpdateParametersArgs,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -24,6 +27,12 @@ pub fn handler(
         max_slots_in_epoch,
     )?;
 
+    emit!(UpdateParametersEvent {
+        authority: ctx.accounts.authority.key(),
+        previous_parameters: config.parameters,
+        new_parameters,
+    });
+
     config.parameters = new_parameters;
 
     Ok(())
@@ -0,0 +1,180 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke_signed,
+        stake::{state::StakeStateV2, tools::get_minimum_delegation},
+    },
+};
+use spl_stake_pool::{
+    instruction::PreferredValidatorType, minimum_delegation, state::ValidatorListHeader,
+};
+
+use crate::{
+    delegation::{preferred_validators_calculation, UndirectedPoolLamports},
+    directed_stake::DirectedStakeMeta,
+    errors::StewardError,
+    events::UpdatePreferredValidatorsEvent,
+    stake_pool_utils::deserialize_stake_pool,
+    utils::{get_stake_pool_address, state_checks, vote_pubkey_at_validator_list_index},
+    Config, StewardStateAccount, StewardStateAccountV2, StewardStateEnum,
+};
+
+#[derive(Accounts)]
+pub struct UpdatePreferredValidators<'info> {
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    #[account(
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: CPI program
+    #[account(address = spl_stake_pool::ID)]
+    pub stake_pool_program: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(
+        mut,
+        address = get_stake_pool_address(&config)?
+    )]
+    pub stake_pool: AccountInfo<'info>,
+
+    /// CHECK: passing through, checks are done by spl-stake-pool
+    #[account(address = deserialize_stake_pool(&stake_pool)?.validator_list)]
+    pub validator_list: AccountInfo<'info>,
+}
+
+/*
+Permissionless. Points the pool's preferred deposit validator at the highest-scored validator furthest below
+its target, and the preferred withdraw validator at the lowest-scored validator furthest above its target, so
+stake deposits and withdrawals move stake toward targets without using rebalance budget.
+
+Targets are computed on the same pool lamports as `rebalance`. Can be cranked during Rebalance or once the
+cycle's rebalancing is done in Idle. Only preferred validators that changed are updated.
+*/
+pub fn handler(ctx: Context<UpdatePreferredValidators>) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let clock = Clock::get()?;
+
+    require!(
+        config.parameters.auto_preferred_validators_enabled(),
+        StewardError::AutoPreferredValidatorsDisabled
+    );
+
+    let stake_pool = deserialize_stake_pool(&ctx.accounts.stake_pool)?;
+
+    let (preferred_deposit_validator, preferred_withdraw_validator) = {
+        let state_account = ctx.accounts.state_account.load()?;
+
+        state_checks(
+            &clock,
+            &config,
+            &state_account,
+            &ctx.accounts.validator_list,
+            None,
+        )?;
+
+        require!(
+            matches!(
+                state_account.state.state_tag,
+                StewardStateEnum::Rebalance | StewardStateEnum::Idle
+            ),
+            StewardError::InvalidState
+        );
+
+        let directed_stake_meta = ctx.accounts.directed_stake_meta.load()?;
+
+        let minimum_delegation = minimum_delegation(get_minimum_delegation()?);
+        let stake_rent = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+
+        let validator_list_data = &mut ctx.accounts.validator_list.try_borrow_mut_data()?;
+        let (_, validator_list) = ValidatorListHeader::deserialize_vec(validator_list_data)?;
+
+        // Same pool lamports that `rebalance` computes validator targets on
+        let pool_lamports = UndirectedPoolLamports::new(
+            &config.parameters,
+            stake_pool.total_lamports,
+            directed_stake_meta.total_staked_lamports(),
+            validator_list.len() as usize,
+            minimum_delegation,
+            stake_rent,
+        );
+
+        let preferred = preferred_validators_calculation(
+            &state_account.state,
+            &directed_stake_meta,
+            pool_lamports.delegable_lamports(),
+            &validator_list,
+            minimum_delegation,
+            stake_rent,
+        )?;
+
+        (
+            preferred
+                .deposit
+                .map(|index| vote_pubkey_at_validator_list_index(&validator_list, index))
+                .transpose()?,
+            preferred
+                .withdraw
+                .map(|index| vote_pubkey_at_validator_list_index(&validator_list, index))
+                .transpose()?,
+        )
+    };
+
+    let mut updated = false;
+    for (validator_type, current, preferred) in [
+        (
+            PreferredValidatorType::Deposit,
+            stake_pool.preferred_deposit_validator_vote_address,
+            preferred_deposit_validator,
+        ),
+        (
+            PreferredValidatorType::Withdraw,
+            stake_pool.preferred_withdraw_validator_vote_address,
+            preferred_withdraw_validator,
+        ),
+    ] {
+        if current == preferred {
+            continue;
+        }
+
+        invoke_signed(
+            &spl_stake_pool::instruction::set_preferred_validator(
+                ctx.accounts.stake_pool_program.key,
+                &ctx.accounts.stake_pool.key(),
+                &ctx.accounts.state_account.key(),
+                &ctx.accounts.validator_list.key(),
+                validator_type,
+                preferred,
+            ),
+            &[
+                ctx.accounts.stake_pool.to_account_info(),
+                ctx.accounts.state_account.to_account_info(),
+                ctx.accounts.validator_list.to_account_info(),
+            ],
+            &[&[
+                StewardStateAccount::SEED,
+                &ctx.accounts.config.key().to_bytes(),
+                &[ctx.bumps.state_account],
+            ]],
+        )?;
+        updated = true;
+    }
+
+    if updated {
+        emit!(UpdatePreferredValidatorsEvent {
+            epoch: clock.epoch as u16,
+            preferred_deposit_validator,
+            preferred_withdraw_validator,
+        });
+    }
+
+    Ok(())
+}
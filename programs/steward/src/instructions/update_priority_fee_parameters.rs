@@ -1,5 +1,6 @@
 use crate::{
-    utils::get_config_priority_fee_parameter_authority, Config, UpdatePriorityFeeParametersArgs,
+    events::UpdatePriorityFeeParametersEvent, utils::get_config_priority_fee_parameter_authority,
+    Config, UpdatePriorityFeeParametersArgs,
 };
 use anchor_lang::prelude::*;
 
@@ -26,6 +27,12 @@ pub fn handler(
         max_slots_in_epoch,
     )?;
 
+    emit!(UpdatePriorityFeeParametersEvent {
+        authority: ctx.accounts.authority.key(),
+        previous_parameters: config.parameters,
+        new_parameters,
+    });
+
     config.parameters = new_parameters;
 
     Ok(())
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use spl_stake_pool::state::ValidatorListHeader;
+
+use crate::{
+    constants::LAMPORT_BALANCE_DEFAULT,
+    directed_stake::DirectedStakeMeta,
+    errors::StewardError,
+    events::{AccountingDriftEvent, VerifyInvariantsEvent},
+    utils::{
+        get_validator_list, stake_lamports_at_validator_list_index,
+        vote_pubkey_at_validator_list_index,
+    },
+    Config, StewardStateAccount, StewardStateAccountV2,
+};
+
+/// `directed_stake_lamports` disagrees with the staked lamports of the validator's directed stake target,
+/// or points at a target for another vote account
+pub const ACCOUNTING_DRIFT_DIRECTED_TARGET: u8 = 1 << 0;
+/// `directed_stake_lamports` is above the validator's `validator_lamport_balances` entry
+pub const ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE: u8 = 1 << 1;
+/// `directed_stake_lamports` is above the validator's active stake in the ValidatorList
+pub const ACCOUNTING_DRIFT_DIRECTED_ABOVE_VALIDATOR_LIST: u8 = 1 << 2;
+
+/// Drift that only the steward's own instructions can cause, and that pauses the steward when
+/// `verify_invariants` is called with `pause_on_drift`. The other flags can be caused by stake
+/// withdrawals and are corrected by the next rebalance.
+pub const ACCOUNTING_DRIFT_PAUSE_FLAGS: u8 = ACCOUNTING_DRIFT_DIRECTED_TARGET;
+
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        seeds = [StewardStateAccount::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub state_account: AccountLoader<'info, StewardStateAccountV2>,
+
+    #[account(
+        seeds = [DirectedStakeMeta::SEED, config.key().as_ref()],
+        bump
+    )]
+    pub directed_stake_meta: AccountLoader<'info, DirectedStakeMeta>,
+
+    /// CHECK: Checked against the config's stake pool
+    #[account(address = get_validator_list(&config)?)]
+    pub validator_list: AccountInfo<'info>,
+}
+
+/// Returns the `ACCOUNTING_DRIFT_*` flags for one validator.
+///
+/// `target` is the vote account and staked lamports of the directed stake target
+/// `directed_stake_meta_indices` points at, if any.
+pub fn accounting_drift_flags(
+    vote_account: &Pubkey,
+    validator_lamport_balance: u64,
+    validator_list_active_lamports: u64,
+    directed_stake_lamports: u64,
+    target: Option<(Pubkey, u64)>,
+) -> u8 {
+    let mut drift_flags = 0;
+
+    let target_mismatch = match target {
+        Some((target_vote_account, target_total_staked_lamports)) => {
+            target_vote_account != *vote_account
+                || target_total_staked_lamports != directed_stake_lamports
+        }
+        None => directed_stake_lamports != 0,
+    };
+    if target_mismatch {
+        drift_flags |= ACCOUNTING_DRIFT_DIRECTED_TARGET;
+    }
+
+    if validator_lamport_balance != LAMPORT_BALANCE_DEFAULT
+        && directed_stake_lamports > validator_lamport_balance
+    {
+        drift_flags |= ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE;
+    }
+
+    if directed_stake_lamports > validator_list_active_lamports {
+        drift_flags |= ACCOUNTING_DRIFT_DIRECTED_ABOVE_VALIDATOR_LIST;
+    }
+
+    drift_flags
+}
+
+/*
+Permissionless. Checks the steward's lamport accounting for validator list indices `start_index..end_index`
+against the ValidatorList and DirectedStakeMeta, emitting an AccountingDriftEvent for each validator that disagrees.
+Changes nothing unless `pause_on_drift` is set and drift in `ACCOUNTING_DRIFT_PAUSE_FLAGS` is found, in which case
+the steward is paused until the admin reconciles the accounts and resumes it. Meant to be simulated by monitoring.
+*/
+pub fn handler(
+    ctx: Context<VerifyInvariants>,
+    start_index: u64,
+    end_index: u64,
+    pause_on_drift: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let state_account = ctx.accounts.state_account.load()?;
+    let directed_stake_meta = ctx.accounts.directed_stake_meta.load()?;
+
+    let validator_list_data = &mut ctx.accounts.validator_list.try_borrow_mut_data()?;
+    let (header, validator_list) = ValidatorListHeader::deserialize_vec(validator_list_data)?;
+    require!(
+        header.account_type == spl_stake_pool::state::AccountType::ValidatorList,
+        StewardError::ValidatorListTypeMismatch
+    );
+
+    let end_index = end_index.min(validator_list.len() as u64);
+    require!(
+        start_index < end_index,
+        StewardError::ValidatorIndexOutOfBounds
+    );
+
+    let mut validators_with_drift: u64 = 0;
+    let mut pause = false;
+    for index in start_index as usize..end_index as usize {
+        let vote_account = vote_pubkey_at_validator_list_index(&validator_list, index)?;
+        let (validator_list_active_lamports, _) =
+            stake_lamports_at_validator_list_index(&validator_list, index)?;
+        let validator_lamport_balance = state_account.state.validator_lamport_balances[index];
+        let directed_stake_lamports = directed_stake_meta.directed_stake_lamports[index];
+
+        let target = directed_stake_meta
+            .targets
+            .get(directed_stake_meta.directed_stake_meta_indices[index] as usize)
+            .map(|target| (target.vote_pubkey, target.total_staked_lamports));
+
+        let drift_flags = accounting_drift_flags(
+            &vote_account,
+            validator_lamport_balance,
+            validator_list_active_lamports,
+            directed_stake_lamports,
+            target,
+        );
+        if drift_flags == 0 {
+            continue;
+        }
+
+        validators_with_drift = validators_with_drift.saturating_add(1);
+        pause |= drift_flags & ACCOUNTING_DRIFT_PAUSE_FLAGS != 0;
+        emit!(AccountingDriftEvent {
+            epoch: clock.epoch,
+            validator_list_index: index as u64,
+            vote_account,
+            drift_flags,
+            validator_lamport_balance,
+            validator_list_active_lamports,
+            directed_stake_lamports,
+            target_total_staked_lamports: target.map(|target| target.1).unwrap_or_default(),
+        });
+    }
+
+    let paused = pause_on_drift && pause;
+    if paused {
+        ctx.accounts.config.load_mut()?.set_paused(true);
+    }
+
+    emit!(VerifyInvariantsEvent {
+        epoch: clock.epoch,
+        start_index,
+        end_index,
+        validators_with_drift,
+        paused,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accounting_drift_flags() {
+        let vote_account = Pubkey::new_unique();
+
+        // Consistent accounting
+        assert_eq!(
+            accounting_drift_flags(&vote_account, 1_000, 1_000, 600, Some((vote_account, 600))),
+            0
+        );
+        assert_eq!(
+            accounting_drift_flags(&vote_account, LAMPORT_BALANCE_DEFAULT, 0, 0, None),
+            0
+        );
+
+        // Mirrors disagree, or the index points at another validator's target
+        assert_eq!(
+            accounting_drift_flags(&vote_account, 1_000, 1_000, 600, Some((vote_account, 500))),
+            ACCOUNTING_DRIFT_DIRECTED_TARGET
+        );
+        assert_eq!(
+            accounting_drift_flags(
+                &vote_account,
+                1_000,
+                1_000,
+                600,
+                Some((Pubkey::new_unique(), 600))
+            ),
+            ACCOUNTING_DRIFT_DIRECTED_TARGET
+        );
+        assert_eq!(
+            accounting_drift_flags(&vote_account, 1_000, 1_000, 600, None),
+            ACCOUNTING_DRIFT_DIRECTED_TARGET
+        );
+
+        // A withdrawal not yet reconciled by rebalancing
+        assert_eq!(
+            accounting_drift_flags(&vote_account, 500, 400, 600, Some((vote_account, 600))),
+            ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE
+                | ACCOUNTING_DRIFT_DIRECTED_ABOVE_VALIDATOR_LIST
+        );
+        assert_eq!(
+            ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE & ACCOUNTING_DRIFT_PAUSE_FLAGS,
+            0
+        );
+    }
+}
@@ -40,7 +40,9 @@ x) epoch_maintenance ( once per epoch )
 2) compute_delegations
 3) idle
 4) compute_instant_unstake ( once per validator )
-5) rebalance ( once per validator )
+5) rebalance ( once per validator, or `rebalance_many` for several validators at a time )
+
+x) update_preferred_validators ( after rebalance, if `auto_preferred_validators` is enabled )
 
 For the remaining epochs in a cycle, the state will repeat idle->compute_instant_unstake->rebalance.
 After `num_epochs_between_scoring` epochs, the state can transition back to ComputeScores.
@@ -127,6 +129,16 @@ pub mod steward {
         instructions::compute_score::handler(ctx, validator_list_index as usize)
     }
 
+    /// Computes score for a validator added to the pool after ComputeScores finished for the
+    /// current cycle, recomputing delegations if they were already computed.
+    /// Must be called in validator list order, starting at `num_pool_validators`.
+    pub fn compute_score_added_validator(
+        ctx: Context<ComputeScoreAddedValidator>,
+        validator_list_index: u64,
+    ) -> Result<()> {
+        instructions::compute_score_added_validator::handler(ctx, validator_list_index as usize)
+    }
+
     /// Computes delegation for a validator for the current cycle.
     /// All validators must have delegations computed before stake can be delegated
     pub fn compute_delegations(ctx: Context<ComputeDelegations>) -> Result<()> {
@@ -138,7 +150,16 @@ pub mod steward {
         instructions::idle::handler(ctx)
     }
 
-    /// Checks if a validator at `validator_list_index` should be instant unstaked, and marks it if so
+    /// Creates the CycleReport for `cycle_start_epoch`, which cranks fill in when it is passed to them
+    pub fn initialize_cycle_report(
+        ctx: Context<InitializeCycleReport>,
+        cycle_start_epoch: u64,
+    ) -> Result<()> {
+        instructions::initialize_cycle_report::handler(ctx, cycle_start_epoch)
+    }
+
+    /// Checks if a validator at `validator_list_index` should be instant unstaked, and marks it if so.
+    /// Pauses the steward if more validators are marked than the circuit breaker allows
     pub fn compute_instant_unstake(
         ctx: Context<ComputeInstantUnstake>,
         validator_list_index: u64,
@@ -152,6 +173,15 @@ pub mod steward {
         instructions::rebalance::handler(ctx, validator_list_index as usize)
     }
 
+    /// Rebalances each validator at `validator_list_indices` in one instruction, with each validator's
+    /// accounts passed as remaining accounts
+    pub fn rebalance_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebalanceMany<'info>>,
+        validator_list_indices: Vec<u64>,
+    ) -> Result<()> {
+        instructions::rebalance_many::handler(ctx, validator_list_indices)
+    }
+
     /// Increases or decreases stake for a validator at `validator_list_index` using directed stake targets
     pub fn rebalance_directed(
         ctx: Context<RebalanceDirected>,
@@ -176,7 +206,8 @@ pub mod steward {
         instructions::pause_steward::handler(ctx)
     }
 
-    /// Resumes the steward, allowing state transitions to continue
+    /// Resumes the steward, allowing state transitions to continue. Unstaking that tripped the
+    /// circuit breaker this cycle is acknowledged and no longer counts toward its limits
     pub fn resume_steward(ctx: Context<ResumeSteward>) -> Result<()> {
         instructions::resume_steward::handler(ctx)
     }
@@ -252,6 +283,15 @@ pub mod steward {
         instructions::migrate_directed_to_algorithmic::handler(ctx)
     }
 
+    /// One-time migration: makes cranked ticket contributions authoritative. The uploaded portion
+    /// of each target is kept until the tickets are cranked and replace it. Tickets can only be
+    /// cranked after it, and uploads are no longer accepted.
+    pub fn migrate_directed_targets_to_tickets(
+        ctx: Context<MigrateDirectedTargetsToTickets>,
+    ) -> Result<()> {
+        instructions::migrate_directed_targets_to_tickets::handler(ctx)
+    }
+
     /* Passthrough instructions */
     /* passthrough to spl-stake-pool, where the signer is Staker. Must be invoked by `config.authority` */
 
@@ -459,4 +499,287 @@ pub mod steward {
     pub fn sync_directed_stake_lamports(ctx: Context<SyncDirectedStakeLamports>) -> Result<()> {
         instructions::sync_directed_stake_lamports::handler(ctx)
     }
+
+    /// Initialize the DirectedStakeContribution account of a ticket
+    pub fn initialize_directed_stake_contribution(
+        ctx: Context<InitializeDirectedStakeContribution>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_contribution::handler(ctx)
+    }
+
+    /// Recompute a ticket's directed stake targets from the ticket holder's pool token balance
+    pub fn crank_directed_stake_ticket(
+        ctx: Context<CrankDirectedStakeTicket>,
+        validator_list_indices: Vec<u32>,
+    ) -> Result<()> {
+        instructions::crank_directed_stake_ticket::handler(ctx, validator_list_indices)
+    }
+
+    /// Record a sample of the ticket holder's pool token balance for time-weighted directed stake
+    pub fn sample_directed_stake_balance(ctx: Context<SampleDirectedStakeBalance>) -> Result<()> {
+        instructions::sample_directed_stake_balance::handler(ctx)
+    }
+
+    /// Initialize the DirectedStakeBalanceSources account of a ticket
+    pub fn initialize_directed_stake_balance_sources(
+        ctx: Context<InitializeDirectedStakeBalanceSources>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_balance_sources::handler(ctx)
+    }
+
+    /// Attribute JitoSOL held in a token or position account to a ticket
+    pub fn add_directed_stake_balance_source(
+        ctx: Context<AddDirectedStakeBalanceSource>,
+        source_type: u16,
+    ) -> Result<()> {
+        instructions::add_directed_stake_balance_source::handler(ctx, source_type)
+    }
+
+    /// Remove a balance source from a ticket
+    pub fn remove_directed_stake_balance_source(
+        ctx: Context<RemoveDirectedStakeBalanceSource>,
+        source_account: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_directed_stake_balance_source::handler(ctx, source_account)
+    }
+
+    /// Remove the directed stake targets contributed by a closed ticket
+    pub fn clear_closed_directed_stake_ticket(
+        ctx: Context<ClearClosedDirectedStakeTicket>,
+    ) -> Result<()> {
+        instructions::clear_closed_directed_stake_ticket::handler(ctx)
+    }
+
+    /// Create the accounts holding a ticket's additional preferences
+    pub fn initialize_directed_stake_ticket_extension(
+        ctx: Context<InitializeDirectedStakeTicketExtension>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_ticket_extension::handler(ctx)
+    }
+
+    /// Update a range of a ticket's additional preferences
+    pub fn update_directed_stake_ticket_extension(
+        ctx: Context<UpdateDirectedStakeTicketExtension>,
+        start_index: u16,
+        num_preferences: u16,
+        preferences: Vec<DirectedStakeExtensionPreference>,
+    ) -> Result<()> {
+        instructions::update_directed_stake_ticket_extension::handler(
+            ctx,
+            start_index,
+            num_preferences,
+            preferences,
+        )
+    }
+
+    /// Permissionless. Recompute the directed stake targets of a range of a ticket's additional preferences
+    pub fn crank_directed_stake_ticket_extension(
+        ctx: Context<CrankDirectedStakeTicketExtension>,
+        start_index: u16,
+        validator_list_indices: Vec<u32>,
+    ) -> Result<()> {
+        instructions::crank_directed_stake_ticket_extension::handler(
+            ctx,
+            start_index,
+            validator_list_indices,
+        )
+    }
+
+    /// Remove the directed stake targets contributed by the extension of a closed ticket
+    pub fn clear_closed_directed_stake_ticket_extension(
+        ctx: Context<ClearClosedDirectedStakeTicketExtension>,
+    ) -> Result<()> {
+        instructions::clear_closed_directed_stake_ticket_extension::handler(ctx)
+    }
+
+    /// Create the account through which a validator controls the directed stake it receives
+    pub fn initialize_directed_stake_validator_consent(
+        ctx: Context<InitializeDirectedStakeValidatorConsent>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_validator_consent::handler(ctx)
+    }
+
+    /// Opt a validator in or out of directed stake and set its maximum directed stake
+    pub fn update_directed_stake_validator_consent(
+        ctx: Context<UpdateDirectedStakeValidatorConsent>,
+        opted_out: bool,
+        max_directed_stake_lamports: u64,
+    ) -> Result<()> {
+        instructions::update_directed_stake_validator_consent::handler(
+            ctx,
+            opted_out,
+            max_directed_stake_lamports,
+        )
+    }
+
+    /// Open a directed stake ticket without a whitelist entry by holding the minimum JitoSOL balance
+    pub fn initialize_permissionless_directed_stake_ticket(
+        ctx: Context<InitializePermissionlessDirectedStakeTicket>,
+    ) -> Result<()> {
+        instructions::initialize_permissionless_directed_stake_ticket::handler(ctx)
+    }
+
+    /// Permissionless. Close a permissionless ticket whose holder fell below the minimum JitoSOL balance
+    pub fn close_underfunded_directed_stake_ticket(
+        ctx: Context<CloseUnderfundedDirectedStakeTicket>,
+    ) -> Result<()> {
+        instructions::close_underfunded_directed_stake_ticket::handler(ctx)
+    }
+
+    /// Create a named basket of validators managed by `curator`
+    pub fn initialize_directed_stake_basket(
+        ctx: Context<InitializeDirectedStakeBasket>,
+        name: [u8; 32],
+        curator: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_basket::handler(ctx, name, curator)
+    }
+
+    /// Replace the validators and weights of a basket
+    pub fn update_directed_stake_basket(
+        ctx: Context<UpdateDirectedStakeBasket>,
+        validators: Vec<DirectedStakeBasketEntry>,
+    ) -> Result<()> {
+        instructions::update_directed_stake_basket::handler(ctx, validators)
+    }
+
+    /// Create the account tracking what a ticket contributes through a basket
+    pub fn initialize_directed_stake_basket_contribution(
+        ctx: Context<InitializeDirectedStakeBasketContribution>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_basket_contribution::handler(ctx)
+    }
+
+    /// Permissionless. Recompute the directed stake targets a ticket contributes to a range of a basket's validators
+    pub fn crank_directed_stake_ticket_basket(
+        ctx: Context<CrankDirectedStakeTicketBasket>,
+        start_index: u16,
+        validator_list_indices: Vec<u32>,
+    ) -> Result<()> {
+        instructions::crank_directed_stake_ticket_basket::handler(
+            ctx,
+            start_index,
+            validator_list_indices,
+        )
+    }
+
+    /// Remove the directed stake targets a ticket contributes through a basket it no longer points at
+    pub fn clear_directed_stake_ticket_basket(
+        ctx: Context<ClearDirectedStakeTicketBasket>,
+    ) -> Result<()> {
+        instructions::clear_directed_stake_ticket_basket::handler(ctx)
+    }
+
+    /// Create the account holding a ticket's scheduled preference changes
+    pub fn initialize_directed_stake_preference_schedule(
+        ctx: Context<InitializeDirectedStakePreferenceSchedule>,
+    ) -> Result<()> {
+        instructions::initialize_directed_stake_preference_schedule::handler(ctx)
+    }
+
+    /// Schedule a ticket's preferences to change at `activation_epoch`
+    pub fn schedule_directed_stake_preferences(
+        ctx: Context<ScheduleDirectedStakePreferences>,
+        activation_epoch: u64,
+        preferences: Vec<DirectedStakePreference>,
+    ) -> Result<()> {
+        instructions::schedule_directed_stake_preferences::handler(
+            ctx,
+            activation_epoch,
+            preferences,
+        )
+    }
+
+    /// Cancel the preference change scheduled for `activation_epoch`
+    pub fn cancel_scheduled_directed_stake_preferences(
+        ctx: Context<CancelScheduledDirectedStakePreferences>,
+        activation_epoch: u64,
+    ) -> Result<()> {
+        instructions::cancel_scheduled_directed_stake_preferences::handler(ctx, activation_epoch)
+    }
+
+    /// Permissionless. Apply a ticket's scheduled preferences once their activation epoch is reached
+    pub fn apply_scheduled_directed_stake_preferences(
+        ctx: Context<ApplyScheduledDirectedStakePreferences>,
+        validator_list_indices: Vec<u32>,
+    ) -> Result<()> {
+        instructions::apply_scheduled_directed_stake_preferences::handler(
+            ctx,
+            validator_list_indices,
+        )
+    }
+
+    /// Close a ticket's preference schedule
+    pub fn close_directed_stake_preference_schedule(
+        ctx: Context<CloseDirectedStakePreferenceSchedule>,
+    ) -> Result<()> {
+        instructions::close_directed_stake_preference_schedule::handler(ctx)
+    }
+
+    /// Permissionless. Point the pool's preferred deposit and withdraw validators at the
+    /// validators furthest below and above their targets
+    pub fn update_preferred_validators(ctx: Context<UpdatePreferredValidators>) -> Result<()> {
+        instructions::update_preferred_validators::handler(ctx)
+    }
+
+    /// Creates the account that records graded instant unstake fractions for `rebalance`
+    pub fn initialize_instant_unstake_fractions(
+        ctx: Context<InitializeInstantUnstakeFractions>,
+    ) -> Result<()> {
+        instructions::initialize_instant_unstake_fractions::handler(ctx)
+    }
+
+    /// Creates the account that tracks validators warned by `compute_score` before being unscored
+    pub fn initialize_score_warnings(ctx: Context<InitializeScoreWarnings>) -> Result<()> {
+        instructions::initialize_score_warnings::handler(ctx)
+    }
+
+    /// Increases the ScoreWarnings account by 10KiB each ix until it reaches ScoreWarnings::SIZE
+    pub fn realloc_score_warnings(ctx: Context<ReallocScoreWarnings>) -> Result<()> {
+        instructions::realloc_score_warnings::handler(ctx)
+    }
+
+    /// Signed by the vote account's authorized withdrawer. Marks the validator for removal from the pool
+    /// and blocks it from being added back for `validator_exit_cooldown_epochs` epochs
+    pub fn request_validator_exit(
+        ctx: Context<RequestValidatorExit>,
+        validator_list_index: u64,
+    ) -> Result<()> {
+        instructions::request_validator_exit::handler(ctx, validator_list_index as usize)
+    }
+
+    /// Signed by the vote account's authorized withdrawer. Lifts the block set by `request_validator_exit`
+    pub fn request_validator_rejoin(ctx: Context<RequestValidatorRejoin>) -> Result<()> {
+        instructions::request_validator_rejoin::handler(ctx)
+    }
+
+    /// Creates the account holding a validator's commission pledge. Signed by the vote account's authorized withdrawer
+    pub fn initialize_commission_pledge(ctx: Context<InitializeCommissionPledge>) -> Result<()> {
+        instructions::initialize_commission_pledge::handler(ctx)
+    }
+
+    /// Signed by the vote account's authorized withdrawer. Pledges maximum commissions until an end epoch,
+    /// which earns a scoring tiebreaker while kept and instant unstaking when broken
+    pub fn pledge_commission(
+        ctx: Context<PledgeCommission>,
+        args: PledgeCommissionArgs,
+    ) -> Result<()> {
+        instructions::pledge_commission::handler(ctx, args)
+    }
+
+    /// Permissionless. Creates the journal recording stake moved to and from a validator by rebalancing
+    pub fn initialize_rebalance_journal(ctx: Context<InitializeRebalanceJournal>) -> Result<()> {
+        instructions::initialize_rebalance_journal::handler(ctx)
+    }
+
+    /// Permissionless. Checks lamport accounting for validator list indices `start_index..end_index`,
+    /// emitting an event for each validator that drifted. Pauses the steward on drift if `pause_on_drift` is set
+    pub fn verify_invariants(
+        ctx: Context<VerifyInvariants>,
+        start_index: u64,
+        end_index: u64,
+        pause_on_drift: bool,
+    ) -> Result<()> {
+        instructions::verify_invariants::handler(ctx, start_index, end_index, pause_on_drift)
+    }
 }
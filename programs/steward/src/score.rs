@@ -28,11 +28,12 @@ pub mod running_bam;
 /// differences in Tier 1 (inflation commission) always dominate lower tiers, creating
 /// a strict priority ordering.
 ///
-/// The encoding uses 64 bits distributed across 4 tiers:
+/// The encoding uses 64 bits distributed across 5 tiers:
 /// - Bits 56-63 (8 bits):  Tier 1 - Inflation commission (inverted)
 /// - Bits 42-55 (14 bits): Tier 2 - MEV commission (inverted)
-/// - Bits 25-41 (17 bits): Tier 3 - Validator age (direct)
-/// - Bits 0-24 (25 bits):  Tier 4 - Vote credits ratio (direct)
+/// - Bit 41 (1 bit):       Tier 3 - Commission pledge
+/// - Bits 25-40 (16 bits): Tier 4 - Validator age (direct)
+/// - Bits 0-24 (25 bits):  Tier 5 - Vote credits ratio (direct)
 ///
 /// Higher raw scores are always better, as commission values are inverted during encoding.
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,19 +57,28 @@ pub struct ValidatorScoreComponents {
     /// epochs, inverted so lower commission yields a higher score.
     pub mev_commission_bps: u16,
 
+    /// Commission Pledge
+    ///
+    /// **Tier 3** (bit 41)
+    ///
+    /// Among validators equal on both commission tiers, validators whose `CommissionPledge`
+    /// earns the tiebreaker (see `COMMISSION_PLEDGE_SCORE_BIT`) are preferred. Set by
+    /// `compute_score`, never by `encode_validator_score`.
+    pub commission_pledge: bool,
+
     /// Validator Age (epochs with non-zero vote credits)
     ///
-    /// **Tier 3** (bits 25-41)
+    /// **Tier 4** (bits 25-40)
     ///
-    /// Among validators equal on both commission tiers, older validators are preferred.
+    /// Among validators equal on all above tiers, older validators are preferred.
     /// Age is measured in epochs where the validator produced non-zero vote credits,
-    /// rewarding longevity and reliability. Maximum representable age is 131,071 epochs
-    /// (approximately 716 years at ~2 days per epoch).
+    /// rewarding longevity and reliability. Maximum representable age is 65,535 epochs
+    /// (approximately 358 years at ~2 days per epoch).
     pub validator_age: u32,
 
     /// Vote Credits Ratio (normalized performance score)
     ///
-    /// **Tier 4 - Lowest Priority** (bits 0-24)
+    /// **Tier 5 - Lowest Priority** (bits 0-24)
     ///
     /// Among validators equal on all above tiers, higher performance is preferred.
     /// This represents vote credits relative to total possible credits, scaled by
@@ -78,7 +88,7 @@ pub struct ValidatorScoreComponents {
 
 impl std::fmt::Display for ValidatorScoreComponents {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Inflation Commission: {}\nMEV commission BPS: {}\nValidator Age: {}\nVote Credits: {}\n", self.inflation_commission, self.mev_commission_bps, self.validator_age, self.vote_credits)
+        write!(f, "Inflation Commission: {}\nMEV commission BPS: {}\nCommission Pledge: {}\nValidator Age: {}\nVote Credits: {}\n", self.inflation_commission, self.mev_commission_bps, self.commission_pledge, self.validator_age, self.vote_credits)
     }
 }
 
@@ -93,7 +103,7 @@ impl ValidatorScoreComponents {
     /// // Perfect validator: 0% commissions, max age, max credits
     /// let inflation_commission = 0;
     /// let mev_commission_bps = 0;
-    /// let validator_age = 131071;
+    /// let validator_age = 65535;
     /// let vote_credits = 33554431;
     ///
     /// let score = encode_validator_score(inflation_commission, mev_commission_bps, validator_age, vote_credits).unwrap();
@@ -101,6 +111,7 @@ impl ValidatorScoreComponents {
     /// let components = ValidatorScoreComponents::decode(score);
     /// assert_eq!(components.inflation_commission, inflation_commission);
     /// assert_eq!(components.mev_commission_bps, mev_commission_bps);
+    /// assert!(!components.commission_pledge);
     /// assert_eq!(components.validator_age, validator_age);
     /// assert_eq!(components.vote_credits, vote_credits);
     /// ```
@@ -113,31 +124,36 @@ impl ValidatorScoreComponents {
         let mev_score = (raw_score >> 42) & 0x3FFF; // 14 bits
         let mev_commission_bps = 10000u16.saturating_sub(mev_score as u16);
 
-        // Tier 3: Extract validator age directly (bits 25-41)
-        let validator_age = ((raw_score >> 25) & 0x1FFFF) as u32; // 17 bits
+        // Tier 3: Extract commission pledge (bit 41)
+        let commission_pledge = raw_score & COMMISSION_PLEDGE_SCORE_BIT != 0;
+
+        // Tier 4: Extract validator age directly (bits 25-40)
+        let validator_age = ((raw_score >> 25) & 0xFFFF) as u32; // 16 bits
 
-        // Tier 4: Extract vote credits directly (bits 0-24)
+        // Tier 5: Extract vote credits directly (bits 0-24)
         let vote_credits = (raw_score & 0x1FFFFFF) as u32; // 25 bits
 
         Self {
             inflation_commission,
             mev_commission_bps,
+            commission_pledge,
             validator_age,
             vote_credits,
         }
     }
 }
 
-/// Encode a 4-tier validator score into a u64 with the following bit layout:
+/// Encode a validator score into a u64 with the following bit layout:
 /// Bits 56-63 (8 bits):  Inflation commission (inverted, 0-100%)
 /// Bits 42-55 (14 bits): MEV commission (inverted, 0-10000 bps)
-/// Bits 25-41 (17 bits): Validator age (direct, epochs)
+/// Bit 41 (1 bit):       Commission pledge, left unset (see `COMMISSION_PLEDGE_SCORE_BIT`)
+/// Bits 25-40 (16 bits): Validator age (direct, epochs)
 /// Bits 0-24 (25 bits):  Vote credits (direct value)
 ///
 /// The tiers are in descending order of importance. The highest bits (56-63) contain
 /// the most important factor (inflation commission), so when comparing scores as u64 values,
 /// differences in higher-order bits will dominate lower-order bits. This creates a
-/// hierarchical comparison where inflation commission > MEV commission > age > credits.
+/// hierarchical comparison where inflation commission > MEV commission > pledge > age > credits.
 ///
 /// Higher scores are better in all cases.
 pub fn encode_validator_score(
@@ -153,11 +169,11 @@ pub fn encode_validator_score(
     let mev_score =
         (BASIS_POINTS_MAX as u64).saturating_sub(mev_commission_bps.min(BASIS_POINTS_MAX) as u64);
 
-    // Tier 3: Validator age (direct - older validators score higher)
-    // Cap at 17 bits max value (131,071 epochs = ~716 years)
-    let age_score = (validator_age as u64).min((1u64 << 17) - 1);
+    // Tier 4: Validator age (direct - older validators score higher)
+    // Cap at 16 bits max value (65,535 epochs = ~358 years)
+    let age_score = (validator_age as u64).min((1u64 << 16) - 1);
 
-    // Tier 4: Vote credits ratio (normalized performance, scaled by 10M for precision)
+    // Tier 5: Vote credits ratio (normalized performance, scaled by 10M for precision)
     // Cap at 25 bits max value (33,554,431)
     let credits_score = (vote_credits as u64).min((1u64 << 25) - 1);
 
@@ -167,6 +183,11 @@ pub fn encode_validator_score(
     Ok(score)
 }
 
+/// Tier 3 of the score. Set by `compute_score` for validators with an unbroken `CommissionPledge` covering
+/// the next cycle whose pledged maxima are within the scoring thresholds, ranking them above validators with
+/// the same commission tiers and no pledge.
+pub const COMMISSION_PLEDGE_SCORE_BIT: u64 = 1 << 41;
+
 /// Calculate the average MEV commission over a window of epochs
 pub fn calculate_avg_mev_commission(
     validator: &ValidatorHistory,
@@ -209,7 +230,7 @@ pub struct ScoreComponentsV5 {
     /// Final score with binary filters applied to raw_score (0 if any filter fails, raw_score otherwise)
     pub score: u64,
 
-    /// The 4-tier encoded score (before binary filters)
+    /// The tiered encoded score (before binary filters), see `ValidatorScoreComponents`
     pub raw_score: u64,
 
     /// Maximum inflation commission used in scoring (0-100)
@@ -264,6 +285,62 @@ pub struct ScoreComponentsV5 {
     pub priority_fee_merkle_root_upload_authority_score: u8,
 }
 
+/////// Binary filter flags, used to select filters that warn before unscoring a validator ///////
+pub const SCORE_FILTER_MEV_COMMISSION: u16 = 1 << 0;
+pub const SCORE_FILTER_BLACKLIST: u16 = 1 << 1;
+pub const SCORE_FILTER_SUPERMINORITY: u16 = 1 << 2;
+pub const SCORE_FILTER_DELINQUENCY: u16 = 1 << 3;
+pub const SCORE_FILTER_RUNNING_BAM: u16 = 1 << 4;
+pub const SCORE_FILTER_COMMISSION: u16 = 1 << 5;
+pub const SCORE_FILTER_HISTORICAL_COMMISSION: u16 = 1 << 6;
+pub const SCORE_FILTER_MERKLE_ROOT_UPLOAD_AUTHORITY: u16 = 1 << 7;
+pub const SCORE_FILTER_PRIORITY_FEE_COMMISSION: u16 = 1 << 8;
+pub const SCORE_FILTER_PRIORITY_FEE_MERKLE_ROOT_UPLOAD_AUTHORITY: u16 = 1 << 9;
+
+/// Filters that can be given a grace period. Blacklisting is an admin decision, so it always applies immediately
+pub const SCORE_WARNING_FILTERS_ALLOWED: u16 = SCORE_FILTER_MEV_COMMISSION
+    | SCORE_FILTER_SUPERMINORITY
+    | SCORE_FILTER_DELINQUENCY
+    | SCORE_FILTER_RUNNING_BAM
+    | SCORE_FILTER_COMMISSION
+    | SCORE_FILTER_HISTORICAL_COMMISSION
+    | SCORE_FILTER_MERKLE_ROOT_UPLOAD_AUTHORITY
+    | SCORE_FILTER_PRIORITY_FEE_COMMISSION
+    | SCORE_FILTER_PRIORITY_FEE_MERKLE_ROOT_UPLOAD_AUTHORITY;
+
+impl ScoreComponentsV5 {
+    /// Flags of the binary filters this validator failed
+    pub fn failed_filters(&self) -> u16 {
+        [
+            (self.mev_commission_score, SCORE_FILTER_MEV_COMMISSION),
+            (self.blacklisted_score, SCORE_FILTER_BLACKLIST),
+            (self.superminority_score, SCORE_FILTER_SUPERMINORITY),
+            (self.delinquency_score, SCORE_FILTER_DELINQUENCY),
+            (self.running_bam_score, SCORE_FILTER_RUNNING_BAM),
+            (self.commission_score, SCORE_FILTER_COMMISSION),
+            (
+                self.historical_commission_score,
+                SCORE_FILTER_HISTORICAL_COMMISSION,
+            ),
+            (
+                self.merkle_root_upload_authority_score,
+                SCORE_FILTER_MERKLE_ROOT_UPLOAD_AUTHORITY,
+            ),
+            (
+                self.priority_fee_commission_score,
+                SCORE_FILTER_PRIORITY_FEE_COMMISSION,
+            ),
+            (
+                self.priority_fee_merkle_root_upload_authority_score,
+                SCORE_FILTER_PRIORITY_FEE_MERKLE_ROOT_UPLOAD_AUTHORITY,
+            ),
+        ]
+        .iter()
+        .filter(|(filter_score, _)| *filter_score == 0)
+        .fold(0, |failed_filters, (_, flag)| failed_filters | flag)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
 pub struct ScoreDetails {
     /// Max MEV commission observed
@@ -817,10 +894,14 @@ pub fn calculate_priority_fee_commission(
 
 #[event]
 #[derive(Debug, PartialEq, Eq)]
-pub struct InstantUnstakeComponentsV3 {
+pub struct InstantUnstakeComponentsV4 {
     /// Aggregate of all checks
     pub instant_unstake: bool,
 
+    /// Share of current stake to instant unstake (in basis points). `BASIS_POINTS_MAX` fully unstakes
+    /// the validator, lower values come from graded instant unstake
+    pub instant_unstake_fraction_bps: u16,
+
     /// Checks if validator has missed > instant_unstake_delinquency_threshold_ratio of votes this epoch
     pub delinquency_check: bool,
 
@@ -847,7 +928,7 @@ pub struct InstantUnstakeComponentsV3 {
     pub details: InstantUnstakeDetails,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub struct InstantUnstakeDetails {
     /// Latest epoch credits
     pub epoch_credits_latest: u64,
@@ -870,6 +951,7 @@ pub struct InstantUnstakeDetails {
 
 /// Method to calculate if a validator should be unstaked instantly this epoch.
 /// Before running, checks are needed on cluster and validator history to be updated this epoch past the halfway point of the epoch.
+/// Checks matching a score filter flag in `skipped_filters` are not applied.
 pub fn instant_unstake_validator(
     validator: &ValidatorHistory,
     cluster: &ClusterHistory,
@@ -877,7 +959,8 @@ pub fn instant_unstake_validator(
     epoch_start_slot: u64,
     current_epoch: u16,
     tvc_activation_epoch: u64,
-) -> Result<InstantUnstakeComponentsV3> {
+    skipped_filters: u16,
+) -> Result<InstantUnstakeComponentsV4> {
     let params = &config.parameters;
 
     /////// Shared calculations ///////
@@ -936,6 +1019,17 @@ pub fn instant_unstake_validator(
                 .priority_fee_merkle_root_upload_authority_latest(),
         )?;
 
+    // Checks matching a filter the validator is in its warning grace period for are skipped
+    let delinquency_check = delinquency_check && skipped_filters & SCORE_FILTER_DELINQUENCY == 0;
+    let mev_commission_check =
+        mev_commission_check && skipped_filters & SCORE_FILTER_MEV_COMMISSION == 0;
+    let commission_check = commission_check && skipped_filters & SCORE_FILTER_COMMISSION == 0;
+    let is_bad_merkle_root_upload_authority = is_bad_merkle_root_upload_authority
+        && skipped_filters & SCORE_FILTER_MERKLE_ROOT_UPLOAD_AUTHORITY == 0;
+    let is_bad_priority_fee_merkle_root_upload_authority =
+        is_bad_priority_fee_merkle_root_upload_authority
+            && skipped_filters & SCORE_FILTER_PRIORITY_FEE_MERKLE_ROOT_UPLOAD_AUTHORITY == 0;
+
     let instant_unstake = delinquency_check
         || commission_check
         || mev_commission_check
@@ -943,8 +1037,46 @@ pub fn instant_unstake_validator(
         || is_bad_merkle_root_upload_authority
         || is_bad_priority_fee_merkle_root_upload_authority;
 
-    Ok(InstantUnstakeComponentsV3 {
+    /////// Graded instant unstake ///////
+    let delinquency_severity_bps = calculate_instant_unstake_delinquency_severity(
+        total_blocks_latest,
+        cluster_history_slot_index,
+        epoch_credits_latest,
+        validator_history_slot_index,
+        params.instant_unstake_delinquency_threshold_ratio,
+    )?;
+    let commission_severity_bps = calculate_instant_unstake_commission_severity(
+        commission as u16,
+        params.commission_threshold as u16,
+        COMMISSION_MAX as u16,
+    );
+    let mev_commission_severity_bps = calculate_instant_unstake_commission_severity(
+        mev_commission_bps,
+        params.mev_commission_bps_threshold,
+        BASIS_POINTS_MAX,
+    );
+
+    let instant_unstake_fraction_bps = calculate_instant_unstake_fraction(
         instant_unstake,
+        is_blacklisted
+            || is_bad_merkle_root_upload_authority
+            || is_bad_priority_fee_merkle_root_upload_authority,
+        [
+            (delinquency_severity_bps, SCORE_FILTER_DELINQUENCY),
+            (commission_severity_bps, SCORE_FILTER_COMMISSION),
+            (mev_commission_severity_bps, SCORE_FILTER_MEV_COMMISSION),
+        ]
+        .into_iter()
+        .filter(|(_, filter)| skipped_filters & filter == 0)
+        .map(|(severity_bps, _)| severity_bps)
+        .max()
+        .unwrap_or(0),
+        params.instant_unstake_max_fraction_bps,
+    );
+
+    Ok(InstantUnstakeComponentsV4 {
+        instant_unstake,
+        instant_unstake_fraction_bps,
         delinquency_check,
         commission_check,
         mev_commission_check,
@@ -1021,6 +1153,79 @@ pub fn calculate_instant_unstake_commission(
     (commission_check, commission)
 }
 
+/// Calculates how far the validator's vote rate relative to block production is below
+/// `instant_unstake_delinquency_threshold_ratio`, as a share of the threshold (in basis points)
+pub fn calculate_instant_unstake_delinquency_severity(
+    total_blocks_latest: u32,
+    cluster_history_slot_index: u64,
+    epoch_credits_latest: u32,
+    validator_history_slot_index: u64,
+    instant_unstake_delinquency_threshold_ratio: f64,
+) -> Result<u16> {
+    if cluster_history_slot_index == 0 || validator_history_slot_index == 0 {
+        return Err(StewardError::ArithmeticError.into());
+    }
+
+    let blocks_produced_rate = total_blocks_latest as f64 / cluster_history_slot_index as f64;
+    let vote_credits_rate = epoch_credits_latest as f64 / validator_history_slot_index as f64;
+
+    if blocks_produced_rate > 0. {
+        let ratio = vote_credits_rate / (blocks_produced_rate * (TVC_MULTIPLIER as f64));
+        Ok(instant_unstake_severity_bps(
+            instant_unstake_delinquency_threshold_ratio - ratio,
+            instant_unstake_delinquency_threshold_ratio,
+        ))
+    } else {
+        Ok(0)
+    }
+}
+
+/// Calculates how far `commission` is above `commission_threshold`, as a share of the range
+/// between the threshold and `commission_max` (in basis points)
+pub fn calculate_instant_unstake_commission_severity(
+    commission: u16,
+    commission_threshold: u16,
+    commission_max: u16,
+) -> u16 {
+    instant_unstake_severity_bps(
+        commission as f64 - commission_threshold as f64,
+        commission_max as f64 - commission_threshold as f64,
+    )
+}
+
+fn instant_unstake_severity_bps(excess: f64, range: f64) -> u16 {
+    if excess <= 0. {
+        return 0;
+    }
+    if range <= 0. {
+        return BASIS_POINTS_MAX;
+    }
+    ((excess / range).min(1.) * BASIS_POINTS_MAX as f64).round() as u16
+}
+
+/// Calculates the share of a flagged validator's current stake to instant unstake (in basis points).
+/// Validators failing a check that always requires full removal, and all validators when graded instant
+/// unstake is disabled, are fully unstaked. Otherwise the most severe failed check is scaled by
+/// `instant_unstake_max_fraction_bps`.
+pub fn calculate_instant_unstake_fraction(
+    instant_unstake: bool,
+    requires_full_removal: bool,
+    max_severity_bps: u16,
+    instant_unstake_max_fraction_bps: u16,
+) -> u16 {
+    if !instant_unstake {
+        return 0;
+    }
+    if requires_full_removal || instant_unstake_max_fraction_bps == 0 {
+        return BASIS_POINTS_MAX;
+    }
+
+    let fraction_bps = (max_severity_bps as u32)
+        .saturating_mul(instant_unstake_max_fraction_bps as u32)
+        .saturating_div(BASIS_POINTS_MAX as u32);
+    (fraction_bps as u16).clamp(1, BASIS_POINTS_MAX)
+}
+
 /// Checks if the validator is blacklisted
 pub fn calculate_instant_unstake_blacklist(config: &Config, validator_index: u32) -> Result<bool> {
     config
@@ -1,6 +1,9 @@
 use std::mem::size_of;
 
-use crate::{parameters::Parameters, utils::U8Bool, LargeBitMask, StewardStateV1, StewardStateV2};
+use crate::{
+    parameters::Parameters, utils::U8Bool, CircuitBreakerBaseline, LargeBitMask, StewardStateV1,
+    StewardStateV2, UnstakeCarryover,
+};
 use anchor_lang::prelude::*;
 use borsh::BorshSerialize;
 use type_layout::TypeLayout;
@@ -63,8 +66,14 @@ pub struct Config {
     /// The authoirty that can override directed stake tickets
     pub directed_stake_ticket_override_authority: Pubkey,
 
+    /// Unstake cap budget carried between cycles, updated by `rebalance`
+    pub unstake_carryover: UnstakeCarryover,
+
+    /// Unstaking activity acknowledged by the admin when resuming after the circuit breaker tripped
+    pub circuit_breaker_baseline: CircuitBreakerBaseline,
+
     /// Padding for future governance parameters
-    pub _padding: [u8; 888],
+    pub _padding: [u8; 800],
 }
 
 impl Config {
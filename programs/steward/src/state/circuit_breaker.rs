@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    constants::BASIS_POINTS_MAX, errors::StewardError, utils::U8Bool, Parameters, StewardStateV2,
+};
+
+/// Unstaking activity of the current cycle that the circuit breaker ignores. When the admin resumes
+/// a steward paused by the circuit breaker, the activity that tripped it is acknowledged here so the
+/// steward can finish the cycle without pausing again.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq, Eq)]
+#[zero_copy]
+pub struct CircuitBreakerBaseline {
+    /// `next_cycle_epoch` of the cycle the baselines were taken in
+    pub cycle_end_epoch: u64,
+
+    /// Lamports unstaked this cycle before the admin last resumed the steward
+    pub unstake_lamports: u64,
+
+    /// Validators marked for instant unstake this cycle before the admin last resumed the steward
+    pub instant_unstake_count: u64,
+
+    /// Set by `resume_steward`. The baselines are taken by the next instruction that checks the
+    /// circuit breaker, before it unstakes or marks anything.
+    pub resumed: U8Bool,
+
+    pub _padding0: [u8; 7],
+}
+
+impl CircuitBreakerBaseline {
+    /// Takes the baselines from `state` if the admin resumed the steward since the last check, and
+    /// clears them when a new cycle has started
+    pub fn sync(&mut self, state: &StewardStateV2) -> Result<()> {
+        if self.resumed.into() {
+            *self = Self {
+                cycle_end_epoch: state.next_cycle_epoch,
+                unstake_lamports: state.unstake_total()?,
+                instant_unstake_count: state.instant_unstake.count() as u64,
+                ..Self::default()
+            };
+        } else if self.cycle_end_epoch != state.next_cycle_epoch {
+            *self = Self {
+                cycle_end_epoch: state.next_cycle_epoch,
+                ..Self::default()
+            };
+        }
+
+        Ok(())
+    }
+
+    pub fn set_resumed(&mut self) {
+        self.resumed = true.into();
+    }
+
+    /// Lamports that can be unstaked this cycle before the circuit breaker trips:
+    /// `circuit_breaker_unstake_bps` of `stake_pool_lamports` on top of the acknowledged unstaking.
+    /// None when the circuit breaker is disabled.
+    pub fn unstake_limit(
+        &self,
+        parameters: &Parameters,
+        stake_pool_lamports: u64,
+    ) -> Result<Option<u64>> {
+        if parameters.circuit_breaker_unstake_bps == 0 {
+            return Ok(None);
+        }
+
+        let unstake_limit: u64 = (stake_pool_lamports as u128)
+            .checked_mul(parameters.circuit_breaker_unstake_bps as u128)
+            .and_then(|x| x.checked_div(BASIS_POINTS_MAX as u128))
+            .ok_or(StewardError::ArithmeticError)?
+            .try_into()
+            .map_err(|_| StewardError::ArithmeticCastError)?;
+
+        Ok(Some(unstake_limit.saturating_add(self.unstake_lamports)))
+    }
+}
@@ -0,0 +1,194 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+use validator_history::ValidatorHistory;
+
+use crate::{utils::U8Bool, Parameters};
+
+/// A validator's promise to keep its commissions at or below a maximum until `end_epoch`.
+/// Validators with an unbroken pledge covering the next cycle are ranked above validators without one
+/// in the same score tier. Breaking it instantly unstakes the validator and starts a cooldown during
+/// which it cannot pledge again.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct CommissionPledge {
+    /// Steward config the pledge was made to
+    pub config: Pubkey,
+
+    pub vote_account: Pubkey,
+
+    /// Epoch the current pledge was made
+    pub start_epoch: u64,
+
+    /// Last epoch covered by the current pledge
+    pub end_epoch: u64,
+
+    /// Epoch the current pledge was found broken, if `broken` is set
+    pub broken_epoch: u64,
+
+    /// First epoch the validator may pledge again after breaking a pledge
+    pub cooldown_end_epoch: u64,
+
+    /// Maximum MEV commission, in basis points
+    pub max_mev_commission_bps: u16,
+
+    /// Maximum priority fee commission, in basis points
+    pub max_priority_fee_commission_bps: u16,
+
+    /// Maximum inflation commission, in percent
+    pub max_commission: u8,
+
+    /// Whether the current pledge has been broken
+    pub broken: U8Bool,
+
+    pub _padding0: [u8; 2],
+
+    // 64 bytes reserved for future use
+    pub _padding1: [u8; 64],
+}
+
+impl CommissionPledge {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"commission_pledge";
+
+    pub fn is_broken(&self) -> bool {
+        self.broken.into()
+    }
+
+    /// A pledge is active from the epoch it was made through `end_epoch`, unless it has been broken
+    pub fn is_active(&self, epoch: u64) -> bool {
+        !self.is_broken()
+            && self.end_epoch != 0
+            && self.start_epoch <= epoch
+            && epoch <= self.end_epoch
+    }
+
+    pub fn is_cooling_down(&self, epoch: u64) -> bool {
+        epoch < self.cooldown_end_epoch
+    }
+
+    /// Whether the pledge earns the scoring tiebreaker: it must be unbroken, cover the whole next cycle,
+    /// and promise commissions no higher than the scoring thresholds
+    pub fn counts_for_score(&self, epoch: u64, next_cycle_epoch: u64, params: &Parameters) -> bool {
+        self.is_active(epoch)
+            && self.end_epoch >= next_cycle_epoch
+            && self.max_commission <= params.commission_threshold
+            && self.max_mev_commission_bps <= params.mev_commission_bps_threshold
+            && self.max_priority_fee_commission_bps <= params.priority_fee_max_commission_bps
+    }
+
+    /// Whether any commission recorded in `validator`'s history between `start_epoch` and the earlier of
+    /// `epoch` and `end_epoch` exceeds the pledged maximum. Always false for a pledge already marked broken.
+    pub fn is_breached(&self, validator: &ValidatorHistory, epoch: u64) -> bool {
+        if self.is_broken() || self.end_epoch == 0 || epoch < self.start_epoch {
+            return false;
+        }
+        let start_epoch = self.start_epoch as u16;
+        let end_epoch = epoch.min(self.end_epoch) as u16;
+
+        let commission_breached = validator
+            .history
+            .commission_range(start_epoch, end_epoch)
+            .into_iter()
+            .flatten()
+            .any(|commission| commission > self.max_commission);
+        let mev_commission_breached = validator
+            .history
+            .mev_commission_range(start_epoch, end_epoch)
+            .into_iter()
+            .flatten()
+            .any(|commission| commission > self.max_mev_commission_bps);
+        let priority_fee_commission_breached = validator
+            .history
+            .priority_fee_commission_range(start_epoch, end_epoch)
+            .into_iter()
+            .flatten()
+            .any(|commission| commission > self.max_priority_fee_commission_bps);
+
+        commission_breached || mev_commission_breached || priority_fee_commission_breached
+    }
+
+    /// Marks the pledge broken at `epoch` and starts the cooldown
+    pub fn mark_broken(&mut self, epoch: u64, cooldown_epochs: u64) {
+        self.broken = U8Bool::from(true);
+        self.broken_epoch = epoch;
+        self.cooldown_end_epoch = epoch.saturating_add(cooldown_epochs);
+    }
+}
+
+/// Terms of a pledge made with `pledge_commission`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PledgeCommissionArgs {
+    /// Maximum inflation commission, in percent
+    pub max_commission: u8,
+    /// Maximum MEV commission, in basis points
+    pub max_mev_commission_bps: u16,
+    /// Maximum priority fee commission, in basis points
+    pub max_priority_fee_commission_bps: u16,
+    /// Last epoch covered by the pledge
+    pub end_epoch: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator_history::{CircBuf, ValidatorHistoryEntry};
+
+    fn pledge(start_epoch: u64, end_epoch: u64) -> CommissionPledge {
+        let mut pledge: CommissionPledge = bytemuck::Zeroable::zeroed();
+        pledge.start_epoch = start_epoch;
+        pledge.end_epoch = end_epoch;
+        pledge.max_commission = 5;
+        pledge.max_mev_commission_bps = 800;
+        pledge.max_priority_fee_commission_bps = 5000;
+        pledge
+    }
+
+    #[test]
+    fn test_is_breached() {
+        let mut validator: Box<ValidatorHistory> = Box::new(bytemuck::Zeroable::zeroed());
+        validator.history = CircBuf::default();
+        for (epoch, commission, mev_commission) in [(10, 10, 800), (11, 5, 800), (12, 5, 900)] {
+            validator.history.push(ValidatorHistoryEntry {
+                epoch,
+                commission,
+                mev_commission,
+                ..ValidatorHistoryEntry::default()
+            });
+        }
+
+        // Commissions before the pledge was made don't count
+        let mut commission_pledge = pledge(11, 20);
+        assert!(!commission_pledge.is_breached(&validator, 11));
+        assert!(commission_pledge.is_breached(&validator, 12));
+
+        // Nor do commissions after it ended
+        assert!(!pledge(11, 11).is_breached(&validator, 12));
+
+        commission_pledge.mark_broken(12, 5);
+        assert!(!commission_pledge.is_active(12));
+        assert!(!commission_pledge.is_breached(&validator, 12));
+        assert!(commission_pledge.is_cooling_down(16));
+        assert!(!commission_pledge.is_cooling_down(17));
+    }
+
+    #[test]
+    fn test_counts_for_score() {
+        let mut params: Parameters = bytemuck::Zeroable::zeroed();
+        params.commission_threshold = 5;
+        params.mev_commission_bps_threshold = 1000;
+        params.priority_fee_max_commission_bps = 5000;
+
+        let commission_pledge = pledge(11, 20);
+        assert!(commission_pledge.counts_for_score(11, 20, &params));
+        // Must cover the whole next cycle
+        assert!(!commission_pledge.counts_for_score(11, 21, &params));
+        // Not made yet
+        assert!(!commission_pledge.counts_for_score(10, 20, &params));
+
+        // Pledged maximum above the scoring threshold
+        params.commission_threshold = 4;
+        assert!(!commission_pledge.counts_for_score(11, 20, &params));
+    }
+}
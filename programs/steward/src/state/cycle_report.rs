@@ -0,0 +1,297 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    delegation::RebalanceType, errors::StewardError, score::ScoreComponentsV5, StewardStateV2,
+};
+
+/// Summary of a single delegation cycle, keyed by the epoch the cycle started in.
+/// The state machine fills it in when the account is passed to `compute_score`,
+/// `compute_delegations`, `compute_instant_unstake`, `rebalance` and `rebalance_many`.
+/// With `cycle_reports_enabled`, these fail without the report, so every cycle is fully reported.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct CycleReport {
+    /// Steward config this report belongs to
+    pub config: Pubkey,
+
+    /// Epoch in which ComputeScores started for this cycle
+    pub cycle_start_epoch: u64,
+
+    /// Slot of the first ComputeScores instruction in the cycle. If scoring is restarted,
+    /// the scoring counts are reset to match the new run.
+    pub start_computing_scores_slot: u64,
+
+    /// Number of validators in the pool at the start of the cycle
+    pub num_pool_validators: u64,
+
+    /////// ComputeScores ///////
+    /// Number of validators scored
+    pub validators_scored: u64,
+
+    /// Number of validators with a non-zero score after all filters
+    pub validators_with_nonzero_score: u64,
+
+    /// Number of scored validators passing each binary filter
+    pub mev_commission_passed: u64,
+    pub commission_passed: u64,
+    pub historical_commission_passed: u64,
+    pub blacklisted_passed: u64,
+    pub superminority_passed: u64,
+    pub delinquency_passed: u64,
+    pub running_bam_passed: u64,
+    pub merkle_root_upload_authority_passed: u64,
+    pub priority_fee_commission_passed: u64,
+    pub priority_fee_merkle_root_upload_authority_passed: u64,
+
+    /////// ComputeDelegations ///////
+    /// Number of validators given a non-zero delegation
+    pub validators_delegated: u64,
+
+    /////// ComputeInstantUnstake ///////
+    /// Number of validators marked for instant unstake
+    pub validators_instant_unstaked: u64,
+
+    /////// Rebalance ///////
+    /// Number of rebalances of each `RebalanceTypeTag`
+    pub rebalance_none_count: u64,
+    pub rebalance_increase_count: u64,
+    pub rebalance_decrease_count: u64,
+
+    /// Lamports moved for each `RebalanceTypeTag`
+    pub increase_lamports: u64,
+    pub decrease_lamports: u64,
+
+    /// Breakdown of `decrease_lamports`
+    pub scoring_unstake_lamports: u64,
+    pub instant_unstake_lamports: u64,
+    pub stake_deposit_unstake_lamports: u64,
+
+    /////// Pool membership ///////
+    /// Validators added to the pool since the cycle started
+    pub validators_added: u64,
+
+    /// Validators removed from the pool since the cycle started
+    pub validators_removed: u64,
+
+    pub _padding0: [u8; 256],
+}
+
+impl CycleReport {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"cycle_report";
+
+    /// Checks that this report belongs to `config` and the cycle currently tracked by `state`
+    pub fn check_cycle(
+        &self,
+        config: &Pubkey,
+        state: &StewardStateV2,
+        epoch_schedule: &EpochSchedule,
+    ) -> Result<()> {
+        require!(
+            self.config == *config
+                && self.cycle_start_epoch
+                    == epoch_schedule.get_epoch(state.start_computing_scores_slot),
+            StewardError::InvalidCycleReport
+        );
+        Ok(())
+    }
+
+    pub fn record_score(&mut self, state: &StewardStateV2, score: &ScoreComponentsV5) {
+        if self.start_computing_scores_slot != state.start_computing_scores_slot {
+            self.reset_scoring(state);
+        }
+
+        self.validators_scored += 1;
+        self.validators_with_nonzero_score += u64::from(score.score > 0);
+        self.mev_commission_passed += u64::from(score.mev_commission_score);
+        self.commission_passed += u64::from(score.commission_score);
+        self.historical_commission_passed += u64::from(score.historical_commission_score);
+        self.blacklisted_passed += u64::from(score.blacklisted_score);
+        self.superminority_passed += u64::from(score.superminority_score);
+        self.delinquency_passed += u64::from(score.delinquency_score);
+        self.running_bam_passed += u64::from(score.running_bam_score);
+        self.merkle_root_upload_authority_passed +=
+            u64::from(score.merkle_root_upload_authority_score);
+        self.priority_fee_commission_passed += u64::from(score.priority_fee_commission_score);
+        self.priority_fee_merkle_root_upload_authority_passed +=
+            u64::from(score.priority_fee_merkle_root_upload_authority_score);
+
+        self.record_pool_membership(state);
+    }
+
+    pub fn record_delegations(&mut self, state: &StewardStateV2) {
+        self.validators_delegated = state
+            .delegations
+            .iter()
+            .take(state.num_pool_validators as usize)
+            .filter(|delegation| delegation.numerator > 0)
+            .count() as u64;
+
+        self.record_pool_membership(state);
+    }
+
+    pub fn record_instant_unstake(&mut self, state: &StewardStateV2) {
+        self.validators_instant_unstaked = state.instant_unstake.count() as u64;
+
+        self.record_pool_membership(state);
+    }
+
+    pub fn record_rebalance(
+        &mut self,
+        state: &StewardStateV2,
+        rebalance_type: &RebalanceType,
+    ) -> Result<()> {
+        match rebalance_type {
+            RebalanceType::None => {
+                self.rebalance_none_count += 1;
+            }
+            RebalanceType::Increase(lamports) => {
+                self.rebalance_increase_count += 1;
+                self.increase_lamports = self
+                    .increase_lamports
+                    .checked_add(*lamports)
+                    .ok_or(StewardError::ArithmeticError)?;
+            }
+            RebalanceType::Decrease(decrease_components) => {
+                self.rebalance_decrease_count += 1;
+                self.decrease_lamports = self
+                    .decrease_lamports
+                    .checked_add(decrease_components.total_unstake_lamports)
+                    .ok_or(StewardError::ArithmeticError)?;
+                self.scoring_unstake_lamports = self
+                    .scoring_unstake_lamports
+                    .checked_add(decrease_components.scoring_unstake_lamports)
+                    .ok_or(StewardError::ArithmeticError)?;
+                self.instant_unstake_lamports = self
+                    .instant_unstake_lamports
+                    .checked_add(decrease_components.instant_unstake_lamports)
+                    .ok_or(StewardError::ArithmeticError)?;
+                self.stake_deposit_unstake_lamports = self
+                    .stake_deposit_unstake_lamports
+                    .checked_add(decrease_components.stake_deposit_unstake_lamports)
+                    .ok_or(StewardError::ArithmeticError)?;
+            }
+        }
+
+        self.record_pool_membership(state);
+        Ok(())
+    }
+
+    fn reset_scoring(&mut self, state: &StewardStateV2) {
+        self.start_computing_scores_slot = state.start_computing_scores_slot;
+        self.num_pool_validators = state.num_pool_validators;
+        self.validators_scored = 0;
+        self.validators_with_nonzero_score = 0;
+        self.mev_commission_passed = 0;
+        self.commission_passed = 0;
+        self.historical_commission_passed = 0;
+        self.blacklisted_passed = 0;
+        self.superminority_passed = 0;
+        self.delinquency_passed = 0;
+        self.running_bam_passed = 0;
+        self.merkle_root_upload_authority_passed = 0;
+        self.priority_fee_commission_passed = 0;
+        self.priority_fee_merkle_root_upload_authority_passed = 0;
+    }
+
+    /// Removals are counted by the state where they happen. The pool holds
+    /// `num_pool_validators + validators_added` validators, so the validators added this cycle are the
+    /// growth of the pool since the start of the cycle plus the removals.
+    fn record_pool_membership(&mut self, state: &StewardStateV2) {
+        self.validators_removed = state.validators_removed as u64;
+        self.validators_added = state
+            .num_pool_validators
+            .saturating_add(state.validators_added as u64)
+            .saturating_add(state.validators_removed as u64)
+            .saturating_sub(self.num_pool_validators);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::DecreaseComponents, Delegation};
+
+    #[test]
+    fn test_record_rebalance_and_pool_membership() {
+        let mut state: Box<StewardStateV2> = Box::new(bytemuck::Zeroable::zeroed());
+        state.num_pool_validators = 10;
+        state.delegations[0] = Delegation::new(1, 2);
+        state.delegations[1] = Delegation::new(1, 2);
+
+        let mut report: Box<CycleReport> = Box::new(bytemuck::Zeroable::zeroed());
+        report.num_pool_validators = 10;
+
+        report.record_delegations(&state);
+        assert_eq!(report.validators_delegated, 2);
+
+        report
+            .record_rebalance(&state, &RebalanceType::Increase(100))
+            .unwrap();
+        report
+            .record_rebalance(
+                &state,
+                &RebalanceType::Decrease(DecreaseComponents {
+                    scoring_unstake_lamports: 30,
+                    instant_unstake_lamports: 20,
+                    stake_deposit_unstake_lamports: 0,
+                    total_unstake_lamports: 50,
+                    directed_unstake_lamports: 0,
+                }),
+            )
+            .unwrap();
+
+        // One validator removed and two added mid-cycle
+        state.num_pool_validators = 9;
+        state.validators_added = 2;
+        state.validators_removed = 1;
+        report
+            .record_rebalance(&state, &RebalanceType::None)
+            .unwrap();
+        assert_eq!(report.validators_added, 2);
+        assert_eq!(report.validators_removed, 1);
+
+        // One of the added validators is scored into the cycle and the other is removed again
+        state.num_pool_validators = 10;
+        state.validators_added = 0;
+        state.validators_removed = 2;
+        report
+            .record_rebalance(&state, &RebalanceType::None)
+            .unwrap();
+
+        assert_eq!(report.rebalance_increase_count, 1);
+        assert_eq!(report.rebalance_decrease_count, 1);
+        assert_eq!(report.rebalance_none_count, 2);
+        assert_eq!(report.increase_lamports, 100);
+        assert_eq!(report.decrease_lamports, 50);
+        assert_eq!(report.scoring_unstake_lamports, 30);
+        assert_eq!(report.instant_unstake_lamports, 20);
+        assert_eq!(report.validators_added, 2);
+        assert_eq!(report.validators_removed, 2);
+    }
+
+    #[test]
+    fn test_check_cycle() {
+        let epoch_schedule = EpochSchedule::custom(32, 32, false);
+        let config = Pubkey::new_unique();
+        let mut state: Box<StewardStateV2> = Box::new(bytemuck::Zeroable::zeroed());
+        state.start_computing_scores_slot = 32 * 5;
+
+        let mut report: Box<CycleReport> = Box::new(bytemuck::Zeroable::zeroed());
+        report.config = config;
+        report.cycle_start_epoch = 5;
+        assert!(report.check_cycle(&config, &state, &epoch_schedule).is_ok());
+
+        assert!(report
+            .check_cycle(&Pubkey::new_unique(), &state, &epoch_schedule)
+            .is_err());
+
+        state.start_computing_scores_slot = 32 * 8;
+        assert!(report
+            .check_cycle(&config, &state, &epoch_schedule)
+            .is_err());
+    }
+}
@@ -2,8 +2,9 @@ use std::mem::size_of;
 
 use crate::constants::MAX_VALIDATORS;
 use crate::errors::StewardError::{
-    AlreadyPermissioned, DirectedStakeValidatorListFull, StakerNotInWhitelist,
-    ValidatorNotInWhitelist,
+    AlreadyPermissioned, BalanceSourceAlreadyAdded, BalanceSourceNotFound, BalanceSourcesFull,
+    DirectedStakePreferenceScheduleFull, DirectedStakeValidatorListFull, InvalidParameterValue,
+    ScheduledDirectedStakePreferencesNotFound, StakerNotInWhitelist, ValidatorNotInWhitelist,
 };
 use crate::utils::U8Bool;
 use anchor_lang::prelude::*;
@@ -11,6 +12,13 @@ use borsh::{BorshDeserialize, BorshSerialize};
 
 pub const MAX_PERMISSIONED_DIRECTED_STAKERS: usize = 2048;
 pub const MAX_PREFERENCES_PER_TICKET: usize = 8;
+pub const MAX_PREFERENCES_PER_TICKET_EXTENSION: usize = 200;
+pub const MAX_BALANCE_SOURCES_PER_TICKET: usize = 8;
+/// `source_type` of plain token accounts holding the pool mint, the only balance sources counted
+/// on-chain
+pub const TOKEN_ACCOUNT_BALANCE_SOURCE_TYPE: u16 = 0;
+pub const MAX_VALIDATORS_PER_BASKET: usize = 64;
+pub const MAX_SCHEDULED_PREFERENCE_SETS: usize = 4;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum DirectedStakeRecordType {
@@ -25,7 +33,10 @@ pub struct DirectedStakeMeta {
     // u64 for alignment, max permissioned validators is much smaller
     pub total_stake_targets: u64,
     pub directed_unstake_total: u64,
-    pub padding0: [u8; 63],
+    /// Set by `migrate_directed_targets_to_tickets`. Targets are then computed from tickets alone:
+    /// tickets can only be cranked once it is set, and the upload authority can no longer change targets.
+    pub ticket_targets_only: U8Bool,
+    pub padding0: [u8; 62],
     pub is_initialized: U8Bool,
     pub targets: [DirectedStakeTarget; MAX_VALIDATORS],
     // Total staked lamports indexed by validator list index
@@ -38,7 +49,8 @@ impl Default for DirectedStakeMeta {
         Self {
             total_stake_targets: 0,
             directed_unstake_total: 0,
-            padding0: [0; 63],
+            ticket_targets_only: U8Bool::from(false),
+            padding0: [0; 62],
             is_initialized: U8Bool::from(true),
             targets: [DirectedStakeTarget::default(); MAX_VALIDATORS],
             directed_stake_lamports: [0; MAX_VALIDATORS],
@@ -51,8 +63,12 @@ impl Default for DirectedStakeMeta {
 impl DirectedStakeMeta {
     pub const SIZE: usize = 8 + size_of::<Self>();
     pub const SEED: &'static [u8] = b"meta";
-    // Byte position of is_initialized field: 8 (discriminator) + 8*5 (u64 fields) + 62 (padding0) = 110
-    pub const IS_INITIALIZED_BYTE_POSITION: usize = 8 + 8 * 5 + 63;
+    // Byte position of is_initialized field: 8 (discriminator) + 8*5 (u64 fields) + 1 (ticket_targets_only) + 62 (padding0) = 111
+    pub const IS_INITIALIZED_BYTE_POSITION: usize = 8 + 8 * 5 + 1 + 62;
+
+    pub fn ticket_targets_only(&self) -> bool {
+        self.ticket_targets_only.into()
+    }
 
     /// Get the index of a particular validator in the targets array
     pub fn get_target_index(&self, vote_pubkey: &Pubkey) -> Option<usize> {
@@ -113,6 +129,159 @@ impl DirectedStakeMeta {
         }
         total
     }
+
+    /// Get the index of the target for `vote_pubkey`, creating one if it does not exist yet.
+    /// `validator_list_index` must already be verified to hold `vote_pubkey`.
+    pub fn get_or_create_target_index(
+        &mut self,
+        vote_pubkey: &Pubkey,
+        validator_list_index: usize,
+        epoch: u64,
+    ) -> Result<usize> {
+        let mapped_index = self.directed_stake_meta_indices[validator_list_index];
+        if mapped_index != u64::MAX
+            && self.targets[mapped_index as usize].vote_pubkey == *vote_pubkey
+        {
+            return Ok(mapped_index as usize);
+        }
+        if let Some(target_index) = self.get_target_index(vote_pubkey) {
+            return Ok(target_index);
+        }
+
+        let target_index = self.total_stake_targets as usize;
+        if target_index >= MAX_VALIDATORS {
+            return Err(error!(DirectedStakeValidatorListFull));
+        }
+        self.targets[target_index] = DirectedStakeTarget {
+            vote_pubkey: *vote_pubkey,
+            total_target_lamports: 0,
+            total_staked_lamports: 0,
+            target_last_updated_epoch: epoch,
+            staked_last_updated_epoch: 0,
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
+        };
+        self.total_stake_targets += 1;
+        self.directed_stake_lamports[validator_list_index] = 0;
+        self.directed_stake_meta_indices[validator_list_index] = target_index as u64;
+        Ok(target_index)
+    }
+
+    /// Add a ticket contribution to the target at `target_index`. With `replaces_migrated`, set on
+    /// the first crank of a contribution account, the contribution replaces as much of the target's
+    /// remaining migrated upload as it covers.
+    pub fn add_ticket_contribution(
+        &mut self,
+        target_index: usize,
+        lamports: u64,
+        replaces_migrated: bool,
+        epoch: u64,
+    ) {
+        let target = &mut self.targets[target_index];
+        let replaced_lamports = if replaces_migrated {
+            lamports.min(target.migrated_target_lamports)
+        } else {
+            0
+        };
+        target.migrated_target_lamports -= replaced_lamports;
+        target.total_target_lamports = target
+            .total_target_lamports
+            .saturating_sub(replaced_lamports);
+        target.total_target_lamports = target.total_target_lamports.saturating_add(lamports);
+        target.ticket_target_lamports = target.ticket_target_lamports.saturating_add(lamports);
+        target.target_last_updated_epoch = epoch;
+    }
+
+    /// Decays the target at `target_index` if it has not been refreshed for `expiry_epochs`,
+    /// removing `decay_bps` of it, rounded up, or all of it when `decay_bps` is 0.
+    /// The ticket contributions and migrated upload of a decayed target are reset: what remains of
+    /// the target becomes `unattributed_target_lamports`, which decays at the same rate on every
+    /// later call until it reaches 0, whether or not the target is refreshed in the meantime.
+    /// Called at most once per epoch for each target.
+    /// Returns the lamports removed from `total_target_lamports`.
+    pub fn decay_stale_target(
+        &mut self,
+        target_index: usize,
+        epoch: u64,
+        expiry_epochs: u64,
+        decay_bps: u16,
+    ) -> u64 {
+        let target = &mut self.targets[target_index];
+        if expiry_epochs == 0 {
+            return 0;
+        }
+
+        // Rounded up so that decaying lamports eventually reach 0
+        let decay = |lamports: u64| -> u64 {
+            if decay_bps == 0 {
+                lamports
+            } else {
+                ((lamports as u128) * (decay_bps as u128)).div_ceil(10_000) as u64
+            }
+        };
+
+        if epoch.saturating_sub(target.target_last_updated_epoch) < expiry_epochs {
+            let unattributed_decay =
+                decay(target.unattributed_target_lamports).min(target.total_target_lamports);
+            target.unattributed_target_lamports = target
+                .unattributed_target_lamports
+                .saturating_sub(unattributed_decay);
+            target.total_target_lamports = target
+                .total_target_lamports
+                .saturating_sub(unattributed_decay);
+            return unattributed_decay;
+        }
+
+        let target_decay = decay(target.total_target_lamports);
+        target.total_target_lamports = target.total_target_lamports.saturating_sub(target_decay);
+        target.ticket_target_lamports = 0;
+        target.ticket_decay_epoch = epoch;
+        target.unattributed_target_lamports = target.total_target_lamports;
+        target.migrated_target_lamports = 0;
+        target_decay
+    }
+
+    /// Remove all previously applied contributions of a ticket and clear them from `contribution`
+    pub fn remove_ticket_contributions(
+        &mut self,
+        contribution: &mut DirectedStakeContribution,
+        epoch: u64,
+    ) {
+        for entry in contribution
+            .contributions
+            .iter()
+            .take(contribution.num_contributions as usize)
+        {
+            self.remove_contribution_entry(entry, epoch);
+        }
+        contribution.contributions =
+            [DirectedStakeContributionEntry::default(); MAX_PREFERENCES_PER_TICKET];
+        contribution.num_contributions = 0;
+    }
+
+    /// Remove the lamports of a single contribution entry from its target. Entries reset by a
+    /// decay of the target are ignored.
+    pub fn remove_contribution_entry(
+        &mut self,
+        entry: &DirectedStakeContributionEntry,
+        epoch: u64,
+    ) {
+        if entry.lamports == 0 {
+            return;
+        }
+        let target = &mut self.targets[entry.target_index as usize];
+        if target.vote_pubkey != entry.vote_pubkey
+            || entry.applied_epoch < target.ticket_decay_epoch
+        {
+            return;
+        }
+        target.total_target_lamports = target.total_target_lamports.saturating_sub(entry.lamports);
+        target.ticket_target_lamports =
+            target.ticket_target_lamports.saturating_sub(entry.lamports);
+        target.target_last_updated_epoch = epoch;
+    }
 }
 
 #[derive(BorshSerialize, Debug, Default)]
@@ -128,18 +297,29 @@ pub struct DirectedStakeTarget {
     pub target_last_updated_epoch: u64,
     /// Last updated epoch for staked lamports
     pub staked_last_updated_epoch: u64,
-    // Alignment compliant reserve space for future use
-    pub _padding0: [u8; 32],
+    /// Portion of `total_target_lamports` contributed by cranked tickets
+    pub ticket_target_lamports: u64,
+    /// Epoch the target last decayed. Ticket contributions applied before it were reset by the
+    /// decay and are no longer subtracted when the ticket is cranked or cleared.
+    pub ticket_decay_epoch: u64,
+    /// Portion of `total_target_lamports` left over by a decay and no longer attributed to any ticket
+    /// or upload. It keeps decaying every epoch until it reaches 0, even once the target is refreshed.
+    pub unattributed_target_lamports: u64,
+    /// Portion of `total_target_lamports` uploaded before the migration to ticket targets. Each
+    /// lamport contributed by a cranked ticket replaces one of them, so holders are not counted twice.
+    pub migrated_target_lamports: u64,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 #[account(zero_copy)]
 pub struct DirectedStakePreference {
     /// Validator vote pubkey
     pub vote_pubkey: Pubkey,
     /// Percentage of directed stake allocated towards this validator
     pub stake_share_bps: u16,
-    pub _padding0: [u8; 94],
+    /// `vote_pubkey` is the address of a `DirectedStakeBasket` whose validators share the stake
+    pub is_basket: U8Bool,
+    pub _padding0: [u8; 93],
 }
 
 impl DirectedStakePreference {
@@ -147,7 +327,18 @@ impl DirectedStakePreference {
         Self {
             vote_pubkey,
             stake_share_bps,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
+        }
+    }
+
+    /// Preference directing `stake_share_bps` to the validators of the basket at `basket`
+    pub fn basket(basket: Pubkey, stake_share_bps: u16) -> Self {
+        Self {
+            vote_pubkey: basket,
+            stake_share_bps,
+            is_basket: U8Bool::from(true),
+            _padding0: [0; 93],
         }
     }
 
@@ -155,7 +346,8 @@ impl DirectedStakePreference {
         Self {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 0,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }
     }
 
@@ -176,9 +368,14 @@ pub struct DirectedStakeTicket {
     pub ticket_update_authority: Pubkey,
     /// Is the ticket holder a protocol vs. an individual pubkey
     pub ticket_holder_is_protocol: U8Bool,
-    // 15 bytes required for alignment
+    /// Opened by a holder meeting `directed_stake_permissionless_ticket_min_balance` instead of
+    /// a whitelist entry
+    pub is_permissionless: U8Bool,
+    /// Sum of the stake shares of the ticket's `DirectedStakeTicketExtension` preferences
+    pub extension_stake_share_bps: u16,
+    // 12 bytes required for alignment
     // + 110 bytes reserved for future use
-    pub _padding0: [u8; 125],
+    pub _padding0: [u8; 122],
 }
 
 impl DirectedStakeTicket {
@@ -202,7 +399,9 @@ impl DirectedStakeTicket {
             staker_preferences: staker_preferences_arr,
             ticket_update_authority,
             ticket_holder_is_protocol,
-            _padding0: [0; 125],
+            is_permissionless: U8Bool::from(false),
+            extension_stake_share_bps: 0,
+            _padding0: [0; 122],
         }
     }
 
@@ -214,7 +413,115 @@ impl DirectedStakeTicket {
             .take(self.num_preferences as usize)
             .map(|pref| pref.stake_share_bps as u32)
             .sum();
-        total_bps <= 10_000
+        total_bps + self.extension_stake_share_bps as u32 <= 10_000
+    }
+
+    /// The ticket's own preferences, excluding unused slots
+    pub fn preferences(&self) -> &[DirectedStakePreference] {
+        let num_preferences = (self.num_preferences as usize).min(self.staker_preferences.len());
+        &self.staker_preferences[..num_preferences]
+    }
+
+    /// Sum of the stake shares of the ticket's own preferences
+    pub fn stake_share_bps(&self) -> u32 {
+        self.staker_preferences
+            .iter()
+            .take(self.num_preferences as usize)
+            .map(|pref| pref.stake_share_bps as u32)
+            .sum()
+    }
+
+    // This is intended to be called off-chain while computing directed stake meta
+    pub fn get_allocations(&self, total_lamports: u64) -> Vec<(Pubkey, u128)> {
+        let mut allocations: Vec<(Pubkey, u128)> = Vec::new();
+        for pref in self
+            .staker_preferences
+            .iter()
+            .take(self.num_preferences as usize)
+        {
+            let lamports: u128 = (total_lamports as u128)
+                .saturating_mul(pref.stake_share_bps as u128)
+                .saturating_div(10_000);
+            if lamports > 0 {
+                allocations.push((pref.vote_pubkey, lamports));
+            }
+        }
+        allocations
+    }
+
+    // Same as `get_allocations`, including the preferences of the ticket's extension
+    pub fn get_allocations_with_extension(
+        &self,
+        extension: Option<&DirectedStakeTicketExtension>,
+        total_lamports: u64,
+    ) -> Vec<(Pubkey, u128)> {
+        let mut allocations = self.get_allocations(total_lamports);
+        if let Some(extension) = extension {
+            allocations.extend(extension.get_allocations(total_lamports));
+        }
+        allocations
+    }
+}
+
+/// Compact preference stored in a `DirectedStakeTicketExtension`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+#[account(zero_copy)]
+pub struct DirectedStakeExtensionPreference {
+    /// Validator vote pubkey
+    pub vote_pubkey: Pubkey,
+    /// Percentage of directed stake allocated towards this validator
+    pub stake_share_bps: u16,
+    /// `vote_pubkey` is the address of a `DirectedStakeBasket` whose validators share the stake
+    pub is_basket: U8Bool,
+    pub _padding0: [u8; 5],
+}
+
+impl DirectedStakeExtensionPreference {
+    pub fn new(vote_pubkey: Pubkey, stake_share_bps: u16) -> Self {
+        Self {
+            vote_pubkey,
+            stake_share_bps,
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 5],
+        }
+    }
+
+    /// Preference directing `stake_share_bps` to the validators of the basket at `basket`
+    pub fn basket(basket: Pubkey, stake_share_bps: u16) -> Self {
+        Self {
+            vote_pubkey: basket,
+            stake_share_bps,
+            is_basket: U8Bool::from(true),
+            _padding0: [0; 5],
+        }
+    }
+}
+
+/// Additional preferences of a `DirectedStakeTicket`, beyond the `MAX_PREFERENCES_PER_TICKET`
+/// held by the ticket itself. The stake shares of the ticket and its extension together must be
+/// less than or equal to 10_000 bps.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeTicketExtension {
+    pub ticket: Pubkey,
+    pub num_preferences: u64,
+    pub staker_preferences:
+        [DirectedStakeExtensionPreference; MAX_PREFERENCES_PER_TICKET_EXTENSION],
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl DirectedStakeTicketExtension {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"ticket_extension";
+
+    /// Sum of the stake shares of the extension's preferences
+    pub fn stake_share_bps(&self) -> u32 {
+        self.staker_preferences
+            .iter()
+            .take(self.num_preferences as usize)
+            .map(|pref| pref.stake_share_bps as u32)
+            .sum()
     }
 
     // This is intended to be called off-chain while computing directed stake meta
@@ -236,6 +543,486 @@ impl DirectedStakeTicket {
     }
 }
 
+/// Lamports a ticket has applied to a single `DirectedStakeTarget`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+#[account(zero_copy)]
+pub struct DirectedStakeContributionEntry {
+    /// Validator vote pubkey
+    pub vote_pubkey: Pubkey,
+    /// Index of the validator's target in `DirectedStakeMeta::targets`
+    pub target_index: u64,
+    /// Lamports added to the target's `total_target_lamports`
+    pub lamports: u64,
+    /// Epoch the lamports were added in
+    pub applied_epoch: u64,
+}
+
+/// Tracks what a `DirectedStakeTicket` currently contributes to `DirectedStakeMeta` targets,
+/// so the contribution can be replaced when the ticket is cranked again.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeContribution {
+    pub config: Pubkey,
+    pub ticket: Pubkey,
+    /// JitoSOL balance used to compute the contributions at the last crank
+    pub token_balance: u64,
+    /// Epoch of the last crank
+    pub last_updated_epoch: u64,
+    pub num_contributions: u64,
+    pub contributions: [DirectedStakeContributionEntry; MAX_PREFERENCES_PER_TICKET],
+
+    /////// Balance sampling, used when `directed_stake_sample_interval_slots` is set ///////
+    /// Epoch currently being sampled
+    pub sample_epoch: u64,
+    /// Index of the current bucket in `sample_epoch`
+    pub sample_bucket: u64,
+    /// Lowest balance sampled in the current bucket
+    pub sample_bucket_min_balance: u64,
+    /// Sum of the lowest balances of the closed buckets in `sample_epoch`
+    pub sample_balance_sum: u64,
+    /// Slot of the last sample
+    pub last_sample_slot: u64,
+    /// Average balance over `average_balance_epoch`, buckets without a sample count as 0.
+    /// 0 unless sampling already ran when `average_balance_epoch` started.
+    pub average_balance: u64,
+    pub average_balance_epoch: u64,
+    /// First epoch of the current run of consecutively sampled epochs
+    pub sample_start_epoch: u64,
+    /// Whether the contributions already replaced the uploaded targets kept by the migration to
+    /// ticket targets. Only the first crank after the migration replaces them.
+    pub migrated_target_replaced: U8Bool,
+    // 63 bytes reserved for future use
+    pub _padding0: [u8; 63],
+}
+
+impl DirectedStakeContribution {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"contribution";
+
+    /// Total lamports currently contributed by the ticket
+    pub fn total_lamports(&self) -> u64 {
+        self.contributions
+            .iter()
+            .take(self.num_contributions as usize)
+            .fold(0u64, |total, entry| total.saturating_add(entry.lamports))
+    }
+
+    /// Records a balance sample taken at `slot`. The epoch is split into buckets of
+    /// `sample_interval_slots` and each bucket counts the lowest balance sampled in it, so a
+    /// balance borrowed for a single transaction is only credited if no other sample is taken in
+    /// the same bucket. Moving into a new epoch finalizes the average of the previous one. Only an
+    /// epoch sampled from its start is credited: the average is 0 after the epoch in which sampling
+    /// started, or restarted after an unsampled epoch, so a balance held for a single crank earns nothing.
+    pub fn record_balance_sample(
+        &mut self,
+        token_balance: u64,
+        slot: u64,
+        sample_interval_slots: u64,
+        epoch_schedule: &EpochSchedule,
+    ) {
+        let epoch = epoch_schedule.get_epoch(slot);
+        let bucket = (slot - epoch_schedule.get_first_slot_in_epoch(epoch)) / sample_interval_slots;
+
+        if epoch != self.sample_epoch || self.last_sample_slot == 0 {
+            self.close_sample_bucket();
+            let previous_epoch_sampled =
+                self.last_sample_slot != 0 && self.sample_epoch + 1 == epoch;
+            if !previous_epoch_sampled {
+                self.sample_start_epoch = epoch;
+            }
+            // Epochs are credited once sampling already ran when they started
+            self.average_balance =
+                if previous_epoch_sampled && self.sample_epoch > self.sample_start_epoch {
+                    let num_buckets = epoch_schedule
+                        .get_slots_in_epoch(self.sample_epoch)
+                        .div_ceil(sample_interval_slots);
+                    self.sample_balance_sum / num_buckets.max(1)
+                } else {
+                    0
+                };
+            self.average_balance_epoch = epoch.saturating_sub(1);
+            self.sample_epoch = epoch;
+            self.sample_balance_sum = 0;
+            self.sample_bucket = bucket;
+            self.sample_bucket_min_balance = token_balance;
+        } else if bucket != self.sample_bucket {
+            self.close_sample_bucket();
+            self.sample_bucket = bucket;
+            self.sample_bucket_min_balance = token_balance;
+        } else {
+            self.sample_bucket_min_balance = self.sample_bucket_min_balance.min(token_balance);
+        }
+
+        self.last_sample_slot = slot;
+    }
+
+    fn close_sample_bucket(&mut self) {
+        self.sample_balance_sum = self
+            .sample_balance_sum
+            .saturating_add(self.sample_bucket_min_balance);
+        self.sample_bucket_min_balance = 0;
+    }
+}
+
+/// Tracks what the preferences of a `DirectedStakeTicketExtension` currently contribute to
+/// `DirectedStakeMeta` targets. `contributions[i]` belongs to the extension's i-th preference.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeExtensionContribution {
+    pub config: Pubkey,
+    pub ticket: Pubkey,
+    pub contributions: [DirectedStakeContributionEntry; MAX_PREFERENCES_PER_TICKET_EXTENSION],
+    /// Same as `DirectedStakeContribution::migrated_target_replaced`, for the extension's preferences
+    pub migrated_target_replaced: U8Bool,
+    // 63 bytes reserved for future use
+    pub _padding0: [u8; 63],
+}
+
+impl DirectedStakeExtensionContribution {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"extension_contribution";
+
+    /// Total lamports currently contributed by the extension
+    pub fn total_lamports(&self) -> u64 {
+        self.contributions
+            .iter()
+            .fold(0u64, |total, entry| total.saturating_add(entry.lamports))
+    }
+}
+
+/// Tracks what the preferences of a ticket and its extension pointing at a `DirectedStakeBasket`
+/// currently contribute to `DirectedStakeMeta` targets. `contributions[i]` belongs to the basket's
+/// i-th validator.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeBasketContribution {
+    pub config: Pubkey,
+    pub ticket: Pubkey,
+    pub basket: Pubkey,
+    pub contributions: [DirectedStakeContributionEntry; MAX_VALIDATORS_PER_BASKET],
+    /// Same as `DirectedStakeContribution::migrated_target_replaced`, for the basket's validators
+    pub migrated_target_replaced: U8Bool,
+    // 63 bytes reserved for future use
+    pub _padding0: [u8; 63],
+}
+
+impl DirectedStakeBasketContribution {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"basket_contribution";
+
+    /// Total lamports currently contributed through the basket
+    pub fn total_lamports(&self) -> u64 {
+        self.contributions
+            .iter()
+            .fold(0u64, |total, entry| total.saturating_add(entry.lamports))
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+#[account(zero_copy)]
+pub struct DirectedStakeBalanceSource {
+    /// Token or position account holding JitoSOL on behalf of the ticket holder
+    pub account: Pubkey,
+    /// Owner of the position, who signed to attribute it to the ticket
+    pub owner: Pubkey,
+    /// Identifies the layout of `account`. Token accounts (`TOKEN_ACCOUNT_BALANCE_SOURCE_TYPE`)
+    /// are counted by the ticket crank, other layouts only by the off-chain balance adapters.
+    pub source_type: u16,
+    pub _padding0: [u8; 30],
+}
+
+/// Additional token and position accounts whose JitoSOL counts toward a ticket's balance
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeBalanceSources {
+    pub ticket: Pubkey,
+    pub num_sources: u64,
+    pub sources: [DirectedStakeBalanceSource; MAX_BALANCE_SOURCES_PER_TICKET],
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl DirectedStakeBalanceSources {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"balance_sources";
+
+    pub fn add_source(&mut self, account: Pubkey, owner: Pubkey, source_type: u16) -> Result<()> {
+        let num_sources = self.num_sources as usize;
+        if self.sources[..num_sources]
+            .iter()
+            .any(|source| source.account == account)
+        {
+            return Err(error!(BalanceSourceAlreadyAdded));
+        }
+        if num_sources >= MAX_BALANCE_SOURCES_PER_TICKET {
+            return Err(error!(BalanceSourcesFull));
+        }
+        self.sources[num_sources] = DirectedStakeBalanceSource {
+            account,
+            owner,
+            source_type,
+            _padding0: [0; 30],
+        };
+        self.num_sources += 1;
+        Ok(())
+    }
+
+    /// Removes the source for `account`, returning it
+    pub fn remove_source(&mut self, account: &Pubkey) -> Result<DirectedStakeBalanceSource> {
+        let num_sources = self.num_sources as usize;
+        let index = self.sources[..num_sources]
+            .iter()
+            .position(|source| source.account == *account)
+            .ok_or(BalanceSourceNotFound)?;
+        let removed = self.sources[index];
+        self.sources[index] = self.sources[num_sources - 1];
+        self.sources[num_sources - 1] = DirectedStakeBalanceSource::default();
+        self.num_sources -= 1;
+        Ok(removed)
+    }
+}
+
+/// Set by a validator to control the directed stake it receives
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeValidatorConsent {
+    pub config: Pubkey,
+    pub vote_account: Pubkey,
+    /// Maximum directed stake target of the validator, 0 for no maximum
+    pub max_directed_stake_lamports: u64,
+    /// Validator does not accept directed stake
+    pub opted_out: U8Bool,
+    // 63 bytes reserved for future use
+    pub _padding0: [u8; 63],
+}
+
+impl DirectedStakeValidatorConsent {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"validator_consent";
+
+    /// Lamports of `lamports` the validator accepts on top of a target of `current_target_lamports`
+    pub fn accepted_lamports(&self, lamports: u64, current_target_lamports: u64) -> u64 {
+        if self.opted_out.into() {
+            return 0;
+        }
+        if self.max_directed_stake_lamports == 0 {
+            return lamports;
+        }
+        lamports.min(
+            self.max_directed_stake_lamports
+                .saturating_sub(current_target_lamports),
+        )
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeBasketEntry {
+    /// Validator vote pubkey
+    pub vote_pubkey: Pubkey,
+    /// Share of the basket's stake allocated towards this validator
+    pub weight_bps: u16,
+    pub _padding0: [u8; 6],
+}
+
+impl DirectedStakeBasketEntry {
+    pub fn new(vote_pubkey: Pubkey, weight_bps: u16) -> Self {
+        Self {
+            vote_pubkey,
+            weight_bps,
+            _padding0: [0; 6],
+        }
+    }
+}
+
+/// Curated, weighted list of validators that ticket preferences can direct stake to as a group.
+/// Updating the basket re-targets every ticket pointing at it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakeBasket {
+    pub config: Pubkey,
+    /// Authority that can update the basket validators
+    pub curator: Pubkey,
+    /// UTF-8 name, zero padded
+    pub name: [u8; 32],
+    pub num_validators: u64,
+    /// Weights of the validators must sum to 10_000 bps
+    pub validators: [DirectedStakeBasketEntry; MAX_VALIDATORS_PER_BASKET],
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl DirectedStakeBasket {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"basket";
+
+    pub fn validators(&self) -> &[DirectedStakeBasketEntry] {
+        &self.validators[..self.num_validators as usize]
+    }
+
+    /// Share of a ticket's stake directed to the basket at `basket`, summed over the preferences of
+    /// the ticket and its extension pointing at it
+    pub fn ticket_stake_share_bps(
+        basket: &Pubkey,
+        ticket: &DirectedStakeTicket,
+        extension: Option<&DirectedStakeTicketExtension>,
+    ) -> u32 {
+        let ticket_bps: u32 = ticket
+            .preferences()
+            .iter()
+            .filter(|pref| bool::from(pref.is_basket) && pref.vote_pubkey == *basket)
+            .map(|pref| pref.stake_share_bps as u32)
+            .sum();
+        let extension_bps: u32 = extension
+            .map(|extension| {
+                extension
+                    .staker_preferences
+                    .iter()
+                    .take(extension.num_preferences as usize)
+                    .filter(|pref| bool::from(pref.is_basket) && pref.vote_pubkey == *basket)
+                    .map(|pref| pref.stake_share_bps as u32)
+                    .sum()
+            })
+            .unwrap_or(0);
+        ticket_bps + extension_bps
+    }
+
+    /// Splits `lamports` across the basket validators by weight
+    pub fn get_allocations(&self, lamports: u64) -> Vec<(Pubkey, u64)> {
+        self.validators()
+            .iter()
+            .map(|entry| {
+                let allocation = (lamports as u128)
+                    .saturating_mul(entry.weight_bps as u128)
+                    .saturating_div(10_000);
+                (entry.vote_pubkey, allocation as u64)
+            })
+            .collect()
+    }
+}
+
+/// Preference set that replaces a ticket's preferences once `activation_epoch` is reached
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct ScheduledDirectedStakePreferences {
+    pub activation_epoch: u64,
+    pub num_preferences: u64,
+    pub preferences: [DirectedStakePreference; MAX_PREFERENCES_PER_TICKET],
+}
+
+impl Default for ScheduledDirectedStakePreferences {
+    fn default() -> Self {
+        Self {
+            activation_epoch: 0,
+            num_preferences: 0,
+            preferences: [DirectedStakePreference::empty(); MAX_PREFERENCES_PER_TICKET],
+        }
+    }
+}
+
+impl ScheduledDirectedStakePreferences {
+    pub fn preferences(&self) -> &[DirectedStakePreference] {
+        &self.preferences[..self.num_preferences as usize]
+    }
+}
+
+/// Queue of future preference sets of a `DirectedStakeTicket`, ordered by activation epoch.
+/// Due sets are applied to the ticket by the permissionless `apply_scheduled_directed_stake_preferences`.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct DirectedStakePreferenceSchedule {
+    pub ticket: Pubkey,
+    pub num_scheduled: u64,
+    pub scheduled: [ScheduledDirectedStakePreferences; MAX_SCHEDULED_PREFERENCE_SETS],
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl DirectedStakePreferenceSchedule {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"preference_schedule";
+
+    pub fn scheduled(&self) -> &[ScheduledDirectedStakePreferences] {
+        &self.scheduled[..self.num_scheduled as usize]
+    }
+
+    /// Schedules `preferences` for `activation_epoch`, replacing any set already scheduled for that epoch
+    pub fn schedule(
+        &mut self,
+        activation_epoch: u64,
+        preferences: &[DirectedStakePreference],
+    ) -> Result<()> {
+        if preferences.len() > MAX_PREFERENCES_PER_TICKET {
+            return Err(InvalidParameterValue.into());
+        }
+        let mut entry = ScheduledDirectedStakePreferences {
+            activation_epoch,
+            num_preferences: preferences.len() as u64,
+            ..Default::default()
+        };
+        entry.preferences[..preferences.len()].copy_from_slice(preferences);
+
+        let num_scheduled = self.num_scheduled as usize;
+        if let Some(existing) = self.scheduled[..num_scheduled]
+            .iter_mut()
+            .find(|scheduled| scheduled.activation_epoch == activation_epoch)
+        {
+            *existing = entry;
+            return Ok(());
+        }
+        if num_scheduled >= MAX_SCHEDULED_PREFERENCE_SETS {
+            return Err(DirectedStakePreferenceScheduleFull.into());
+        }
+        let index = self.scheduled[..num_scheduled]
+            .iter()
+            .position(|scheduled| scheduled.activation_epoch > activation_epoch)
+            .unwrap_or(num_scheduled);
+        self.scheduled.copy_within(index..num_scheduled, index + 1);
+        self.scheduled[index] = entry;
+        self.num_scheduled += 1;
+        Ok(())
+    }
+
+    /// Removes the set scheduled for `activation_epoch`
+    pub fn cancel(&mut self, activation_epoch: u64) -> Result<()> {
+        let num_scheduled = self.num_scheduled as usize;
+        let index = self.scheduled[..num_scheduled]
+            .iter()
+            .position(|scheduled| scheduled.activation_epoch == activation_epoch)
+            .ok_or(ScheduledDirectedStakePreferencesNotFound)?;
+        self.scheduled.copy_within(index + 1..num_scheduled, index);
+        self.scheduled[num_scheduled - 1] = ScheduledDirectedStakePreferences::default();
+        self.num_scheduled -= 1;
+        Ok(())
+    }
+
+    /// The latest set whose activation epoch is at or before `epoch`
+    pub fn due(&self, epoch: u64) -> Option<&ScheduledDirectedStakePreferences> {
+        self.scheduled()
+            .iter()
+            .rev()
+            .find(|scheduled| scheduled.activation_epoch <= epoch)
+    }
+
+    /// Removes every set that is due at `epoch`, returning the latest one.
+    /// Earlier due sets are superseded and never applied.
+    pub fn take_due(&mut self, epoch: u64) -> Option<ScheduledDirectedStakePreferences> {
+        let due = *self.due(epoch)?;
+        let num_scheduled = self.num_scheduled as usize;
+        let num_due = self
+            .scheduled()
+            .iter()
+            .take_while(|scheduled| scheduled.activation_epoch <= epoch)
+            .count();
+        self.scheduled.copy_within(num_due..num_scheduled, 0);
+        for scheduled in self.scheduled[num_scheduled - num_due..num_scheduled].iter_mut() {
+            *scheduled = ScheduledDirectedStakePreferences::default();
+        }
+        self.num_scheduled -= num_due as u64;
+        Some(due)
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 #[account(zero_copy)]
 pub struct DirectedStakeWhitelist {
@@ -277,6 +1064,15 @@ impl DirectedStakeWhitelist {
             .any(|pk| pk == staker)
     }
 
+    /// Number of whitelisted records of the given type
+    pub fn total_permissioned(&self, record_type: DirectedStakeRecordType) -> u16 {
+        match record_type {
+            DirectedStakeRecordType::Validator => self.total_permissioned_validators,
+            DirectedStakeRecordType::User => self.total_permissioned_user_stakers,
+            DirectedStakeRecordType::Protocol => self.total_permissioned_protocol_stakers,
+        }
+    }
+
     pub fn is_staker_permissioned(&self, staker: &Pubkey) -> bool {
         self.is_user_staker_permissioned(staker) || self.is_protocol_staker_permissioned(staker)
     }
@@ -0,0 +1,131 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::constants::BASIS_POINTS_MAX;
+
+/// Maximum number of validators with a partial instant unstake in a single epoch
+pub const MAX_INSTANT_UNSTAKE_FRACTIONS: usize = 250;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq, Eq)]
+#[zero_copy]
+pub struct InstantUnstakeFraction {
+    pub vote_account: Pubkey,
+
+    /// Share of the validator's current stake to instant unstake (in basis points)
+    pub fraction_bps: u16,
+
+    pub _padding0: [u8; 6],
+}
+
+/// Graded instant unstake fractions of the validators flagged in `compute_instant_unstake` this epoch.
+/// Entries are keyed by vote account so they survive validator list index shifts.
+/// Flagged validators without an entry are fully unstaked.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct InstantUnstakeFractions {
+    /// Steward config these fractions belong to
+    pub config: Pubkey,
+
+    /// Epoch the entries were computed in. Entries from an earlier epoch are stale
+    pub epoch: u64,
+
+    pub num_entries: u64,
+
+    pub entries: [InstantUnstakeFraction; MAX_INSTANT_UNSTAKE_FRACTIONS],
+
+    pub bump: u8,
+
+    pub _padding0: [u8; 7],
+}
+
+impl InstantUnstakeFractions {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"instant_unstake_fractions";
+
+    /// Fraction to instant unstake from `vote_account`, if it has a partial instant unstake.
+    /// Callers check that `epoch` is current.
+    pub fn get(&self, vote_account: &Pubkey) -> Option<u16> {
+        self.entries[..self.num_entries as usize]
+            .iter()
+            .find(|entry| entry.vote_account == *vote_account)
+            .map(|entry| entry.fraction_bps)
+    }
+
+    /// Records the instant unstake fraction of `vote_account` for `epoch`, clearing entries from earlier epochs.
+    /// A fraction of 0 or `BASIS_POINTS_MAX` removes the entry, as the validator is either not unstaked or fully unstaked.
+    /// Returns false if a partial fraction could not be recorded because all entries are in use.
+    pub fn record(&mut self, epoch: u64, vote_account: &Pubkey, fraction_bps: u16) -> bool {
+        if self.epoch != epoch {
+            self.epoch = epoch;
+            self.num_entries = 0;
+        }
+
+        let num_entries = self.num_entries as usize;
+        let position = self.entries[..num_entries]
+            .iter()
+            .position(|entry| entry.vote_account == *vote_account);
+
+        if fraction_bps == 0 || fraction_bps >= BASIS_POINTS_MAX {
+            if let Some(position) = position {
+                self.entries[position] = self.entries[num_entries - 1];
+                self.entries[num_entries - 1] = InstantUnstakeFraction::default();
+                self.num_entries -= 1;
+            }
+            return true;
+        }
+
+        let position = match position {
+            Some(position) => position,
+            None if num_entries < MAX_INSTANT_UNSTAKE_FRACTIONS => {
+                self.num_entries += 1;
+                num_entries
+            }
+            None => return false,
+        };
+
+        self.entries[position] = InstantUnstakeFraction {
+            vote_account: *vote_account,
+            fraction_bps,
+            _padding0: [0; 6],
+        };
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_get() {
+        let mut fractions: Box<InstantUnstakeFractions> = Box::new(bytemuck::Zeroable::zeroed());
+        let vote_a = Pubkey::new_unique();
+        let vote_b = Pubkey::new_unique();
+
+        assert!(fractions.record(10, &vote_a, 2_500));
+        assert!(fractions.record(10, &vote_b, 5_000));
+        assert_eq!(fractions.epoch, 10);
+        assert_eq!(fractions.get(&vote_a), Some(2_500));
+
+        // Full and zero fractions remove the entry
+        assert!(fractions.record(10, &vote_a, BASIS_POINTS_MAX));
+        assert_eq!(fractions.get(&vote_a), None);
+        assert_eq!(fractions.get(&vote_b), Some(5_000));
+        assert_eq!(fractions.num_entries, 1);
+
+        // A new epoch clears the previous entries
+        assert!(fractions.record(11, &vote_a, 1_000));
+        assert_eq!(fractions.get(&vote_b), None);
+        assert_eq!(fractions.num_entries, 1);
+
+        for _ in 1..MAX_INSTANT_UNSTAKE_FRACTIONS {
+            assert!(fractions.record(11, &Pubkey::new_unique(), 1_000));
+        }
+        assert!(!fractions.record(11, &Pubkey::new_unique(), 1_000));
+        // Existing entries can still be updated when full
+        assert!(fractions.record(11, &vote_a, 3_000));
+        assert_eq!(fractions.get(&vote_a), Some(3_000));
+    }
+}
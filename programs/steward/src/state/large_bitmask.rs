@@ -10,7 +10,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use crate::errors::StewardError;
 
 //We are allocating at this size to handle future growth of ValidatorHistory accounts, at 2800 in June 2024
-const LARGE_BITMASK_INDEXES: usize = 20_000;
+pub const LARGE_BITMASK_INDEXES: usize = 20_000;
 
 #[allow(clippy::manual_div_ceil)]
 #[allow(clippy::identity_op)]
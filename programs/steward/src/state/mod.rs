@@ -1,13 +1,29 @@
 pub mod accounts;
 pub mod bitmask;
+pub mod circuit_breaker;
+pub mod commission_pledge;
+pub mod cycle_report;
 pub mod directed_stake;
+pub mod instant_unstake_fractions;
 pub mod large_bitmask;
 pub mod parameters;
+pub mod rebalance_journal;
+pub mod score_warnings;
 pub mod steward_state;
+pub mod unstake_carryover;
+pub mod validator_exit;
 
 pub use accounts::*;
 pub use bitmask::*;
+pub use circuit_breaker::*;
+pub use commission_pledge::*;
+pub use cycle_report::*;
 pub use directed_stake::*;
+pub use instant_unstake_fractions::*;
 pub use large_bitmask::*;
 pub use parameters::*;
+pub use rebalance_journal::*;
+pub use score_warnings::*;
 pub use steward_state::*;
+pub use unstake_carryover::*;
+pub use validator_exit::*;
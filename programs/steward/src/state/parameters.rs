@@ -9,9 +9,11 @@ use validator_history::utils::cast_epoch;
 use crate::{
     constants::{
         BASIS_POINTS_MAX, COMMISSION_MAX, COMPUTE_SCORE_SLOT_RANGE_MIN, EPOCH_PROGRESS_MAX,
-        MAX_VALIDATORS, NUM_EPOCHS_BETWEEN_SCORING_MAX, VALIDATOR_HISTORY_FIRST_RELIABLE_EPOCH,
+        MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH, MAX_VALIDATORS, NUM_EPOCHS_BETWEEN_SCORING_MAX,
+        VALIDATOR_HISTORY_FIRST_RELIABLE_EPOCH,
     },
     errors::StewardError,
+    score::SCORE_WARNING_FILTERS_ALLOWED,
 };
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone)]
@@ -52,6 +54,42 @@ pub struct UpdateParametersArgs {
     /// out of those epochs to qualify for delegation.
     /// `None` means do not update the current value.
     pub jito_bam_window_epochs: Option<u8>,
+
+    // Circuit breaker parameters
+    pub circuit_breaker_instant_unstake_count: Option<u16>,
+    pub circuit_breaker_unstake_bps: Option<u16>,
+
+    // Directed stake parameters
+    pub directed_stake_sample_interval_slots: Option<u64>,
+    pub directed_stake_target_expiry_epochs: Option<u64>,
+    pub directed_stake_target_decay_bps: Option<u16>,
+    pub directed_stake_permissionless_ticket_min_balance: Option<u64>,
+
+    // Reserve parameters
+    pub reserve_liquidity_target_lamports: Option<u64>,
+    pub reserve_liquidity_target_bps: Option<u16>,
+
+    // Preferred validator parameters
+    pub auto_preferred_validators: Option<bool>,
+
+    // Graded instant unstake parameters
+    pub instant_unstake_max_fraction_bps: Option<u16>,
+
+    // Score warning parameters
+    pub score_warning_filters: Option<u16>,
+
+    // Validator exit parameters
+    pub validator_exit_cooldown_epochs: Option<u64>,
+
+    // Commission pledge parameters
+    pub commission_pledge_cooldown_epochs: Option<u64>,
+
+    // Rebalance parameters
+    pub minimum_rebalance_lamports: Option<u64>,
+    pub unstake_carryover_ceiling_bps: Option<u16>,
+
+    // Cycle report parameters
+    pub cycle_reports_enabled: Option<bool>,
 }
 
 #[cfg(feature = "idl-build")]
@@ -180,6 +218,86 @@ impl IdlBuild for UpdateParametersArgs {
                         ty: IdlType::Option(Box::new(IdlType::U8)),
                         docs: Default::default(),
                     },
+                    IdlField {
+                        name: "circuit_breaker_instant_unstake_count".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "circuit_breaker_unstake_bps".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "directed_stake_sample_interval_slots".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "directed_stake_target_expiry_epochs".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "directed_stake_target_decay_bps".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "directed_stake_permissionless_ticket_min_balance".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "reserve_liquidity_target_lamports".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "reserve_liquidity_target_bps".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "auto_preferred_validators".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::Bool)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "instant_unstake_max_fraction_bps".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "score_warning_filters".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "validator_exit_cooldown_epochs".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "commission_pledge_cooldown_epochs".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "minimum_rebalance_lamports".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U64)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "unstake_carryover_ceiling_bps".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::U16)),
+                        docs: Default::default(),
+                    },
+                    IdlField {
+                        name: "cycle_reports_enabled".to_string(),
+                        ty: IdlType::Option(Box::new(IdlType::Bool)),
+                        docs: Default::default(),
+                    },
                 ])),
             },
             docs: Default::default(),
@@ -194,7 +312,7 @@ impl IdlBuild for UpdateParametersArgs {
 
 static_assertions::const_assert_eq!(size_of::<Parameters>(), 352);
 
-#[derive(BorshSerialize, Default)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
 #[zero_copy]
 pub struct Parameters {
     /////// Scoring parameters ///////
@@ -285,7 +403,94 @@ pub struct Parameters {
 
     pub _padding_0: [u8; 4],
 
-    pub _padding_1: [u64; 28],
+    /////// Circuit breaker parameters ///////
+    /// Maximum number of validators that can be marked for instant unstake in a cycle before
+    /// the steward pauses itself. 0 disables the check.
+    pub circuit_breaker_instant_unstake_count: u16,
+
+    /// Maximum amount of the pool that can be unstaked in a cycle across scoring, instant and
+    /// stake deposit unstakes before the steward pauses itself (in basis points). 0 disables the check.
+    pub circuit_breaker_unstake_bps: u16,
+
+    /// Share of a stale directed stake target removed each epoch during `rebalance_directed`
+    /// (in basis points). 0 resets stale targets to zero at once.
+    pub directed_stake_target_decay_bps: u16,
+
+    pub _padding_3: [u8; 2],
+
+    /////// Directed stake parameters ///////
+    /// Length of the buckets the epoch is split into when sampling ticket holder balances
+    /// with `crank_directed_stake_ticket`. Each bucket counts the lowest balance sampled in it,
+    /// and ticket targets are computed from the average over the previous epoch.
+    /// 0 disables sampling, which makes ticket cranks and `migrate_directed_targets_to_tickets` fail.
+    pub directed_stake_sample_interval_slots: u64,
+
+    /// Number of epochs after which a directed stake target that has not been refreshed is
+    /// considered stale and decays according to `directed_stake_target_decay_bps`. 0 disables expiry.
+    pub directed_stake_target_expiry_epochs: u64,
+
+    /// Minimum JitoSOL balance (in pool token base units) for a holder to open a directed stake
+    /// ticket without a whitelist entry. Tickets opened this way can be closed by anyone once the
+    /// holder's balance falls below it. 0 disables permissionless tickets.
+    pub directed_stake_permissionless_ticket_min_balance: u64,
+
+    /////// Reserve parameters ///////
+    /// Lamports kept in the reserve as a liquidity buffer for SOL withdrawals. `rebalance` does
+    /// not stake them, and unstakes toward them when the reserve is below target.
+    /// The larger of this and `reserve_liquidity_target_bps` is targeted.
+    pub reserve_liquidity_target_lamports: u64,
+
+    /// Share of the pool TVL kept in the reserve as a liquidity buffer (in basis points)
+    pub reserve_liquidity_target_bps: u16,
+
+    /////// Graded instant unstake parameters ///////
+    /// Share of a flagged validator's current stake that is instant unstaked when the delinquency,
+    /// commission or MEV commission check fails at full severity (in basis points). Lower severities
+    /// unstake proportionally less and keep the validator's delegation. Blacklisted validators and bad
+    /// merkle root upload authorities are always fully unstaked. 0 disables graded instant unstake.
+    pub instant_unstake_max_fraction_bps: u16,
+
+    /////// Score warning parameters ///////
+    /// `SCORE_FILTER_*` flags of the binary filters that warn a validator before unscoring it. A validator
+    /// first failing only these filters keeps its score for the rest of the cycle, and is unscored if it
+    /// still fails them in a later cycle. 0 disables warnings.
+    pub score_warning_filters: u16,
+
+    /////// Preferred validator parameters ///////
+    /// When 1, `update_preferred_validators` sets the pool's preferred deposit validator to the
+    /// highest-scored validator furthest below target and the preferred withdraw validator to the
+    /// lowest-scored validator furthest above target. 0 disables it.
+    pub auto_preferred_validators: u8,
+
+    /////// Cycle report parameters ///////
+    /// When 1, every cycle must have a `CycleReport`: the instruction starting a cycle and every crank
+    /// that fills in the report fails without it. 0 leaves passing the report up to the cranker.
+    pub cycle_reports_enabled: u8,
+
+    /////// Validator exit parameters ///////
+    /// Number of epochs after `request_validator_exit` during which `auto_add_validator_to_pool`
+    /// will not re-add the validator, unless it calls `request_validator_rejoin`. 0 does not block re-adding.
+    pub validator_exit_cooldown_epochs: u64,
+
+    /////// Commission pledge parameters ///////
+    /// Number of epochs after a `CommissionPledge` is broken during which the validator cannot pledge again
+    pub commission_pledge_cooldown_epochs: u64,
+
+    /////// Rebalance parameters ///////
+    /// Increases and decreases smaller than this are skipped by `rebalance`, so dust moves don't each
+    /// create a transient stake account. Decreases that include instant unstake lamports always happen.
+    /// 0 disables it.
+    pub minimum_rebalance_lamports: u64,
+
+    /// Budget of each of the scoring, instant and stake deposit unstake caps left unused in a cycle is
+    /// carried into the next cycle, up to this share of the pool per cap (in basis points). Carried
+    /// budget that is not used is carried again, so large reallocations are spread over quiet cycles.
+    /// Takes effect from the next cycle. 0 disables carry-over.
+    pub unstake_carryover_ceiling_bps: u16,
+
+    pub _padding_5: [u8; 6],
+
+    pub _padding_1: [u64; 18],
     /// The minimum epoch progress for computing scores
     pub compute_score_epoch_progress: f64,
 
@@ -307,6 +512,31 @@ impl Parameters {
         u64::from_le_bytes(self.undirected_stake_ceiling_lamports)
     }
 
+    pub fn auto_preferred_validators_enabled(&self) -> bool {
+        self.auto_preferred_validators == 1
+    }
+
+    pub fn cycle_reports_enabled(&self) -> bool {
+        self.cycle_reports_enabled == 1
+    }
+
+    pub fn graded_instant_unstake_enabled(&self) -> bool {
+        self.instant_unstake_max_fraction_bps > 0
+    }
+
+    pub fn score_warnings_enabled(&self) -> bool {
+        self.score_warning_filters != 0
+    }
+
+    /// Lamports to keep in the reserve for a pool of `total_pool_lamports`, the larger of
+    /// `reserve_liquidity_target_lamports` and `reserve_liquidity_target_bps` of the pool
+    pub fn reserve_liquidity_target(&self, total_pool_lamports: u64) -> u64 {
+        let target_from_bps = (total_pool_lamports as u128)
+            .saturating_mul(self.reserve_liquidity_target_bps as u128)
+            .saturating_div(BASIS_POINTS_MAX as u128) as u64;
+        target_from_bps.max(self.reserve_liquidity_target_lamports)
+    }
+
     /// Merges the updated parameters with the current parameters and validates them
     pub fn get_valid_updated_parameters(
         self,
@@ -339,6 +569,22 @@ impl Parameters {
             undirected_stake_ceiling_lamports,
             jito_bam_minimum_epochs,
             jito_bam_window_epochs,
+            circuit_breaker_instant_unstake_count,
+            circuit_breaker_unstake_bps,
+            directed_stake_sample_interval_slots,
+            directed_stake_target_expiry_epochs,
+            directed_stake_target_decay_bps,
+            directed_stake_permissionless_ticket_min_balance,
+            reserve_liquidity_target_lamports,
+            reserve_liquidity_target_bps,
+            auto_preferred_validators,
+            instant_unstake_max_fraction_bps,
+            score_warning_filters,
+            validator_exit_cooldown_epochs,
+            commission_pledge_cooldown_epochs,
+            minimum_rebalance_lamports,
+            unstake_carryover_ceiling_bps,
+            cycle_reports_enabled,
         } = *args;
 
         let mut new_parameters = self;
@@ -441,6 +687,76 @@ impl Parameters {
             new_parameters.jito_bam_window_epochs = jito_bam_window_epochs;
         }
 
+        if let Some(circuit_breaker_instant_unstake_count) = circuit_breaker_instant_unstake_count {
+            new_parameters.circuit_breaker_instant_unstake_count =
+                circuit_breaker_instant_unstake_count;
+        }
+
+        if let Some(circuit_breaker_unstake_bps) = circuit_breaker_unstake_bps {
+            new_parameters.circuit_breaker_unstake_bps = circuit_breaker_unstake_bps;
+        }
+
+        if let Some(directed_stake_sample_interval_slots) = directed_stake_sample_interval_slots {
+            new_parameters.directed_stake_sample_interval_slots =
+                directed_stake_sample_interval_slots;
+        }
+
+        if let Some(directed_stake_target_expiry_epochs) = directed_stake_target_expiry_epochs {
+            new_parameters.directed_stake_target_expiry_epochs =
+                directed_stake_target_expiry_epochs;
+        }
+
+        if let Some(directed_stake_target_decay_bps) = directed_stake_target_decay_bps {
+            new_parameters.directed_stake_target_decay_bps = directed_stake_target_decay_bps;
+        }
+
+        if let Some(directed_stake_permissionless_ticket_min_balance) =
+            directed_stake_permissionless_ticket_min_balance
+        {
+            new_parameters.directed_stake_permissionless_ticket_min_balance =
+                directed_stake_permissionless_ticket_min_balance;
+        }
+
+        if let Some(reserve_liquidity_target_lamports) = reserve_liquidity_target_lamports {
+            new_parameters.reserve_liquidity_target_lamports = reserve_liquidity_target_lamports;
+        }
+
+        if let Some(reserve_liquidity_target_bps) = reserve_liquidity_target_bps {
+            new_parameters.reserve_liquidity_target_bps = reserve_liquidity_target_bps;
+        }
+
+        if let Some(auto_preferred_validators) = auto_preferred_validators {
+            new_parameters.auto_preferred_validators = auto_preferred_validators as u8;
+        }
+
+        if let Some(cycle_reports_enabled) = cycle_reports_enabled {
+            new_parameters.cycle_reports_enabled = cycle_reports_enabled as u8;
+        }
+
+        if let Some(instant_unstake_max_fraction_bps) = instant_unstake_max_fraction_bps {
+            new_parameters.instant_unstake_max_fraction_bps = instant_unstake_max_fraction_bps;
+        }
+
+        if let Some(score_warning_filters) = score_warning_filters {
+            new_parameters.score_warning_filters = score_warning_filters;
+        }
+
+        if let Some(validator_exit_cooldown_epochs) = validator_exit_cooldown_epochs {
+            new_parameters.validator_exit_cooldown_epochs = validator_exit_cooldown_epochs;
+        }
+
+        if let Some(commission_pledge_cooldown_epochs) = commission_pledge_cooldown_epochs {
+            new_parameters.commission_pledge_cooldown_epochs = commission_pledge_cooldown_epochs;
+        }
+
+        if let Some(minimum_rebalance_lamports) = minimum_rebalance_lamports {
+            new_parameters.minimum_rebalance_lamports = minimum_rebalance_lamports;
+        }
+
+        if let Some(unstake_carryover_ceiling_bps) = unstake_carryover_ceiling_bps {
+            new_parameters.unstake_carryover_ceiling_bps = unstake_carryover_ceiling_bps;
+        }
+
         // Validation will throw an error if any of the parameters are invalid
         new_parameters.validate(current_epoch, slots_per_epoch)?;
 
@@ -549,6 +865,10 @@ impl Parameters {
             return Err(StewardError::InvalidParameterValue.into());
         }
 
+        if self.unstake_carryover_ceiling_bps > BASIS_POINTS_MAX {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
         if !(0. ..=EPOCH_PROGRESS_MAX).contains(&self.instant_unstake_epoch_progress) {
             return Err(StewardError::InvalidParameterValue.into());
         }
@@ -597,6 +917,46 @@ impl Parameters {
             return Err(StewardError::InvalidParameterValue.into());
         }
 
+        if self.circuit_breaker_instant_unstake_count as usize > MAX_VALIDATORS {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.circuit_breaker_unstake_bps > BASIS_POINTS_MAX {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.directed_stake_sample_interval_slots > 0
+            && (self.directed_stake_sample_interval_slots > slots_per_epoch
+                || slots_per_epoch.div_ceil(self.directed_stake_sample_interval_slots)
+                    > MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH)
+        {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.directed_stake_target_decay_bps > BASIS_POINTS_MAX {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.reserve_liquidity_target_bps > BASIS_POINTS_MAX {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.auto_preferred_validators > 1 {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.cycle_reports_enabled > 1 {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.instant_unstake_max_fraction_bps > BASIS_POINTS_MAX {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
+        if self.score_warning_filters & !SCORE_WARNING_FILTERS_ALLOWED != 0 {
+            return Err(StewardError::InvalidParameterValue.into());
+        }
+
         Ok(())
     }
 }
@@ -696,12 +1056,45 @@ mod tests {
             undirected_stake_ceiling_lamports: (10_000_000u64 * 1_000_000_000u64).to_le_bytes(),
             jito_bam_minimum_epochs: 10,
             jito_bam_window_epochs: 10,
+            circuit_breaker_instant_unstake_count: 0,
+            circuit_breaker_unstake_bps: 0,
             _padding_0: [0; 4],
-            _padding_1: [0; 28],
+            _padding_3: [0; 2],
+            directed_stake_sample_interval_slots: 0,
+            directed_stake_target_expiry_epochs: 0,
+            directed_stake_target_decay_bps: 0,
+            directed_stake_permissionless_ticket_min_balance: 0,
+            reserve_liquidity_target_lamports: 0,
+            reserve_liquidity_target_bps: 0,
+            instant_unstake_max_fraction_bps: 0,
+            score_warning_filters: 0,
+            auto_preferred_validators: 0,
+            cycle_reports_enabled: 0,
+            validator_exit_cooldown_epochs: 0,
+            commission_pledge_cooldown_epochs: 0,
+            minimum_rebalance_lamports: 0,
+            unstake_carryover_ceiling_bps: 0,
+            _padding_5: [0; 6],
+            _padding_1: [0; 18],
             _padding_2: [0; 6],
         }
     }
 
+    #[test]
+    fn test_reserve_liquidity_target() {
+        let mut params = valid_parameters();
+        assert_eq!(params.reserve_liquidity_target(1_000_000), 0);
+
+        // The larger of the absolute and relative targets is used
+        params.reserve_liquidity_target_lamports = 5_000;
+        params.reserve_liquidity_target_bps = 100;
+        assert_eq!(params.reserve_liquidity_target(1_000_000), 10_000);
+        assert_eq!(params.reserve_liquidity_target(100_000), 5_000);
+
+        params.reserve_liquidity_target_bps = 10_001;
+        assert!(params.validate(CURRENT_EPOCH, SLOTS_PER_EPOCH).is_err());
+    }
+
     #[test]
     fn test_jito_bam_window_exceeds_window_max() {
         {
@@ -0,0 +1,199 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{delegation::RebalanceType, directed_delegation, utils::U8Bool};
+
+/// Number of stake moves kept per validator. Older entries are overwritten
+pub const REBALANCE_JOURNAL_LENGTH: usize = 64;
+
+/// `RebalanceJournalEntry::reason` tags
+pub const REBALANCE_REASON_SCORING: u8 = 0;
+pub const REBALANCE_REASON_INSTANT_UNSTAKE: u8 = 1;
+pub const REBALANCE_REASON_STAKE_DEPOSIT: u8 = 2;
+pub const REBALANCE_REASON_DIRECTED: u8 = 3;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[zero_copy]
+pub struct RebalanceJournalEntry {
+    /// Lamports moved
+    pub lamports: u64,
+
+    /// Epoch the move was applied in
+    pub epoch: u16,
+
+    /// Whether stake was added to the validator, or removed from it
+    pub increase: U8Bool,
+
+    /// One of the `REBALANCE_REASON_*` tags
+    pub reason: u8,
+
+    pub _padding0: [u8; 4],
+}
+
+/// Ring buffer of the stake increases and decreases applied to a validator by `rebalance` and
+/// `rebalance_directed`, which both require it to exist while the vote account is open.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct RebalanceJournal {
+    /// Steward config this journal belongs to
+    pub config: Pubkey,
+
+    pub vote_account: Pubkey,
+
+    /// Total number of entries ever recorded. The next entry is written at
+    /// `num_recorded % REBALANCE_JOURNAL_LENGTH`
+    pub num_recorded: u64,
+
+    pub entries: [RebalanceJournalEntry; REBALANCE_JOURNAL_LENGTH],
+
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl RebalanceJournal {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"rebalance_journal";
+
+    /// Records a move of `lamports`, ignoring empty moves
+    pub fn record(&mut self, epoch: u64, increase: bool, reason: u8, lamports: u64) {
+        if lamports == 0 {
+            return;
+        }
+        let index = (self.num_recorded % REBALANCE_JOURNAL_LENGTH as u64) as usize;
+        self.entries[index] = RebalanceJournalEntry {
+            lamports,
+            epoch: epoch as u16,
+            increase: U8Bool::from(increase),
+            reason,
+            _padding0: [0; 4],
+        };
+        self.num_recorded = self.num_recorded.saturating_add(1);
+    }
+
+    /// Records an undirected rebalance. Increases are tagged as scoring, and each decrease
+    /// component as its own entry
+    pub fn record_rebalance(&mut self, epoch: u64, rebalance_type: &RebalanceType) {
+        match rebalance_type {
+            RebalanceType::Increase(lamports) => {
+                self.record(epoch, true, REBALANCE_REASON_SCORING, *lamports)
+            }
+            RebalanceType::Decrease(decrease_components) => {
+                for (reason, lamports) in [
+                    (
+                        REBALANCE_REASON_SCORING,
+                        decrease_components.scoring_unstake_lamports,
+                    ),
+                    (
+                        REBALANCE_REASON_INSTANT_UNSTAKE,
+                        decrease_components.instant_unstake_lamports,
+                    ),
+                    (
+                        REBALANCE_REASON_STAKE_DEPOSIT,
+                        decrease_components.stake_deposit_unstake_lamports,
+                    ),
+                    (
+                        REBALANCE_REASON_DIRECTED,
+                        decrease_components.directed_unstake_lamports,
+                    ),
+                ] {
+                    self.record(epoch, false, reason, lamports);
+                }
+            }
+            RebalanceType::None => {}
+        }
+    }
+
+    /// Records a directed rebalance
+    pub fn record_directed_rebalance(
+        &mut self,
+        epoch: u64,
+        rebalance_type: &directed_delegation::RebalanceType,
+    ) {
+        match rebalance_type {
+            directed_delegation::RebalanceType::Increase(lamports) => {
+                self.record(epoch, true, REBALANCE_REASON_DIRECTED, *lamports)
+            }
+            directed_delegation::RebalanceType::Decrease(decrease_components) => self.record(
+                epoch,
+                false,
+                REBALANCE_REASON_DIRECTED,
+                decrease_components.directed_unstake_lamports,
+            ),
+            directed_delegation::RebalanceType::None => {}
+        }
+    }
+
+    /// Recorded entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &RebalanceJournalEntry> {
+        let len = REBALANCE_JOURNAL_LENGTH as u64;
+        let start = self.num_recorded.saturating_sub(len);
+        (start..self.num_recorded).map(move |i| &self.entries[(i % len) as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::DecreaseComponents;
+
+    #[test]
+    fn test_record_rebalance() {
+        let mut journal: Box<RebalanceJournal> = Box::new(bytemuck::Zeroable::zeroed());
+
+        journal.record_rebalance(10, &RebalanceType::Increase(100));
+        journal.record_rebalance(10, &RebalanceType::None);
+        journal.record_rebalance(
+            11,
+            &RebalanceType::Decrease(DecreaseComponents {
+                scoring_unstake_lamports: 5,
+                instant_unstake_lamports: 0,
+                stake_deposit_unstake_lamports: 7,
+                total_unstake_lamports: 12,
+                directed_unstake_lamports: 0,
+            }),
+        );
+
+        let entries = journal
+            .entries()
+            .map(|entry| {
+                (
+                    entry.epoch,
+                    bool::from(entry.increase),
+                    entry.reason,
+                    entry.lamports,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (10, true, REBALANCE_REASON_SCORING, 100),
+                (11, false, REBALANCE_REASON_SCORING, 5),
+                (11, false, REBALANCE_REASON_STAKE_DEPOSIT, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_wraps() {
+        let mut journal: Box<RebalanceJournal> = Box::new(bytemuck::Zeroable::zeroed());
+
+        for lamports in 1..=(REBALANCE_JOURNAL_LENGTH as u64 + 2) {
+            journal.record(12, true, REBALANCE_REASON_DIRECTED, lamports);
+        }
+
+        // The two oldest entries were overwritten
+        let lamports = journal
+            .entries()
+            .map(|entry| entry.lamports)
+            .collect::<Vec<_>>();
+        assert_eq!(lamports.len(), REBALANCE_JOURNAL_LENGTH);
+        assert_eq!(lamports[0], 3);
+        assert_eq!(
+            *lamports.last().unwrap(),
+            REBALANCE_JOURNAL_LENGTH as u64 + 2
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{LargeBitMask, LARGE_BITMASK_INDEXES};
+
+/// Outcome of checking a scored validator against its warnings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreWarningStatus {
+    /// Filters apply as usual
+    None,
+    /// First failure of a warning filter, the validator keeps its score for the rest of the cycle
+    Warned,
+    /// Already warned this cycle, the validator keeps its score
+    InGrace,
+}
+
+/// Validators warned for failing a binary filter in `Parameters::score_warning_filters`, indexed by
+/// `ValidatorHistory` index so they are unaffected by validator list changes.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct ScoreWarnings {
+    /// Steward config these warnings belong to
+    pub config: Pubkey,
+
+    /// `next_cycle_epoch` of the cycle `in_grace` belongs to
+    pub cycle_end_epoch: u64,
+
+    /// Validators that have been warned and have failed a warning filter in every cycle since
+    pub warned: LargeBitMask,
+
+    /// Validators warned in the current cycle, which keep their score until it ends
+    pub in_grace: LargeBitMask,
+
+    /// Warning filters each validator in `in_grace` was warned for, the only checks it is spared
+    pub warned_filters: [u16; LARGE_BITMASK_INDEXES],
+
+    pub bump: u8,
+
+    pub _padding0: [u8; 7],
+}
+
+impl ScoreWarnings {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"score_warnings";
+
+    /// Updates the warning of the validator at `validator_history_index` scored in the cycle ending at
+    /// `cycle_end_epoch`, given the `failed_filters` flags of its score and the `warning_filters` parameter.
+    /// Grace is only given when every failed filter is a warning filter. Passing all warning filters clears the warning.
+    pub fn check(
+        &mut self,
+        cycle_end_epoch: u64,
+        validator_history_index: usize,
+        failed_filters: u16,
+        warning_filters: u16,
+    ) -> Result<ScoreWarningStatus> {
+        if self.cycle_end_epoch != cycle_end_epoch {
+            self.cycle_end_epoch = cycle_end_epoch;
+            self.in_grace = LargeBitMask::default();
+        }
+
+        if failed_filters & warning_filters == 0 {
+            self.warned.set(validator_history_index, false)?;
+            self.in_grace.set(validator_history_index, false)?;
+            self.warned_filters[validator_history_index] = 0;
+            return Ok(ScoreWarningStatus::None);
+        }
+
+        if failed_filters & !warning_filters != 0 {
+            return Ok(ScoreWarningStatus::None);
+        }
+
+        if self.in_grace.get(validator_history_index)? {
+            return Ok(ScoreWarningStatus::InGrace);
+        }
+
+        if self.warned.get(validator_history_index)? {
+            return Ok(ScoreWarningStatus::None);
+        }
+
+        self.warned.set(validator_history_index, true)?;
+        self.in_grace.set(validator_history_index, true)?;
+        self.warned_filters[validator_history_index] = failed_filters;
+        Ok(ScoreWarningStatus::Warned)
+    }
+
+    /// Whether the validator at `validator_history_index` is in its grace period in the cycle ending at `cycle_end_epoch`
+    pub fn is_in_grace(
+        &self,
+        cycle_end_epoch: u64,
+        validator_history_index: usize,
+    ) -> Result<bool> {
+        if self.cycle_end_epoch != cycle_end_epoch {
+            return Ok(false);
+        }
+        self.in_grace.get(validator_history_index)
+    }
+
+    /// Filters the validator at `validator_history_index` was warned for, if it is in its grace period in the
+    /// cycle ending at `cycle_end_epoch`, otherwise 0
+    pub fn grace_filters(
+        &self,
+        cycle_end_epoch: u64,
+        validator_history_index: usize,
+    ) -> Result<u16> {
+        if !self.is_in_grace(cycle_end_epoch, validator_history_index)? {
+            return Ok(0);
+        }
+        Ok(self.warned_filters[validator_history_index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score::{
+        SCORE_FILTER_BLACKLIST as BLACKLIST, SCORE_FILTER_COMMISSION as COMMISSION,
+    };
+
+    #[test]
+    fn test_check() {
+        let mut warnings: Box<ScoreWarnings> = Box::new(bytemuck::Zeroable::zeroed());
+
+        // First failure is warned, and stays in grace for the rest of the cycle
+        assert_eq!(
+            warnings.check(10, 3, COMMISSION, COMMISSION).unwrap(),
+            ScoreWarningStatus::Warned
+        );
+        assert_eq!(
+            warnings.check(10, 3, COMMISSION, COMMISSION).unwrap(),
+            ScoreWarningStatus::InGrace
+        );
+
+        // Still failing next cycle, no more grace
+        assert_eq!(
+            warnings.check(20, 3, COMMISSION, COMMISSION).unwrap(),
+            ScoreWarningStatus::None
+        );
+        assert_eq!(
+            warnings.check(30, 3, COMMISSION, COMMISSION).unwrap(),
+            ScoreWarningStatus::None
+        );
+
+        // Passing clears the warning, so a later failure is warned again
+        assert_eq!(
+            warnings.check(40, 3, 0, COMMISSION).unwrap(),
+            ScoreWarningStatus::None
+        );
+        assert_eq!(
+            warnings.check(50, 3, COMMISSION, COMMISSION).unwrap(),
+            ScoreWarningStatus::Warned
+        );
+
+        // Failing a filter without warnings is not warned
+        assert_eq!(
+            warnings
+                .check(50, 4, COMMISSION | BLACKLIST, COMMISSION)
+                .unwrap(),
+            ScoreWarningStatus::None
+        );
+        assert!(!warnings.warned.get(4).unwrap());
+
+        // Grace only applies in the cycle the validator was warned in
+        assert!(warnings.is_in_grace(50, 3).unwrap());
+        assert!(!warnings.is_in_grace(50, 4).unwrap());
+        assert!(!warnings.is_in_grace(60, 3).unwrap());
+
+        // Grace only covers the filters the validator was warned for
+        assert_eq!(warnings.grace_filters(50, 3).unwrap(), COMMISSION);
+        assert_eq!(warnings.grace_filters(50, 4).unwrap(), 0);
+        assert_eq!(warnings.grace_filters(60, 3).unwrap(), 0);
+    }
+}
@@ -4,17 +4,23 @@ use std::fmt::Display;
 use crate::{
     bitmask::BitMask,
     constants::{
-        LAMPORT_BALANCE_DEFAULT, MAX_VALIDATORS, SORTED_INDEX_DEFAULT, TVC_ACTIVATION_EPOCH,
+        BASIS_POINTS_MAX, LAMPORT_BALANCE_DEFAULT, MAX_VALIDATORS, SORTED_INDEX_DEFAULT,
+        TVC_ACTIVATION_EPOCH,
     },
     delegation::{
-        decrease_stake_calculation, increase_stake_calculation, RebalanceType, UnstakeState,
+        decrease_stake_calculation, increase_stake_calculation, instant_unstake_fraction_bps,
+        skip_dust_rebalance, RebalanceType, UnstakeState,
     },
     errors::StewardError,
-    events::{DecreaseComponents, StateTransition},
+    events::{CircuitBreakerEvent, DecreaseComponents, StateTransition},
     score::{
-        instant_unstake_validator, validator_score, InstantUnstakeComponentsV3, ScoreComponentsV5,
+        instant_unstake_validator, validator_score, InstantUnstakeComponentsV4, ScoreComponentsV5,
+        COMMISSION_PLEDGE_SCORE_BIT,
+    },
+    state::{
+        directed_stake::DirectedStakeMeta, CircuitBreakerBaseline, CommissionPledge,
+        InstantUnstakeFractions, ScoreWarningStatus, ScoreWarnings, UnstakeCarryover,
     },
-    state::directed_stake::DirectedStakeMeta,
     utils::{epoch_progress, get_target_lamports},
     Config, Parameters,
 };
@@ -192,7 +198,9 @@ pub struct StewardStateV2 {
 
     /// Number of validators added to the pool in the current cycle
     pub validators_added: u16,
-    pub _padding0: [u8; 2],
+
+    /// Number of validators removed from the pool in the current cycle
+    pub validators_removed: u16,
 
     /// Raw score without binary filters applied. Used as secondary priority, to determine order for unstaking.
     pub raw_scores: [u64; MAX_VALIDATORS],
@@ -250,6 +258,10 @@ impl Delegation {
     }
 }
 
+/// Delegation of a validator added to the cycle during Rebalance, until the next ComputeInstantUnstake
+/// has checked it. Targets no stake, like any delegation with a zero numerator.
+const PENDING_DELEGATION: Delegation = Delegation::new(0, 0);
+
 impl AnchorSerialize for StewardStateEnum {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         (*self as u64).serialize(writer)
@@ -339,6 +351,10 @@ pub const REBALANCE_DIRECTED_COMPLETE: u32 = 1 << 7;
 /// state transition layer that it needs to be reset to the IDLE state
 /// this flag is set in in epoch_maintenance and unset in the IDLE state transition
 pub const RESET_TO_IDLE: u32 = 1 << 16;
+/// Set when a validator added mid-cycle is scored during Rebalance, after the instant unstake check for
+/// the epoch ran without it. Its delegation stays zero until the next ComputeInstantUnstake completes,
+/// when it gets its share. Unset in the ComputeInstantUnstake and new cycle transitions
+pub const RECOMPUTE_DELEGATIONS: u32 = 1 << 17;
 // BITS 24-31 RESERVED FOR FUTURE USE
 
 impl StewardStateV2 {
@@ -376,7 +392,9 @@ impl StewardStateV2 {
                 params.instant_unstake_epoch_progress,
                 params.compute_score_epoch_progress,
             ),
-            StewardStateEnum::ComputeInstantUnstake => self.transition_compute_instant_unstake(),
+            StewardStateEnum::ComputeInstantUnstake => {
+                self.transition_compute_instant_unstake(params)
+            }
             StewardStateEnum::Rebalance => self.transition_rebalance(),
             StewardStateEnum::RebalanceDirected => self
                 .transition_rebalance_directed(epoch_progress, params.compute_score_epoch_progress),
@@ -413,6 +431,7 @@ impl StewardStateV2 {
         if current_epoch >= self.next_cycle_epoch {
             self.unset_flag(COMPUTE_SCORE);
             self.unset_flag(COMPUTE_DELEGATIONS);
+            self.unset_flag(RECOMPUTE_DELEGATIONS);
             self.instant_unstake = BitMask::default();
             self.progress = BitMask::default();
         }
@@ -445,11 +464,23 @@ impl StewardStateV2 {
     }
 
     #[inline]
-    fn transition_compute_instant_unstake(&mut self) -> Result<()> {
+    fn transition_compute_instant_unstake(&mut self, params: &Parameters) -> Result<()> {
         if self.progress.is_complete(self.num_pool_validators)? {
             self.state_tag = StewardStateEnum::Rebalance;
             self.progress = BitMask::default();
             self.set_flag(COMPUTE_INSTANT_UNSTAKES);
+
+            // Validators added during the last Rebalance have now been checked for instant unstake
+            if self.has_flag(RECOMPUTE_DELEGATIONS) {
+                self.unset_flag(RECOMPUTE_DELEGATIONS);
+                let added_indices: Vec<usize> = (0..self.num_pool_validators as usize)
+                    .filter(|&index| self.delegations[index] == PENDING_DELEGATION)
+                    .collect();
+                for &index in added_indices.iter() {
+                    self.delegations[index] = Delegation::default();
+                }
+                self.delegate_added_validators(&added_indices, params)?;
+            }
         }
         Ok(())
     }
@@ -552,6 +583,10 @@ impl StewardStateV2 {
                 .checked_sub(1)
                 .ok_or(StewardError::ArithmeticError)?;
         }
+        self.validators_removed = self
+            .validators_removed
+            .checked_add(1)
+            .ok_or(StewardError::ArithmeticError)?;
 
         // Refresh range bounds after decrement
         let num_pool_validators = self.num_pool_validators as usize;
@@ -639,13 +674,16 @@ impl StewardStateV2 {
     }
 
     /// Mark a validator for removal from the pool - this happens right after
-    /// `remove_validator_from_pool` has been called on the stake pool
+    /// `remove_validator_from_pool` has been called on the stake pool, or when a validator requests an exit
     /// This is cleaned up in the next epoch
     pub fn mark_validator_for_removal(&mut self, index: usize) -> Result<()> {
         self.validators_to_remove.set(index, true)
     }
 
+    /// Mark a validator for removal within the current epoch. Clears the regular removal mark
+    /// left by `request_validator_exit`, so the validator is only counted once
     pub fn mark_validator_for_immediate_removal(&mut self, index: usize) -> Result<()> {
+        self.validators_to_remove.set(index, false)?;
         self.validators_for_immediate_removal.set(index, true)
     }
 
@@ -663,6 +701,7 @@ impl StewardStateV2 {
     /// Computes score for a validator for the current epoch, stores score, and yield score component.
     /// Inserts this validator's index into sorted_score_indices and sorted_yield_score_indices, sorted by
     /// score and yield score respectively, descending.
+    /// With `score_warnings`, a validator warned for failing a warning filter keeps its raw score this cycle.
     ///
     /// Mutates: scores, yield_scores, sorted_score_indices, sorted_yield_score_indices, progress
     #[allow(clippy::too_many_arguments)]
@@ -675,6 +714,8 @@ impl StewardStateV2 {
         cluster: &ClusterHistory,
         config: &Config,
         num_pool_validators: u64,
+        score_warnings: Option<&mut ScoreWarnings>,
+        commission_pledge: Option<&CommissionPledge>,
     ) -> Result<Option<ScoreComponentsV5>> {
         if matches!(self.state_tag, StewardStateEnum::ComputeScores) {
             let current_epoch = clock.epoch;
@@ -705,6 +746,7 @@ impl StewardStateV2 {
                 );
                 self.num_pool_validators = num_pool_validators;
                 self.validators_added = 0;
+                self.validators_removed = 0;
             }
             // Skip scoring if already processed
             if self.progress.get(index)? {
@@ -739,30 +781,22 @@ impl StewardStateV2 {
                 return Ok(None);
             }
 
-            // Check that latest_update_slot is within the current epoch to guarantee previous epoch data is complete
-            let last_update_slot = validator
-                .history
-                .vote_account_last_update_slot_latest()
-                .ok_or(StewardError::VoteHistoryNotRecentEnough)?;
-            if last_update_slot < epoch_schedule.get_first_slot_in_epoch(current_epoch) {
-                return Err(StewardError::VoteHistoryNotRecentEnough.into());
-            }
-
-            // Check that cluster history is within current epoch to guarantee previous epoch data is complete
-            if cluster.cluster_history_last_update_slot
-                < epoch_schedule.get_first_slot_in_epoch(current_epoch)
-            {
-                return Err(StewardError::ClusterHistoryNotRecentEnough.into());
-            }
-
             // Calculate score with binary filters applied
-            let score_components = validator_score(
+            let mut score_components = validator_score_with_recent_history(
+                clock,
+                epoch_schedule,
                 validator,
                 cluster,
                 config,
-                current_epoch as u16,
-                TVC_ACTIVATION_EPOCH,
             )?;
+            self.apply_commission_pledge(
+                &mut score_components,
+                commission_pledge,
+                clock.epoch,
+                config,
+            );
+
+            self.apply_score_warning(&mut score_components, validator, score_warnings, config)?;
 
             // Store both raw score and final score
             self.raw_scores[index] = score_components.raw_score;
@@ -803,21 +837,7 @@ impl StewardStateV2 {
                 return Err(StewardError::InvalidState.into());
             }
 
-            let validators_to_delegate = select_validators_to_delegate(
-                &self.scores[..self.num_pool_validators as usize],
-                &self.sorted_score_indices[..self.num_pool_validators as usize],
-                config.parameters.num_delegation_validators as usize,
-            );
-
-            let num_delegation_validators = validators_to_delegate.len();
-
-            // Assign equal share of pool to each validator
-            for index in validators_to_delegate {
-                self.delegations[index as usize] = Delegation {
-                    numerator: 1,
-                    denominator: num_delegation_validators as u32,
-                };
-            }
+            self.assign_delegations(&config.parameters);
 
             self.set_flag(COMPUTE_DELEGATIONS);
 
@@ -826,11 +846,216 @@ impl StewardStateV2 {
         Err(StewardError::InvalidState.into())
     }
 
+    /// Assigns an equal share of the pool to the top `num_delegation_validators` validators by score
+    fn assign_delegations(&mut self, params: &Parameters) {
+        let validators_to_delegate = select_validators_to_delegate(
+            &self.scores[..self.num_pool_validators as usize],
+            &self.sorted_score_indices[..self.num_pool_validators as usize],
+            params.num_delegation_validators as usize,
+        );
+
+        let num_delegation_validators = validators_to_delegate.len();
+
+        // Assign equal share of pool to each validator
+        for index in validators_to_delegate {
+            self.delegations[index as usize] = Delegation {
+                numerator: 1,
+                denominator: num_delegation_validators as u32,
+            };
+        }
+    }
+
+    /// Gives the validators at `added_indices`, added to the cycle after delegations were computed, an equal
+    /// share if they score in the top `num_delegation_validators`. Validators they push out of the top lose
+    /// their delegation, while validators whose delegation was zeroed by instant unstake this cycle keep none.
+    fn delegate_added_validators(
+        &mut self,
+        added_indices: &[usize],
+        params: &Parameters,
+    ) -> Result<()> {
+        let validators_to_delegate = select_validators_to_delegate(
+            &self.scores[..self.num_pool_validators as usize],
+            &self.sorted_score_indices[..self.num_pool_validators as usize],
+            params.num_delegation_validators as usize,
+        );
+
+        let mut in_top = BitMask::default();
+        for &index in validators_to_delegate.iter() {
+            in_top.set(index as usize, true)?;
+        }
+        for index in 0..self.num_pool_validators as usize {
+            if self.delegations[index].numerator > 0 && !in_top.get(index)? {
+                self.delegations[index] = Delegation::default();
+            }
+        }
+
+        let delegated_indices: Vec<usize> = validators_to_delegate
+            .into_iter()
+            .map(|index| index as usize)
+            .filter(|index| self.delegations[*index].numerator > 0 || added_indices.contains(index))
+            .collect();
+        for &index in delegated_indices.iter() {
+            self.delegations[index] = Delegation {
+                numerator: 1,
+                denominator: delegated_indices.len() as u32,
+            };
+        }
+        Ok(())
+    }
+
+    /// One instruction per validator, in validator list order.
+    /// Scores a validator added to the pool after ComputeScores finished for the current cycle, and
+    /// brings it into the cycle by moving it from `validators_added` into `num_pool_validators`.
+    /// Only the validator at index `num_pool_validators` can be scored, so that the validators in the
+    /// cycle remain the contiguous range `[0, num_pool_validators)`.
+    ///
+    /// Score warnings apply as in `compute_score`.
+    ///
+    /// If delegations were already computed this cycle, a newcomer scoring in the top
+    /// `num_delegation_validators` gets its share without waiting for the next cycle. Validators
+    /// already instant unstaked this cycle keep no delegation.
+    /// During Rebalance the instant unstake check for the epoch has already run without the newcomer,
+    /// so its delegation stays zero, marked as pending, and `RECOMPUTE_DELEGATIONS` defers its share
+    /// until the next ComputeInstantUnstake has checked it.
+    ///
+    /// Mutates: scores, raw_scores, sorted_score_indices, sorted_raw_score_indices, delegations,
+    /// num_pool_validators, validators_added, status_flags
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_score_added_validator(
+        &mut self,
+        clock: &Clock,
+        epoch_schedule: &EpochSchedule,
+        validator: &ValidatorHistory,
+        index: usize,
+        cluster: &ClusterHistory,
+        config: &Config,
+        score_warnings: Option<&mut ScoreWarnings>,
+        commission_pledge: Option<&CommissionPledge>,
+    ) -> Result<ScoreComponentsV5> {
+        // Scores from the regular ComputeScores run must be complete, and the cycle must not be over
+        require!(
+            self.has_flag(COMPUTE_SCORE)
+                && !matches!(
+                    self.state_tag,
+                    StewardStateEnum::ComputeScores | StewardStateEnum::RebalanceDirected
+                )
+                && clock.epoch < self.next_cycle_epoch,
+            StewardError::InvalidState
+        );
+
+        require!(
+            self.validators_added > 0 && index == self.num_pool_validators as usize,
+            StewardError::ValidatorIndexOutOfBounds
+        );
+
+        require!(
+            !self.validators_to_remove.get(index)?
+                && !self.validators_for_immediate_removal.get(index)?,
+            StewardError::ValidatorsHaveNotBeenRemoved
+        );
+
+        let mut score_components =
+            validator_score_with_recent_history(clock, epoch_schedule, validator, cluster, config)?;
+        self.apply_commission_pledge(
+            &mut score_components,
+            commission_pledge,
+            clock.epoch,
+            config,
+        );
+        self.apply_score_warning(&mut score_components, validator, score_warnings, config)?;
+
+        self.raw_scores[index] = score_components.raw_score;
+        self.scores[index] = score_components.score;
+
+        // All validators in the cycle have been scored, so the sorted arrays hold num_pool_validators entries
+        let num_scores_calculated = self.num_pool_validators as usize;
+        insert_sorted_index(
+            &mut self.sorted_score_indices,
+            &self.scores,
+            index as u16,
+            self.scores[index],
+            num_scores_calculated,
+        )?;
+        insert_sorted_index(
+            &mut self.sorted_raw_score_indices,
+            &self.raw_scores,
+            index as u16,
+            self.raw_scores[index],
+            num_scores_calculated,
+        )?;
+
+        self.num_pool_validators = self
+            .num_pool_validators
+            .checked_add(1)
+            .ok_or(StewardError::ArithmeticError)?;
+        self.validators_added = self
+            .validators_added
+            .checked_sub(1)
+            .ok_or(StewardError::ArithmeticError)?;
+
+        if self.has_flag(COMPUTE_DELEGATIONS) {
+            if matches!(self.state_tag, StewardStateEnum::Rebalance) {
+                self.delegations[index] = PENDING_DELEGATION;
+                self.set_flag(RECOMPUTE_DELEGATIONS);
+            } else {
+                self.delegate_added_validators(&[index], &config.parameters)?;
+            }
+        }
+
+        Ok(score_components)
+    }
+
+    /// Updates the validator's warning, and gives warned validators their raw score for the rest of the cycle
+    fn apply_score_warning(
+        &self,
+        score_components: &mut ScoreComponentsV5,
+        validator: &ValidatorHistory,
+        score_warnings: Option<&mut ScoreWarnings>,
+        config: &Config,
+    ) -> Result<()> {
+        if let Some(score_warnings) = score_warnings {
+            let status = score_warnings.check(
+                self.next_cycle_epoch,
+                validator.index as usize,
+                score_components.failed_filters(),
+                config.parameters.score_warning_filters,
+            )?;
+            if status != ScoreWarningStatus::None {
+                score_components.score = score_components.raw_score;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets `COMMISSION_PLEDGE_SCORE_BIT` in the raw score, and in the score unless it is 0, if the
+    /// validator's pledge earns the tiebreaker for the next cycle
+    fn apply_commission_pledge(
+        &self,
+        score_components: &mut ScoreComponentsV5,
+        commission_pledge: Option<&CommissionPledge>,
+        epoch: u64,
+        config: &Config,
+    ) {
+        if let Some(commission_pledge) = commission_pledge {
+            if commission_pledge.counts_for_score(epoch, self.next_cycle_epoch, &config.parameters)
+            {
+                score_components.raw_score |= COMMISSION_PLEDGE_SCORE_BIT;
+                if score_components.score != 0 {
+                    score_components.score |= COMMISSION_PLEDGE_SCORE_BIT;
+                }
+            }
+        }
+    }
+
     /// One instruction per validator.
     /// Check a set of criteria that determine whether a validator should be kicked from the pool
     /// If so, set the validator.index bit in `instant_unstake` to true
+    /// With `score_warnings`, checks matching the filters a validator was warned for are skipped during its grace period.
+    /// Returns `InstantUnstakeCircuitBreakerTripped` without changing any state if marking the validator would
+    /// trip the instant unstake circuit breaker of `config`.
     ///
     /// Mutates: instant_unstake, progress
+    #[allow(clippy::too_many_arguments)]
     pub fn compute_instant_unstake(
         &mut self,
         clock: &Clock,
@@ -839,7 +1064,8 @@ impl StewardStateV2 {
         index: usize,
         cluster: &ClusterHistory,
         config: &Config,
-    ) -> Result<Option<InstantUnstakeComponentsV3>> {
+        score_warnings: Option<&ScoreWarnings>,
+    ) -> Result<Option<InstantUnstakeComponentsV4>> {
         if matches!(self.state_tag, StewardStateEnum::ComputeInstantUnstake) {
             if clock.epoch >= self.next_cycle_epoch {
                 return Err(StewardError::InvalidState.into());
@@ -886,6 +1112,15 @@ impl StewardStateV2 {
                 return Err(StewardError::ClusterHistoryNotRecentEnough.into());
             }
 
+            // Delegation continues through a warned validator's grace period, for the filters it was warned for
+            let skipped_filters = match score_warnings {
+                Some(score_warnings) => {
+                    score_warnings.grace_filters(self.next_cycle_epoch, validator.index as usize)?
+                        & config.parameters.score_warning_filters
+                }
+                None => 0,
+            };
+
             let instant_unstake_result = instant_unstake_validator(
                 validator,
                 cluster,
@@ -893,8 +1128,21 @@ impl StewardStateV2 {
                 first_slot,
                 clock.epoch as u16,
                 TVC_ACTIVATION_EPOCH,
+                skipped_filters,
             )?;
 
+            // Checked before the validator is marked, so the validator that would trip the circuit
+            // breaker is not unstaked
+            if instant_unstake_result.instant_unstake
+                && self.instant_unstake_circuit_breaker_tripped(
+                    &config.parameters,
+                    &config.circuit_breaker_baseline,
+                    1,
+                )
+            {
+                return Err(StewardError::InstantUnstakeCircuitBreakerTripped.into());
+            }
+
             self.instant_unstake
                 .set(index, instant_unstake_result.instant_unstake)?;
             self.progress.set(index, true)?;
@@ -1000,7 +1248,10 @@ impl StewardStateV2 {
     /// this validator should get more or less stake, and updates internal state. If the validator is being instant-unstaked,
     /// delegations are distributed to other eligible validators.
     /// stake_pool_lamports and reserve_lamports are the raw values from stake pool and reserve accounts, respectively, not adjusted for rent.
-    ///
+    /// Unstaking caps include the budget carried over from the previous cycle in `unstake_carryover`.
+    /// Returns `UnstakeCircuitBreakerTripped` without changing any state if a decrease would take the
+    /// lamports unstaked this cycle above the limit of `circuit_breaker_baseline`, which is taken from
+    /// the same `stake_pool_lamports` as the unstaking caps.
     ///
     /// Mutates: validator_lamport_balances delegations, unstake_total, stake_deposit_unstake_total, instant_unstake_total, progress
     #[allow(clippy::too_many_arguments)]
@@ -1016,6 +1267,9 @@ impl StewardStateV2 {
         minimum_delegation: u64,
         stake_rent: u64,
         parameters: &Parameters,
+        instant_unstake_fractions: Option<&InstantUnstakeFractions>,
+        unstake_carryover: &UnstakeCarryover,
+        circuit_breaker_baseline: Option<&CircuitBreakerBaseline>,
     ) -> Result<RebalanceType> {
         if matches!(self.state_tag, StewardStateEnum::Rebalance) {
             if current_epoch >= self.next_cycle_epoch {
@@ -1065,6 +1319,13 @@ impl StewardStateV2 {
                 )
                 .ok_or(StewardError::ArithmeticError)?;
 
+            let unstake_limit = match circuit_breaker_baseline {
+                Some(circuit_breaker_baseline) => {
+                    circuit_breaker_baseline.unstake_limit(parameters, stake_pool_lamports)?
+                }
+                None => None,
+            };
+
             let target_lamports =
                 get_target_lamports(&self.delegations[index], stake_pool_lamports)?;
 
@@ -1082,6 +1343,7 @@ impl StewardStateV2 {
             Otherwise, keep the internal balance the same to ensure we still see the stake deposit delta, until it can be unstaked.
             */
 
+            let previous_lamport_balance = self.validator_lamport_balances[index];
             self.validator_lamport_balances[index] = match (
                 current_total_lamports < self.validator_lamport_balances[index],
                 current_undirected_lamports < target_lamports,
@@ -1095,33 +1357,8 @@ impl StewardStateV2 {
             let rebalance = if target_lamports < current_undirected_lamports
                 || self.instant_unstake.get(index)?
             {
-                let scoring_unstake_cap: u64 = (stake_pool_lamports as u128)
-                    .checked_mul(parameters.scoring_unstake_cap_bps as u128)
-                    .and_then(|x| x.checked_div(10_000))
-                    .ok_or(StewardError::ArithmeticError)?
-                    .try_into()
-                    .map_err(|_| StewardError::ArithmeticCastError)?;
-                let instant_unstake_cap: u64 = (stake_pool_lamports as u128)
-                    .checked_mul(parameters.instant_unstake_cap_bps as u128)
-                    .and_then(|x| x.checked_div(10_000))
-                    .ok_or(StewardError::ArithmeticError)?
-                    .try_into()
-                    .map_err(|_| StewardError::ArithmeticCastError)?;
-                let stake_deposit_unstake_cap: u64 = (stake_pool_lamports as u128)
-                    .checked_mul(parameters.stake_deposit_unstake_cap_bps as u128)
-                    .and_then(|x| x.checked_div(10_000))
-                    .ok_or(StewardError::ArithmeticError)?
-                    .try_into()
-                    .map_err(|_| StewardError::ArithmeticCastError)?;
-
-                let unstake_state = UnstakeState {
-                    stake_deposit_unstake_total: self.stake_deposit_unstake_total,
-                    instant_unstake_total: self.instant_unstake_total,
-                    scoring_unstake_total: self.scoring_unstake_total,
-                    stake_deposit_unstake_cap,
-                    instant_unstake_cap,
-                    scoring_unstake_cap,
-                };
+                let unstake_state =
+                    self.unstake_state(parameters, stake_pool_lamports, unstake_carryover)?;
 
                 decrease_stake_calculation(
                     self,
@@ -1133,6 +1370,7 @@ impl StewardStateV2 {
                     validator_list,
                     minimum_delegation,
                     stake_rent,
+                    instant_unstake_fractions,
                 )?
             } else if current_undirected_lamports < target_lamports {
                 increase_stake_calculation(
@@ -1150,6 +1388,22 @@ impl StewardStateV2 {
                 RebalanceType::None
             };
 
+            let rebalance = skip_dust_rebalance(rebalance, parameters.minimum_rebalance_lamports);
+
+            // Checked before anything is unstaked, so the decrease that would trip the circuit
+            // breaker is never issued
+            if let RebalanceType::Decrease(decrease_components) = &rebalance {
+                let unstake_lamports = decrease_components
+                    .scoring_unstake_lamports
+                    .checked_add(decrease_components.instant_unstake_lamports)
+                    .and_then(|x| x.checked_add(decrease_components.stake_deposit_unstake_lamports))
+                    .ok_or(StewardError::ArithmeticError)?;
+                if self.unstake_circuit_breaker_tripped(unstake_limit, unstake_lamports)? {
+                    self.validator_lamport_balances[index] = previous_lamport_balance;
+                    return Err(StewardError::UnstakeCircuitBreakerTripped.into());
+                }
+            }
+
             // Update internal state based on rebalance
             match rebalance {
                 RebalanceType::Decrease(DecreaseComponents {
@@ -1179,7 +1433,17 @@ impl StewardStateV2 {
                         .checked_add(instant_unstake_lamports)
                         .ok_or(StewardError::ArithmeticError)?;
 
-                    if instant_unstake_lamports > 0 && self.delegations[index].numerator > 0 {
+                    // A graded instant unstake keeps the validator's delegation
+                    let full_instant_unstake = instant_unstake_fraction_bps(
+                        instant_unstake_fractions,
+                        validator_list,
+                        index,
+                    )? >= BASIS_POINTS_MAX;
+
+                    if instant_unstake_lamports > 0
+                        && full_instant_unstake
+                        && self.delegations[index].numerator > 0
+                    {
                         // Ensure this validator gets no more stake and distribute the delegation to the other eligible
                         // by lowering their denominator
                         for i in 0..index {
@@ -1218,6 +1482,137 @@ impl StewardStateV2 {
         }
         Err(StewardError::InvalidState.into())
     }
+
+    /// Unstake totals of this cycle and the caps they are limited by: each cap's share of
+    /// `stake_pool_lamports` plus the budget carried over from the previous cycle
+    pub fn unstake_state(
+        &self,
+        parameters: &Parameters,
+        stake_pool_lamports: u64,
+        unstake_carryover: &UnstakeCarryover,
+    ) -> Result<UnstakeState> {
+        let unstake_cap = |cap_bps: u32, carryover_lamports: u64| -> Result<u64> {
+            let cap: u64 = (stake_pool_lamports as u128)
+                .checked_mul(cap_bps as u128)
+                .and_then(|x| x.checked_div(10_000))
+                .ok_or(StewardError::ArithmeticError)?
+                .try_into()
+                .map_err(|_| StewardError::ArithmeticCastError)?;
+            Ok(cap.saturating_add(carryover_lamports))
+        };
+
+        Ok(UnstakeState {
+            stake_deposit_unstake_total: self.stake_deposit_unstake_total,
+            instant_unstake_total: self.instant_unstake_total,
+            scoring_unstake_total: self.scoring_unstake_total,
+            stake_deposit_unstake_cap: unstake_cap(
+                parameters.stake_deposit_unstake_cap_bps,
+                unstake_carryover.stake_deposit_carryover_lamports,
+            )?,
+            instant_unstake_cap: unstake_cap(
+                parameters.instant_unstake_cap_bps,
+                unstake_carryover.instant_carryover_lamports,
+            )?,
+            scoring_unstake_cap: unstake_cap(
+                parameters.scoring_unstake_cap_bps,
+                unstake_carryover.scoring_carryover_lamports,
+            )?,
+        })
+    }
+
+    /// Total lamports unstaked this cycle for scoring, instant unstake and stake deposits
+    pub fn unstake_total(&self) -> Result<u64> {
+        self.scoring_unstake_total
+            .checked_add(self.instant_unstake_total)
+            .and_then(|x| x.checked_add(self.stake_deposit_unstake_total))
+            .ok_or(StewardError::ArithmeticError.into())
+    }
+
+    /// Returns true if marking `additional_count` more validators for instant unstake would mark more
+    /// validators this cycle than `circuit_breaker_instant_unstake_count` allows, not counting those
+    /// acknowledged in `baseline`. Always false when the limit is 0.
+    pub fn instant_unstake_circuit_breaker_tripped(
+        &self,
+        parameters: &Parameters,
+        baseline: &CircuitBreakerBaseline,
+        additional_count: u64,
+    ) -> bool {
+        parameters.circuit_breaker_instant_unstake_count != 0
+            && (self.instant_unstake.count() as u64)
+                .saturating_add(additional_count)
+                .saturating_sub(baseline.instant_unstake_count)
+                > parameters.circuit_breaker_instant_unstake_count as u64
+    }
+
+    /// Returns true if unstaking `additional_unstake_lamports` on top of the lamports unstaked this
+    /// cycle would exceed `unstake_limit`, see [`CircuitBreakerBaseline::unstake_limit`]
+    pub fn unstake_circuit_breaker_tripped(
+        &self,
+        unstake_limit: Option<u64>,
+        additional_unstake_lamports: u64,
+    ) -> Result<bool> {
+        let Some(unstake_limit) = unstake_limit else {
+            return Ok(false);
+        };
+
+        let unstake_total = self
+            .unstake_total()?
+            .checked_add(additional_unstake_lamports)
+            .ok_or(StewardError::ArithmeticError)?;
+
+        Ok(unstake_total > unstake_limit)
+    }
+
+    pub fn circuit_breaker_event(
+        &self,
+        clock: &Clock,
+        parameters: &Parameters,
+    ) -> Result<CircuitBreakerEvent> {
+        Ok(CircuitBreakerEvent {
+            epoch: clock.epoch,
+            slot: clock.slot,
+            instant_unstake_count: self.instant_unstake.count() as u64,
+            unstake_total_lamports: self.unstake_total()?,
+            circuit_breaker_instant_unstake_count: parameters.circuit_breaker_instant_unstake_count,
+            circuit_breaker_unstake_bps: parameters.circuit_breaker_unstake_bps,
+        })
+    }
+}
+
+/// Computes the score of a validator, requiring validator and cluster history to have been
+/// updated in the current epoch so that the previous epoch's data is complete
+fn validator_score_with_recent_history(
+    clock: &Clock,
+    epoch_schedule: &EpochSchedule,
+    validator: &ValidatorHistory,
+    cluster: &ClusterHistory,
+    config: &Config,
+) -> Result<ScoreComponentsV5> {
+    let current_epoch = clock.epoch;
+
+    // Check that latest_update_slot is within the current epoch to guarantee previous epoch data is complete
+    let last_update_slot = validator
+        .history
+        .vote_account_last_update_slot_latest()
+        .ok_or(StewardError::VoteHistoryNotRecentEnough)?;
+    if last_update_slot < epoch_schedule.get_first_slot_in_epoch(current_epoch) {
+        return Err(StewardError::VoteHistoryNotRecentEnough.into());
+    }
+
+    // Check that cluster history is within current epoch to guarantee previous epoch data is complete
+    if cluster.cluster_history_last_update_slot
+        < epoch_schedule.get_first_slot_in_epoch(current_epoch)
+    {
+        return Err(StewardError::ClusterHistoryNotRecentEnough.into());
+    }
+
+    validator_score(
+        validator,
+        cluster,
+        config,
+        current_epoch as u16,
+        TVC_ACTIVATION_EPOCH,
+    )
 }
 
 /// Inserts index into sorted_indices at the correct position, shifting elements as needed. Sorted by score descending.
@@ -1301,7 +1696,7 @@ mod tests {
             stake_deposit_unstake_total: 0,
             status_flags: 0,
             validators_added: 0,
-            _padding0: [0; 2],
+            validators_removed: 0,
         }
     }
 
@@ -1470,4 +1865,80 @@ mod tests {
         assert_eq!(new_directed, 5_000_000);
         assert_eq!(new_total, 5_000_000);
     }
+
+    #[test]
+    fn test_instant_unstake_circuit_breaker() {
+        let mut state = default_state();
+        let mut parameters = Parameters::default();
+        let mut baseline = CircuitBreakerBaseline::default();
+
+        state.instant_unstake.set(0, true).unwrap();
+        state.instant_unstake.set(1, true).unwrap();
+
+        // Disabled when the limit is 0
+        assert!(!state.instant_unstake_circuit_breaker_tripped(&parameters, &baseline, 1));
+
+        // A third validator would go over the limit, so it is never marked
+        parameters.circuit_breaker_instant_unstake_count = 2;
+        assert!(!state.instant_unstake_circuit_breaker_tripped(&parameters, &baseline, 0));
+        assert!(state.instant_unstake_circuit_breaker_tripped(&parameters, &baseline, 1));
+
+        // Resuming acknowledges the validators flagged so far
+        baseline.set_resumed();
+        baseline.sync(&state).unwrap();
+        assert_eq!(baseline.instant_unstake_count, 2);
+        assert!(!state.instant_unstake_circuit_breaker_tripped(&parameters, &baseline, 1));
+    }
+
+    #[test]
+    fn test_unstake_circuit_breaker() {
+        let mut state = default_state();
+        let mut parameters = Parameters::default();
+        let mut baseline = CircuitBreakerBaseline::default();
+        let stake_pool_lamports = 1_000_000_000;
+
+        state.scoring_unstake_total = 40_000_000;
+        state.instant_unstake_total = 30_000_000;
+        state.stake_deposit_unstake_total = 30_000_000;
+        assert_eq!(state.unstake_total().unwrap(), 100_000_000);
+
+        // Disabled when the limit is 0
+        let unstake_limit = baseline
+            .unstake_limit(&parameters, stake_pool_lamports)
+            .unwrap();
+        assert_eq!(unstake_limit, None);
+        assert!(!state
+            .unstake_circuit_breaker_tripped(unstake_limit, u64::MAX / 2)
+            .unwrap());
+
+        // 10% of the pool moved, limit is 10%
+        parameters.circuit_breaker_unstake_bps = 1_000;
+        let unstake_limit = baseline
+            .unstake_limit(&parameters, stake_pool_lamports)
+            .unwrap();
+        assert_eq!(unstake_limit, Some(100_000_000));
+        assert!(!state
+            .unstake_circuit_breaker_tripped(unstake_limit, 0)
+            .unwrap());
+        assert!(state
+            .unstake_circuit_breaker_tripped(unstake_limit, 1)
+            .unwrap());
+
+        // Resuming acknowledges the lamports unstaked so far
+        baseline.set_resumed();
+        baseline.sync(&state).unwrap();
+        let unstake_limit = baseline
+            .unstake_limit(&parameters, stake_pool_lamports)
+            .unwrap();
+        assert_eq!(unstake_limit, Some(200_000_000));
+        assert!(!state
+            .unstake_circuit_breaker_tripped(unstake_limit, 100_000_000)
+            .unwrap());
+
+        // The baseline is cleared when the next cycle starts
+        state.next_cycle_epoch += 10;
+        baseline.sync(&state).unwrap();
+        assert_eq!(baseline.unstake_lamports, 0);
+        assert_eq!(baseline.cycle_end_epoch, state.next_cycle_epoch);
+    }
 }
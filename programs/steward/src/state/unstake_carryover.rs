@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{constants::BASIS_POINTS_MAX, delegation::UnstakeState, errors::StewardError};
+
+/// Scoring, instant and stake deposit unstake cap budget left unused in one cycle and carried
+/// into the next, when `unstake_carryover_ceiling_bps` is set
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq, Eq)]
+#[zero_copy]
+pub struct UnstakeCarryover {
+    /// `next_cycle_epoch` of the cycle the carryover lamports are added to
+    pub cycle_end_epoch: u64,
+
+    /// Lamports added to the scoring unstake cap this cycle
+    pub scoring_carryover_lamports: u64,
+
+    /// Lamports added to the instant unstake cap this cycle
+    pub instant_carryover_lamports: u64,
+
+    /// Lamports added to the stake deposit unstake cap this cycle
+    pub stake_deposit_carryover_lamports: u64,
+
+    /// Scoring unstake cap budget left after the last rebalance this cycle
+    pub scoring_unused_lamports: u64,
+
+    /// Instant unstake cap budget left after the last rebalance this cycle
+    pub instant_unused_lamports: u64,
+
+    /// Stake deposit unstake cap budget left after the last rebalance this cycle
+    pub stake_deposit_unused_lamports: u64,
+}
+
+impl UnstakeCarryover {
+    /// On the first rebalance of the cycle ending at `next_cycle_epoch`, carries the budget left unused
+    /// in the previous cycle into this one, up to `ceiling_bps` of `stake_pool_lamports` per cap.
+    /// Does nothing for the rest of the cycle.
+    pub fn start_cycle(
+        &mut self,
+        next_cycle_epoch: u64,
+        ceiling_bps: u16,
+        stake_pool_lamports: u64,
+    ) -> Result<()> {
+        if self.cycle_end_epoch == next_cycle_epoch {
+            return Ok(());
+        }
+
+        let ceiling: u64 = (stake_pool_lamports as u128)
+            .checked_mul(ceiling_bps as u128)
+            .and_then(|x| x.checked_div(BASIS_POINTS_MAX as u128))
+            .ok_or(StewardError::ArithmeticError)?
+            .try_into()
+            .map_err(|_| StewardError::ArithmeticCastError)?;
+
+        *self = Self {
+            cycle_end_epoch: next_cycle_epoch,
+            scoring_carryover_lamports: self.scoring_unused_lamports.min(ceiling),
+            instant_carryover_lamports: self.instant_unused_lamports.min(ceiling),
+            stake_deposit_carryover_lamports: self.stake_deposit_unused_lamports.min(ceiling),
+            ..Self::default()
+        };
+
+        Ok(())
+    }
+
+    /// Records the budget of each cap left in `unstake_state`, to be carried into the next cycle
+    pub fn record_unused(&mut self, unstake_state: &UnstakeState) {
+        self.scoring_unused_lamports = unstake_state
+            .scoring_unstake_cap
+            .saturating_sub(unstake_state.scoring_unstake_total);
+        self.instant_unused_lamports = unstake_state
+            .instant_unstake_cap
+            .saturating_sub(unstake_state.instant_unstake_total);
+        self.stake_deposit_unused_lamports = unstake_state
+            .stake_deposit_unstake_cap
+            .saturating_sub(unstake_state.stake_deposit_unstake_total);
+    }
+}
@@ -0,0 +1,32 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Created when a validator requests to exit the pool, and closed when it requests to rejoin
+#[derive(BorshSerialize, BorshDeserialize)]
+#[account(zero_copy)]
+pub struct ValidatorExit {
+    /// Steward config the validator exited
+    pub config: Pubkey,
+
+    pub vote_account: Pubkey,
+
+    /// Epoch the exit was requested
+    pub exit_epoch: u64,
+
+    /// First epoch `auto_add_validator_to_pool` may re-add the validator
+    pub rejoin_epoch: u64,
+
+    // 64 bytes reserved for future use
+    pub _padding0: [u8; 64],
+}
+
+impl ValidatorExit {
+    pub const SIZE: usize = 8 + size_of::<Self>();
+    pub const SEED: &'static [u8] = b"validator_exit";
+
+    pub fn is_blocked(&self, epoch: u64) -> bool {
+        epoch < self.rejoin_epoch
+    }
+}
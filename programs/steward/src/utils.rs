@@ -1,21 +1,36 @@
-use std::ops::Not;
+use std::{
+    cell::{Ref, RefMut},
+    ops::Not,
+};
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{clock::Epoch, program_pack::Pack, stake};
+use anchor_lang::solana_program::{
+    clock::Epoch,
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    program_pack::Pack,
+    stake,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    vote,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use spl_pod::{bytemuck::pod_from_bytes, primitives::PodU64};
 use spl_stake_pool::{
     big_vec::BigVec,
     state::{StakeStatus, ValidatorListHeader, ValidatorStakeInfo},
 };
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
 
 use crate::{
     constants::{
-        PUBKEY_SIZE, STAKE_STATUS_OFFSET, TRANSIENT_STAKE_SEED_LENGTH, TRANSIENT_STAKE_SEED_OFFSET,
-        U64_SIZE, VEC_SIZE_BYTES, VOTE_ADDRESS_OFFSET,
+        ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, PUBKEY_SIZE, STAKE_STATUS_OFFSET,
+        TRANSIENT_STAKE_SEED_LENGTH, TRANSIENT_STAKE_SEED_OFFSET, U64_SIZE, VEC_SIZE_BYTES,
+        VOTE_ADDRESS_OFFSET,
     },
     errors::StewardError,
-    Config, Delegation, StewardStateAccountV2, StewardStateEnum,
+    state::directed_stake::TOKEN_ACCOUNT_BALANCE_SOURCE_TYPE,
+    CommissionPledge, Config, Delegation, DirectedStakeBalanceSources,
+    DirectedStakeTicketExtension, DirectedStakeValidatorConsent, RebalanceJournal,
+    StewardStateAccountV2, StewardStateEnum, ValidatorExit,
 };
 
 /// Checks called before any cranking state function. Note that expected_state is optional -
@@ -230,6 +245,23 @@ pub fn vote_pubkey_at_validator_list_index(
     Ok(pubkey)
 }
 
+/// Utility to efficiently extract the stake status from a validator list.
+#[inline(always)]
+pub fn stake_status_at_validator_list_index(
+    validator_list: &BigVec<'_>,
+    index: usize,
+) -> Result<u8> {
+    let stake_status_index = VEC_SIZE_BYTES
+        .saturating_add(index.saturating_mul(ValidatorStakeInfo::LEN))
+        .checked_add(STAKE_STATUS_OFFSET)
+        .ok_or(StewardError::ArithmeticError)?;
+    validator_list
+        .data
+        .get(stake_status_index)
+        .copied()
+        .ok_or_else(|| StewardError::ValidatorIndexOutOfBounds.into())
+}
+
 pub fn get_validator_stake_info_at_index(
     validator_list_account_info: &AccountInfo,
     validator_list_index: usize,
@@ -426,8 +458,144 @@ pub fn validator_exists_in_list(
     Ok(false)
 }
 
+/// Ensures the current instruction was not invoked through CPI and that only steward and compute
+/// budget instructions ran before it in the transaction. Balances read after that were held when
+/// the transaction started, so they cannot have been borrowed for it with a flash loan.
+pub fn require_transaction_start_balances(instructions_sysvar: &AccountInfo) -> Result<()> {
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+        StewardError::DirectedStakeBalanceNotAtTransactionStart
+    );
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    for index in 0..current_index as usize {
+        let program_id = load_instruction_at_checked(index, instructions_sysvar)?.program_id;
+        require!(
+            program_id == crate::id() || program_id == COMPUTE_BUDGET_PROGRAM_ID,
+            StewardError::DirectedStakeBalanceNotAtTransactionStart
+        );
+    }
+    Ok(())
+}
+
+/// Returns the balance of `owner`'s associated token account for `mint`, or 0 if the account does not exist.
+/// Only the associated token account is accepted so that a cranker cannot pick another account of `owner`.
+pub fn associated_token_account_balance(
+    token_account_info: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<u64> {
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    require_keys_eq!(
+        *token_account_info.key,
+        expected_address,
+        StewardError::InvalidTokenAccount
+    );
+
+    if token_account_info.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(
+        *token_account_info.owner,
+        *token_program,
+        StewardError::InvalidTokenAccount
+    );
+
+    let data = token_account_info.try_borrow_data()?;
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&data)?.base;
+    require!(
+        token_account.mint == *mint && token_account.owner == *owner,
+        StewardError::InvalidTokenAccount
+    );
+    Ok(token_account.amount)
+}
+
+/// Pool tokens held by a ticket holder: the balance of their associated token account plus the
+/// token accounts attributed to the ticket in its `DirectedStakeBalanceSources`.
+///
+/// `source_accounts[i]` must be the account of the i-th balance source. Only token accounts of
+/// `mint` that are still owned by the ticket holder are counted, so a source can never count
+/// toward more than one ticket. Other source types are only counted off-chain.
+pub fn ticket_holder_token_balance(
+    holder_token_account_info: &AccountInfo,
+    balance_sources_info: &AccountInfo,
+    source_accounts: &[AccountInfo],
+    ticket: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<u64> {
+    let mut balance =
+        associated_token_account_balance(holder_token_account_info, holder, mint, token_program)?;
+
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[DirectedStakeBalanceSources::SEED, ticket.as_ref()],
+        &crate::id(),
+    );
+    require_keys_eq!(
+        *balance_sources_info.key,
+        expected_address,
+        StewardError::InvalidBalanceSources
+    );
+
+    if balance_sources_info.data_is_empty() {
+        require!(
+            source_accounts.is_empty(),
+            StewardError::InvalidBalanceSources
+        );
+        return Ok(balance);
+    }
+    require_keys_eq!(
+        *balance_sources_info.owner,
+        crate::id(),
+        StewardError::InvalidBalanceSources
+    );
+    let data = balance_sources_info.try_borrow_data()?;
+    let balance_sources = DirectedStakeBalanceSources::try_deserialize(&mut data.as_ref())?;
+
+    let num_sources = balance_sources.num_sources as usize;
+    require!(
+        source_accounts.len() == num_sources,
+        StewardError::InvalidBalanceSources
+    );
+
+    for (source, source_account) in balance_sources
+        .sources
+        .iter()
+        .take(num_sources)
+        .zip(source_accounts.iter())
+    {
+        require_keys_eq!(
+            *source_account.key,
+            source.account,
+            StewardError::InvalidBalanceSources
+        );
+
+        // The associated token account is already counted
+        if source.source_type != TOKEN_ACCOUNT_BALANCE_SOURCE_TYPE
+            || source_account.key == holder_token_account_info.key
+            || source_account.data_is_empty()
+            || source_account.owner != token_program
+        {
+            continue;
+        }
+        let source_data = source_account.try_borrow_data()?;
+        let Ok(token_account) = StateWithExtensions::<TokenAccount>::unpack(&source_data) else {
+            continue;
+        };
+        if token_account.base.mint == *mint && token_account.base.owner == *holder {
+            balance = balance.saturating_add(token_account.base.amount);
+        }
+    }
+
+    Ok(balance)
+}
+
 /// A boolean type stored as a u8.
-#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq, Eq)]
 #[zero_copy]
 pub struct U8Bool {
     pub value: u8,
@@ -460,3 +628,202 @@ impl From<U8Bool> for bool {
         val.is_true()
     }
 }
+
+/// Borrows the zero copy account held by `account_info`, checking its owner and discriminator. Used for accounts
+/// that may not exist and therefore can't be declared as an `AccountLoader`.
+pub fn load_zero_copy<'a, T: ZeroCopy + Owner>(
+    account_info: &'a AccountInfo,
+) -> Result<Ref<'a, T>> {
+    if *account_info.owner != T::owner() {
+        return Err(error!(
+            anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+        ));
+    }
+    let data = account_info.try_borrow_data()?;
+    let discriminator = T::DISCRIMINATOR;
+    let end = discriminator.len() + std::mem::size_of::<T>();
+    if data.len() < end || &data[..discriminator.len()] != discriminator {
+        return Err(error!(
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        ));
+    }
+    Ok(Ref::map(data, |data| {
+        bytemuck::from_bytes(&data[discriminator.len()..end])
+    }))
+}
+
+/// Mutable version of [`load_zero_copy`]
+pub fn load_zero_copy_mut<'a, T: ZeroCopy + Owner>(
+    account_info: &'a AccountInfo,
+) -> Result<RefMut<'a, T>> {
+    if *account_info.owner != T::owner() {
+        return Err(error!(
+            anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+        ));
+    }
+    require!(
+        account_info.is_writable,
+        anchor_lang::error::ErrorCode::AccountNotMutable
+    );
+    let data = account_info.try_borrow_mut_data()?;
+    let discriminator = T::DISCRIMINATOR;
+    let end = discriminator.len() + std::mem::size_of::<T>();
+    if data.len() < end || &data[..discriminator.len()] != discriminator {
+        return Err(error!(
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        ));
+    }
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(&mut data[discriminator.len()..end])
+    }))
+}
+
+/// Borrows the `DirectedStakeTicketExtension` of `ticket` from `extension_account_info`, which must be its
+/// extension PDA. Returns None if the ticket has no extension.
+pub fn load_ticket_extension<'a>(
+    extension_account_info: &'a AccountInfo,
+    ticket: &Pubkey,
+) -> Result<Option<Ref<'a, DirectedStakeTicketExtension>>> {
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[DirectedStakeTicketExtension::SEED, ticket.as_ref()],
+        &crate::id(),
+    );
+    require_keys_eq!(
+        *extension_account_info.key,
+        expected_address,
+        StewardError::Unauthorized
+    );
+
+    if extension_account_info.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(load_zero_copy(extension_account_info)?))
+}
+
+/// Mutably borrows the `RebalanceJournal` of `vote_account` from `rebalance_journal_account_info`, which must
+/// be its journal PDA. Fails if the validator has no journal, so no rebalance goes unrecorded, unless its vote
+/// account was closed: no journal can be created for it anymore, so None is returned instead.
+pub fn load_rebalance_journal_mut<'a>(
+    rebalance_journal_account_info: &'a AccountInfo,
+    config: &Pubkey,
+    vote_account: &AccountInfo,
+) -> Result<Option<RefMut<'a, RebalanceJournal>>> {
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[
+            RebalanceJournal::SEED,
+            config.as_ref(),
+            vote_account.key.as_ref(),
+        ],
+        &crate::id(),
+    );
+    require_keys_eq!(
+        *rebalance_journal_account_info.key,
+        expected_address,
+        StewardError::InvalidRebalanceJournal
+    );
+
+    if rebalance_journal_account_info.data_is_empty() {
+        require!(
+            *vote_account.owner != vote::program::ID,
+            StewardError::RebalanceJournalNotInitialized
+        );
+        return Ok(None);
+    }
+    Ok(Some(load_zero_copy_mut(rebalance_journal_account_info)?))
+}
+
+/// Loads the `DirectedStakeValidatorConsent` of `vote_pubkey` from `consent_account_info`, which must be
+/// its consent PDA. Returns None if the validator has not created one.
+pub fn load_validator_consent(
+    consent_account_info: &AccountInfo,
+    config: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> Result<Option<DirectedStakeValidatorConsent>> {
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[
+            DirectedStakeValidatorConsent::SEED,
+            config.as_ref(),
+            vote_pubkey.as_ref(),
+        ],
+        &crate::id(),
+    );
+    require_keys_eq!(
+        *consent_account_info.key,
+        expected_address,
+        StewardError::InvalidValidatorConsent
+    );
+
+    if consent_account_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(
+        *consent_account_info.owner,
+        crate::id(),
+        StewardError::InvalidValidatorConsent
+    );
+    let data = consent_account_info.try_borrow_data()?;
+    let consent = DirectedStakeValidatorConsent::try_deserialize(&mut data.as_ref())?;
+    Ok(Some(consent))
+}
+
+/// Loads the `CommissionPledge` of `vote_pubkey` from `commission_pledge_account_info`, which must be its
+/// pledge PDA. Returns None if the validator has not created one.
+pub fn load_commission_pledge(
+    commission_pledge_account_info: &AccountInfo,
+    config: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> Result<Option<CommissionPledge>> {
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[
+            CommissionPledge::SEED,
+            config.as_ref(),
+            vote_pubkey.as_ref(),
+        ],
+        &crate::id(),
+    );
+    require_keys_eq!(
+        *commission_pledge_account_info.key,
+        expected_address,
+        StewardError::InvalidCommissionPledge
+    );
+
+    if commission_pledge_account_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(
+        *commission_pledge_account_info.owner,
+        crate::id(),
+        StewardError::InvalidCommissionPledge
+    );
+    let data = commission_pledge_account_info.try_borrow_data()?;
+    let commission_pledge = CommissionPledge::try_deserialize(&mut data.as_ref())?;
+    Ok(Some(commission_pledge))
+}
+
+/// Writes `commission_pledge` back to the account it was loaded from with `load_commission_pledge`
+pub fn store_commission_pledge(
+    commission_pledge_account_info: &AccountInfo,
+    commission_pledge: &CommissionPledge,
+) -> Result<()> {
+    let mut data = commission_pledge_account_info.try_borrow_mut_data()?;
+    data[8..CommissionPledge::SIZE].copy_from_slice(bytemuck::bytes_of(commission_pledge));
+    Ok(())
+}
+
+/// Loads the `ValidatorExit` from `validator_exit_account_info`, whose address is checked by the caller.
+/// Returns None if the validator has not requested to exit or has since rejoined.
+pub fn load_validator_exit(
+    validator_exit_account_info: &AccountInfo,
+) -> Result<Option<ValidatorExit>> {
+    if validator_exit_account_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(
+        *validator_exit_account_info.owner,
+        crate::id(),
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let data = validator_exit_account_info.try_borrow_data()?;
+    let validator_exit = ValidatorExit::try_deserialize(&mut data.as_ref())?;
+    Ok(Some(validator_exit))
+}
@@ -15,8 +15,12 @@ use validator_history::{ClusterHistory, Config as ValidatorHistoryConfig, Valida
 pub type Error = Box<dyn std::error::Error>;
 use jito_steward::{
     stake_pool_utils::{StakePool, ValidatorList},
-    Config as StewardConfig, DirectedStakeMeta, DirectedStakeTicket, DirectedStakeWhitelist,
-    StewardStateAccount, StewardStateAccountV2,
+    CommissionPledge, Config as StewardConfig, CycleReport, DirectedStakeBalanceSources,
+    DirectedStakeBasket, DirectedStakeBasketContribution, DirectedStakeContribution,
+    DirectedStakeExtensionContribution, DirectedStakeMeta, DirectedStakePreferenceSchedule,
+    DirectedStakeTicket, DirectedStakeTicketExtension, DirectedStakeValidatorConsent,
+    DirectedStakeWhitelist, InstantUnstakeFractions, RebalanceJournal, ScoreWarnings,
+    StewardStateAccount, StewardStateAccountV2, ValidatorExit,
 };
 
 use solana_sdk::account::Account;
@@ -33,6 +37,129 @@ use crate::models::{
 
 use super::transactions::get_multiple_accounts_batched;
 
+/// Fetches all [`DirectedStakeTicketExtension`] accounts of the program, keyed by ticket address.
+pub async fn get_directed_stake_ticket_extensions(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<Pubkey, DirectedStakeTicketExtension>, JitoTransactionError> {
+    let discriminator = <DirectedStakeTicketExtension as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let extensions: HashMap<Pubkey, DirectedStakeTicketExtension> = accounts
+        .iter()
+        .filter_map(|(_, account)| {
+            let extension =
+                DirectedStakeTicketExtension::try_deserialize(&mut account.data.as_slice()).ok()?;
+            Some((extension.ticket, extension))
+        })
+        .collect();
+
+    Ok(extensions)
+}
+
+/// Fetches all [`DirectedStakePreferenceSchedule`] accounts of the program, keyed by ticket address
+pub async fn get_directed_stake_preference_schedules(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<Pubkey, DirectedStakePreferenceSchedule>, JitoTransactionError> {
+    let discriminator = <DirectedStakePreferenceSchedule as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let schedules: HashMap<Pubkey, DirectedStakePreferenceSchedule> = accounts
+        .iter()
+        .filter_map(|(_, account)| {
+            let schedule =
+                DirectedStakePreferenceSchedule::try_deserialize(&mut account.data.as_slice())
+                    .ok()?;
+            Some((schedule.ticket, schedule))
+        })
+        .collect();
+
+    Ok(schedules)
+}
+
+/// Fetches all [`DirectedStakeBasketContribution`] accounts of the program, keyed by ticket and
+/// basket address
+pub async fn get_directed_stake_basket_contributions(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<(Pubkey, Pubkey), DirectedStakeBasketContribution>, JitoTransactionError> {
+    let discriminator = <DirectedStakeBasketContribution as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let basket_contributions: HashMap<(Pubkey, Pubkey), DirectedStakeBasketContribution> = accounts
+        .iter()
+        .filter_map(|(_, account)| {
+            let contribution =
+                DirectedStakeBasketContribution::try_deserialize(&mut account.data.as_slice())
+                    .ok()?;
+            Some(((contribution.ticket, contribution.basket), contribution))
+        })
+        .collect();
+
+    Ok(basket_contributions)
+}
+
 // ---------------- GET ACCOUNTS ----------------
 
 pub async fn get_all_validator_accounts(
@@ -308,6 +435,125 @@ pub async fn get_directed_stake_tickets(
     Ok(tickets)
 }
 
+/// Fetches all [`DirectedStakeBasket`] accounts of the program, keyed by basket address
+pub async fn get_directed_stake_baskets(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<Pubkey, DirectedStakeBasket>, JitoTransactionError> {
+    let discriminator = <DirectedStakeBasket as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let baskets: HashMap<Pubkey, DirectedStakeBasket> = accounts
+        .iter()
+        .filter_map(|(pda, account)| {
+            let basket = DirectedStakeBasket::try_deserialize(&mut account.data.as_slice()).ok()?;
+            Some((*pda, basket))
+        })
+        .collect();
+
+    Ok(baskets)
+}
+
+/// Fetches all [`DirectedStakeBalanceSources`] accounts of the program, keyed by ticket address
+pub async fn get_directed_stake_balance_sources(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<Pubkey, DirectedStakeBalanceSources>, JitoTransactionError> {
+    let discriminator = <DirectedStakeBalanceSources as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let balance_sources: HashMap<Pubkey, DirectedStakeBalanceSources> = accounts
+        .iter()
+        .filter_map(|(_, account)| {
+            let sources =
+                DirectedStakeBalanceSources::try_deserialize(&mut account.data.as_slice()).ok()?;
+            Some((sources.ticket, sources))
+        })
+        .collect();
+
+    Ok(balance_sources)
+}
+
+/// Fetches all [`DirectedStakeContribution`] accounts of the program, keyed by ticket address
+pub async fn get_directed_stake_contributions(
+    client: Arc<RpcClient>,
+    program_id: &Pubkey,
+) -> Result<HashMap<Pubkey, DirectedStakeContribution>, JitoTransactionError> {
+    let discriminator = <DirectedStakeContribution as Discriminator>::DISCRIMINATOR;
+    let memcmp_filter = RpcFilterType::Memcmp(Memcmp::new(
+        0,
+        MemcmpEncodedBytes::Base58(solana_sdk::bs58::encode(discriminator).into_string()),
+    ));
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            program_id,
+            solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp_filter]),
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            },
+        )
+        .await?;
+
+    let contributions: HashMap<Pubkey, DirectedStakeContribution> = accounts
+        .iter()
+        .filter_map(|(_, account)| {
+            let contribution =
+                DirectedStakeContribution::try_deserialize(&mut account.data.as_slice()).ok()?;
+            Some((contribution.ticket, contribution))
+        })
+        .collect();
+
+    Ok(contributions)
+}
+
 // ---------------- GET ACCOUNTS ----------------
 
 pub async fn get_steward_config_account(
@@ -482,6 +728,118 @@ pub async fn get_directed_stake_ticket(
     Ok(directed_stake_ticket)
 }
 
+/// Fetches and deserializes the [`DirectedStakeTicketExtension`] of a ticket, returning `None`
+/// if the ticket has no extension.
+pub async fn get_directed_stake_ticket_extension(
+    client: Arc<RpcClient>,
+    ticket_address: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<DirectedStakeTicketExtension>, JitoTransactionError> {
+    let ticket_extension_pda =
+        get_directed_stake_ticket_extension_address(ticket_address, program_id);
+
+    let Some(account) = client
+        .get_account_with_commitment(&ticket_extension_pda, CommitmentConfig::confirmed())
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+
+    let ticket_extension = DirectedStakeTicketExtension::try_deserialize(
+        &mut account.data.as_slice(),
+    )
+    .map_err(|e| {
+        JitoTransactionError::Custom(format!(
+            "Failed to deserialize directed stake ticket extension account: {e}"
+        ))
+    })?;
+
+    Ok(Some(ticket_extension))
+}
+
+/// Fetches and deserializes the [`CycleReport`] account
+///
+/// This function retrieves the report for the cycle that started in `cycle_start_epoch`
+/// for a given steward configuration.
+pub async fn get_cycle_report(
+    client: Arc<RpcClient>,
+    steward_config_address: &Pubkey,
+    cycle_start_epoch: u64,
+    program_id: &Pubkey,
+) -> Result<Box<CycleReport>, JitoTransactionError> {
+    let cycle_report_pda =
+        get_cycle_report_address(steward_config_address, cycle_start_epoch, program_id);
+
+    let cycle_report_account_data = client.get_account_data(&cycle_report_pda).await?;
+
+    let cycle_report = Box::new(
+        CycleReport::try_deserialize(&mut cycle_report_account_data.as_slice()).map_err(|e| {
+            JitoTransactionError::Custom(format!("Failed to deserialize cycle report account: {e}"))
+        })?,
+    );
+
+    Ok(cycle_report)
+}
+
+/// Returns the [`CycleReport`] address that cranks must pass when cycle reports are enabled, or None
+/// when they are not. A new cycle starts with the next ComputeScore instruction once
+/// `next_cycle_epoch` is reached.
+pub async fn get_required_cycle_report_address(
+    client: &Arc<RpcClient>,
+    all_steward_accounts: &AllStewardAccounts,
+    program_id: &Pubkey,
+) -> Result<Option<Pubkey>, JitoTransactionError> {
+    if !all_steward_accounts
+        .config_account
+        .parameters
+        .cycle_reports_enabled()
+    {
+        return Ok(None);
+    }
+
+    let state = &all_steward_accounts.state_account.state;
+    let current_epoch = client.get_epoch_info().await?.epoch;
+    let cycle_start_epoch = if current_epoch >= state.next_cycle_epoch {
+        current_epoch
+    } else {
+        client
+            .get_epoch_schedule()
+            .await?
+            .get_epoch(state.start_computing_scores_slot)
+    };
+
+    Ok(Some(get_cycle_report_address(
+        &all_steward_accounts.config_address,
+        cycle_start_epoch,
+        program_id,
+    )))
+}
+
+pub async fn get_rebalance_journal(
+    client: Arc<RpcClient>,
+    steward_config_address: &Pubkey,
+    vote_account: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Box<RebalanceJournal>, JitoTransactionError> {
+    let rebalance_journal_pda =
+        get_rebalance_journal_address(steward_config_address, vote_account, program_id);
+
+    let rebalance_journal_account_data = client.get_account_data(&rebalance_journal_pda).await?;
+
+    let rebalance_journal = Box::new(
+        RebalanceJournal::try_deserialize(&mut rebalance_journal_account_data.as_slice()).map_err(
+            |e| {
+                JitoTransactionError::Custom(format!(
+                    "Failed to deserialize rebalance journal account: {e}"
+                ))
+            },
+        )?,
+    );
+
+    Ok(rebalance_journal)
+}
+
 // ---------------- GET ADDRESSES ----------------
 
 pub fn get_steward_state_address(steward_program_id: &Pubkey, steward_config: &Pubkey) -> Pubkey {
@@ -606,3 +964,232 @@ pub fn get_directed_stake_ticket_address(
 
     directed_stake_ticket_pda
 }
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeBalanceSources`] account
+/// of a ticket.
+pub fn get_directed_stake_balance_sources_address(ticket: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (balance_sources_pda, _bump) = Pubkey::find_program_address(
+        &[DirectedStakeBalanceSources::SEED, ticket.as_ref()],
+        program_id,
+    );
+    balance_sources_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeContribution`] account.
+///
+/// This function calculates the deterministic address of the account tracking a ticket's
+/// contribution to directed stake targets using the ticket address.
+pub fn get_directed_stake_contribution_address(ticket: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (directed_stake_contribution_pda, _bump) = Pubkey::find_program_address(
+        &[DirectedStakeContribution::SEED, ticket.as_ref()],
+        program_id,
+    );
+
+    directed_stake_contribution_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeTicketExtension`] account.
+///
+/// This function calculates the deterministic address of the account holding a ticket's
+/// additional preferences using the ticket address.
+pub fn get_directed_stake_ticket_extension_address(ticket: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (ticket_extension_pda, _bump) = Pubkey::find_program_address(
+        &[DirectedStakeTicketExtension::SEED, ticket.as_ref()],
+        program_id,
+    );
+
+    ticket_extension_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakePreferenceSchedule`] account.
+///
+/// This function calculates the deterministic address of the account holding a ticket's
+/// scheduled preference changes using the ticket address.
+pub fn get_directed_stake_preference_schedule_address(
+    ticket: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (preference_schedule_pda, _bump) = Pubkey::find_program_address(
+        &[DirectedStakePreferenceSchedule::SEED, ticket.as_ref()],
+        program_id,
+    );
+
+    preference_schedule_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeExtensionContribution`]
+/// account.
+///
+/// This function calculates the deterministic address of the account tracking the contribution
+/// of a ticket's extension preferences to directed stake targets using the ticket address.
+pub fn get_directed_stake_extension_contribution_address(
+    ticket: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (extension_contribution_pda, _bump) = Pubkey::find_program_address(
+        &[DirectedStakeExtensionContribution::SEED, ticket.as_ref()],
+        program_id,
+    );
+
+    extension_contribution_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeValidatorConsent`] account.
+///
+/// This function calculates the deterministic address of the account through which a validator
+/// controls the directed stake it receives, using the steward configuration and vote account.
+pub fn get_directed_stake_validator_consent_address(
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (validator_consent_pda, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeValidatorConsent::SEED,
+            steward_config.as_ref(),
+            vote_account.as_ref(),
+        ],
+        program_id,
+    );
+
+    validator_consent_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`CycleReport`] account.
+///
+/// This function calculates the deterministic address of the cycle report account
+/// using the steward configuration and the epoch the cycle started in.
+pub fn get_cycle_report_address(
+    steward_config: &Pubkey,
+    cycle_start_epoch: u64,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (cycle_report_pda, _bump) = Pubkey::find_program_address(
+        &[
+            CycleReport::SEED,
+            steward_config.as_ref(),
+            &cycle_start_epoch.to_le_bytes(),
+        ],
+        program_id,
+    );
+
+    cycle_report_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`InstantUnstakeFractions`] account.
+pub fn get_instant_unstake_fractions_address(
+    steward_config: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (instant_unstake_fractions_pda, _bump) = Pubkey::find_program_address(
+        &[InstantUnstakeFractions::SEED, steward_config.as_ref()],
+        program_id,
+    );
+
+    instant_unstake_fractions_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`ScoreWarnings`] account.
+pub fn get_score_warnings_address(steward_config: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (score_warnings_pda, _bump) =
+        Pubkey::find_program_address(&[ScoreWarnings::SEED, steward_config.as_ref()], program_id);
+
+    score_warnings_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`ValidatorExit`] account of a validator,
+/// which exists while it has exited the pool.
+pub fn get_validator_exit_address(
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (validator_exit_pda, _bump) = Pubkey::find_program_address(
+        &[
+            ValidatorExit::SEED,
+            steward_config.as_ref(),
+            vote_account.as_ref(),
+        ],
+        program_id,
+    );
+
+    validator_exit_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`CommissionPledge`] account of a validator.
+pub fn get_commission_pledge_address(
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (commission_pledge_pda, _bump) = Pubkey::find_program_address(
+        &[
+            CommissionPledge::SEED,
+            steward_config.as_ref(),
+            vote_account.as_ref(),
+        ],
+        program_id,
+    );
+
+    commission_pledge_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`RebalanceJournal`] account of a validator.
+pub fn get_rebalance_journal_address(
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (rebalance_journal_pda, _bump) = Pubkey::find_program_address(
+        &[
+            RebalanceJournal::SEED,
+            steward_config.as_ref(),
+            vote_account.as_ref(),
+        ],
+        program_id,
+    );
+
+    rebalance_journal_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeBasket`] account.
+///
+/// This function calculates the deterministic address of a named basket of validators, using the
+/// steward configuration and the zero padded basket name.
+pub fn get_directed_stake_basket_address(
+    steward_config: &Pubkey,
+    name: &[u8; 32],
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (basket_pda, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeBasket::SEED,
+            steward_config.as_ref(),
+            name.as_ref(),
+        ],
+        program_id,
+    );
+
+    basket_pda
+}
+
+/// Derives the Program Derived Address (PDA) for the [`DirectedStakeBasketContribution`] account.
+///
+/// This function calculates the deterministic address of the account tracking what a ticket
+/// contributes to directed stake targets through a basket, using the ticket and basket addresses.
+pub fn get_directed_stake_basket_contribution_address(
+    ticket: &Pubkey,
+    basket: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    let (basket_contribution_pda, _bump) = Pubkey::find_program_address(
+        &[
+            DirectedStakeBasketContribution::SEED,
+            ticket.as_ref(),
+            basket.as_ref(),
+        ],
+        program_id,
+    );
+
+    basket_contribution_pda
+}
@@ -0,0 +1,384 @@
+//! Attribution of JitoSOL held outside of a ticket holder's associated token account.
+//!
+//! Tickets declare additional token or position accounts on-chain in their
+//! [`DirectedStakeBalanceSources`] account. Each source has a `source_type` which selects the
+//! [`BalanceAdapter`] used to decode it. A source is only counted if the adapter reports the same
+//! owner that signed to attribute it to the ticket.
+//!
+//! `crank_directed_stake_ticket` counts token account sources held by the ticket holder
+//! on-chain. The adapters here are used by [`compute_directed_stake_meta_with_registry`] for
+//! targets uploaded off-chain, where positions of other layouts and owners can be counted since
+//! each source is only attributed to one ticket.
+//!
+//! [`compute_directed_stake_meta_with_registry`]: crate::utils::instructions::compute_directed_stake_meta_with_registry
+
+use std::collections::{HashMap, HashSet};
+
+use jito_steward::{DirectedStakeBalanceSource, DirectedStakeBalanceSources, DirectedStakeTicket};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+/// Source type of plain SPL Token and Token-2022 accounts holding the pool mint
+pub const TOKEN_ACCOUNT_SOURCE_TYPE: u16 =
+    jito_steward::state::directed_stake::TOKEN_ACCOUNT_BALANCE_SOURCE_TYPE;
+
+/// Owner and pool token amount of a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionBalance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Decodes the JitoSOL held in a position account of a given layout
+pub trait BalanceAdapter: Send + Sync {
+    /// `source_type` of the sources handled by this adapter
+    fn source_type(&self) -> u16;
+
+    /// Returns the owner and the amount of `pool_mint` tokens held by `account`, or `None`
+    /// if the account does not match the layout
+    fn position_balance(&self, account: &Account, pool_mint: &Pubkey) -> Option<PositionBalance>;
+}
+
+/// Token accounts of the pool mint, owned by either token program
+pub struct TokenAccountAdapter;
+
+impl BalanceAdapter for TokenAccountAdapter {
+    fn source_type(&self) -> u16 {
+        TOKEN_ACCOUNT_SOURCE_TYPE
+    }
+
+    fn position_balance(&self, account: &Account, pool_mint: &Pubkey) -> Option<PositionBalance> {
+        if spl_token_2022::check_spl_token_program_account(&account.owner).is_err() {
+            return None;
+        }
+        let token_account = StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+        if token_account.base.mint != *pool_mint {
+            return None;
+        }
+        Some(PositionBalance {
+            owner: token_account.base.owner,
+            amount: token_account.base.amount,
+        })
+    }
+}
+
+/// Positions that store their owner and pool token amount at fixed offsets, such as most
+/// lending vault deposits and single-sided LP receipts
+pub struct FixedOffsetAdapter {
+    pub source_type: u16,
+    /// Program owning the position accounts
+    pub program_id: Pubkey,
+    /// Prefix every position account starts with
+    pub discriminator: Vec<u8>,
+    /// Offset of the owner pubkey
+    pub owner_offset: usize,
+    /// Offset of the mint pubkey of the deposited tokens
+    pub mint_offset: usize,
+    /// Offset of the little-endian u64 amount of pool tokens
+    pub amount_offset: usize,
+}
+
+impl BalanceAdapter for FixedOffsetAdapter {
+    fn source_type(&self) -> u16 {
+        self.source_type
+    }
+
+    fn position_balance(&self, account: &Account, pool_mint: &Pubkey) -> Option<PositionBalance> {
+        if account.owner != self.program_id || !account.data.starts_with(&self.discriminator) {
+            return None;
+        }
+        let mint_bytes = account
+            .data
+            .get(self.mint_offset..self.mint_offset.checked_add(32)?)?;
+        if Pubkey::try_from(mint_bytes).ok()? != *pool_mint {
+            return None;
+        }
+        let owner_bytes = account
+            .data
+            .get(self.owner_offset..self.owner_offset.checked_add(32)?)?;
+        let amount_bytes = account
+            .data
+            .get(self.amount_offset..self.amount_offset.checked_add(8)?)?;
+        Some(PositionBalance {
+            owner: Pubkey::try_from(owner_bytes).ok()?,
+            amount: u64::from_le_bytes(amount_bytes.try_into().ok()?),
+        })
+    }
+}
+
+/// Adapters keyed by `source_type`. The default registry handles token accounts.
+pub struct BalanceAttributionRegistry {
+    adapters: HashMap<u16, Box<dyn BalanceAdapter>>,
+}
+
+impl Default for BalanceAttributionRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            adapters: HashMap::new(),
+        };
+        registry.register(Box::new(TokenAccountAdapter));
+        registry
+    }
+}
+
+impl BalanceAttributionRegistry {
+    /// Registers `adapter`, returning the adapter previously registered for its source type
+    pub fn register(
+        &mut self,
+        adapter: Box<dyn BalanceAdapter>,
+    ) -> Option<Box<dyn BalanceAdapter>> {
+        self.adapters.insert(adapter.source_type(), adapter)
+    }
+
+    /// Pool tokens held by `source`, or 0 if it has no adapter, does not decode, or is no longer
+    /// owned by the owner that attributed it
+    pub fn source_balance(
+        &self,
+        source: &DirectedStakeBalanceSource,
+        account: Option<&Account>,
+        pool_mint: &Pubkey,
+    ) -> u64 {
+        let (Some(adapter), Some(account)) = (self.adapters.get(&source.source_type), account)
+        else {
+            return 0;
+        };
+        adapter
+            .position_balance(account, pool_mint)
+            .filter(|position| position.owner == source.owner)
+            .map(|position| position.amount)
+            .unwrap_or(0)
+    }
+
+    /// Sums the attributed balance of each ticket holder.
+    ///
+    /// `balance_sources` is keyed by ticket address and `accounts` holds the fetched source
+    /// accounts. Sources in `excluded_accounts` (typically the holders' associated token
+    /// accounts, which are counted separately) are skipped, and a source attributed to several
+    /// tickets only counts toward the ticket with the lowest address.
+    pub fn attributed_balances(
+        &self,
+        tickets: &HashMap<Pubkey, DirectedStakeTicket>,
+        balance_sources: &HashMap<Pubkey, DirectedStakeBalanceSources>,
+        accounts: &HashMap<Pubkey, Account>,
+        excluded_accounts: &HashSet<Pubkey>,
+        pool_mint: &Pubkey,
+    ) -> HashMap<Pubkey, u64> {
+        let mut ticket_addresses: Vec<&Pubkey> = tickets.keys().collect();
+        ticket_addresses.sort();
+
+        let mut counted_accounts: HashSet<Pubkey> = excluded_accounts.clone();
+        let mut balances = HashMap::new();
+        for ticket_address in ticket_addresses {
+            let Some(sources) = balance_sources.get(ticket_address) else {
+                continue;
+            };
+            let balance = sources
+                .sources
+                .iter()
+                .take(sources.num_sources as usize)
+                .filter(|source| counted_accounts.insert(source.account))
+                .fold(0u64, |total, source| {
+                    total.saturating_add(self.source_balance(
+                        source,
+                        accounts.get(&source.account),
+                        pool_mint,
+                    ))
+                });
+            if balance > 0 {
+                balances
+                    .entry(tickets[ticket_address].ticket_update_authority)
+                    .and_modify(|total: &mut u64| *total = total.saturating_add(balance))
+                    .or_insert(balance);
+            }
+        }
+        balances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, Discriminator};
+    use solana_program::program_pack::Pack;
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint,
+            owner,
+            amount,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        Account {
+            data,
+            owner: spl_token_2022::id(),
+            ..Account::default()
+        }
+    }
+
+    fn empty_sources() -> DirectedStakeBalanceSources {
+        let mut data = DirectedStakeBalanceSources::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakeBalanceSources::SIZE, 0);
+        DirectedStakeBalanceSources::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    fn source(account: Pubkey, owner: Pubkey, source_type: u16) -> DirectedStakeBalanceSource {
+        DirectedStakeBalanceSource {
+            account,
+            owner,
+            source_type,
+            _padding0: [0; 30],
+        }
+    }
+
+    #[test]
+    fn test_source_balance_verifies_owner_and_mint() {
+        let registry = BalanceAttributionRegistry::default();
+        let pool_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = token_account(pool_mint, owner, 1_000);
+        let address = Pubkey::new_unique();
+
+        let valid = source(address, owner, TOKEN_ACCOUNT_SOURCE_TYPE);
+        assert_eq!(
+            registry.source_balance(&valid, Some(&account), &pool_mint),
+            1_000
+        );
+
+        // Transferred to another owner since it was attributed
+        let stale = source(address, Pubkey::new_unique(), TOKEN_ACCOUNT_SOURCE_TYPE);
+        assert_eq!(
+            registry.source_balance(&stale, Some(&account), &pool_mint),
+            0
+        );
+
+        // Other mint, unknown source type and missing account
+        assert_eq!(
+            registry.source_balance(&valid, Some(&account), &Pubkey::new_unique()),
+            0
+        );
+        assert_eq!(
+            registry.source_balance(&source(address, owner, 7), Some(&account), &pool_mint),
+            0
+        );
+        assert_eq!(registry.source_balance(&valid, None, &pool_mint), 0);
+    }
+
+    #[test]
+    fn test_attributed_balances_count_each_source_once() {
+        let registry = BalanceAttributionRegistry::default();
+        let pool_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let holder_ata = Pubkey::new_unique();
+        let shared = Pubkey::new_unique();
+
+        let ticket_a = Pubkey::new_unique();
+        let ticket_b = Pubkey::new_unique();
+        let authority_a = Pubkey::new_unique();
+        let authority_b = Pubkey::new_unique();
+        let tickets = HashMap::from([
+            (
+                ticket_a,
+                DirectedStakeTicket::new(authority_a, false.into(), &[]),
+            ),
+            (
+                ticket_b,
+                DirectedStakeTicket::new(authority_b, false.into(), &[]),
+            ),
+        ]);
+
+        let mut sources_a = empty_sources();
+        sources_a.add_source(shared, owner, 0).unwrap();
+        sources_a.add_source(holder_ata, owner, 0).unwrap();
+        let mut sources_b = empty_sources();
+        sources_b.add_source(shared, owner, 0).unwrap();
+        let balance_sources = HashMap::from([(ticket_a, sources_a), (ticket_b, sources_b)]);
+
+        let accounts = HashMap::from([
+            (shared, token_account(pool_mint, owner, 1_000)),
+            (holder_ata, token_account(pool_mint, owner, 500)),
+        ]);
+
+        let balances = registry.attributed_balances(
+            &tickets,
+            &balance_sources,
+            &accounts,
+            &HashSet::from([holder_ata]),
+            &pool_mint,
+        );
+        let first_authority = if ticket_a < ticket_b {
+            authority_a
+        } else {
+            authority_b
+        };
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[&first_authority], 1_000);
+    }
+
+    fn fixed_offset_registry(program_id: Pubkey) -> BalanceAttributionRegistry {
+        let mut registry = BalanceAttributionRegistry::default();
+        registry.register(Box::new(FixedOffsetAdapter {
+            source_type: 1,
+            program_id,
+            discriminator: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            owner_offset: 8,
+            mint_offset: 40,
+            amount_offset: 72,
+        }));
+        registry
+    }
+
+    fn fixed_offset_position(program_id: Pubkey, owner: Pubkey, mint: Pubkey) -> Account {
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&2_500u64.to_le_bytes());
+        Account {
+            data,
+            owner: program_id,
+            ..Account::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_offset_adapter() {
+        let program_id = Pubkey::new_unique();
+        let pool_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let registry = fixed_offset_registry(program_id);
+        let account = fixed_offset_position(program_id, owner, pool_mint);
+
+        let position = source(Pubkey::new_unique(), owner, 1);
+        assert_eq!(
+            registry.source_balance(&position, Some(&account), &pool_mint),
+            2_500
+        );
+
+        let truncated = Account {
+            data: account.data[..76].to_vec(),
+            ..account.clone()
+        };
+        assert_eq!(
+            registry.source_balance(&position, Some(&truncated), &pool_mint),
+            0
+        );
+    }
+
+    #[test]
+    fn test_fixed_offset_adapter_foreign_mint() {
+        let program_id = Pubkey::new_unique();
+        let pool_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let registry = fixed_offset_registry(program_id);
+
+        // A deposit of another token in the same program is not counted
+        let account = fixed_offset_position(program_id, owner, Pubkey::new_unique());
+        let position = source(Pubkey::new_unique(), owner, 1);
+        assert_eq!(
+            registry.source_balance(&position, Some(&account), &pool_mint),
+            0
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jito_steward::events::{
+    AdminMarkForRemovalEvent, CopyDirectedStakeTargetsEvent, DirectedStakeTicketClosedEvent,
+    DirectedStakeTicketInitializedEvent, DirectedStakeTicketUpdatedEvent, SetNewAuthorityEvent,
+    SetPausedEvent, SyncDirectedStakeLamportsEvent, UpdateBlacklistEvent,
+    UpdateDirectedStakeWhitelistEvent, UpdateParametersEvent, UpdatePriorityFeeParametersEvent,
+};
+
+/// Prefix of the log line written by `emit!`, followed by the base64 encoded event
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Steward admin and configuration events, used to monitor governance actions
+#[derive(Debug, Clone)]
+pub enum StewardAdminEvent {
+    UpdateParameters(UpdateParametersEvent),
+    UpdatePriorityFeeParameters(UpdatePriorityFeeParametersEvent),
+    UpdateBlacklist(UpdateBlacklistEvent),
+    SetNewAuthority(SetNewAuthorityEvent),
+    SetPaused(SetPausedEvent),
+    AdminMarkForRemoval(AdminMarkForRemovalEvent),
+    UpdateDirectedStakeWhitelist(UpdateDirectedStakeWhitelistEvent),
+}
+
+/// Directed stake ticket and target lifecycle events, used to audit preference changes
+#[derive(Debug, Clone)]
+pub enum DirectedStakeEvent {
+    TicketInitialized(DirectedStakeTicketInitializedEvent),
+    TicketUpdated(DirectedStakeTicketUpdatedEvent),
+    TicketClosed(DirectedStakeTicketClosedEvent),
+    CopyTargets(CopyDirectedStakeTargetsEvent),
+    SyncLamports(SyncDirectedStakeLamportsEvent),
+}
+
+/// Deserializes `data` as `T` if it starts with the event discriminator of `T`
+pub fn decode_event<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut &body[..]).ok()
+}
+
+/// Decodes a single event payload (discriminator followed by the serialized event)
+pub fn decode_admin_event(data: &[u8]) -> Option<StewardAdminEvent> {
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::UpdateParameters(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::UpdatePriorityFeeParameters(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::UpdateBlacklist(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::SetNewAuthority(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::SetPaused(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::AdminMarkForRemoval(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(StewardAdminEvent::UpdateDirectedStakeWhitelist(event));
+    }
+    None
+}
+
+/// Decodes a single directed stake event payload (discriminator followed by the serialized event)
+pub fn decode_directed_stake_event(data: &[u8]) -> Option<DirectedStakeEvent> {
+    if let Some(event) = decode_event(data) {
+        return Some(DirectedStakeEvent::TicketInitialized(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(DirectedStakeEvent::TicketUpdated(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(DirectedStakeEvent::TicketClosed(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(DirectedStakeEvent::CopyTargets(event));
+    }
+    if let Some(event) = decode_event(data) {
+        return Some(DirectedStakeEvent::SyncLamports(event));
+    }
+    None
+}
+
+/// Returns the decoded payloads of all `Program data:` lines in a transaction's log messages
+pub fn program_data_from_logs(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .collect()
+}
+
+/// Decodes all steward admin events from a transaction's log messages, skipping any other logs
+pub fn decode_admin_events_from_logs(logs: &[String]) -> Vec<StewardAdminEvent> {
+    program_data_from_logs(logs)
+        .iter()
+        .filter_map(|data| decode_admin_event(data))
+        .collect()
+}
+
+/// Decodes all directed stake events from a transaction's log messages, skipping any other logs
+pub fn decode_directed_stake_events_from_logs(logs: &[String]) -> Vec<DirectedStakeEvent> {
+    program_data_from_logs(logs)
+        .iter()
+        .filter_map(|data| decode_directed_stake_event(data))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+    use jito_steward::{DirectedStakePreference, Parameters};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn to_log(event: &impl Event) -> String {
+        format!("{PROGRAM_DATA_PREFIX}{}", STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn test_decode_admin_events_from_logs() {
+        let authority = Pubkey::new_unique();
+        let previous_parameters = Parameters::default();
+        let new_parameters = Parameters {
+            num_delegation_validators: 400,
+            ..Parameters::default()
+        };
+
+        let logs = vec![
+            "Program log: Instruction: UpdateParameters".to_string(),
+            to_log(&UpdateParametersEvent {
+                authority,
+                previous_parameters,
+                new_parameters,
+            }),
+            to_log(&SetPausedEvent {
+                authority,
+                previous_paused: false,
+                paused: true,
+            }),
+            format!("{PROGRAM_DATA_PREFIX}not base64"),
+        ];
+
+        let events = decode_admin_events_from_logs(&logs);
+        assert_eq!(events.len(), 2);
+
+        match &events[0] {
+            StewardAdminEvent::UpdateParameters(event) => {
+                assert_eq!(event.authority, authority);
+                assert_eq!(event.previous_parameters.num_delegation_validators, 0);
+                assert_eq!(event.new_parameters.num_delegation_validators, 400);
+            }
+            _ => panic!("Expected UpdateParameters"),
+        }
+
+        match &events[1] {
+            StewardAdminEvent::SetPaused(event) => {
+                assert!(!event.previous_paused);
+                assert!(event.paused);
+            }
+            _ => panic!("Expected SetPaused"),
+        }
+    }
+
+    #[test]
+    fn test_decode_directed_stake_events_from_logs() {
+        let ticket = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let vote_pubkey = Pubkey::new_unique();
+        let previous_preference = DirectedStakePreference::new(Pubkey::new_unique(), 10_000);
+        let new_preference = DirectedStakePreference::new(vote_pubkey, 5_000);
+
+        let logs = vec![
+            to_log(&DirectedStakeTicketUpdatedEvent {
+                ticket,
+                signer,
+                previous_preferences: vec![previous_preference],
+                new_preferences: vec![new_preference],
+            }),
+            to_log(&SetPausedEvent {
+                authority: signer,
+                previous_paused: false,
+                paused: true,
+            }),
+            to_log(&CopyDirectedStakeTargetsEvent {
+                signer,
+                vote_pubkey,
+                validator_list_index: 3,
+                epoch: 700,
+                requested_target_lamports: 2_000,
+                previous_total_target_lamports: 500,
+                total_target_lamports: 1_500,
+            }),
+        ];
+
+        let events = decode_directed_stake_events_from_logs(&logs);
+        assert_eq!(events.len(), 2);
+
+        match &events[0] {
+            DirectedStakeEvent::TicketUpdated(event) => {
+                assert_eq!(event.ticket, ticket);
+                assert_eq!(event.signer, signer);
+                assert_eq!(
+                    event.previous_preferences[0].vote_pubkey,
+                    previous_preference.vote_pubkey
+                );
+                assert_eq!(event.new_preferences[0].vote_pubkey, vote_pubkey);
+                assert_eq!(event.new_preferences[0].stake_share_bps, 5_000);
+            }
+            _ => panic!("Expected TicketUpdated"),
+        }
+
+        match &events[1] {
+            DirectedStakeEvent::CopyTargets(event) => {
+                assert_eq!(event.vote_pubkey, vote_pubkey);
+                assert_eq!(event.requested_target_lamports, 2_000);
+                assert_eq!(event.total_target_lamports, 1_500);
+            }
+            _ => panic!("Expected CopyTargets"),
+        }
+    }
+}
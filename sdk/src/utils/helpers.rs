@@ -1,6 +1,10 @@
 use std::{collections::HashMap, sync::Arc};
 
-use jito_steward::{constants::BASIS_POINTS_MAX, DirectedStakeMeta, DirectedStakeTicket};
+use jito_steward::{
+    constants::BASIS_POINTS_MAX, DirectedStakeBasket, DirectedStakeContribution, DirectedStakeMeta,
+    DirectedStakePreference, DirectedStakePreferenceSchedule, DirectedStakeTicket,
+    DirectedStakeTicketExtension, MAX_PREFERENCES_PER_TICKET,
+};
 use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
 use solana_sdk::{account::Account, pubkey::Pubkey, stake::state::StakeStateV2};
 use spl_associated_token_account::get_associated_token_address;
@@ -289,16 +293,169 @@ pub fn calculate_conversion_rate_bps(
         .ok_or(JitoInstructionError::ArithmeticError)
 }
 
+/// Returns the time-weighted average balance of each ticket holder over the epoch before
+/// `current_epoch`, as sampled on-chain with `sample_directed_stake_balance`.
+///
+/// `contributions` is keyed by ticket address. Holders without a contribution account or
+/// without samples in the previous epoch get a balance of 0.
+pub fn time_weighted_balances(
+    tickets: &HashMap<Pubkey, DirectedStakeTicket>,
+    contributions: &HashMap<Pubkey, DirectedStakeContribution>,
+    current_epoch: u64,
+) -> HashMap<Pubkey, u64> {
+    tickets
+        .iter()
+        .map(|(ticket_address, ticket)| {
+            let balance = contributions
+                .get(ticket_address)
+                .filter(|contribution| contribution.average_balance_epoch + 1 == current_epoch)
+                .map(|contribution| contribution.average_balance)
+                .unwrap_or(0);
+            (ticket.ticket_update_authority, balance)
+        })
+        .collect()
+}
+
+/// Returns the addresses of the permissionless tickets whose holder's JitoSOL balance is below
+/// `min_balance` and can be closed with `close_underfunded_directed_stake_ticket`.
+///
+/// `holder_balances` is keyed by ticket update authority, holders without a balance count as 0.
+/// A `min_balance` of 0 disables permissionless tickets, making all of them closable.
+pub fn underfunded_permissionless_tickets(
+    tickets: &HashMap<Pubkey, DirectedStakeTicket>,
+    holder_balances: &HashMap<Pubkey, u64>,
+    min_balance: u64,
+) -> Vec<Pubkey> {
+    tickets
+        .iter()
+        .filter(|(_, ticket)| bool::from(ticket.is_permissionless))
+        .filter(|(_, ticket)| {
+            holder_balances
+                .get(&ticket.ticket_update_authority)
+                .copied()
+                .unwrap_or(0)
+                < min_balance
+                || min_balance == 0
+        })
+        .map(|(ticket_address, _)| *ticket_address)
+        .collect()
+}
+
+/// Returns the baskets the preferences of `ticket` point at that neither `preferences` nor
+/// `extension` point at anymore, in the order `update_directed_stake_ticket` expects their
+/// `DirectedStakeBasketContribution` accounts.
+pub fn left_directed_stake_baskets(
+    ticket: &DirectedStakeTicket,
+    extension: Option<&DirectedStakeTicketExtension>,
+    preferences: &[DirectedStakePreference],
+) -> Vec<Pubkey> {
+    let mut updated_ticket = *ticket;
+    let num_preferences = preferences.len().min(MAX_PREFERENCES_PER_TICKET);
+    updated_ticket.num_preferences = num_preferences as u16;
+    updated_ticket.staker_preferences =
+        [DirectedStakePreference::empty(); MAX_PREFERENCES_PER_TICKET];
+    updated_ticket.staker_preferences[..num_preferences]
+        .copy_from_slice(&preferences[..num_preferences]);
+
+    let mut left_baskets: Vec<Pubkey> = Vec::new();
+    for preference in ticket.preferences() {
+        if bool::from(preference.is_basket)
+            && !left_baskets.contains(&preference.vote_pubkey)
+            && DirectedStakeBasket::ticket_stake_share_bps(
+                &preference.vote_pubkey,
+                &updated_ticket,
+                extension,
+            ) == 0
+        {
+            left_baskets.push(preference.vote_pubkey);
+        }
+    }
+    left_baskets
+}
+
+/// Applies the latest scheduled preference set that is due at `epoch` to each ticket, as
+/// `apply_scheduled_directed_stake_preferences` would, and returns the addresses of the tickets
+/// with a due set. `schedules` is keyed by ticket address.
+///
+/// Due sets exceeding 10_000 bps together with the ticket extension are dropped on-chain, so they
+/// are not applied here either.
+pub fn apply_due_scheduled_preferences(
+    tickets: &mut HashMap<Pubkey, DirectedStakeTicket>,
+    schedules: &HashMap<Pubkey, DirectedStakePreferenceSchedule>,
+    epoch: u64,
+) -> Vec<Pubkey> {
+    let mut due_tickets = Vec::new();
+    for (ticket_address, ticket) in tickets.iter_mut() {
+        let Some(due) = schedules
+            .get(ticket_address)
+            .and_then(|schedule| schedule.due(epoch))
+        else {
+            continue;
+        };
+        due_tickets.push(*ticket_address);
+
+        let preferences = due.preferences();
+        let total_bps: u32 = preferences
+            .iter()
+            .map(|pref| pref.stake_share_bps as u32)
+            .sum();
+        if total_bps + ticket.extension_stake_share_bps as u32 > BASIS_POINTS_MAX as u32 {
+            continue;
+        }
+        ticket.num_preferences = preferences.len() as u16;
+        ticket.staker_preferences = [DirectedStakePreference::empty(); MAX_PREFERENCES_PER_TICKET];
+        ticket.staker_preferences[..preferences.len()].copy_from_slice(preferences);
+    }
+    due_tickets
+}
+
 /// Aggregates validator target delegations from all tickets.
 ///
 /// For each ticket and each validator preference, calculates the lamports to allocate
 /// and aggregates them per validator. Initializes all existing validator targets from
 /// the metadata to 0 before aggregation to ensure validators no longer receiving
 /// delegations are properly reset.
+///
+/// `jitosol_balances` can either be spot balances or the output of [`time_weighted_balances`].
 pub fn aggregate_validator_targets(
     tickets: &[DirectedStakeTicket],
     jitosol_balances: &HashMap<Pubkey, u64>,
     conversion_rate_bps: u64,
+) -> Result<HashMap<Pubkey, u64>, JitoInstructionError> {
+    aggregate_validator_targets_with_extensions(
+        tickets,
+        &HashMap::new(),
+        jitosol_balances,
+        conversion_rate_bps,
+    )
+}
+
+/// Same as [`aggregate_validator_targets`], including the preferences of each ticket's
+/// [`DirectedStakeTicketExtension`]. `extensions` is keyed by ticket update authority.
+pub fn aggregate_validator_targets_with_extensions(
+    tickets: &[DirectedStakeTicket],
+    extensions: &HashMap<Pubkey, DirectedStakeTicketExtension>,
+    jitosol_balances: &HashMap<Pubkey, u64>,
+    conversion_rate_bps: u64,
+) -> Result<HashMap<Pubkey, u64>, JitoInstructionError> {
+    aggregate_validator_targets_with_baskets(
+        tickets,
+        extensions,
+        &HashMap::new(),
+        jitosol_balances,
+        conversion_rate_bps,
+    )
+}
+
+/// Same as [`aggregate_validator_targets_with_extensions`], expanding preferences that point at
+/// a [`DirectedStakeBasket`] into the basket's validators by weight. `baskets` is keyed by
+/// basket address, preferences pointing at a missing basket are skipped.
+pub fn aggregate_validator_targets_with_baskets(
+    tickets: &[DirectedStakeTicket],
+    extensions: &HashMap<Pubkey, DirectedStakeTicketExtension>,
+    baskets: &HashMap<Pubkey, DirectedStakeBasket>,
+    jitosol_balances: &HashMap<Pubkey, u64>,
+    conversion_rate_bps: u64,
 ) -> Result<HashMap<Pubkey, u64>, JitoInstructionError> {
     let mut validator_target_delegations: HashMap<Pubkey, u64> = HashMap::new();
 
@@ -312,23 +469,61 @@ pub fn aggregate_validator_targets(
             continue;
         }
 
-        for preference in &ticket.staker_preferences {
+        let extension_preferences = extensions
+            .get(&ticket.ticket_update_authority)
+            .map(|extension| &extension.staker_preferences[..extension.num_preferences as usize])
+            .unwrap_or_default()
+            .iter()
+            .map(|preference| {
+                (
+                    preference.vote_pubkey,
+                    preference.stake_share_bps,
+                    bool::from(preference.is_basket),
+                )
+            });
+
+        for (vote_pubkey, stake_share_bps, is_basket) in ticket
+            .staker_preferences
+            .iter()
+            .map(|preference| {
+                (
+                    preference.vote_pubkey,
+                    preference.stake_share_bps,
+                    bool::from(preference.is_basket),
+                )
+            })
+            .chain(extension_preferences)
+        {
             // Skip default/empty vote pubkeys
-            if preference.vote_pubkey.eq(&Pubkey::default()) {
+            if vote_pubkey.eq(&Pubkey::default()) {
                 continue;
             }
 
-            let allocated_tokens = preference.get_allocation(jitosol_balance);
-            let allocation_lamports = allocated_tokens
-                .checked_mul(conversion_rate_bps as u128)
-                .and_then(|n| n.checked_div(BASIS_POINTS_MAX as u128))
-                .map(|n| n as u64)
-                .ok_or(JitoInstructionError::ArithmeticError)?;
-
-            validator_target_delegations
-                .entry(preference.vote_pubkey)
-                .and_modify(|total| *total = total.saturating_add(allocation_lamports))
-                .or_insert(allocation_lamports);
+            let allocated_tokens = (jitosol_balance as u128)
+                .saturating_mul(stake_share_bps as u128)
+                .saturating_div(BASIS_POINTS_MAX as u128);
+
+            let allocations = if is_basket {
+                match baskets.get(&vote_pubkey) {
+                    Some(basket) => basket.get_allocations(allocated_tokens as u64),
+                    None => continue,
+                }
+            } else {
+                vec![(vote_pubkey, allocated_tokens as u64)]
+            };
+
+            for (vote_pubkey, allocated_tokens) in allocations {
+                let allocation_lamports = (allocated_tokens as u128)
+                    .checked_mul(conversion_rate_bps as u128)
+                    .and_then(|n| n.checked_div(BASIS_POINTS_MAX as u128))
+                    .map(|n| n as u64)
+                    .ok_or(JitoInstructionError::ArithmeticError)?;
+
+                validator_target_delegations
+                    .entry(vote_pubkey)
+                    .and_modify(|total| *total = total.saturating_add(allocation_lamports))
+                    .or_insert(allocation_lamports);
+            }
         }
     }
 
@@ -338,7 +533,8 @@ pub fn aggregate_validator_targets(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use jito_steward::{utils::U8Bool, DirectedStakePreference};
+    use anchor_lang::{AccountDeserialize, Discriminator};
+    use jito_steward::{utils::U8Bool, DirectedStakeExtensionPreference, DirectedStakePreference};
 
     #[test]
     fn test_is_live_vote_account_true_for_vote_owned_account() {
@@ -369,7 +565,8 @@ mod tests {
         let mut staker_preferences = [DirectedStakePreference {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 0,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; 8];
 
         for (i, (vote_pubkey, stake_share_bps)) in preferences.into_iter().enumerate() {
@@ -377,7 +574,8 @@ mod tests {
                 staker_preferences[i] = DirectedStakePreference {
                     vote_pubkey,
                     stake_share_bps,
-                    _padding0: [0; 94],
+                    is_basket: U8Bool::from(false),
+                    _padding0: [0; 93],
                 };
             }
         }
@@ -387,7 +585,9 @@ mod tests {
             ticket_holder_is_protocol: U8Bool::from(true),
             ticket_update_authority: authority,
             staker_preferences,
-            _padding0: [0; 125],
+            is_permissionless: U8Bool::from(false),
+            extension_stake_share_bps: 0,
+            _padding0: [0; 122],
         }
     }
 
@@ -435,6 +635,270 @@ mod tests {
         assert_eq!(*targets.get(&validator2).unwrap(), 40_000_000);
     }
 
+    #[test]
+    fn test_aggregate_validator_targets_with_extensions() {
+        let validator1 = Pubkey::new_unique();
+        let validator2 = Pubkey::new_unique();
+        let authority1 = Pubkey::new_unique();
+
+        let mut jitosol_balances = HashMap::new();
+        jitosol_balances.insert(authority1, 100_000_000);
+
+        let tickets = vec![create_ticket(authority1, vec![(validator1, 2000)])];
+
+        let mut data = DirectedStakeTicketExtension::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakeTicketExtension::SIZE, 0);
+        let mut extension =
+            DirectedStakeTicketExtension::try_deserialize(&mut data.as_slice()).unwrap();
+        for i in 0..100 {
+            extension.staker_preferences[i] = DirectedStakeExtensionPreference::new(
+                if i % 2 == 0 { validator1 } else { validator2 },
+                80,
+            );
+        }
+        extension.num_preferences = 100;
+        // Preferences past num_preferences are ignored
+        extension.staker_preferences[100] = DirectedStakeExtensionPreference::new(validator2, 500);
+
+        let mut extensions = HashMap::new();
+        extensions.insert(authority1, extension);
+
+        let targets = aggregate_validator_targets_with_extensions(
+            &tickets,
+            &extensions,
+            &jitosol_balances,
+            10_000,
+        )
+        .unwrap();
+        assert_eq!(*targets.get(&validator1).unwrap(), 60_000_000);
+        assert_eq!(*targets.get(&validator2).unwrap(), 40_000_000);
+    }
+
+    fn empty_contribution() -> DirectedStakeContribution {
+        let mut data = DirectedStakeContribution::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakeContribution::SIZE, 0);
+        DirectedStakeContribution::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_validator_targets_with_baskets() {
+        use jito_steward::DirectedStakeBasketEntry;
+
+        let validator1 = Pubkey::new_unique();
+        let validator2 = Pubkey::new_unique();
+        let validator3 = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let basket_address = Pubkey::new_unique();
+        let missing_basket = Pubkey::new_unique();
+
+        let mut ticket = create_ticket(authority, vec![(validator1, 2_000)]);
+        ticket.staker_preferences[1] = DirectedStakePreference::basket(basket_address, 5_000);
+        ticket.staker_preferences[2] = DirectedStakePreference::basket(missing_basket, 3_000);
+
+        let mut data = DirectedStakeBasket::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakeBasket::SIZE, 0);
+        let mut basket = DirectedStakeBasket::try_deserialize(&mut data.as_slice()).unwrap();
+        basket.validators[0] = DirectedStakeBasketEntry::new(validator2, 6_000);
+        basket.validators[1] = DirectedStakeBasketEntry::new(validator3, 4_000);
+        basket.num_validators = 2;
+        let mut baskets = HashMap::new();
+        baskets.insert(basket_address, basket);
+
+        let mut jitosol_balances = HashMap::new();
+        jitosol_balances.insert(authority, 100_000_000);
+
+        let targets = aggregate_validator_targets_with_baskets(
+            &[ticket],
+            &HashMap::new(),
+            &baskets,
+            &jitosol_balances,
+            10_000,
+        )
+        .unwrap();
+
+        assert_eq!(targets.len(), 3);
+        assert_eq!(*targets.get(&validator1).unwrap(), 20_000_000);
+        assert_eq!(*targets.get(&validator2).unwrap(), 30_000_000);
+        assert_eq!(*targets.get(&validator3).unwrap(), 20_000_000);
+        assert!(!targets.contains_key(&basket_address));
+        assert!(!targets.contains_key(&missing_basket));
+    }
+
+    #[test]
+    fn test_time_weighted_balances() {
+        let validator1 = Pubkey::new_unique();
+        let validator2 = Pubkey::new_unique();
+        let authority1 = Pubkey::new_unique();
+        let authority2 = Pubkey::new_unique();
+        let authority3 = Pubkey::new_unique();
+        let ticket1 = Pubkey::new_unique();
+        let ticket2 = Pubkey::new_unique();
+        let ticket3 = Pubkey::new_unique();
+
+        let tickets_vec = create_tickets(authority1, authority2, validator1, validator2);
+        let mut tickets = HashMap::new();
+        tickets.insert(ticket1, tickets_vec[0]);
+        tickets.insert(ticket2, tickets_vec[1]);
+        tickets.insert(
+            ticket3,
+            create_ticket(authority3, vec![(validator1, 10_000)]),
+        );
+
+        let mut contribution1 = empty_contribution();
+        contribution1.average_balance = 100_000_000;
+        contribution1.average_balance_epoch = 9;
+        // Average from an older epoch is stale
+        let mut contribution2 = empty_contribution();
+        contribution2.average_balance = 50_000_000;
+        contribution2.average_balance_epoch = 7;
+
+        let mut contributions = HashMap::new();
+        contributions.insert(ticket1, contribution1);
+        contributions.insert(ticket2, contribution2);
+
+        let balances = time_weighted_balances(&tickets, &contributions, 10);
+        assert_eq!(balances.len(), 3);
+        assert_eq!(*balances.get(&authority1).unwrap(), 100_000_000);
+        assert_eq!(*balances.get(&authority2).unwrap(), 0);
+        assert_eq!(*balances.get(&authority3).unwrap(), 0);
+
+        let tickets: Vec<DirectedStakeTicket> = tickets.values().copied().collect();
+        let targets = aggregate_validator_targets(&tickets, &balances, 10_000).unwrap();
+        assert_eq!(*targets.get(&validator1).unwrap(), 60_000_000);
+        assert_eq!(*targets.get(&validator2).unwrap(), 40_000_000);
+    }
+
+    #[test]
+    fn test_underfunded_permissionless_tickets() {
+        let validator = Pubkey::new_unique();
+        let funded = Pubkey::new_unique();
+        let underfunded = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+        let whitelisted = Pubkey::new_unique();
+
+        let mut tickets = HashMap::new();
+        for authority in [funded, underfunded, missing] {
+            let mut ticket = create_ticket(authority, vec![(validator, 10_000)]);
+            ticket.is_permissionless = U8Bool::from(true);
+            tickets.insert(authority, ticket);
+        }
+        tickets.insert(
+            whitelisted,
+            create_ticket(whitelisted, vec![(validator, 10_000)]),
+        );
+
+        let mut balances = HashMap::new();
+        balances.insert(funded, 1_000);
+        balances.insert(underfunded, 999);
+        balances.insert(whitelisted, 0);
+
+        let mut closable = underfunded_permissionless_tickets(&tickets, &balances, 1_000);
+        closable.sort();
+        let mut expected = vec![underfunded, missing];
+        expected.sort();
+        assert_eq!(closable, expected);
+
+        // Disabled permissionless tickets can all be closed, whitelisted tickets are never closed
+        let mut closable = underfunded_permissionless_tickets(&tickets, &balances, 0);
+        closable.sort();
+        let mut expected = vec![funded, underfunded, missing];
+        expected.sort();
+        assert_eq!(closable, expected);
+    }
+
+    #[test]
+    fn test_left_directed_stake_baskets() {
+        let validator = Pubkey::new_unique();
+        let kept_basket = Pubkey::new_unique();
+        let left_basket = Pubkey::new_unique();
+        let extension_basket = Pubkey::new_unique();
+
+        let mut ticket = create_ticket(Pubkey::new_unique(), vec![(validator, 1_000)]);
+        ticket.staker_preferences[1] = DirectedStakePreference::basket(left_basket, 1_000);
+        ticket.staker_preferences[2] = DirectedStakePreference::basket(kept_basket, 1_000);
+        ticket.staker_preferences[3] = DirectedStakePreference::basket(left_basket, 1_000);
+        ticket.staker_preferences[4] = DirectedStakePreference::basket(extension_basket, 1_000);
+        ticket.num_preferences = 5;
+
+        let mut data = DirectedStakeTicketExtension::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakeTicketExtension::SIZE, 0);
+        let mut extension =
+            DirectedStakeTicketExtension::try_deserialize(&mut data.as_slice()).unwrap();
+        extension.staker_preferences[0] =
+            DirectedStakeExtensionPreference::basket(extension_basket, 1_000);
+        extension.num_preferences = 1;
+
+        let preferences = vec![
+            DirectedStakePreference::new(validator, 5_000),
+            DirectedStakePreference::basket(kept_basket, 5_000),
+        ];
+        // A basket pointed at twice is only listed once
+        assert_eq!(
+            left_directed_stake_baskets(&ticket, Some(&extension), &preferences),
+            vec![left_basket]
+        );
+        assert_eq!(
+            left_directed_stake_baskets(&ticket, None, &preferences),
+            vec![left_basket, extension_basket]
+        );
+        assert!(left_directed_stake_baskets(&ticket, None, ticket.preferences()).is_empty());
+    }
+
+    #[test]
+    fn test_apply_due_scheduled_preferences() {
+        let authority = Pubkey::new_unique();
+        let current_validator = Pubkey::new_unique();
+        let scheduled_validator = Pubkey::new_unique();
+        let later_validator = Pubkey::new_unique();
+        let ticket_address = Pubkey::new_unique();
+
+        let mut data = DirectedStakePreferenceSchedule::DISCRIMINATOR.to_vec();
+        data.resize(DirectedStakePreferenceSchedule::SIZE, 0);
+        let mut schedule =
+            DirectedStakePreferenceSchedule::try_deserialize(&mut data.as_slice()).unwrap();
+        schedule.ticket = ticket_address;
+        schedule
+            .schedule(20, &[DirectedStakePreference::new(later_validator, 10_000)])
+            .unwrap();
+        schedule
+            .schedule(
+                10,
+                &[DirectedStakePreference::new(scheduled_validator, 5_000)],
+            )
+            .unwrap();
+        let schedules = HashMap::from([(ticket_address, schedule)]);
+
+        let mut ticket = create_ticket(authority, vec![(current_validator, 10_000)]);
+        ticket.num_preferences = 1;
+        let original = HashMap::from([(ticket_address, ticket)]);
+
+        // Nothing is due before the first activation epoch
+        let mut tickets = original.clone();
+        assert!(apply_due_scheduled_preferences(&mut tickets, &schedules, 9).is_empty());
+        assert_eq!(
+            tickets[&ticket_address].preferences()[0].vote_pubkey,
+            current_validator
+        );
+
+        let mut tickets = original.clone();
+        assert_eq!(
+            apply_due_scheduled_preferences(&mut tickets, &schedules, 10),
+            vec![ticket_address]
+        );
+        let preferences = tickets[&ticket_address].preferences();
+        assert_eq!(preferences.len(), 1);
+        assert_eq!(preferences[0].vote_pubkey, scheduled_validator);
+        assert_eq!(preferences[0].stake_share_bps, 5_000);
+
+        // Only the latest due set is applied
+        let mut tickets = original;
+        apply_due_scheduled_preferences(&mut tickets, &schedules, 25);
+        assert_eq!(
+            tickets[&ticket_address].preferences()[0].vote_pubkey,
+            later_validator
+        );
+    }
+
     #[test]
     fn test_aggregate_multiple_tickets_same_validator() {
         let validator1 = Pubkey::new_unique();
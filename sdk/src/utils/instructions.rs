@@ -1,29 +1,64 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+    sync::Arc,
+};
 
 use anchor_lang::{InstructionData, ToAccountMetas};
-use jito_steward::{DirectedStakePreference, DirectedStakeTicket};
+use jito_steward::{
+    DirectedStakeBalanceSources, DirectedStakeBasket, DirectedStakeBasketContribution,
+    DirectedStakeBasketEntry, DirectedStakeExtensionPreference, DirectedStakePreference,
+    DirectedStakeTicket, DirectedStakeTicketExtension, PledgeCommissionArgs, ScoreWarnings,
+};
 use kobe_client::client::KobeClient;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
-    instruction::Instruction,
+    account::Account,
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
 };
+use spl_associated_token_account::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+use spl_stake_pool::{find_stake_program_address, find_transient_stake_program_address};
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
 use validator_history::{constants::MAX_ALLOC_BYTES, ValidatorHistory};
 
 use crate::{
-    models::errors::JitoInstructionError,
+    models::{
+        aggregate_accounts::AllStewardAccounts,
+        errors::{JitoInstructionError, JitoTransactionError},
+    },
     utils::{
         accounts::{
-            get_directed_stake_meta_address, get_directed_stake_ticket_address,
-            get_directed_stake_tickets, get_directed_stake_whitelist_address,
-            get_stake_pool_account, get_steward_config_account, get_validator_list_account,
+            get_commission_pledge_address, get_directed_stake_balance_sources,
+            get_directed_stake_balance_sources_address, get_directed_stake_basket_address,
+            get_directed_stake_basket_contribution_address,
+            get_directed_stake_basket_contributions, get_directed_stake_baskets,
+            get_directed_stake_contribution_address, get_directed_stake_contributions,
+            get_directed_stake_extension_contribution_address, get_directed_stake_meta_address,
+            get_directed_stake_preference_schedule_address,
+            get_directed_stake_preference_schedules, get_directed_stake_ticket_address,
+            get_directed_stake_ticket_extension_address, get_directed_stake_ticket_extensions,
+            get_directed_stake_tickets, get_directed_stake_validator_consent_address,
+            get_directed_stake_whitelist_address, get_instant_unstake_fractions_address,
+            get_rebalance_journal_address, get_score_warnings_address, get_stake_pool_account,
+            get_steward_config_account, get_validator_exit_address, get_validator_list_account,
         },
-        helpers::{aggregate_validator_targets, calculate_conversion_rate_bps, get_token_balance},
+        balance_attribution::BalanceAttributionRegistry,
+        helpers::{
+            aggregate_validator_targets_with_baskets, apply_due_scheduled_preferences,
+            calculate_conversion_rate_bps, get_token_balance, time_weighted_balances,
+            underfunded_permissionless_tickets, ProgressionInfo,
+        },
+        transactions::get_multiple_accounts_batched,
     },
 };
 
-use super::accounts::{get_validator_history_address, get_validator_history_config_address};
+use super::accounts::{
+    get_steward_state_address, get_validator_history_address, get_validator_history_config_address,
+};
 
 pub fn get_create_validator_history_instructions(
     vote_account: &Pubkey,
@@ -65,32 +100,531 @@ pub fn get_create_validator_history_instructions(
     ixs
 }
 
+/// Read-only metas of the [`jito_steward::DirectedStakeValidatorConsent`] PDAs of `vote_pubkeys`,
+/// passed as remaining accounts to instructions that check validator consent.
+pub fn validator_consent_account_metas<'a>(
+    steward_config: &Pubkey,
+    vote_pubkeys: impl IntoIterator<Item = &'a Pubkey>,
+    program_id: &Pubkey,
+) -> Vec<AccountMeta> {
+    vote_pubkeys
+        .into_iter()
+        .map(|vote_pubkey| {
+            AccountMeta::new_readonly(
+                get_directed_stake_validator_consent_address(
+                    steward_config,
+                    vote_pubkey,
+                    program_id,
+                ),
+                false,
+            )
+        })
+        .collect()
+}
+
+/// Read-only metas of the accounts of a ticket's `balance_sources`, passed as remaining accounts
+/// to instructions that count the ticket holder's balance.
+pub fn balance_source_account_metas(
+    balance_sources: Option<&DirectedStakeBalanceSources>,
+) -> Vec<AccountMeta> {
+    balance_sources
+        .map(|sources| {
+            sources
+                .sources
+                .iter()
+                .take(sources.num_sources as usize)
+                .map(|source| AccountMeta::new_readonly(source.account, false))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Creates an instruction to update a directed stake ticket.
 ///
 /// This instruction allows a signer to update their stake delegation preferences by specifying
-/// which validators they want to direct their stake to and in what proportions.
+/// which validators they want to direct their stake to and in what proportions. Preferences
+/// created with [`DirectedStakePreference::basket`] direct stake to a [`jito_steward::DirectedStakeBasket`].
+///
+/// `left_baskets` are the baskets the ticket stops pointing at, as returned by
+/// [`left_directed_stake_baskets`](crate::utils::helpers::left_directed_stake_baskets), whose
+/// contributions are removed by the update.
 pub fn update_directed_stake_ticket(
     program_id: &Pubkey,
     steward_config: &Pubkey,
     ticket_update_authority: &Pubkey,
     signer: &Pubkey,
     preferences: Vec<DirectedStakePreference>,
+    left_baskets: &[Pubkey],
 ) -> Instruction {
     let whitelist_account = get_directed_stake_whitelist_address(steward_config, program_id);
     let ticket_account =
         get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
 
+    let mut accounts = jito_steward::accounts::UpdateDirectedStakeTicket {
+        config: *steward_config,
+        whitelist_account,
+        ticket_account,
+        directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+        ticket_extension: get_directed_stake_ticket_extension_address(&ticket_account, program_id),
+        signer: *signer,
+    }
+    .to_account_metas(None);
+    accounts.extend(preference_account_metas(
+        steward_config,
+        &preferences,
+        program_id,
+    ));
+    accounts.extend(left_baskets.iter().map(|basket| {
+        AccountMeta::new(
+            get_directed_stake_basket_contribution_address(&ticket_account, basket, program_id),
+            false,
+        )
+    }));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: jito_steward::instruction::UpdateDirectedStakeTicket { preferences }.data(),
+    }
+}
+
+/// Remaining accounts checked against ticket preferences: the consent PDA of each preference's
+/// validator, or the basket itself for preferences pointing at a basket
+fn preference_account_metas(
+    steward_config: &Pubkey,
+    preferences: &[DirectedStakePreference],
+    program_id: &Pubkey,
+) -> Vec<AccountMeta> {
+    preference_target_account_metas(
+        steward_config,
+        preferences
+            .iter()
+            .map(|preference| (&preference.vote_pubkey, bool::from(preference.is_basket))),
+        program_id,
+    )
+}
+
+/// Same as [`preference_account_metas`] for `(vote_pubkey, is_basket)` pairs
+fn preference_target_account_metas<'a>(
+    steward_config: &Pubkey,
+    preferences: impl IntoIterator<Item = (&'a Pubkey, bool)>,
+    program_id: &Pubkey,
+) -> Vec<AccountMeta> {
+    preferences
+        .into_iter()
+        .map(|(vote_pubkey, is_basket)| {
+            if is_basket {
+                AccountMeta::new_readonly(*vote_pubkey, false)
+            } else {
+                AccountMeta::new_readonly(
+                    get_directed_stake_validator_consent_address(
+                        steward_config,
+                        vote_pubkey,
+                        program_id,
+                    ),
+                    false,
+                )
+            }
+        })
+        .collect()
+}
+
+/// Creates the instructions scheduling `preferences` to replace the preferences of the ticket of
+/// `ticket_update_authority` at `activation_epoch`, preceded by
+/// `InitializeDirectedStakePreferenceSchedule` when `initialize` is set. Initialization must be
+/// signed by the ticket update authority.
+pub fn schedule_directed_stake_preferences(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_update_authority: &Pubkey,
+    signer: &Pubkey,
+    activation_epoch: u64,
+    preferences: Vec<DirectedStakePreference>,
+    initialize: bool,
+) -> Vec<Instruction> {
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
+    let preference_schedule =
+        get_directed_stake_preference_schedule_address(&ticket_account, program_id);
+
+    let mut instructions = Vec::new();
+    if initialize {
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::InitializeDirectedStakePreferenceSchedule {
+                config: *steward_config,
+                ticket_account,
+                preference_schedule,
+                system_program: solana_program::system_program::id(),
+                authority: *signer,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakePreferenceSchedule {}.data(),
+        });
+    }
+
+    let mut accounts = jito_steward::accounts::ScheduleDirectedStakePreferences {
+        config: *steward_config,
+        whitelist_account: get_directed_stake_whitelist_address(steward_config, program_id),
+        ticket_account,
+        preference_schedule,
+        signer: *signer,
+    }
+    .to_account_metas(None);
+    accounts.extend(preference_account_metas(
+        steward_config,
+        &preferences,
+        program_id,
+    ));
+    instructions.push(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: jito_steward::instruction::ScheduleDirectedStakePreferences {
+            activation_epoch,
+            preferences,
+        }
+        .data(),
+    });
+
+    instructions
+}
+
+/// Creates an instruction cancelling the preference change scheduled for `activation_epoch` on
+/// the ticket of `ticket_update_authority`
+pub fn cancel_scheduled_directed_stake_preferences(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_update_authority: &Pubkey,
+    signer: &Pubkey,
+    activation_epoch: u64,
+) -> Instruction {
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::CancelScheduledDirectedStakePreferences {
+            config: *steward_config,
+            ticket_account,
+            preference_schedule: get_directed_stake_preference_schedule_address(
+                &ticket_account,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::CancelScheduledDirectedStakePreferences {
+            activation_epoch,
+        }
+        .data(),
+    }
+}
+
+/// Creates a permissionless instruction applying the due scheduled preferences of `ticket_account`.
+/// `preferences` is the latest due set and `validator_list_indices[i]` the validator list index of
+/// its i-th preference, or `u32::MAX` if the validator is not in the pool.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_scheduled_directed_stake_preferences(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list: &Pubkey,
+    ticket_account: &Pubkey,
+    preferences: &[DirectedStakePreference],
+    validator_list_indices: Vec<u32>,
+) -> Instruction {
+    let mut accounts = jito_steward::accounts::ApplyScheduledDirectedStakePreferences {
+        config: *steward_config,
+        whitelist_account: get_directed_stake_whitelist_address(steward_config, program_id),
+        ticket_account: *ticket_account,
+        preference_schedule: get_directed_stake_preference_schedule_address(
+            ticket_account,
+            program_id,
+        ),
+        directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+        contribution_account: get_directed_stake_contribution_address(ticket_account, program_id),
+        stake_pool: *stake_pool,
+        validator_list: *validator_list,
+    }
+    .to_account_metas(None);
+    accounts.extend(preference_account_metas(
+        steward_config,
+        preferences,
+        program_id,
+    ));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: jito_steward::instruction::ApplyScheduledDirectedStakePreferences {
+            validator_list_indices,
+        }
+        .data(),
+    }
+}
+
+/// Creates an instruction closing the preference schedule of `ticket_account`, returning the rent
+/// to `signer`. Must be signed by the ticket update authority unless the ticket has been closed.
+pub fn close_directed_stake_preference_schedule(
+    program_id: &Pubkey,
+    ticket_account: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
     Instruction {
         program_id: *program_id,
-        accounts: jito_steward::accounts::UpdateDirectedStakeTicket {
+        accounts: jito_steward::accounts::CloseDirectedStakePreferenceSchedule {
+            ticket_account: *ticket_account,
+            preference_schedule: get_directed_stake_preference_schedule_address(
+                ticket_account,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::CloseDirectedStakePreferenceSchedule {}.data(),
+    }
+}
+
+/// Builds the permissionless instructions applying the scheduled preferences of every ticket of
+/// `steward_config` with a set due in the current epoch
+pub async fn compute_scheduled_directed_stake_preference_applications(
+    client: Arc<RpcClient>,
+    steward_config: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let mut tickets: HashMap<Pubkey, DirectedStakeTicket> =
+        get_directed_stake_tickets(client.clone(), program_id)
+            .await?
+            .into_iter()
+            .filter(|(address, ticket)| {
+                *address
+                    == get_directed_stake_ticket_address(
+                        steward_config,
+                        &ticket.ticket_update_authority,
+                        program_id,
+                    )
+            })
+            .collect();
+    let schedules = get_directed_stake_preference_schedules(client.clone(), program_id).await?;
+    let epoch = client.get_epoch_info().await?.epoch;
+
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
+    let validator_list_account =
+        get_validator_list_account(&client, &stake_pool_account.validator_list).await?;
+    let validator_list_index_map: HashMap<Pubkey, u32> = validator_list_account
+        .validators
+        .iter()
+        .enumerate()
+        .map(|(index, v)| (v.vote_account_address, index as u32))
+        .collect();
+
+    Ok(
+        apply_due_scheduled_preferences(&mut tickets, &schedules, epoch)
+            .iter()
+            .filter_map(|ticket_address| {
+                let due = schedules.get(ticket_address)?.due(epoch)?;
+                let preferences = due.preferences();
+                let validator_list_indices = preferences
+                    .iter()
+                    .map(|preference| {
+                        validator_list_index_map
+                            .get(&preference.vote_pubkey)
+                            .copied()
+                            .unwrap_or(u32::MAX)
+                    })
+                    .collect();
+                Some(apply_scheduled_directed_stake_preferences(
+                    program_id,
+                    steward_config,
+                    &config_account.stake_pool,
+                    &stake_pool_account.validator_list,
+                    ticket_address,
+                    preferences,
+                    validator_list_indices,
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// Number of extension preferences cranked by a single `CrankDirectedStakeTicketExtension`
+pub const EXTENSION_PREFERENCES_PER_CRANK: usize = 16;
+
+/// Number of basket validators cranked by a single `CrankDirectedStakeTicketBasket`
+pub const BASKET_VALIDATORS_PER_CRANK: usize = 16;
+
+/// Number of extension preferences written by a single `UpdateDirectedStakeTicketExtension`,
+/// keeping the transaction under the size limit
+pub const EXTENSION_PREFERENCES_PER_UPDATE: usize = 16;
+
+/// Creates the instructions setting the extension preferences of a ticket, preceded by
+/// `InitializeDirectedStakeTicketExtension` when `initialize` is set. The preferences are split
+/// across [`EXTENSION_PREFERENCES_PER_UPDATE`] sized instructions, each of which should be sent
+/// in its own transaction. Initialization must be signed by the ticket update authority.
+/// Preferences created with [`DirectedStakeExtensionPreference::basket`] direct stake to a
+/// [`jito_steward::DirectedStakeBasket`].
+pub fn update_directed_stake_ticket_extension(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_update_authority: &Pubkey,
+    signer: &Pubkey,
+    preferences: Vec<DirectedStakeExtensionPreference>,
+    initialize: bool,
+) -> Vec<Instruction> {
+    let whitelist_account = get_directed_stake_whitelist_address(steward_config, program_id);
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
+    let ticket_extension = get_directed_stake_ticket_extension_address(&ticket_account, program_id);
+
+    let mut instructions = Vec::new();
+    if initialize {
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::InitializeDirectedStakeTicketExtension {
+                config: *steward_config,
+                ticket_account,
+                ticket_extension,
+                extension_contribution: get_directed_stake_extension_contribution_address(
+                    &ticket_account,
+                    program_id,
+                ),
+                system_program: solana_program::system_program::id(),
+                authority: *ticket_update_authority,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeTicketExtension {}.data(),
+        });
+    }
+
+    let num_preferences = preferences.len() as u16;
+    // Clearing all preferences still takes one instruction
+    let chunks: Vec<&[DirectedStakeExtensionPreference]> = if preferences.is_empty() {
+        vec![&[]]
+    } else {
+        preferences
+            .chunks(EXTENSION_PREFERENCES_PER_UPDATE)
+            .collect()
+    };
+    for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+        let mut accounts = jito_steward::accounts::UpdateDirectedStakeTicketExtension {
             config: *steward_config,
             whitelist_account,
             ticket_account,
+            ticket_extension,
+            signer: *signer,
+        }
+        .to_account_metas(None);
+        accounts.extend(preference_target_account_metas(
+            steward_config,
+            chunk
+                .iter()
+                .map(|preference| (&preference.vote_pubkey, bool::from(preference.is_basket))),
+            program_id,
+        ));
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts,
+            data: jito_steward::instruction::UpdateDirectedStakeTicketExtension {
+                start_index: (chunk_index * EXTENSION_PREFERENCES_PER_UPDATE) as u16,
+                num_preferences,
+                preferences: chunk.to_vec(),
+            }
+            .data(),
+        });
+    }
+    instructions
+}
+
+/// Creates the instructions setting whether a validator accepts directed stake and the maximum
+/// directed stake target it accepts (0 for no maximum), preceded by
+/// `InitializeDirectedStakeValidatorConsent` when `initialize` is set. `signer` must be the
+/// validator identity or the vote account's authorized withdrawer.
+pub fn update_directed_stake_validator_consent(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    signer: &Pubkey,
+    opted_out: bool,
+    max_directed_stake_lamports: u64,
+    initialize: bool,
+) -> Vec<Instruction> {
+    let validator_consent =
+        get_directed_stake_validator_consent_address(steward_config, vote_account, program_id);
+
+    let mut instructions = Vec::new();
+    if initialize {
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::InitializeDirectedStakeValidatorConsent {
+                config: *steward_config,
+                vote_account: *vote_account,
+                validator_consent,
+                system_program: solana_program::system_program::id(),
+                signer: *signer,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeValidatorConsent {}.data(),
+        });
+    }
+    instructions.push(Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::UpdateDirectedStakeValidatorConsent {
+            config: *steward_config,
+            vote_account: *vote_account,
+            validator_consent,
             signer: *signer,
         }
         .to_account_metas(None),
-        data: jito_steward::instruction::UpdateDirectedStakeTicket { preferences }.data(),
+        data: jito_steward::instruction::UpdateDirectedStakeValidatorConsent {
+            opted_out,
+            max_directed_stake_lamports,
+        }
+        .data(),
+    });
+    instructions
+}
+
+/// Creates the instructions pledging maximum commissions until `args.end_epoch`, preceded by
+/// `InitializeCommissionPledge` when `initialize` is set. `authorized_withdrawer` must be the vote
+/// account's authorized withdrawer.
+pub fn pledge_commission(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    authorized_withdrawer: &Pubkey,
+    args: PledgeCommissionArgs,
+    initialize: bool,
+) -> Vec<Instruction> {
+    let commission_pledge = get_commission_pledge_address(steward_config, vote_account, program_id);
+
+    let mut instructions = Vec::new();
+    if initialize {
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::InitializeCommissionPledge {
+                config: *steward_config,
+                vote_account: *vote_account,
+                commission_pledge,
+                system_program: solana_program::system_program::id(),
+                authorized_withdrawer: *authorized_withdrawer,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeCommissionPledge {}.data(),
+        });
     }
+    instructions.push(Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::PledgeCommission {
+            config: *steward_config,
+            vote_account: *vote_account,
+            commission_pledge,
+            authorized_withdrawer: *authorized_withdrawer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::PledgeCommission { args }.data(),
+    });
+    instructions
 }
 
 /// Computes directed stake metadata and generates instructions to update stake targets for
@@ -99,13 +633,18 @@ pub fn update_directed_stake_ticket(
 /// This function aggregates directed stake ticket holders' preferences, converts JitoSOL balances
 /// to lamports, and emits one `CopyDirectedStakeTargets` instruction per validator that appears in
 /// a current ticket. Validators that are no longer referenced by any ticket are left untouched —
-/// they are **not** reset to `0`.
+/// they are **not** reset to `0`. Such targets decay on-chain once they go unrefreshed for
+/// `directed_stake_target_expiry_epochs`, and targets of closed tickets can be removed with
+/// `clear_closed_directed_stake_ticket`.
 ///
 /// # Process Overview
 ///
-/// 1. Fetches all directed stake tickets from the program
+/// 1. Fetches all directed stake tickets from the program, applying scheduled preference sets
+///    that are due in the current epoch (see [`apply_due_scheduled_preferences`])
 /// 2. For each ticket holder:
-///    - Retrieves their JitoSOL token balance
+///    - Retrieves their JitoSOL token balance, or their time-weighted average balance over the
+///      previous epoch when `directed_stake_sample_interval_slots` is set (see
+///      [`compute_directed_stake_balance_samples`])
 ///    - Converts JitoSOL to lamports using the stake pool's conversion rate
 ///    - Applies their allocation preferences across validators
 /// 3. Aggregates total target delegations per validator (only from tickets with a balance)
@@ -120,6 +659,14 @@ pub fn update_directed_stake_ticket(
 /// conversion_rate_bps = (stake_pool.total_lamports * 10,000) / pool_token_supply
 /// allocation_lamports = (allocation_jitosol * conversion_rate_bps) / 10,000
 /// ```
+///
+/// Uploaded targets are added on top of the targets contributed by cranked tickets, so this
+/// must not be used for tickets that are cranked with [`compute_directed_stake_ticket_cranks`].
+/// Once `migrate_directed_targets_to_tickets` has run, uploads for validators with ticket
+/// contributions are rejected.
+///
+/// Balance sources declared by tickets are counted with the default
+/// [`BalanceAttributionRegistry`], see [`compute_directed_stake_meta_with_registry`].
 pub async fn compute_directed_stake_meta(
     client: Arc<RpcClient>,
     token_mint_address: &Pubkey,
@@ -128,29 +675,104 @@ pub async fn compute_directed_stake_meta(
     authority_pubkey: &Pubkey,
     program_id: &Pubkey,
 ) -> Result<Vec<Instruction>, JitoInstructionError> {
-    let ticket_map = get_directed_stake_tickets(client.clone(), program_id).await?;
+    compute_directed_stake_meta_with_registry(
+        client,
+        token_mint_address,
+        stake_pool_address,
+        steward_config,
+        authority_pubkey,
+        program_id,
+        &BalanceAttributionRegistry::default(),
+    )
+    .await
+}
+
+/// Same as [`compute_directed_stake_meta`], adding the JitoSOL held in each ticket's
+/// [`jito_steward::DirectedStakeBalanceSources`] to the holder's balance using the adapters of
+/// `registry`. Balance sources are not sampled on-chain, so they are only counted when
+/// `directed_stake_sample_interval_slots` is disabled.
+pub async fn compute_directed_stake_meta_with_registry(
+    client: Arc<RpcClient>,
+    token_mint_address: &Pubkey,
+    stake_pool_address: &Pubkey,
+    steward_config: &Pubkey,
+    authority_pubkey: &Pubkey,
+    program_id: &Pubkey,
+    registry: &BalanceAttributionRegistry,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let mut ticket_map = get_directed_stake_tickets(client.clone(), program_id).await?;
+    let current_epoch = client.get_epoch_info().await?.epoch;
+
+    // Due scheduled preferences count even if `apply_scheduled_directed_stake_preferences` has
+    // not been cranked yet
+    let schedules = get_directed_stake_preference_schedules(client.clone(), program_id).await?;
+    apply_due_scheduled_preferences(&mut ticket_map, &schedules, current_epoch);
 
     let stake_pool = get_stake_pool_account(&client.clone(), stake_pool_address).await?;
     let conversion_rate_bps =
         calculate_conversion_rate_bps(stake_pool.total_lamports, stake_pool.pool_token_supply)?;
 
-    let mut jitosol_balances = HashMap::new();
-    for ticket in ticket_map.values().copied() {
-        let balance = get_token_balance(
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+
+    let jitosol_balances = if config_account
+        .parameters
+        .directed_stake_sample_interval_slots
+        > 0
+    {
+        let contributions = get_directed_stake_contributions(client.clone(), program_id).await?;
+        time_weighted_balances(&ticket_map, &contributions, current_epoch)
+    } else {
+        let mut jitosol_balances = HashMap::new();
+        for ticket in ticket_map.values().copied() {
+            let balance = get_token_balance(
+                client.clone(),
+                token_mint_address,
+                &ticket.ticket_update_authority,
+            )
+            .await?;
+            jitosol_balances.insert(ticket.ticket_update_authority, balance);
+        }
+
+        let attributed_balances = get_attributed_balances(
             client.clone(),
+            &ticket_map,
             token_mint_address,
-            &ticket.ticket_update_authority,
+            &stake_pool.token_program_id,
+            registry,
+            program_id,
         )
         .await?;
-        jitosol_balances.insert(ticket.ticket_update_authority, balance);
-    }
+        for (holder, balance) in attributed_balances {
+            jitosol_balances
+                .entry(holder)
+                .and_modify(|total| *total = total.saturating_add(balance))
+                .or_insert(balance);
+        }
+        jitosol_balances
+    };
+
+    let extensions: HashMap<Pubkey, DirectedStakeTicketExtension> =
+        get_directed_stake_ticket_extensions(client.clone(), program_id)
+            .await?
+            .into_iter()
+            .filter_map(|(ticket_address, extension)| {
+                let ticket = ticket_map.get(&ticket_address)?;
+                Some((ticket.ticket_update_authority, extension))
+            })
+            .collect();
+
+    let baskets = get_directed_stake_baskets(client.clone(), program_id).await?;
 
     let tickets: Vec<DirectedStakeTicket> = ticket_map.values().copied().collect();
-    let validator_targets =
-        aggregate_validator_targets(&tickets, &jitosol_balances, conversion_rate_bps)?;
+    let validator_targets = aggregate_validator_targets_with_baskets(
+        &tickets,
+        &extensions,
+        &baskets,
+        &jitosol_balances,
+        conversion_rate_bps,
+    )?;
 
     // Get validator list to find indices
-    let config_account = get_steward_config_account(&client, steward_config).await?;
     let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
     let validator_list_address = stake_pool_account.validator_list;
     let validator_list_account =
@@ -175,6 +797,11 @@ pub async fn compute_directed_stake_meta(
                     authority: *authority_pubkey,
                     clock: solana_sdk::sysvar::clock::id(),
                     validator_list: validator_list_address,
+                    validator_consent: get_directed_stake_validator_consent_address(
+                        steward_config,
+                        &vote_pubkey,
+                        program_id,
+                    ),
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::CopyDirectedStakeTargets {
@@ -190,62 +817,945 @@ pub async fn compute_directed_stake_meta(
     Ok(instructions)
 }
 
-/// Computes the directed stake target for the Coinbase validator.
-///
-/// Fetches the Coinbase balance for the previous epoch from the Kobe API and builds a
-/// `CopyDirectedStakeTargets` instruction to set the stake target for `coinbase_vote_pubkey`.
-///
-/// # Process Overview
-///
-/// 1. Fetches the Coinbase balance for the previous epoch via the Kobe API.
-/// 2. Loads the on-chain `DirectedStakeMeta` account and collects any existing targets that
-///    were last updated in the current epoch.
-/// 3. Locates `coinbase_vote_pubkey` in the validator list.
-/// 4. Computes `total_target_lamports` as the Coinbase balance plus any existing target for
-///    this validator in the current epoch (saturating addition).
-/// 5. Emits one `CopyDirectedStakeTargets` instruction for the validator.
-///
-/// # Return Value
-///
-/// Returns an empty vector if:
-/// - The Kobe API returns no Coinbase balance (`coinbase_balance` is `None`), or
-/// - `coinbase_vote_pubkey` is not present in the on-chain validator list.
-pub async fn compute_coinbase_targets(
+/// Fetches the balance sources declared by `tickets` and returns the JitoSOL attributed to
+/// each ticket holder. The holders' associated token accounts are excluded since they are
+/// already counted.
+pub async fn get_attributed_balances(
     client: Arc<RpcClient>,
-    kobe_client: &KobeClient,
-    steward_config: &Pubkey,
-    authority_pubkey: &Pubkey,
+    tickets: &HashMap<Pubkey, DirectedStakeTicket>,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    registry: &BalanceAttributionRegistry,
     program_id: &Pubkey,
-    coinbase_vote_pubkey: &Pubkey,
-) -> Result<Vec<Instruction>, JitoInstructionError> {
-    let epoch_info = client.get_epoch_info().await?;
-    let last_epoch = epoch_info.epoch - 1;
+) -> Result<HashMap<Pubkey, u64>, JitoInstructionError> {
+    let balance_sources = get_directed_stake_balance_sources(client.clone(), program_id).await?;
 
-    let coinbase_balance = kobe_client.get_coinbase_balance(last_epoch).await?;
+    let source_addresses: Vec<Pubkey> = balance_sources
+        .values()
+        .flat_map(|sources| {
+            sources
+                .sources
+                .iter()
+                .take(sources.num_sources as usize)
+                .map(|source| source.account)
+        })
+        .collect();
+    let source_accounts = get_multiple_accounts_batched(&source_addresses, &client)
+        .await
+        .map_err(JitoTransactionError::from)?;
+    let accounts: HashMap<Pubkey, Account> = source_addresses
+        .into_iter()
+        .zip(source_accounts)
+        .filter_map(|(address, account)| Some((address, account?)))
+        .collect();
 
-    let directed_stake_meta_pda = get_directed_stake_meta_address(steward_config, program_id);
-    let config_account = get_steward_config_account(&client, steward_config).await?;
-    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
-    let validator_list_address = stake_pool_account.validator_list;
-    let validator_list_account =
-        get_validator_list_account(&client, &validator_list_address).await?;
+    let excluded_accounts: HashSet<Pubkey> = tickets
+        .values()
+        .flat_map(|ticket| {
+            [
+                get_associated_token_address(&ticket.ticket_update_authority, pool_mint),
+                get_associated_token_address_with_program_id(
+                    &ticket.ticket_update_authority,
+                    pool_mint,
+                    token_program_id,
+                ),
+            ]
+        })
+        .collect();
 
-    let mut instructions = Vec::new();
+    Ok(registry.attributed_balances(
+        tickets,
+        &balance_sources,
+        &accounts,
+        &excluded_accounts,
+        pool_mint,
+    ))
+}
 
-    if let Some(cb_balance) = coinbase_balance.coinbase_balance {
-        if let Some(validator_list_index) = validator_list_account
-            .validators
-            .iter()
-            .position(|v| v.vote_account_address.eq(coinbase_vote_pubkey))
-        {
-            let ix = Instruction {
-                program_id: *program_id,
-                accounts: jito_steward::accounts::CopyDirectedStakeTargets {
-                    config: *steward_config,
+/// Creates the instructions to initialize a ticket's balance sources account, if needed, and
+/// attribute `source_account` to the ticket. Must be signed by both the ticket update authority
+/// and `source_owner`.
+pub fn add_directed_stake_balance_source(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_update_authority: &Pubkey,
+    source_account: &Pubkey,
+    source_owner: &Pubkey,
+    source_type: u16,
+    initialize: bool,
+) -> Vec<Instruction> {
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
+    let balance_sources = get_directed_stake_balance_sources_address(&ticket_account, program_id);
+
+    let mut instructions = Vec::new();
+    if initialize {
+        instructions.push(Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::InitializeDirectedStakeBalanceSources {
+                config: *steward_config,
+                ticket_account,
+                balance_sources,
+                system_program: solana_program::system_program::id(),
+                authority: *ticket_update_authority,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeBalanceSources {}.data(),
+        });
+    }
+    instructions.push(Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::AddDirectedStakeBalanceSource {
+            config: *steward_config,
+            ticket_account,
+            balance_sources,
+            source_account: *source_account,
+            source_owner: *source_owner,
+            authority: *ticket_update_authority,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::AddDirectedStakeBalanceSource { source_type }.data(),
+    });
+    instructions
+}
+
+/// Creates an instruction to remove a balance source from a ticket, signed by either the ticket
+/// update authority or the source owner.
+pub fn remove_directed_stake_balance_source(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_update_authority: &Pubkey,
+    source_account: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_update_authority, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::RemoveDirectedStakeBalanceSource {
+            ticket_account,
+            balance_sources: get_directed_stake_balance_sources_address(
+                &ticket_account,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::RemoveDirectedStakeBalanceSource {
+            source_account: *source_account,
+        }
+        .data(),
+    }
+}
+
+/// Creates an instruction creating an empty basket named `name` managed by `curator`. Must be
+/// signed by the directed stake whitelist authority.
+pub fn initialize_directed_stake_basket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    name: [u8; 32],
+    curator: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializeDirectedStakeBasket {
+            config: *steward_config,
+            basket: get_directed_stake_basket_address(steward_config, &name, program_id),
+            system_program: solana_program::system_program::id(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeDirectedStakeBasket {
+            name,
+            curator: *curator,
+        }
+        .data(),
+    }
+}
+
+/// Creates an instruction replacing the validators and weights of the basket named `name`.
+/// Must be signed by the basket curator.
+pub fn update_directed_stake_basket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    name: [u8; 32],
+    curator: &Pubkey,
+    validators: Vec<DirectedStakeBasketEntry>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::UpdateDirectedStakeBasket {
+            config: *steward_config,
+            whitelist_account: get_directed_stake_whitelist_address(steward_config, program_id),
+            basket: get_directed_stake_basket_address(steward_config, &name, program_id),
+            curator: *curator,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::UpdateDirectedStakeBasket { validators }.data(),
+    }
+}
+
+/// Creates an instruction opening a directed stake ticket for `signer` without a whitelist entry.
+/// `signer` pays the rent and must hold `directed_stake_permissionless_ticket_min_balance`
+/// JitoSOL in its associated token account.
+pub fn initialize_permissionless_directed_stake_ticket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    stake_pool: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializePermissionlessDirectedStakeTicket {
+            config: *steward_config,
+            ticket_account: get_directed_stake_ticket_address(steward_config, signer, program_id),
+            holder_token_account: get_associated_token_address_with_program_id(
+                signer,
+                pool_mint,
+                token_program_id,
+            ),
+            stake_pool: *stake_pool,
+            system_program: solana_program::system_program::id(),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializePermissionlessDirectedStakeTicket {}.data(),
+    }
+}
+
+/// Creates a permissionless instruction closing the permissionless ticket of `ticket_holder`
+/// once its JitoSOL balance, counting the ticket's `balance_sources`, is below
+/// `directed_stake_permissionless_ticket_min_balance`. The rent is returned to `ticket_holder`.
+#[allow(clippy::too_many_arguments)]
+pub fn close_underfunded_directed_stake_ticket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    stake_pool: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    ticket_holder: &Pubkey,
+    balance_sources: Option<&DirectedStakeBalanceSources>,
+) -> Instruction {
+    let ticket_account =
+        get_directed_stake_ticket_address(steward_config, ticket_holder, program_id);
+    let mut accounts = jito_steward::accounts::CloseUnderfundedDirectedStakeTicket {
+        config: *steward_config,
+        ticket_account,
+        ticket_holder: *ticket_holder,
+        holder_token_account: get_associated_token_address_with_program_id(
+            ticket_holder,
+            pool_mint,
+            token_program_id,
+        ),
+        balance_sources: get_directed_stake_balance_sources_address(&ticket_account, program_id),
+        stake_pool: *stake_pool,
+    }
+    .to_account_metas(None);
+    accounts.extend(balance_source_account_metas(balance_sources));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: jito_steward::instruction::CloseUnderfundedDirectedStakeTicket {}.data(),
+    }
+}
+
+/// Builds the instructions closing every permissionless ticket of `steward_config` whose
+/// holder fell below `directed_stake_permissionless_ticket_min_balance`, counting the token
+/// accounts declared in the ticket's balance sources like the program does, each followed by the
+/// instructions clearing the targets it contributed. Cleared contribution rent goes to `payer`.
+pub async fn compute_underfunded_directed_stake_ticket_closures(
+    client: Arc<RpcClient>,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
+    let pool_mint = stake_pool_account.pool_mint;
+    let token_program_id = stake_pool_account.token_program_id;
+
+    let tickets: HashMap<Pubkey, DirectedStakeTicket> =
+        get_directed_stake_tickets(client.clone(), program_id)
+            .await?
+            .into_iter()
+            .filter(|(address, ticket)| {
+                bool::from(ticket.is_permissionless)
+                    && *address
+                        == get_directed_stake_ticket_address(
+                            steward_config,
+                            &ticket.ticket_update_authority,
+                            program_id,
+                        )
+            })
+            .collect();
+
+    let holders: Vec<Pubkey> = tickets
+        .values()
+        .map(|ticket| ticket.ticket_update_authority)
+        .collect();
+    let holder_token_addresses: Vec<Pubkey> = holders
+        .iter()
+        .map(|holder| {
+            get_associated_token_address_with_program_id(holder, &pool_mint, &token_program_id)
+        })
+        .collect();
+    let holder_token_accounts = get_multiple_accounts_batched(&holder_token_addresses, &client)
+        .await
+        .map_err(JitoTransactionError::from)?;
+    let mut holder_balances: HashMap<Pubkey, u64> = holders
+        .into_iter()
+        .zip(holder_token_accounts)
+        .filter_map(|(holder, account)| {
+            let account = account?;
+            let token_account = StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+            Some((holder, token_account.base.amount))
+        })
+        .collect();
+    // The program only counts token account sources, which the default registry handles
+    let attributed_balances = get_attributed_balances(
+        client.clone(),
+        &tickets,
+        &pool_mint,
+        &token_program_id,
+        &BalanceAttributionRegistry::default(),
+        program_id,
+    )
+    .await?;
+    for (holder, attributed) in attributed_balances {
+        holder_balances
+            .entry(holder)
+            .and_modify(|balance| *balance = balance.saturating_add(attributed))
+            .or_insert(attributed);
+    }
+    let balance_sources = get_directed_stake_balance_sources(client.clone(), program_id).await?;
+
+    let closable = underfunded_permissionless_tickets(
+        &tickets,
+        &holder_balances,
+        config_account
+            .parameters
+            .directed_stake_permissionless_ticket_min_balance,
+    );
+
+    let contribution_addresses: Vec<Pubkey> = closable
+        .iter()
+        .map(|address| get_directed_stake_contribution_address(address, program_id))
+        .collect();
+    let contribution_accounts = get_multiple_accounts_batched(&contribution_addresses, &client)
+        .await
+        .map_err(JitoTransactionError::from)?;
+    let extensions = get_directed_stake_ticket_extensions(client.clone(), program_id).await?;
+
+    let mut instructions = Vec::new();
+    for (ticket_address, contribution_account) in closable.iter().zip(contribution_accounts) {
+        instructions.push(close_underfunded_directed_stake_ticket(
+            program_id,
+            steward_config,
+            &config_account.stake_pool,
+            &pool_mint,
+            &token_program_id,
+            &tickets[ticket_address].ticket_update_authority,
+            balance_sources.get(ticket_address),
+        ));
+        if contribution_account.is_some() {
+            instructions.push(clear_closed_directed_stake_ticket(
+                program_id,
+                steward_config,
+                ticket_address,
+                payer,
+            ));
+        }
+        if extensions.contains_key(ticket_address) {
+            instructions.push(clear_closed_directed_stake_ticket_extension(
+                program_id,
+                steward_config,
+                ticket_address,
+                payer,
+            ));
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// Creates a permissionless instruction removing the directed stake targets of a closed ticket
+/// and closing its contribution account, returning the rent to `signer`.
+pub fn clear_closed_directed_stake_ticket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_account: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::ClearClosedDirectedStakeTicket {
+            config: *steward_config,
+            directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+            ticket_account: *ticket_account,
+            contribution_account: get_directed_stake_contribution_address(
+                ticket_account,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::ClearClosedDirectedStakeTicket {}.data(),
+    }
+}
+
+/// Creates a permissionless instruction removing the directed stake targets contributed by the
+/// extension of a closed ticket and closing the extension accounts, returning the rent to
+/// `signer`.
+pub fn clear_closed_directed_stake_ticket_extension(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_account: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::ClearClosedDirectedStakeTicketExtension {
+            config: *steward_config,
+            directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+            ticket_account: *ticket_account,
+            ticket_extension: get_directed_stake_ticket_extension_address(
+                ticket_account,
+                program_id,
+            ),
+            extension_contribution: get_directed_stake_extension_contribution_address(
+                ticket_account,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::ClearClosedDirectedStakeTicketExtension {}.data(),
+    }
+}
+
+/// Creates a permissionless instruction removing the directed stake targets a ticket contributes
+/// through `basket` once the ticket is closed or no longer points at the basket, and closing the
+/// basket contribution account, returning the rent to `signer`.
+pub fn clear_directed_stake_ticket_basket(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    ticket_account: &Pubkey,
+    basket: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::ClearDirectedStakeTicketBasket {
+            config: *steward_config,
+            directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+            ticket_account: *ticket_account,
+            ticket_extension: get_directed_stake_ticket_extension_address(
+                ticket_account,
+                program_id,
+            ),
+            basket: *basket,
+            basket_contribution: get_directed_stake_basket_contribution_address(
+                ticket_account,
+                basket,
+                program_id,
+            ),
+            signer: *signer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::ClearDirectedStakeTicketBasket {}.data(),
+    }
+}
+
+/// Builds instructions for every directed stake ticket of `steward_config` with
+/// `ticket_instructions`, which receives the ticket address, the ticket and its contribution
+/// address. Tickets without a contribution account are preceded by
+/// `InitializeDirectedStakeContribution`.
+async fn build_directed_stake_ticket_instructions(
+    client: &Arc<RpcClient>,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+    mut ticket_instructions: impl FnMut(&Pubkey, &DirectedStakeTicket, &Pubkey) -> Vec<Instruction>,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let ticket_map = get_directed_stake_tickets(client.clone(), program_id).await?;
+
+    // Only tickets belonging to this config
+    let tickets: Vec<(Pubkey, DirectedStakeTicket)> = ticket_map
+        .into_iter()
+        .filter(|(address, ticket)| {
+            *address
+                == get_directed_stake_ticket_address(
+                    steward_config,
+                    &ticket.ticket_update_authority,
+                    program_id,
+                )
+        })
+        .collect();
+
+    let contribution_addresses: Vec<Pubkey> = tickets
+        .iter()
+        .map(|(address, _)| get_directed_stake_contribution_address(address, program_id))
+        .collect();
+    let contribution_accounts = get_multiple_accounts_batched(&contribution_addresses, client)
+        .await
+        .map_err(JitoTransactionError::from)?;
+
+    let mut instructions = Vec::new();
+    for (((ticket_address, ticket), contribution_address), contribution_account) in tickets
+        .iter()
+        .zip(contribution_addresses.iter())
+        .zip(contribution_accounts.iter())
+    {
+        if contribution_account.is_none() {
+            instructions.push(Instruction {
+                program_id: *program_id,
+                accounts: jito_steward::accounts::InitializeDirectedStakeContribution {
+                    config: *steward_config,
+                    ticket_account: *ticket_address,
+                    contribution_account: *contribution_address,
+                    system_program: solana_program::system_program::id(),
+                    payer: *payer,
+                }
+                .to_account_metas(None),
+                data: jito_steward::instruction::InitializeDirectedStakeContribution {}.data(),
+            });
+        }
+
+        instructions.extend(ticket_instructions(
+            ticket_address,
+            ticket,
+            contribution_address,
+        ));
+    }
+
+    Ok(instructions)
+}
+
+/// Builds permissionless `CrankDirectedStakeTicket` instructions for every directed stake ticket
+/// of `steward_config`, preceded by `InitializeDirectedStakeContribution` for tickets that have
+/// never been cranked.
+///
+/// Each crank recomputes the ticket's targets on-chain from the ticket holder's JitoSOL
+/// associated token account, the token accounts of the ticket's balance sources held by the
+/// holder, and the stake pool exchange rate, replacing the ticket's previous
+/// contribution. Preferences for validators outside the validator list are passed as `u32::MAX`.
+/// Each crank also records a balance sample and credits the time-weighted average balance of the
+/// previous epoch, so cranks require `directed_stake_sample_interval_slots` to be set and should be
+/// sent once per sample interval.
+///
+/// Tickets with a [`DirectedStakeTicketExtension`] are followed by
+/// `CrankDirectedStakeTicketExtension` instructions covering up to
+/// [`EXTENSION_PREFERENCES_PER_CRANK`] extension preferences each, which must land after the
+/// ticket's crank in the same epoch.
+///
+/// Every basket the ticket or its extension points at is expanded by
+/// `CrankDirectedStakeTicketBasket` instructions covering up to [`BASKET_VALIDATORS_PER_CRANK`]
+/// basket validators each, preceded by `InitializeDirectedStakeBasketContribution` the first
+/// time and landing after the ticket's crank in the same epoch. Basket contributions of closed
+/// tickets or baskets no longer pointed at are cleared, returning the rent to `payer`.
+pub async fn compute_directed_stake_ticket_cranks(
+    client: Arc<RpcClient>,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
+    let validator_list_address = stake_pool_account.validator_list;
+    let validator_list_account =
+        get_validator_list_account(&client, &validator_list_address).await?;
+    let validator_list_index_map: HashMap<Pubkey, u32> = validator_list_account
+        .validators
+        .iter()
+        .enumerate()
+        .map(|(index, v)| (v.vote_account_address, index as u32))
+        .collect();
+
+    let directed_stake_meta_pda = get_directed_stake_meta_address(steward_config, program_id);
+    let extensions = get_directed_stake_ticket_extensions(client.clone(), program_id).await?;
+    let balance_sources = get_directed_stake_balance_sources(client.clone(), program_id).await?;
+    let baskets: HashMap<Pubkey, DirectedStakeBasket> =
+        get_directed_stake_baskets(client.clone(), program_id)
+            .await?
+            .into_iter()
+            .filter(|(_, basket)| basket.config == *steward_config)
+            .collect();
+    let basket_contributions: HashMap<(Pubkey, Pubkey), DirectedStakeBasketContribution> =
+        get_directed_stake_basket_contributions(client.clone(), program_id)
+            .await?
+            .into_iter()
+            .filter(|(_, contribution)| contribution.config == *steward_config)
+            .collect();
+    let mut cranked_tickets: HashSet<Pubkey> = HashSet::new();
+
+    let mut instructions = build_directed_stake_ticket_instructions(
+        &client,
+        steward_config,
+        payer,
+        program_id,
+        |ticket_address, ticket, contribution_address| {
+            cranked_tickets.insert(*ticket_address);
+            let mut instructions = Vec::new();
+            let validator_list_indices = ticket
+                .staker_preferences
+                .iter()
+                .take(ticket.num_preferences as usize)
+                .map(|preference| {
+                    validator_list_index_map
+                        .get(&preference.vote_pubkey)
+                        .copied()
+                        .unwrap_or(u32::MAX)
+                })
+                .collect();
+
+            let mut accounts = jito_steward::accounts::CrankDirectedStakeTicket {
+                config: *steward_config,
+                directed_stake_meta: directed_stake_meta_pda,
+                ticket_account: *ticket_address,
+                contribution_account: *contribution_address,
+                holder_token_account: get_associated_token_address_with_program_id(
+                    &ticket.ticket_update_authority,
+                    &stake_pool_account.pool_mint,
+                    &stake_pool_account.token_program_id,
+                ),
+                balance_sources: get_directed_stake_balance_sources_address(
+                    ticket_address,
+                    program_id,
+                ),
+                stake_pool: config_account.stake_pool,
+                validator_list: validator_list_address,
+                instructions: solana_sdk::sysvar::instructions::id(),
+            }
+            .to_account_metas(None);
+            accounts.extend(validator_consent_account_metas(
+                steward_config,
+                ticket
+                    .staker_preferences
+                    .iter()
+                    .take(ticket.num_preferences as usize)
+                    .map(|preference| &preference.vote_pubkey),
+                program_id,
+            ));
+            accounts.extend(balance_source_account_metas(
+                balance_sources.get(ticket_address),
+            ));
+            instructions.push(Instruction {
+                program_id: *program_id,
+                accounts,
+                data: jito_steward::instruction::CrankDirectedStakeTicket {
+                    validator_list_indices,
+                }
+                .data(),
+            });
+
+            if let Some(extension) = extensions.get(ticket_address) {
+                let extension_indices: Vec<u32> = extension.staker_preferences
+                    [..extension.num_preferences as usize]
+                    .iter()
+                    .map(|preference| {
+                        validator_list_index_map
+                            .get(&preference.vote_pubkey)
+                            .copied()
+                            .unwrap_or(u32::MAX)
+                    })
+                    .collect();
+                // An empty extension is still cranked once to clear removed preferences
+                let chunks: Vec<&[u32]> = if extension_indices.is_empty() {
+                    vec![&[]]
+                } else {
+                    extension_indices
+                        .chunks(EXTENSION_PREFERENCES_PER_CRANK)
+                        .collect()
+                };
+
+                for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+                    let start_index = chunk_index * EXTENSION_PREFERENCES_PER_CRANK;
+                    let mut accounts = jito_steward::accounts::CrankDirectedStakeTicketExtension {
+                        config: *steward_config,
+                        directed_stake_meta: directed_stake_meta_pda,
+                        ticket_account: *ticket_address,
+                        contribution_account: *contribution_address,
+                        ticket_extension: get_directed_stake_ticket_extension_address(
+                            ticket_address,
+                            program_id,
+                        ),
+                        extension_contribution: get_directed_stake_extension_contribution_address(
+                            ticket_address,
+                            program_id,
+                        ),
+                        stake_pool: config_account.stake_pool,
+                        validator_list: validator_list_address,
+                    }
+                    .to_account_metas(None);
+                    accounts.extend(validator_consent_account_metas(
+                        steward_config,
+                        extension.staker_preferences[start_index..start_index + chunk.len()]
+                            .iter()
+                            .map(|preference| &preference.vote_pubkey),
+                        program_id,
+                    ));
+                    instructions.push(Instruction {
+                        program_id: *program_id,
+                        accounts,
+                        data: jito_steward::instruction::CrankDirectedStakeTicketExtension {
+                            start_index: start_index as u16,
+                            validator_list_indices: chunk.to_vec(),
+                        }
+                        .data(),
+                    });
+                }
+            }
+
+            let extension = extensions.get(ticket_address);
+            let referenced_baskets: HashSet<Pubkey> = ticket
+                .preferences()
+                .iter()
+                .filter(|preference| bool::from(preference.is_basket))
+                .map(|preference| preference.vote_pubkey)
+                .chain(
+                    extension
+                        .map(|extension| {
+                            &extension.staker_preferences[..extension.num_preferences as usize]
+                        })
+                        .unwrap_or_default()
+                        .iter()
+                        .filter(|preference| bool::from(preference.is_basket))
+                        .map(|preference| preference.vote_pubkey),
+                )
+                .collect();
+
+            for (basket_address, basket) in baskets
+                .iter()
+                .filter(|(address, _)| referenced_baskets.contains(address))
+            {
+                let basket_contribution = get_directed_stake_basket_contribution_address(
+                    ticket_address,
+                    basket_address,
+                    program_id,
+                );
+                if !basket_contributions.contains_key(&(*ticket_address, *basket_address)) {
+                    instructions.push(Instruction {
+                        program_id: *program_id,
+                        accounts:
+                            jito_steward::accounts::InitializeDirectedStakeBasketContribution {
+                                config: *steward_config,
+                                ticket_account: *ticket_address,
+                                basket: *basket_address,
+                                basket_contribution,
+                                system_program: solana_program::system_program::id(),
+                                payer: *payer,
+                            }
+                            .to_account_metas(None),
+                        data:
+                            jito_steward::instruction::InitializeDirectedStakeBasketContribution {}
+                                .data(),
+                    });
+                }
+
+                let basket_indices: Vec<u32> = basket
+                    .validators()
+                    .iter()
+                    .map(|entry| {
+                        validator_list_index_map
+                            .get(&entry.vote_pubkey)
+                            .copied()
+                            .unwrap_or(u32::MAX)
+                    })
+                    .collect();
+                // An empty basket is still cranked once to clear removed validators
+                let chunks: Vec<&[u32]> = if basket_indices.is_empty() {
+                    vec![&[]]
+                } else {
+                    basket_indices.chunks(BASKET_VALIDATORS_PER_CRANK).collect()
+                };
+
+                for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+                    let start_index = chunk_index * BASKET_VALIDATORS_PER_CRANK;
+                    let mut accounts = jito_steward::accounts::CrankDirectedStakeTicketBasket {
+                        config: *steward_config,
+                        directed_stake_meta: directed_stake_meta_pda,
+                        ticket_account: *ticket_address,
+                        contribution_account: *contribution_address,
+                        ticket_extension: get_directed_stake_ticket_extension_address(
+                            ticket_address,
+                            program_id,
+                        ),
+                        basket: *basket_address,
+                        basket_contribution,
+                        stake_pool: config_account.stake_pool,
+                        validator_list: validator_list_address,
+                    }
+                    .to_account_metas(None);
+                    accounts.extend(validator_consent_account_metas(
+                        steward_config,
+                        basket.validators()[start_index..start_index + chunk.len()]
+                            .iter()
+                            .map(|entry| &entry.vote_pubkey),
+                        program_id,
+                    ));
+                    instructions.push(Instruction {
+                        program_id: *program_id,
+                        accounts,
+                        data: jito_steward::instruction::CrankDirectedStakeTicketBasket {
+                            start_index: start_index as u16,
+                            validator_list_indices: chunk.to_vec(),
+                        }
+                        .data(),
+                    });
+                }
+            }
+
+            for (_, basket_address) in basket_contributions.keys().filter(|(ticket, basket)| {
+                ticket == ticket_address && !referenced_baskets.contains(basket)
+            }) {
+                instructions.push(clear_directed_stake_ticket_basket(
+                    program_id,
+                    steward_config,
+                    ticket_address,
+                    basket_address,
+                    payer,
+                ));
+            }
+            instructions
+        },
+    )
+    .await?;
+
+    // Basket contributions of closed tickets
+    for (ticket_address, basket_address) in basket_contributions
+        .keys()
+        .filter(|(ticket, _)| !cranked_tickets.contains(ticket))
+    {
+        instructions.push(clear_directed_stake_ticket_basket(
+            program_id,
+            steward_config,
+            ticket_address,
+            basket_address,
+            payer,
+        ));
+    }
+
+    Ok(instructions)
+}
+
+/// Builds permissionless `SampleDirectedStakeBalance` instructions for every directed stake
+/// ticket of `steward_config`, preceded by `InitializeDirectedStakeContribution` for tickets
+/// that have no contribution account yet.
+///
+/// Used with [`compute_directed_stake_meta`] when `directed_stake_sample_interval_slots` is set,
+/// and should be sent once per sample interval.
+pub async fn compute_directed_stake_balance_samples(
+    client: Arc<RpcClient>,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
+    let balance_sources = get_directed_stake_balance_sources(client.clone(), program_id).await?;
+
+    build_directed_stake_ticket_instructions(
+        &client,
+        steward_config,
+        payer,
+        program_id,
+        |ticket_address, ticket, contribution_address| {
+            let mut accounts = jito_steward::accounts::SampleDirectedStakeBalance {
+                config: *steward_config,
+                ticket_account: *ticket_address,
+                contribution_account: *contribution_address,
+                holder_token_account: get_associated_token_address_with_program_id(
+                    &ticket.ticket_update_authority,
+                    &stake_pool_account.pool_mint,
+                    &stake_pool_account.token_program_id,
+                ),
+                balance_sources: get_directed_stake_balance_sources_address(
+                    ticket_address,
+                    program_id,
+                ),
+                stake_pool: config_account.stake_pool,
+                instructions: solana_sdk::sysvar::instructions::id(),
+            }
+            .to_account_metas(None);
+            accounts.extend(balance_source_account_metas(
+                balance_sources.get(ticket_address),
+            ));
+            vec![Instruction {
+                program_id: *program_id,
+                accounts,
+                data: jito_steward::instruction::SampleDirectedStakeBalance {}.data(),
+            }]
+        },
+    )
+    .await
+}
+
+/// Computes the directed stake target for the Coinbase validator.
+///
+/// Fetches the Coinbase balance for the previous epoch from the Kobe API and builds a
+/// `CopyDirectedStakeTargets` instruction to set the stake target for `coinbase_vote_pubkey`.
+///
+/// # Process Overview
+///
+/// 1. Fetches the Coinbase balance for the previous epoch via the Kobe API.
+/// 2. Loads the on-chain `DirectedStakeMeta` account and collects any existing targets that
+///    were last updated in the current epoch.
+/// 3. Locates `coinbase_vote_pubkey` in the validator list.
+/// 4. Computes `total_target_lamports` as the Coinbase balance plus any existing target for
+///    this validator in the current epoch (saturating addition).
+/// 5. Emits one `CopyDirectedStakeTargets` instruction for the validator.
+///
+/// # Return Value
+///
+/// Returns an empty vector if:
+/// - The Kobe API returns no Coinbase balance (`coinbase_balance` is `None`), or
+/// - `coinbase_vote_pubkey` is not present in the on-chain validator list.
+pub async fn compute_coinbase_targets(
+    client: Arc<RpcClient>,
+    kobe_client: &KobeClient,
+    steward_config: &Pubkey,
+    authority_pubkey: &Pubkey,
+    program_id: &Pubkey,
+    coinbase_vote_pubkey: &Pubkey,
+) -> Result<Vec<Instruction>, JitoInstructionError> {
+    let epoch_info = client.get_epoch_info().await?;
+    let last_epoch = epoch_info.epoch - 1;
+
+    let coinbase_balance = kobe_client.get_coinbase_balance(last_epoch).await?;
+
+    let directed_stake_meta_pda = get_directed_stake_meta_address(steward_config, program_id);
+    let config_account = get_steward_config_account(&client, steward_config).await?;
+    let stake_pool_account = get_stake_pool_account(&client, &config_account.stake_pool).await?;
+    let validator_list_address = stake_pool_account.validator_list;
+    let validator_list_account =
+        get_validator_list_account(&client, &validator_list_address).await?;
+
+    let mut instructions = Vec::new();
+
+    if let Some(cb_balance) = coinbase_balance.coinbase_balance {
+        if let Some(validator_list_index) = validator_list_account
+            .validators
+            .iter()
+            .position(|v| v.vote_account_address.eq(coinbase_vote_pubkey))
+        {
+            let ix = Instruction {
+                program_id: *program_id,
+                accounts: jito_steward::accounts::CopyDirectedStakeTargets {
+                    config: *steward_config,
                     directed_stake_meta: directed_stake_meta_pda,
                     authority: *authority_pubkey,
                     clock: solana_sdk::sysvar::clock::id(),
                     validator_list: validator_list_address,
+                    validator_consent: get_directed_stake_validator_consent_address(
+                        steward_config,
+                        coinbase_vote_pubkey,
+                        program_id,
+                    ),
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::CopyDirectedStakeTargets {
@@ -262,3 +1772,283 @@ pub async fn compute_coinbase_targets(
 
     Ok(instructions)
 }
+
+/// Creates the permissionless instruction pointing the preferred deposit and withdraw validators
+/// of `stake_pool` at the validators furthest below and above their targets
+pub fn update_preferred_validators(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::UpdatePreferredValidators {
+            config: *steward_config,
+            state_account: get_steward_state_address(program_id, steward_config),
+            directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+            stake_pool_program: spl_stake_pool::id(),
+            stake_pool: *stake_pool,
+            validator_list: *validator_list,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::UpdatePreferredValidators {}.data(),
+    }
+}
+
+/// Creates the permissionless instruction initializing the account that records graded
+/// instant unstake fractions, paid for by `payer`
+pub fn initialize_instant_unstake_fractions(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializeInstantUnstakeFractions {
+            config: *steward_config,
+            instant_unstake_fractions: get_instant_unstake_fractions_address(
+                steward_config,
+                program_id,
+            ),
+            system_program: solana_program::system_program::id(),
+            payer: *payer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeInstantUnstakeFractions {}.data(),
+    }
+}
+
+/// Creates the permissionless instructions initializing the account that tracks validators
+/// warned before being unscored and growing it to its full size, paid for by `payer`
+pub fn initialize_score_warnings(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<Instruction> {
+    let score_warnings = get_score_warnings_address(steward_config, program_id);
+
+    let mut ixs = vec![Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializeScoreWarnings {
+            config: *steward_config,
+            score_warnings,
+            system_program: solana_program::system_program::id(),
+            payer: *payer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeScoreWarnings {}.data(),
+    }];
+
+    let num_reallocs = (ScoreWarnings::SIZE - jito_steward::constants::MAX_ALLOC_BYTES)
+        .div_ceil(jito_steward::constants::MAX_ALLOC_BYTES);
+    ixs.extend(vec![
+        Instruction {
+            program_id: *program_id,
+            accounts: jito_steward::accounts::ReallocScoreWarnings {
+                config: *steward_config,
+                score_warnings,
+                system_program: solana_program::system_program::id(),
+                payer: *payer,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::ReallocScoreWarnings {}.data(),
+        };
+        num_reallocs
+    ]);
+
+    ixs
+}
+
+/// Creates the instruction marking the validator at `validator_list_index` for removal from the pool and
+/// blocking it from being added back, signed by its vote account's `authorized_withdrawer`
+pub fn request_validator_exit(
+    program_id: &Pubkey,
+    steward_accounts: &AllStewardAccounts,
+    validator_list_index: usize,
+    authorized_withdrawer: &Pubkey,
+) -> Result<Instruction, JitoInstructionError> {
+    let validator = steward_accounts
+        .validator_list_account
+        .validators
+        .get(validator_list_index)
+        .ok_or_else(|| {
+            JitoInstructionError::Custom(format!(
+                "Validator list index {validator_list_index} out of bounds"
+            ))
+        })?;
+    let vote_account = validator.vote_account_address;
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::RequestValidatorExit {
+            config: steward_accounts.config_address,
+            state_account: steward_accounts.state_address,
+            validator_list: steward_accounts.validator_list_address,
+            vote_account,
+            validator_exit: get_validator_exit_address(
+                &steward_accounts.config_address,
+                &vote_account,
+                program_id,
+            ),
+            system_program: solana_program::system_program::id(),
+            authorized_withdrawer: *authorized_withdrawer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::RequestValidatorExit {
+            validator_list_index: validator_list_index as u64,
+        }
+        .data(),
+    })
+}
+
+/// Creates the instruction lifting the block set by `request_validator_exit`, signed by the
+/// vote account's `authorized_withdrawer`
+pub fn request_validator_rejoin(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    authorized_withdrawer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::RequestValidatorRejoin {
+            config: *steward_config,
+            vote_account: *vote_account,
+            validator_exit: get_validator_exit_address(steward_config, vote_account, program_id),
+            authorized_withdrawer: *authorized_withdrawer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::RequestValidatorRejoin {}.data(),
+    }
+}
+
+/// Creates the permissionless instruction initializing the journal of stake moved to and from
+/// `vote_account` by rebalancing, paid for by `payer`
+pub fn initialize_rebalance_journal(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    vote_account: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::InitializeRebalanceJournal {
+            config: *steward_config,
+            rebalance_journal: get_rebalance_journal_address(
+                steward_config,
+                vote_account,
+                program_id,
+            ),
+            vote_account: *vote_account,
+            system_program: solana_program::system_program::id(),
+            payer: *payer,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeRebalanceJournal {}.data(),
+    }
+}
+
+/// Creates the permissionless instruction checking the steward's lamport accounting for validator
+/// list indices `start_index..end_index`, pausing the steward on drift if `pause_on_drift` is set
+pub fn verify_invariants(
+    program_id: &Pubkey,
+    steward_config: &Pubkey,
+    validator_list: &Pubkey,
+    start_index: u64,
+    end_index: u64,
+    pause_on_drift: bool,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: jito_steward::accounts::VerifyInvariants {
+            config: *steward_config,
+            state_account: get_steward_state_address(program_id, steward_config),
+            directed_stake_meta: get_directed_stake_meta_address(steward_config, program_id),
+            validator_list: *validator_list,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::VerifyInvariants {
+            start_index,
+            end_index,
+            pause_on_drift,
+        }
+        .data(),
+    }
+}
+
+/// Creates the permissionless instruction rebalancing each of `validators` in one instruction.
+pub fn rebalance_many(
+    program_id: &Pubkey,
+    steward_accounts: &AllStewardAccounts,
+    validators: &[ProgressionInfo],
+    cycle_report: Option<Pubkey>,
+    instant_unstake_fractions: Option<Pubkey>,
+) -> Instruction {
+    let stake_pool = steward_accounts.stake_pool_address;
+
+    let mut accounts = jito_steward::accounts::RebalanceMany {
+        config: steward_accounts.config_address,
+        state_account: steward_accounts.state_address,
+        stake_pool_program: spl_stake_pool::id(),
+        stake_pool,
+        withdraw_authority: steward_accounts.stake_pool_withdraw_authority,
+        validator_list: steward_accounts.validator_list_address,
+        reserve_stake: steward_accounts.stake_pool_account.reserve_stake,
+        clock: solana_sdk::sysvar::clock::id(),
+        rent: solana_sdk::sysvar::rent::id(),
+        stake_history: solana_sdk::sysvar::stake_history::id(),
+        stake_config: solana_sdk::stake::config::ID,
+        system_program: solana_program::system_program::id(),
+        stake_program: solana_sdk::stake::program::id(),
+        directed_stake_meta: get_directed_stake_meta_address(
+            &steward_accounts.config_address,
+            program_id,
+        ),
+        cycle_report,
+        instant_unstake_fractions,
+    }
+    .to_account_metas(None);
+
+    for validator_info in validators {
+        let validator = &steward_accounts.validator_list_account.validators[validator_info.index];
+        let (stake_account, _) = find_stake_program_address(
+            &spl_stake_pool::id(),
+            &validator_info.vote_account,
+            &stake_pool,
+            NonZeroU32::new(u32::from(validator.validator_seed_suffix)),
+        );
+        let (transient_stake_account, _) = find_transient_stake_program_address(
+            &spl_stake_pool::id(),
+            &validator_info.vote_account,
+            &stake_pool,
+            validator.transient_seed_suffix.into(),
+        );
+
+        accounts.extend([
+            AccountMeta::new_readonly(validator_info.vote_account, false),
+            AccountMeta::new(stake_account, false),
+            AccountMeta::new(transient_stake_account, false),
+            AccountMeta::new(
+                get_rebalance_journal_address(
+                    &steward_accounts.config_address,
+                    &validator_info.vote_account,
+                    program_id,
+                ),
+                false,
+            ),
+        ]);
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: jito_steward::instruction::RebalanceMany {
+            validator_list_indices: validators
+                .iter()
+                .map(|validator_info| validator_info.index as u64)
+                .collect(),
+        }
+        .data(),
+    }
+}
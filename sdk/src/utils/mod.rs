@@ -1,5 +1,7 @@
 pub mod accounts;
+pub mod balance_attribution;
 pub mod debug;
+pub mod events;
 pub mod helpers;
 pub mod instructions;
 pub mod transactions;
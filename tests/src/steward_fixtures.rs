@@ -18,8 +18,9 @@ use jito_steward::{
     constants::{MAX_VALIDATORS, SORTED_INDEX_DEFAULT, STAKE_POOL_WITHDRAW_SEED},
     instructions::AuthorityType,
     stake_pool_utils::{StakePool, ValidatorList},
-    Config, Delegation, LargeBitMask, Parameters, StewardStateAccount, StewardStateAccountV2,
-    StewardStateEnum, StewardStateV2, UpdateParametersArgs, UpdatePriorityFeeParametersArgs,
+    CircuitBreakerBaseline, CommissionPledge, Config, Delegation, LargeBitMask, Parameters,
+    RebalanceJournal, StewardStateAccount, StewardStateAccountV2, StewardStateEnum, StewardStateV2,
+    UnstakeCarryover, UpdateParametersArgs, UpdatePriorityFeeParametersArgs, ValidatorExit,
 };
 use solana_program_test::*;
 #[allow(deprecated)]
@@ -384,6 +385,22 @@ impl TestFixture {
             directed_stake_unstake_cap_bps: Some(10_000),
             jito_bam_minimum_epochs: Some(0),
             jito_bam_window_epochs: Some(0),
+            circuit_breaker_instant_unstake_count: None,
+            circuit_breaker_unstake_bps: None,
+            directed_stake_sample_interval_slots: None,
+            directed_stake_target_expiry_epochs: None,
+            directed_stake_target_decay_bps: None,
+            directed_stake_permissionless_ticket_min_balance: None,
+            reserve_liquidity_target_lamports: None,
+            reserve_liquidity_target_bps: None,
+            auto_preferred_validators: None,
+            instant_unstake_max_fraction_bps: None,
+            score_warning_filters: None,
+            validator_exit_cooldown_epochs: None,
+            commission_pledge_cooldown_epochs: None,
+            minimum_rebalance_lamports: None,
+            unstake_carryover_ceiling_bps: None,
+            cycle_reports_enabled: None,
         });
 
         let update_priority_fee_parameters_args =
@@ -972,6 +989,61 @@ pub struct ExtraValidatorAccounts {
     pub withdraw_authority: Pubkey,
 }
 
+/// Address of the `CommissionPledge` of `vote_account`, which need not exist
+pub fn commission_pledge_address(fixture: &TestFixture, vote_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CommissionPledge::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+            vote_account.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0
+}
+
+/// Address of the `RebalanceJournal` of `vote_account`, which need not exist
+pub fn rebalance_journal_address(fixture: &TestFixture, vote_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            RebalanceJournal::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+            vote_account.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0
+}
+
+/// Creates an empty `RebalanceJournal` for `vote_account` if it does not exist yet, since
+/// rebalancing fails without one. Written directly so test vote accounts need not be real.
+pub async fn initialize_rebalance_journal(fixture: &TestFixture, vote_account: &Pubkey) -> Pubkey {
+    let address = rebalance_journal_address(fixture, vote_account);
+    if fixture.account_exists(&address).await {
+        return address;
+    }
+
+    let mut journal: Box<RebalanceJournal> = Box::new(bytemuck::Zeroable::zeroed());
+    journal.config = fixture.steward_config.pubkey();
+    journal.vote_account = *vote_account;
+
+    let mut data = Vec::with_capacity(RebalanceJournal::SIZE);
+    data.extend_from_slice(RebalanceJournal::DISCRIMINATOR);
+    data.extend_from_slice(bytemuck::bytes_of(journal.as_ref()));
+    let rent: Rent = fixture.get_sysvar().await;
+    fixture.ctx.borrow_mut().set_account(
+        &address,
+        &Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: jito_steward::id(),
+            ..Account::default()
+        }
+        .into(),
+    );
+    address
+}
+
 /// Helper function to initialize directed stake meta
 pub async fn initialize_directed_stake_meta(fixture: &TestFixture) -> Pubkey {
     let directed_stake_meta = Pubkey::find_program_address(
@@ -1156,6 +1228,8 @@ pub async fn crank_rebalance_directed(
                 stake_config: solana_sdk::stake::config::ID,
                 system_program: system_program::id(),
                 stake_program: solana_sdk::stake::program::id(),
+                rebalance_journal: initialize_rebalance_journal(fixture, &vote_account_at_index)
+                    .await,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::RebalanceDirected {
@@ -1261,6 +1335,15 @@ pub async fn auto_add_validator(fixture: &TestFixture, extra_accounts: &ExtraVal
             stake_config: stake::config::ID,
             system_program: system_program::id(),
             stake_program: stake::program::id(),
+            validator_exit: Pubkey::find_program_address(
+                &[
+                    ValidatorExit::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                    extra_accounts.vote_account.as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::AutoAddValidatorToPool {}.data(),
@@ -1413,6 +1496,12 @@ pub async fn crank_compute_score(
                 validator_list: fixture.stake_pool_meta.validator_list,
                 validator_history: extra_validator_accounts[i].validator_history_address,
                 cluster_history: fixture.cluster_history_account,
+                commission_pledge: commission_pledge_address(
+                    fixture,
+                    &extra_validator_accounts[i].vote_account,
+                ),
+                cycle_report: None,
+                score_warnings: None,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::ComputeScore {
@@ -1439,6 +1528,7 @@ pub async fn crank_compute_delegations(fixture: &TestFixture) {
             config: fixture.steward_config.pubkey(),
             state_account: fixture.steward_state,
             validator_list: fixture.stake_pool_meta.validator_list,
+            cycle_report: None,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::ComputeDelegations {}.data(),
@@ -1493,6 +1583,13 @@ pub async fn crank_compute_instant_unstake(
                 validator_history: extra_validator_accounts[i].validator_history_address,
                 validator_list: fixture.stake_pool_meta.validator_list,
                 cluster_history: fixture.cluster_history_account,
+                commission_pledge: commission_pledge_address(
+                    fixture,
+                    &extra_validator_accounts[i].vote_account,
+                ),
+                cycle_report: None,
+                instant_unstake_fractions: None,
+                score_warnings: None,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::ComputeInstantUnstake {
@@ -1550,6 +1647,13 @@ pub async fn crank_rebalance(
                 clock: solana_sdk::sysvar::clock::id(),
                 stake_history: solana_sdk::sysvar::stake_history::id(),
                 stake_config: stake::config::ID,
+                cycle_report: None,
+                instant_unstake_fractions: None,
+                rebalance_journal: initialize_rebalance_journal(
+                    fixture,
+                    &extra_accounts.vote_account,
+                )
+                .await,
             }
             .to_account_metas(None),
             data: jito_steward::instruction::Rebalance {
@@ -1787,7 +1891,9 @@ impl Default for FixtureDefaultAccounts {
             directed_stake_meta_upload_authority: Pubkey::new_unique(),
             directed_stake_whitelist_authority: Pubkey::new_unique(),
             directed_stake_ticket_override_authority: Pubkey::new_unique(),
-            _padding: [0; 888],
+            unstake_carryover: UnstakeCarryover::default(),
+            circuit_breaker_baseline: CircuitBreakerBaseline::default(),
+            _padding: [0; 800],
         };
 
         let directed_stake_meta = Pubkey::find_program_address(
@@ -1828,7 +1934,7 @@ impl Default for FixtureDefaultAccounts {
             stake_deposit_unstake_total: 0,
             validators_added: 0,
             status_flags: 0,
-            _padding0: [0; 2],
+            validators_removed: 0,
         };
         let steward_state_account = Box::new(StewardStateAccountV2 {
             state: steward_state,
@@ -2085,11 +2191,27 @@ pub fn new_vote_account(
     vote_pubkey: Pubkey,
     commission: u8,
     maybe_epoch_credits: Option<Vec<(u64, u64, u64)>>,
+) -> Account {
+    new_vote_account_with_authorized_withdrawer(
+        node_pubkey,
+        vote_pubkey,
+        vote_pubkey,
+        commission,
+        maybe_epoch_credits,
+    )
+}
+
+pub fn new_vote_account_with_authorized_withdrawer(
+    node_pubkey: Pubkey,
+    vote_pubkey: Pubkey,
+    authorized_withdrawer: Pubkey,
+    commission: u8,
+    maybe_epoch_credits: Option<Vec<(u64, u64, u64)>>,
 ) -> Account {
     let vote_init = VoteInit {
         node_pubkey,
         authorized_voter: vote_pubkey,
-        authorized_withdrawer: vote_pubkey,
+        authorized_withdrawer,
         commission,
     };
     let clock = Clock {
@@ -2312,8 +2434,26 @@ impl Default for StateMachineFixtures {
             undirected_stake_ceiling_lamports: (10_000_000 * LAMPORTS_PER_SOL).to_le_bytes(),
             jito_bam_minimum_epochs: 0,
             jito_bam_window_epochs: 0,
+            circuit_breaker_instant_unstake_count: 0,
+            circuit_breaker_unstake_bps: 0,
             _padding_0: [0; 4],
-            _padding_1: [0; 28],
+            _padding_3: [0; 2],
+            directed_stake_sample_interval_slots: 0,
+            directed_stake_target_expiry_epochs: 0,
+            directed_stake_target_decay_bps: 0,
+            directed_stake_permissionless_ticket_min_balance: 0,
+            reserve_liquidity_target_lamports: 0,
+            reserve_liquidity_target_bps: 0,
+            instant_unstake_max_fraction_bps: 0,
+            score_warning_filters: 0,
+            auto_preferred_validators: 0,
+            cycle_reports_enabled: 0,
+            validator_exit_cooldown_epochs: 0,
+            commission_pledge_cooldown_epochs: 0,
+            minimum_rebalance_lamports: 0,
+            unstake_carryover_ceiling_bps: 0,
+            _padding_5: [0; 6],
+            _padding_1: [0; 18],
             _padding_2: [0; 6],
         };
 
@@ -2332,7 +2472,9 @@ impl Default for StateMachineFixtures {
             directed_stake_meta_upload_authority: Pubkey::new_unique(),
             directed_stake_whitelist_authority: Pubkey::new_unique(),
             directed_stake_ticket_override_authority: Pubkey::new_unique(),
-            _padding: [0; 888],
+            unstake_carryover: UnstakeCarryover::default(),
+            circuit_breaker_baseline: CircuitBreakerBaseline::default(),
+            _padding: [0; 800],
         };
 
         // Setup Sysvars: Clock, EpochSchedule
@@ -2472,7 +2614,7 @@ impl Default for StateMachineFixtures {
             validators_added: 0,
             validators_to_remove: BitMask::default(),
             validators_for_immediate_removal: BitMask::default(),
-            _padding0: [0; 2],
+            validators_removed: 0,
         };
 
         StateMachineFixtures {
@@ -2502,6 +2644,15 @@ pub async fn crank_copy_directed_stake_targets(
     vote_pubkey: Pubkey,
     target_lamports: u64,
 ) {
+    let tx = copy_directed_stake_targets_transaction(fixture, vote_pubkey, target_lamports).await;
+    fixture.submit_transaction_assert_success(tx).await;
+}
+
+pub async fn copy_directed_stake_targets_transaction(
+    fixture: &TestFixture,
+    vote_pubkey: Pubkey,
+    target_lamports: u64,
+) -> Transaction {
     let directed_stake_meta = Pubkey::find_program_address(
         &[
             jito_steward::state::directed_stake::DirectedStakeMeta::SEED,
@@ -2529,6 +2680,15 @@ pub async fn crank_copy_directed_stake_targets(
             authority: fixture.keypair.pubkey(),
             clock: solana_sdk::sysvar::clock::id(),
             validator_list: fixture.stake_pool_meta.validator_list,
+            validator_consent: Pubkey::find_program_address(
+                &[
+                    jito_steward::DirectedStakeValidatorConsent::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                    vote_pubkey.as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::CopyDirectedStakeTargets {
@@ -2539,7 +2699,7 @@ pub async fn crank_copy_directed_stake_targets(
         .data(),
     };
 
-    let tx = Transaction::new_signed_with_payer(
+    Transaction::new_signed_with_payer(
         &[ix],
         Some(&fixture.keypair.pubkey()),
         &[&fixture.keypair],
@@ -2549,8 +2709,7 @@ pub async fn crank_copy_directed_stake_targets(
             .get_new_latest_blockhash()
             .await
             .unwrap(),
-    );
-    fixture.submit_transaction_assert_success(tx).await;
+    )
 }
 
 pub async fn crank_directed_stake_permissions(
@@ -1,11 +1,12 @@
 // Unit tests for scoring, instant unstake, and delegation methods
 //
 // Note on score values: The maximum scores in these tests start with 7 (e.g., 7249739868913833600).
-// This is because of the 4-tier bit encoding in encode_validator_score():
+// This is because of the tiered bit encoding in encode_validator_score():
 // - Tier 1 (bits 56-63): Inflation commission (inverted, 0% commission → score of 100)
 // - Tier 2 (bits 42-55): MEV commission (inverted, 0% commission → score of 10000)
-// - Tier 3 (bits 25-41): Validator age
-// - Tier 4 (bits 0-24): Vote credits ratio
+// - Tier 3 (bit 41): Commission pledge
+// - Tier 4 (bits 25-40): Validator age
+// - Tier 5 (bits 0-24): Vote credits ratio
 //
 // When a validator has perfect metrics (0% commissions), the inflation commission score of 100
 // is placed in bits 56-63. In binary: 100 = 0b01100100
@@ -16,17 +17,20 @@ use crate::steward::serialize_validator_list;
 use jito_steward::state::directed_stake::DirectedStakeMeta;
 use jito_steward::{
     constants::{
-        EPOCH_DEFAULT, LAMPORT_BALANCE_DEFAULT, SORTED_INDEX_DEFAULT, TVC_ACTIVATION_EPOCH,
-        VOTE_CREDITS_RATIO_MAX,
+        BASIS_POINTS_MAX, EPOCH_DEFAULT, LAMPORT_BALANCE_DEFAULT, SORTED_INDEX_DEFAULT,
+        TVC_ACTIVATION_EPOCH, VOTE_CREDITS_RATIO_MAX,
     },
     delegation::{
-        decrease_stake_calculation, increase_stake_calculation, RebalanceType, UnstakeState,
+        decrease_stake_calculation, increase_stake_calculation, instant_unstake_target_lamports,
+        skip_dust_rebalance, RebalanceType, UnstakeState,
     },
     errors::StewardError,
     events::DecreaseComponents,
     insert_sorted_index,
     score::{
-        instant_unstake_validator, validator_score, InstantUnstakeComponentsV3,
+        calculate_instant_unstake_commission_severity,
+        calculate_instant_unstake_delinquency_severity, calculate_instant_unstake_fraction,
+        instant_unstake_validator, validator_score, InstantUnstakeComponentsV4,
         InstantUnstakeDetails, ScoreComponentsV5, ScoreDetails,
     },
     select_validators_to_delegate, Delegation,
@@ -1140,13 +1144,15 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: false,
+            instant_unstake_fraction_bps: 0,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1178,13 +1184,15 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1213,13 +1221,15 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: true,
             commission_check: true,
             mev_commission_check: true,
@@ -1250,6 +1260,7 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert_eq!(res, Err(StewardError::ClusterHistoryNotRecentEnough.into()));
@@ -1266,13 +1277,15 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: true,
             commission_check: false,
             mev_commission_check: false,
@@ -1306,6 +1319,7 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
 
     assert_eq!(res, Err(StewardError::VoteHistoryNotRecentEnough.into()));
@@ -1320,12 +1334,14 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: false,
             commission_check: true,
             mev_commission_check: false,
@@ -1355,12 +1371,14 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: false,
+            instant_unstake_fraction_bps: 0,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1390,12 +1408,14 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: false,
+            instant_unstake_fraction_bps: 0,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1429,12 +1449,14 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1467,12 +1489,14 @@ fn test_instant_unstake() {
         start_slot,
         current_epoch,
         TVC_ACTIVATION_EPOCH,
+        0,
     );
     assert!(res.is_ok());
     assert_eq!(
         res.unwrap(),
-        InstantUnstakeComponentsV3 {
+        InstantUnstakeComponentsV4 {
             instant_unstake: true,
+            instant_unstake_fraction_bps: BASIS_POINTS_MAX,
             delinquency_check: false,
             commission_check: false,
             mev_commission_check: false,
@@ -1493,6 +1517,135 @@ fn test_instant_unstake() {
     );
 }
 
+#[test]
+fn test_graded_instant_unstake() {
+    /* Tests:
+        [X] severity is 0 at or below the threshold
+        [X] severity scales linearly up to the maximum
+        [X] fraction scales severity by instant_unstake_max_fraction_bps
+        [X] full removal when disabled or required
+        [X] graded target keeps the rest of the current stake
+    */
+
+    // Delinquency: vote rate at half the 0.7 threshold is 50% severe
+    let severity = calculate_instant_unstake_delinquency_severity(
+        1000,
+        1000,
+        (1000. * 0.35 * TVC_MULTIPLIER as f64) as u32,
+        1000,
+        0.7,
+    )
+    .unwrap();
+    assert_eq!(severity, 5_000);
+
+    // Delinquency: vote rate above the threshold is not severe
+    let severity = calculate_instant_unstake_delinquency_severity(
+        1000,
+        1000,
+        1000 * TVC_MULTIPLIER,
+        1000,
+        0.7,
+    )
+    .unwrap();
+    assert_eq!(severity, 0);
+
+    // Delinquency: no blocks produced is not severe
+    let severity = calculate_instant_unstake_delinquency_severity(0, 1000, 0, 1000, 0.7).unwrap();
+    assert_eq!(severity, 0);
+
+    assert!(calculate_instant_unstake_delinquency_severity(1000, 0, 1000, 1000, 0.7).is_err());
+
+    // Commission: 10% threshold, 55% is halfway to 100%
+    assert_eq!(
+        calculate_instant_unstake_commission_severity(10, 10, 100),
+        0
+    );
+    assert_eq!(
+        calculate_instant_unstake_commission_severity(55, 10, 100),
+        5_000
+    );
+    assert_eq!(
+        calculate_instant_unstake_commission_severity(100, 10, 100),
+        BASIS_POINTS_MAX
+    );
+    // MEV commission: 10% threshold in basis points
+    assert_eq!(
+        calculate_instant_unstake_commission_severity(3_250, 1_000, BASIS_POINTS_MAX),
+        2_500
+    );
+
+    // Fraction
+    assert_eq!(
+        calculate_instant_unstake_fraction(false, false, 5_000, 5_000),
+        0
+    );
+    assert_eq!(
+        calculate_instant_unstake_fraction(true, false, 5_000, 5_000),
+        2_500
+    );
+    assert_eq!(
+        calculate_instant_unstake_fraction(true, false, 5_000, 0),
+        BASIS_POINTS_MAX
+    );
+    assert_eq!(
+        calculate_instant_unstake_fraction(true, true, 5_000, 5_000),
+        BASIS_POINTS_MAX
+    );
+    // A flagged validator always has some stake removed
+    assert_eq!(calculate_instant_unstake_fraction(true, false, 0, 5_000), 1);
+
+    // Graded target: 25% of current stake is removed, unless the delegation target is lower
+    assert_eq!(
+        instant_unstake_target_lamports(1000 * LAMPORTS_PER_SOL, 1000 * LAMPORTS_PER_SOL, 2_500),
+        750 * LAMPORTS_PER_SOL
+    );
+    assert_eq!(
+        instant_unstake_target_lamports(500 * LAMPORTS_PER_SOL, 1000 * LAMPORTS_PER_SOL, 2_500),
+        500 * LAMPORTS_PER_SOL
+    );
+}
+
+#[test]
+fn test_skip_dust_rebalance() {
+    let minimum = LAMPORTS_PER_SOL;
+    let decrease = |instant_unstake_lamports, total_unstake_lamports| {
+        RebalanceType::Decrease(DecreaseComponents {
+            scoring_unstake_lamports: total_unstake_lamports - instant_unstake_lamports,
+            instant_unstake_lamports,
+            stake_deposit_unstake_lamports: 0,
+            total_unstake_lamports,
+            directed_unstake_lamports: 0,
+        })
+    };
+
+    assert!(matches!(
+        skip_dust_rebalance(RebalanceType::Increase(minimum - 1), minimum),
+        RebalanceType::None
+    ));
+    assert!(matches!(
+        skip_dust_rebalance(RebalanceType::Increase(minimum), minimum),
+        RebalanceType::Increase(amount) if amount == minimum
+    ));
+    assert!(matches!(
+        skip_dust_rebalance(decrease(0, minimum - 1), minimum),
+        RebalanceType::None
+    ));
+    assert!(matches!(
+        skip_dust_rebalance(decrease(0, minimum), minimum),
+        RebalanceType::Decrease(_)
+    ));
+    // Instant unstakes are never skipped
+    assert!(matches!(
+        skip_dust_rebalance(decrease(1, 1), minimum),
+        RebalanceType::Decrease(_)
+    ));
+    // Disabled
+    assert!(matches!(
+        skip_dust_rebalance(RebalanceType::Increase(1), 0),
+        RebalanceType::Increase(1)
+    ));
+}
+
 #[test]
 fn test_insert_sorted_index() {
     let mut scores = vec![10];
@@ -1857,6 +2010,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(matches!(result.unwrap(), RebalanceType::None));
@@ -1879,6 +2033,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(match result.unwrap() {
@@ -1913,6 +2068,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(match result.unwrap() {
@@ -1950,6 +2106,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(match result.unwrap() {
@@ -1985,6 +2142,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(match result.unwrap() {
         RebalanceType::Decrease(components) =>
@@ -2017,6 +2175,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(match result.unwrap() {
         RebalanceType::Decrease(components) =>
@@ -2063,6 +2222,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     println!("Should be None: {result:?}");
     assert!(matches!(result.unwrap(), RebalanceType::None));
@@ -2088,6 +2248,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(match result.unwrap() {
         RebalanceType::Decrease(components) =>
@@ -2120,6 +2281,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(match result.unwrap() {
         RebalanceType::Decrease(components) =>
@@ -2154,6 +2316,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         100 * LAMPORTS_PER_SOL,
         50 * LAMPORTS_PER_SOL,
+        None,
     );
     assert!(match result.unwrap() {
         RebalanceType::Decrease(components) =>
@@ -2195,6 +2358,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(match result.unwrap() {
@@ -2231,6 +2395,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         100 * LAMPORTS_PER_SOL,
         0,
+        None,
     );
     assert!(result.is_ok());
     assert!(matches!(result.unwrap(), RebalanceType::None));
@@ -2246,6 +2411,7 @@ fn test_decrease_stake_calculation() {
         &validator_list_bigvec,
         0,
         0,
+        None,
     );
     assert!(match result {
         Err(e) => e == StewardError::ValidatorIndexOutOfBounds.into(),
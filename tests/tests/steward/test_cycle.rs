@@ -170,6 +170,22 @@ async fn test_cycle() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -455,6 +471,22 @@ async fn test_cycle_with_directed_stake_persistent_unstake_state() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -757,6 +789,22 @@ async fn test_cycle_with_directed_stake_unstake_minimum_delegation() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -991,6 +1039,22 @@ async fn test_cycle_with_directed_stake_unstake_cap() {
                 directed_stake_unstake_cap_bps: Some(100),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1223,6 +1287,22 @@ async fn test_cycle_with_directed_stake_noop_copy() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1432,6 +1512,22 @@ async fn test_cycle_with_directed_stake_partial_copy() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1642,6 +1738,22 @@ async fn test_cycle_with_directed_stake_undirected() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1804,6 +1916,22 @@ async fn test_cycle_with_directed_stake_increase_minimum_delegation() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1969,6 +2097,22 @@ async fn test_cycle_with_directed_stake_targets() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -2361,6 +2505,22 @@ async fn test_remove_validator_mid_epoch() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -2662,6 +2822,22 @@ async fn test_add_validator_next_cycle() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -2916,6 +3092,22 @@ async fn test_directed_stake_large_target_low_reserve() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
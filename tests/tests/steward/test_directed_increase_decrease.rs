@@ -30,7 +30,7 @@ fn create_mock_steward_state(num_pool_validators: u16) -> StewardState {
         stake_deposit_unstake_total: 0,
         validators_added: 0,
         status_flags: 0,
-        _padding0: [0; 2],
+        validators_removed: 0,
     }
 }
 
@@ -41,7 +41,8 @@ fn create_mock_directed_stake_meta(
     let mut meta = DirectedStakeMeta {
         total_stake_targets: 0,
         directed_unstake_total: 0,
-        padding0: [0; 63],
+        ticket_targets_only: jito_steward::utils::U8Bool::from(false),
+        padding0: [0; 62],
         is_initialized: jito_steward::utils::U8Bool::from(true),
         directed_stake_lamports: [0; MAX_VALIDATORS],
         directed_stake_meta_indices: [u64::MAX; MAX_VALIDATORS],
@@ -51,7 +52,10 @@ fn create_mock_directed_stake_meta(
             total_staked_lamports: 0,
             target_last_updated_epoch: 0,
             staked_last_updated_epoch: 0,
-            _padding0: [0; 32],
+            ticket_target_lamports: 0,
+            ticket_decay_epoch: 0,
+            unattributed_target_lamports: 0,
+            migrated_target_lamports: 0,
         }; MAX_VALIDATORS],
     };
 
@@ -63,7 +67,10 @@ fn create_mock_directed_stake_meta(
                 total_staked_lamports: *staked_lamports,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             };
         }
     }
@@ -5,7 +5,7 @@ use jito_steward::{
     instructions::AuthorityType,
     stake_pool_utils::ValidatorList,
     state::directed_stake::{DirectedStakeMeta, DirectedStakeTarget},
-    DirectedStakeWhitelist, REBALANCE_DIRECTED_COMPLETE,
+    DirectedStakeWhitelist, ValidatorExit, REBALANCE_DIRECTED_COMPLETE,
 };
 use solana_program::{instruction::Instruction, sysvar};
 use solana_program_test::*;
@@ -14,7 +14,9 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use spl_stake_pool::find_transient_stake_program_address;
-use tests::steward_fixtures::{serialized_steward_state_account, TestFixture};
+use tests::steward_fixtures::{
+    initialize_rebalance_journal, serialized_steward_state_account, TestFixture,
+};
 
 /// Helper function to set the directed stake whitelist authority
 async fn set_directed_stake_whitelist_authority(fixture: &TestFixture) {
@@ -189,6 +191,15 @@ async fn add_validator_to_pool(fixture: &TestFixture, vote_pubkey: Pubkey) {
             stake_config: solana_program::stake::config::ID,
             stake_program: solana_program::stake::program::id(),
             system_program: solana_program::system_program::id(),
+            validator_exit: Pubkey::find_program_address(
+                &[
+                    ValidatorExit::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                    vote_pubkey.as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::AutoAddValidatorToPool {}.data(),
@@ -247,7 +258,8 @@ async fn populate_directed_stake_meta_after_init(
     let meta = DirectedStakeMeta {
         total_stake_targets: 0,
         directed_unstake_total: 0,
-        padding0: [0; 63],
+        ticket_targets_only: jito_steward::utils::U8Bool::from(false),
+        padding0: [0; 62],
         is_initialized: jito_steward::utils::U8Bool::from(true),
         targets: {
             let mut targets = [DirectedStakeTarget {
@@ -256,7 +268,10 @@ async fn populate_directed_stake_meta_after_init(
                 total_staked_lamports: 0,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             }; MAX_VALIDATORS];
 
             // Set the first target
@@ -266,7 +281,10 @@ async fn populate_directed_stake_meta_after_init(
                 total_staked_lamports: staked_lamports,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             };
 
             targets
@@ -398,6 +416,7 @@ async fn test_simple_directed_rebalance_no_action_needed() {
             stake_config: solana_program::stake::config::ID,
             system_program: solana_program::system_program::id(),
             stake_program: solana_program::stake::program::id(),
+            rebalance_journal: initialize_rebalance_journal(&fixture, &vote_account_at_index).await,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::RebalanceDirected {
@@ -532,6 +551,7 @@ async fn test_simple_directed_rebalance_no_targets() {
             stake_config: solana_program::stake::config::ID,
             system_program: solana_program::system_program::id(),
             stake_program: solana_program::stake::program::id(),
+            rebalance_journal: initialize_rebalance_journal(&fixture, &vote_account_at_index).await,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::RebalanceDirected {
@@ -644,6 +664,7 @@ async fn test_directed_rebalance_wrong_state() {
             stake_config: solana_program::stake::config::ID,
             system_program: solana_program::system_program::id(),
             stake_program: solana_program::stake::program::id(),
+            rebalance_journal: initialize_rebalance_journal(&fixture, &vote_account_at_index).await,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::RebalanceDirected {
@@ -2,7 +2,8 @@ use jito_steward::{
     constants::MAX_VALIDATORS,
     state::directed_stake::{DirectedStakePreference, DirectedStakeRecordType},
     utils::U8Bool,
-    DirectedStakeTicket, DirectedStakeWhitelist, MAX_PERMISSIONED_DIRECTED_STAKERS,
+    DirectedStakeBalanceSource, DirectedStakeBalanceSources, DirectedStakeTicket,
+    DirectedStakeWhitelist, MAX_BALANCE_SOURCES_PER_TICKET, MAX_PERMISSIONED_DIRECTED_STAKERS,
     MAX_PREFERENCES_PER_TICKET,
 };
 use solana_sdk::pubkey::Pubkey;
@@ -30,11 +31,14 @@ fn test_directed_stake_ticket_initialization() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 0,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: update_authority,
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
 
     assert_eq!(ticket.num_preferences, 0);
@@ -58,17 +62,21 @@ fn test_directed_stake_ticket_with_preferences() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: validator1,
             stake_share_bps: 4000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: update_authority,
         ticket_holder_is_protocol: U8Bool::from(true),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
 
     ticket.staker_preferences[1] = DirectedStakePreference {
         vote_pubkey: validator2,
         stake_share_bps: 6000,
-        _padding0: [0; 94],
+        is_basket: U8Bool::from(false),
+        _padding0: [0; 93],
     };
 
     assert!(ticket.preferences_valid());
@@ -90,17 +98,21 @@ fn test_directed_stake_ticket_invalid_preferences() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: validator1,
             stake_share_bps: 6000, // 60%
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: update_authority,
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
 
     ticket.staker_preferences[1] = DirectedStakePreference {
         vote_pubkey: validator2,
         stake_share_bps: 6000, // 60% + 60% = 120% > 100%
-        _padding0: [0; 94],
+        is_basket: U8Bool::from(false),
+        _padding0: [0; 93],
     };
 
     assert!(!ticket.preferences_valid());
@@ -251,11 +263,14 @@ fn test_directed_stake_ticket_edge_cases() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 0,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: update_authority,
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
 
     let allocations = ticket.get_allocations(0);
@@ -265,7 +280,8 @@ fn test_directed_stake_ticket_edge_cases() {
     ticket.staker_preferences[0] = DirectedStakePreference {
         vote_pubkey: Pubkey::new_unique(),
         stake_share_bps: 10000, // 100%
-        _padding0: [0; 94],
+        is_basket: U8Bool::from(false),
+        _padding0: [0; 93],
     };
 
     assert!(ticket.preferences_valid());
@@ -315,12 +331,14 @@ fn test_directed_stake_whitelist_validation_logic() {
         DirectedStakePreference {
             vote_pubkey: validator1,
             stake_share_bps: 5000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
         DirectedStakePreference {
             vote_pubkey: validator2,
             stake_share_bps: 5000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
     ];
 
@@ -334,12 +352,14 @@ fn test_directed_stake_whitelist_validation_logic() {
         DirectedStakePreference {
             vote_pubkey: validator1,
             stake_share_bps: 5000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
         DirectedStakePreference {
             vote_pubkey: non_whitelisted_validator, // This should fail validation
             stake_share_bps: 5000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
     ];
 
@@ -498,14 +518,17 @@ fn test_ticket_authorization_scenarios() {
         staker_preferences: [DirectedStakePreference::empty(); MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: update_authority,
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
 
     // Test valid preferences (all validators whitelisted)
     let valid_preferences = vec![DirectedStakePreference {
         vote_pubkey: permissioned_validator,
         stake_share_bps: 10000,
-        _padding0: [0; 94],
+        is_basket: U8Bool::from(false),
+        _padding0: [0; 93],
     }];
 
     // All validators in preferences should be whitelisted
@@ -517,7 +540,8 @@ fn test_ticket_authorization_scenarios() {
     let invalid_preferences = [DirectedStakePreference {
         vote_pubkey: non_permissioned_validator,
         stake_share_bps: 10000,
-        _padding0: [0; 94],
+        is_basket: U8Bool::from(false),
+        _padding0: [0; 93],
     }];
 
     // At least one validator should not be whitelisted
@@ -649,17 +673,20 @@ fn test_preferences_validation_edge_cases() {
         DirectedStakePreference {
             vote_pubkey: validator1, // Valid - whitelisted
             stake_share_bps: 5000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
         DirectedStakePreference {
             vote_pubkey: validator2, // Valid - whitelisted
             stake_share_bps: 3000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
         DirectedStakePreference {
             vote_pubkey: validator3, // Invalid - not whitelisted
             stake_share_bps: 2000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
     ];
 
@@ -679,12 +706,14 @@ fn test_preferences_validation_edge_cases() {
         DirectedStakePreference {
             vote_pubkey: validator1,
             stake_share_bps: 6000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
         DirectedStakePreference {
             vote_pubkey: validator2,
             stake_share_bps: 4000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         },
     ];
 
@@ -856,3 +885,47 @@ fn test_cross_category_staker_operations() {
     assert!(!whitelist.is_protocol_staker_permissioned(&staker));
     assert!(!whitelist.is_staker_permissioned(&staker));
 }
+
+#[test]
+fn test_directed_stake_balance_sources_add_remove() {
+    let mut balance_sources = DirectedStakeBalanceSources {
+        ticket: Pubkey::new_unique(),
+        num_sources: 0,
+        sources: [DirectedStakeBalanceSource::default(); MAX_BALANCE_SOURCES_PER_TICKET],
+        _padding0: [0; 64],
+    };
+    let owner = Pubkey::new_unique();
+    let accounts: Vec<Pubkey> = (0..MAX_BALANCE_SOURCES_PER_TICKET)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+
+    for account in accounts.iter() {
+        balance_sources.add_source(*account, owner, 0).unwrap();
+    }
+    assert_eq!(
+        balance_sources.num_sources as usize,
+        MAX_BALANCE_SOURCES_PER_TICKET
+    );
+
+    // Full
+    assert!(balance_sources
+        .add_source(Pubkey::new_unique(), owner, 0)
+        .is_err());
+
+    // Removing swaps the last source into the removed slot
+    let removed = balance_sources.remove_source(&accounts[0]).unwrap();
+    assert_eq!(removed.owner, owner);
+    assert_eq!(
+        balance_sources.sources[0].account,
+        accounts[MAX_BALANCE_SOURCES_PER_TICKET - 1]
+    );
+    assert!(balance_sources.remove_source(&accounts[0]).is_err());
+
+    // Duplicates are rejected
+    assert!(balance_sources.add_source(accounts[1], owner, 0).is_err());
+    balance_sources.add_source(accounts[0], owner, 1).unwrap();
+    assert_eq!(
+        balance_sources.num_sources as usize,
+        MAX_BALANCE_SOURCES_PER_TICKET
+    );
+}
@@ -9,7 +9,11 @@
 /// 3. Manually construct the instruction data using Borsh serialization
 use anchor_lang::Discriminator;
 use anchor_lang::{
-    solana_program::{instruction::Instruction, pubkey::Pubkey, sysvar},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
     InstructionData, ToAccountMetas,
 };
 use jito_steward::{
@@ -18,11 +22,17 @@ use jito_steward::{
     state::directed_stake::{
         DirectedStakePreference, DirectedStakeRecordType, DirectedStakeTarget,
     },
-    DirectedStakeMeta, DirectedStakeTicket, DirectedStakeWhitelist,
+    Config, DirectedStakeBalanceSources, DirectedStakeBasket, DirectedStakeBasketContribution,
+    DirectedStakeBasketEntry, DirectedStakeContribution, DirectedStakeMeta, DirectedStakeTicket,
+    DirectedStakeTicketExtension, DirectedStakeValidatorConsent, DirectedStakeWhitelist,
 };
 use solana_program_test::*;
-use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
-use tests::steward_fixtures::{system_account, TestFixture};
+use solana_sdk::{
+    clock::Clock, epoch_schedule::EpochSchedule, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use spl_token::solana_program::program_pack::Pack;
+use tests::steward_fixtures::{serialized_config, system_account, TestFixture};
 
 /// Helper function to create a test fixture with directed stake setup
 async fn setup_directed_stake_fixture() -> TestFixture {
@@ -258,6 +268,94 @@ async fn initialize_directed_stake_ticket(
     ticket_account
 }
 
+/// Helper function to build the validator consent remaining accounts for `preferences`, passing
+/// the basket itself for preferences pointing at a basket
+fn validator_consent_metas(
+    fixture: &TestFixture,
+    preferences: &[DirectedStakePreference],
+) -> Vec<AccountMeta> {
+    preferences
+        .iter()
+        .map(|preference| {
+            if preference.is_basket.into() {
+                return AccountMeta::new_readonly(preference.vote_pubkey, false);
+            }
+            AccountMeta::new_readonly(
+                Pubkey::find_program_address(
+                    &[
+                        DirectedStakeValidatorConsent::SEED,
+                        fixture.steward_config.pubkey().as_ref(),
+                        preference.vote_pubkey.as_ref(),
+                    ],
+                    &jito_steward::id(),
+                )
+                .0,
+                false,
+            )
+        })
+        .collect()
+}
+
+/// Remaining accounts of `update_directed_stake_ticket` after the consent PDAs: the basket
+/// contribution of each basket the ticket stops pointing at
+async fn left_basket_contribution_metas(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    preferences: &[DirectedStakePreference],
+) -> Vec<AccountMeta> {
+    let ticket: DirectedStakeTicket = fixture.load_and_deserialize(ticket_account).await;
+    let ticket_extension_address = Pubkey::find_program_address(
+        &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+        &jito_steward::id(),
+    )
+    .0;
+    let ticket_extension: Option<DirectedStakeTicketExtension> =
+        if fixture.account_exists(&ticket_extension_address).await {
+            Some(
+                fixture
+                    .load_and_deserialize(&ticket_extension_address)
+                    .await,
+            )
+        } else {
+            None
+        };
+
+    let mut updated_ticket = ticket;
+    updated_ticket.num_preferences = preferences.len() as u16;
+    updated_ticket.staker_preferences[..preferences.len()].copy_from_slice(preferences);
+
+    let mut left_baskets: Vec<Pubkey> = Vec::new();
+    for preference in ticket.preferences() {
+        if bool::from(preference.is_basket)
+            && !left_baskets.contains(&preference.vote_pubkey)
+            && DirectedStakeBasket::ticket_stake_share_bps(
+                &preference.vote_pubkey,
+                &updated_ticket,
+                ticket_extension.as_ref(),
+            ) == 0
+        {
+            left_baskets.push(preference.vote_pubkey);
+        }
+    }
+    left_baskets
+        .iter()
+        .map(|basket| {
+            AccountMeta::new(
+                Pubkey::find_program_address(
+                    &[
+                        DirectedStakeBasketContribution::SEED,
+                        ticket_account.as_ref(),
+                        basket.as_ref(),
+                    ],
+                    &jito_steward::id(),
+                )
+                .0,
+                false,
+            )
+        })
+        .collect()
+}
+
 /// Helper function to update a directed stake ticket with preferences
 async fn update_directed_stake_ticket(
     fixture: &TestFixture,
@@ -280,9 +378,26 @@ async fn update_directed_stake_ticket(
             config: fixture.steward_config.pubkey(),
             whitelist_account: directed_stake_whitelist,
             ticket_account: *ticket_account,
+            directed_stake_meta: Pubkey::find_program_address(
+                &[
+                    DirectedStakeMeta::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
+            ticket_extension: Pubkey::find_program_address(
+                &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
             signer: signer.pubkey(),
         }
-        .to_account_metas(None),
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(fixture, &preferences))
+        .chain(left_basket_contribution_metas(fixture, ticket_account, &preferences).await)
+        .collect(),
         data: jito_steward::instruction::UpdateDirectedStakeTicket {
             preferences: preferences.clone(),
         }
@@ -679,9 +794,25 @@ async fn test_directed_stake_ticket_validation() {
             config: fixture.steward_config.pubkey(),
             whitelist_account: directed_stake_whitelist,
             ticket_account,
+            directed_stake_meta: Pubkey::find_program_address(
+                &[
+                    DirectedStakeMeta::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
+            ticket_extension: Pubkey::find_program_address(
+                &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
             signer: fixture.keypair.pubkey(),
         }
-        .to_account_metas(None),
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(&fixture, &_invalid_preferences))
+        .collect(),
         data: jito_steward::instruction::UpdateDirectedStakeTicket {
             preferences: _invalid_preferences.clone(),
         }
@@ -1143,9 +1274,25 @@ async fn test_unauthorized_user_cannot_update_ticket() {
             config: fixture.steward_config.pubkey(),
             whitelist_account: directed_stake_whitelist,
             ticket_account,
+            directed_stake_meta: Pubkey::find_program_address(
+                &[
+                    DirectedStakeMeta::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
+            ticket_extension: Pubkey::find_program_address(
+                &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
             signer: unauthorized_staker.pubkey(),
         }
-        .to_account_metas(None),
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(&fixture, &preferences))
+        .collect(),
         data: jito_steward::instruction::UpdateDirectedStakeTicket {
             preferences: preferences.clone(),
         }
@@ -1441,7 +1588,8 @@ async fn test_sync_lamports() {
     let mut meta = DirectedStakeMeta {
         total_stake_targets: 3,
         directed_unstake_total: 0,
-        padding0: [0; 63],
+        ticket_targets_only: jito_steward::utils::U8Bool::from(false),
+        padding0: [0; 62],
         is_initialized: jito_steward::utils::U8Bool::from(true),
         targets: {
             let mut targets = [DirectedStakeTarget {
@@ -1450,7 +1598,10 @@ async fn test_sync_lamports() {
                 total_staked_lamports: 0,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             }; MAX_VALIDATORS];
 
             // Set the three targets with total_staked_lamports set (sync copies from this field)
@@ -1460,7 +1611,10 @@ async fn test_sync_lamports() {
                 total_staked_lamports: 10_000_000_000,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             };
             targets[1] = DirectedStakeTarget {
                 vote_pubkey: validator2,
@@ -1468,7 +1622,10 @@ async fn test_sync_lamports() {
                 total_staked_lamports: 20_000_000_000,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             };
             targets[2] = DirectedStakeTarget {
                 vote_pubkey: validator3,
@@ -1476,7 +1633,10 @@ async fn test_sync_lamports() {
                 total_staked_lamports: 30_000_000_000,
                 target_last_updated_epoch: 0,
                 staked_last_updated_epoch: 0,
-                _padding0: [0; 32],
+                ticket_target_lamports: 0,
+                ticket_decay_epoch: 0,
+                unattributed_target_lamports: 0,
+                migrated_target_lamports: 0,
             };
 
             targets
@@ -1559,3 +1719,1296 @@ async fn test_sync_lamports() {
     assert!(directed_stake_meta.directed_stake_lamports[validator2_index] == 20_000_000_000);
     assert!(directed_stake_meta.directed_stake_lamports[validator3_index] == 30_000_000_000);
 }
+
+/// Helper function to set the stake pool exchange rate and the ticket holder's pool token balance
+async fn set_holder_pool_tokens(
+    fixture: &TestFixture,
+    holder: &Pubkey,
+    token_amount: u64,
+    total_lamports: u64,
+    pool_token_supply: u64,
+) -> Pubkey {
+    let stake_pool: jito_steward::stake_pool_utils::StakePool = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.stake_pool)
+        .await;
+    let mut stake_pool_spl = stake_pool.as_ref().clone();
+    stake_pool_spl.total_lamports = total_lamports;
+    stake_pool_spl.pool_token_supply = pool_token_supply;
+    let pool_mint = stake_pool_spl.pool_mint;
+
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.stake_pool_meta.stake_pool,
+        &tests::stake_pool_utils::serialized_stake_pool_account(
+            stake_pool_spl,
+            std::mem::size_of::<jito_steward::stake_pool_utils::StakePool>(),
+        )
+        .into(),
+    );
+
+    let holder_token_account =
+        spl_associated_token_account::get_associated_token_address(holder, &pool_mint);
+    set_pool_token_account(fixture, &holder_token_account, holder, token_amount).await;
+
+    holder_token_account
+}
+
+/// Sets `address` to a pool mint token account owned by `owner`
+async fn set_pool_token_account(
+    fixture: &TestFixture,
+    address: &Pubkey,
+    owner: &Pubkey,
+    token_amount: u64,
+) {
+    let stake_pool: jito_steward::stake_pool_utils::StakePool = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.stake_pool)
+        .await;
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: stake_pool.pool_mint,
+        owner: *owner,
+        amount: token_amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    }
+    .pack_into_slice(&mut data);
+    fixture.ctx.borrow_mut().set_account(
+        address,
+        &solana_sdk::account::Account {
+            lamports: 2_039_280,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+/// Helper function to turn balance sampling on, with a single bucket per epoch, or off
+async fn set_balance_sampling(fixture: &TestFixture, enabled: bool) {
+    let epoch_schedule: EpochSchedule = fixture.get_sysvar().await;
+    let mut config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    config.parameters.directed_stake_sample_interval_slots = if enabled {
+        epoch_schedule.slots_per_epoch
+    } else {
+        0
+    };
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.steward_config.pubkey(),
+        &serialized_config(config).into(),
+    );
+}
+
+/// Helper function to record `token_amount` as the ticket's average balance over the previous
+/// epoch, as if the ticket had been sampled at that balance since before it started
+async fn set_average_balance(fixture: &TestFixture, ticket_account: &Pubkey, token_amount: u64) {
+    let contribution_account = Pubkey::find_program_address(
+        &[DirectedStakeContribution::SEED, ticket_account.as_ref()],
+        &jito_steward::id(),
+    )
+    .0;
+    let mut contribution: DirectedStakeContribution =
+        if fixture.account_exists(&contribution_account).await {
+            fixture.load_and_deserialize(&contribution_account).await
+        } else {
+            let mut contribution: DirectedStakeContribution = bytemuck::Zeroable::zeroed();
+            contribution.config = fixture.steward_config.pubkey();
+            contribution.ticket = *ticket_account;
+            contribution
+        };
+
+    let clock: Clock = fixture.get_sysvar().await;
+    contribution.sample_epoch = clock.epoch;
+    contribution.sample_bucket = 0;
+    contribution.sample_bucket_min_balance = token_amount;
+    contribution.sample_balance_sum = 0;
+    contribution.last_sample_slot = clock.slot.max(1);
+    contribution.average_balance = token_amount;
+    contribution.average_balance_epoch = clock.epoch.saturating_sub(1);
+    contribution.sample_start_epoch = clock.epoch.saturating_sub(2);
+
+    let mut data = DirectedStakeContribution::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&contribution));
+    fixture.ctx.borrow_mut().set_account(
+        &contribution_account,
+        &solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: jito_steward::id(),
+            ..solana_sdk::account::Account::default()
+        }
+        .into(),
+    );
+}
+
+/// Helper function to crank a directed stake ticket, initializing its contribution account if needed
+async fn crank_directed_stake_ticket(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    holder_token_account: &Pubkey,
+) -> Pubkey {
+    let (tx, contribution_account) =
+        crank_directed_stake_ticket_transaction(fixture, ticket_account, holder_token_account)
+            .await;
+    fixture.submit_transaction_assert_success(tx).await;
+
+    contribution_account
+}
+
+async fn crank_directed_stake_ticket_transaction(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    holder_token_account: &Pubkey,
+) -> (Transaction, Pubkey) {
+    let (ixs, contribution_account) =
+        crank_directed_stake_ticket_instructions(fixture, ticket_account, holder_token_account)
+            .await;
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+
+    (tx, contribution_account)
+}
+
+/// Helper function to build the instructions cranking a ticket, initializing its contribution
+/// account first if needed
+async fn crank_directed_stake_ticket_instructions(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    holder_token_account: &Pubkey,
+) -> (Vec<Instruction>, Pubkey) {
+    let directed_stake_meta = Pubkey::find_program_address(
+        &[
+            DirectedStakeMeta::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let contribution_account = Pubkey::find_program_address(
+        &[DirectedStakeContribution::SEED, ticket_account.as_ref()],
+        &jito_steward::id(),
+    )
+    .0;
+
+    let mut ixs = vec![];
+    if !fixture.account_exists(&contribution_account).await {
+        ixs.push(Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::InitializeDirectedStakeContribution {
+                config: fixture.steward_config.pubkey(),
+                ticket_account: *ticket_account,
+                contribution_account,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                payer: fixture.keypair.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeContribution {}.data(),
+        });
+    }
+
+    let ticket: DirectedStakeTicket = fixture.load_and_deserialize(ticket_account).await;
+    let validator_list: jito_steward::stake_pool_utils::ValidatorList = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.validator_list)
+        .await;
+    let validator_list_indices = ticket
+        .staker_preferences
+        .iter()
+        .take(ticket.num_preferences as usize)
+        .map(|preference| {
+            validator_list
+                .validators
+                .iter()
+                .position(|v| v.vote_account_address == preference.vote_pubkey)
+                .map_or(u32::MAX, |index| index as u32)
+        })
+        .collect();
+
+    let balance_sources = Pubkey::find_program_address(
+        &[DirectedStakeBalanceSources::SEED, ticket_account.as_ref()],
+        &jito_steward::id(),
+    )
+    .0;
+    let balance_source_metas: Vec<AccountMeta> = if fixture.account_exists(&balance_sources).await {
+        let sources: DirectedStakeBalanceSources =
+            fixture.load_and_deserialize(&balance_sources).await;
+        sources.sources[..sources.num_sources as usize]
+            .iter()
+            .map(|source| AccountMeta::new_readonly(source.account, false))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    ixs.push(Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::CrankDirectedStakeTicket {
+            config: fixture.steward_config.pubkey(),
+            directed_stake_meta,
+            ticket_account: *ticket_account,
+            contribution_account,
+            holder_token_account: *holder_token_account,
+            balance_sources,
+            stake_pool: fixture.stake_pool_meta.stake_pool,
+            validator_list: fixture.stake_pool_meta.validator_list,
+            instructions: sysvar::instructions::id(),
+        }
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(
+            fixture,
+            &ticket.staker_preferences[..ticket.num_preferences as usize],
+        ))
+        .chain(balance_source_metas)
+        .collect(),
+        data: jito_steward::instruction::CrankDirectedStakeTicket {
+            validator_list_indices,
+        }
+        .data(),
+    });
+
+    (ixs, contribution_account)
+}
+
+#[tokio::test]
+async fn test_crank_directed_stake_ticket() {
+    let fixture = setup_directed_stake_fixture().await;
+    let directed_stake_meta_address = initialize_directed_stake_meta(&fixture).await;
+    set_directed_stake_meta_upload_authority(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    let validator2 = fixture
+        .get_validator_from_list(1)
+        .await
+        .expect("Validator list should have at least two validators");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+    add_validator_to_whitelist(&fixture, &validator2).await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![
+            DirectedStakePreference::new(validator1, 6000),
+            DirectedStakePreference::new(validator2, 4000),
+        ],
+    )
+    .await;
+
+    // 10 pool tokens at 2 SOL per token
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+    set_average_balance(&fixture, &ticket_account, 10_000_000_000).await;
+    let contribution_account =
+        crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(12_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(8_000_000_000));
+
+    let contribution: DirectedStakeContribution =
+        fixture.load_and_deserialize(&contribution_account).await;
+    assert_eq!(contribution.token_balance, 10_000_000_000);
+    assert_eq!(contribution.num_contributions, 2);
+    assert_eq!(contribution.total_lamports(), 20_000_000_000);
+
+    // The upload authority can no longer change targets
+    let tx = tests::steward_fixtures::copy_directed_stake_targets_transaction(
+        &fixture,
+        validator1,
+        5_000_000_000,
+    )
+    .await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeTargetManagedByTickets")
+        .await;
+
+    // Re-cranking after the average balance halves replaces the previous contribution
+    set_average_balance(&fixture, &ticket_account, 5_000_000_000).await;
+    crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(6_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(4_000_000_000));
+
+    // Moving all preferences to validator2 removes the ticket's stake from validator1
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator2, 10_000)],
+    )
+    .await;
+    crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(0));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(10_000_000_000));
+
+    // Spot balances are never credited
+    set_balance_sampling(&fixture, false).await;
+    let (tx, _) =
+        crank_directed_stake_ticket_transaction(&fixture, &ticket_account, &holder_token_account)
+            .await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeSamplingDisabled")
+        .await;
+}
+
+async fn migrate_directed_targets_to_tickets_transaction(fixture: &TestFixture) -> Transaction {
+    let directed_stake_meta = Pubkey::find_program_address(
+        &[
+            DirectedStakeMeta::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::MigrateDirectedTargetsToTickets {
+            config: fixture.steward_config.pubkey(),
+            directed_stake_meta,
+            authority: fixture.keypair.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::MigrateDirectedTargetsToTickets {}.data(),
+    };
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    )
+}
+
+async fn migrate_directed_targets_to_tickets(fixture: &TestFixture) {
+    let tx = migrate_directed_targets_to_tickets_transaction(fixture).await;
+    fixture.submit_transaction_assert_success(tx).await;
+}
+
+#[tokio::test]
+async fn test_migrate_directed_targets_to_tickets() {
+    let fixture = setup_directed_stake_fixture().await;
+    let directed_stake_meta_address = initialize_directed_stake_meta(&fixture).await;
+    set_directed_stake_meta_upload_authority(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    let validator2 = fixture
+        .get_validator_from_list(1)
+        .await
+        .expect("Validator list should have at least two validators");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator1, 10_000)],
+    )
+    .await;
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+
+    // Uploaded targets already include ticket holders, so tickets cannot be cranked yet
+    let (tx, _) =
+        crank_directed_stake_ticket_transaction(&fixture, &ticket_account, &holder_token_account)
+            .await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeTargetsNotMigrated")
+        .await;
+
+    tests::steward_fixtures::crank_copy_directed_stake_targets(
+        &fixture,
+        validator1,
+        25_000_000_000,
+    )
+    .await;
+    tests::steward_fixtures::crank_copy_directed_stake_targets(&fixture, validator2, 3_000_000_000)
+        .await;
+
+    // Ticket cranks need balance sampling
+    let tx = migrate_directed_targets_to_tickets_transaction(&fixture).await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeSamplingDisabled")
+        .await;
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+
+    // Uploaded targets are kept until tickets are cranked
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert!(meta.ticket_targets_only());
+    assert_eq!(meta.get_target_lamports(&validator1), Some(25_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(3_000_000_000));
+    let target1 = &meta.targets[meta.get_target_index(&validator1).unwrap()];
+    assert_eq!(target1.migrated_target_lamports, 25_000_000_000);
+
+    // The ticket replaces its share of the upload instead of adding to it
+    set_average_balance(&fixture, &ticket_account, 10_000_000_000).await;
+    crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(25_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(3_000_000_000));
+    let target1 = &meta.targets[meta.get_target_index(&validator1).unwrap()];
+    assert_eq!(target1.ticket_target_lamports, 20_000_000_000);
+    assert_eq!(target1.migrated_target_lamports, 5_000_000_000);
+
+    // Later cranks only replace the ticket's own contribution
+    set_average_balance(&fixture, &ticket_account, 5_000_000_000).await;
+    crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(15_000_000_000));
+    let target1 = &meta.targets[meta.get_target_index(&validator1).unwrap()];
+    assert_eq!(target1.migrated_target_lamports, 5_000_000_000);
+
+    // No target can be uploaded anymore, with or without ticket contributions
+    for validator in [validator1, validator2] {
+        let tx = tests::steward_fixtures::copy_directed_stake_targets_transaction(
+            &fixture,
+            validator,
+            5_000_000_000,
+        )
+        .await;
+        fixture
+            .submit_transaction_assert_error(tx, "DirectedStakeTargetManagedByTickets")
+            .await;
+    }
+
+    // The migration only runs once
+    let tx = migrate_directed_targets_to_tickets_transaction(&fixture).await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeTargetsAlreadyMigrated")
+        .await;
+}
+
+#[tokio::test]
+async fn test_crank_directed_stake_ticket_rejects_borrowed_balance() {
+    let fixture = setup_directed_stake_fixture().await;
+    let directed_stake_meta_address = initialize_directed_stake_meta(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator1, 10_000)],
+    )
+    .await;
+
+    // 10 pool tokens at 2 SOL per token
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+    set_average_balance(&fixture, &ticket_account, 10_000_000_000).await;
+    let contribution_account =
+        crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+
+    // 90 pool tokens lent to the holder for the duration of the crank
+    let lender = Keypair::new();
+    let lender_token_account = Pubkey::new_unique();
+    set_pool_token_account(
+        &fixture,
+        &lender_token_account,
+        &lender.pubkey(),
+        90_000_000_000,
+    )
+    .await;
+    let (crank_ixs, _) =
+        crank_directed_stake_ticket_instructions(&fixture, &ticket_account, &holder_token_account)
+            .await;
+    let lend_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &lender_token_account,
+        &holder_token_account,
+        &lender.pubkey(),
+        &[],
+        90_000_000_000,
+    )
+    .unwrap();
+    let repay_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &holder_token_account,
+        &lender_token_account,
+        &holder,
+        &[],
+        90_000_000_000,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[vec![lend_ix], crank_ixs, vec![repay_ix]].concat(),
+        Some(&holder),
+        &[&fixture.keypair, &lender],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeBalanceNotAtTransactionStart")
+        .await;
+
+    // The bucket keeps the balance held at the start of the transaction
+    let contribution: DirectedStakeContribution =
+        fixture.load_and_deserialize(&contribution_account).await;
+    assert_eq!(contribution.sample_bucket_min_balance, 10_000_000_000);
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(20_000_000_000));
+}
+
+#[tokio::test]
+async fn test_crank_directed_stake_ticket_counts_balance_sources() {
+    let fixture = setup_directed_stake_fixture().await;
+    initialize_directed_stake_meta(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator1, 10_000)],
+    )
+    .await;
+
+    // 10 pool tokens at 2 SOL per token in the associated token account
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+
+    // 5 more pool tokens held by the holder in another token account, and 7 held by someone else
+    let holder_source = Pubkey::new_unique();
+    set_pool_token_account(&fixture, &holder_source, &holder, 5_000_000_000).await;
+    let other_owner = Keypair::new();
+    let other_source = Pubkey::new_unique();
+    set_pool_token_account(
+        &fixture,
+        &other_source,
+        &other_owner.pubkey(),
+        7_000_000_000,
+    )
+    .await;
+
+    let balance_sources = Pubkey::find_program_address(
+        &[DirectedStakeBalanceSources::SEED, ticket_account.as_ref()],
+        &jito_steward::id(),
+    )
+    .0;
+    let add_source_ix = |source_account: Pubkey, source_owner: Pubkey| Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::AddDirectedStakeBalanceSource {
+            config: fixture.steward_config.pubkey(),
+            ticket_account,
+            balance_sources,
+            source_account,
+            source_owner,
+            authority: holder,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::AddDirectedStakeBalanceSource { source_type: 0 }.data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            Instruction {
+                program_id: jito_steward::id(),
+                accounts: jito_steward::accounts::InitializeDirectedStakeBalanceSources {
+                    config: fixture.steward_config.pubkey(),
+                    ticket_account,
+                    balance_sources,
+                    system_program: anchor_lang::solana_program::system_program::id(),
+                    authority: holder,
+                }
+                .to_account_metas(None),
+                data: jito_steward::instruction::InitializeDirectedStakeBalanceSources {}.data(),
+            },
+            add_source_ix(holder_source, holder),
+            add_source_ix(other_source, other_owner.pubkey()),
+            // The associated token account is only counted once
+            add_source_ix(holder_token_account, holder),
+        ],
+        Some(&holder),
+        &[&fixture.keypair, &other_owner],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    // Only token accounts held by the ticket holder are sampled on-chain
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+    let contribution_account =
+        crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let contribution: DirectedStakeContribution =
+        fixture.load_and_deserialize(&contribution_account).await;
+    assert_eq!(contribution.sample_bucket_min_balance, 15_000_000_000);
+}
+
+/// Helper function to create a basket curated by the fixture keypair and set its validators
+async fn initialize_directed_stake_basket(
+    fixture: &TestFixture,
+    validators: Vec<DirectedStakeBasketEntry>,
+) -> Pubkey {
+    let mut name = [0u8; 32];
+    name[..5].copy_from_slice(b"south");
+    let basket = Pubkey::find_program_address(
+        &[
+            DirectedStakeBasket::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+            name.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::InitializeDirectedStakeBasket {
+            config: fixture.steward_config.pubkey(),
+            basket,
+            system_program: anchor_lang::solana_program::system_program::id(),
+            authority: fixture.keypair.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::InitializeDirectedStakeBasket {
+            name,
+            curator: fixture.keypair.pubkey(),
+        }
+        .data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture.ctx.borrow().last_blockhash,
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    update_directed_stake_basket(fixture, &basket, validators).await;
+    basket
+}
+
+/// Helper function to replace the validators of a basket curated by the fixture keypair
+async fn update_directed_stake_basket(
+    fixture: &TestFixture,
+    basket: &Pubkey,
+    validators: Vec<DirectedStakeBasketEntry>,
+) {
+    let directed_stake_whitelist = Pubkey::find_program_address(
+        &[
+            DirectedStakeWhitelist::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::UpdateDirectedStakeBasket {
+            config: fixture.steward_config.pubkey(),
+            whitelist_account: directed_stake_whitelist,
+            basket: *basket,
+            curator: fixture.keypair.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::UpdateDirectedStakeBasket { validators }.data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+}
+
+/// Helper function to expand a ticket's basket preferences into the basket validators,
+/// initializing the basket contribution account if needed
+async fn crank_directed_stake_ticket_basket(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    basket: &Pubkey,
+) -> Pubkey {
+    let directed_stake_meta = Pubkey::find_program_address(
+        &[
+            DirectedStakeMeta::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let basket_contribution = Pubkey::find_program_address(
+        &[
+            DirectedStakeBasketContribution::SEED,
+            ticket_account.as_ref(),
+            basket.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+
+    let mut ixs = vec![];
+    if !fixture.account_exists(&basket_contribution).await {
+        ixs.push(Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::InitializeDirectedStakeBasketContribution {
+                config: fixture.steward_config.pubkey(),
+                ticket_account: *ticket_account,
+                basket: *basket,
+                basket_contribution,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                payer: fixture.keypair.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeBasketContribution {}.data(),
+        });
+    }
+
+    let basket_account: DirectedStakeBasket = fixture.load_and_deserialize(basket).await;
+    let validator_list: jito_steward::stake_pool_utils::ValidatorList = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.validator_list)
+        .await;
+    let basket_validators: Vec<DirectedStakePreference> = basket_account
+        .validators()
+        .iter()
+        .map(|entry| DirectedStakePreference::new(entry.vote_pubkey, entry.weight_bps))
+        .collect();
+    let validator_list_indices = basket_validators
+        .iter()
+        .map(|preference| {
+            validator_list
+                .validators
+                .iter()
+                .position(|v| v.vote_account_address == preference.vote_pubkey)
+                .map_or(u32::MAX, |index| index as u32)
+        })
+        .collect();
+
+    ixs.push(Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::CrankDirectedStakeTicketBasket {
+            config: fixture.steward_config.pubkey(),
+            directed_stake_meta,
+            ticket_account: *ticket_account,
+            contribution_account: Pubkey::find_program_address(
+                &[DirectedStakeContribution::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
+            ticket_extension: Pubkey::find_program_address(
+                &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
+            basket: *basket,
+            basket_contribution,
+            stake_pool: fixture.stake_pool_meta.stake_pool,
+            validator_list: fixture.stake_pool_meta.validator_list,
+        }
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(fixture, &basket_validators))
+        .collect(),
+        data: jito_steward::instruction::CrankDirectedStakeTicketBasket {
+            start_index: 0,
+            validator_list_indices,
+        }
+        .data(),
+    });
+
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    basket_contribution
+}
+
+/// Helper function to build the transaction clearing what a ticket contributes through a basket
+async fn clear_directed_stake_ticket_basket_transaction(
+    fixture: &TestFixture,
+    ticket_account: &Pubkey,
+    basket: &Pubkey,
+) -> Transaction {
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::ClearDirectedStakeTicketBasket {
+            config: fixture.steward_config.pubkey(),
+            directed_stake_meta: Pubkey::find_program_address(
+                &[
+                    DirectedStakeMeta::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
+            ticket_account: *ticket_account,
+            ticket_extension: Pubkey::find_program_address(
+                &[DirectedStakeTicketExtension::SEED, ticket_account.as_ref()],
+                &jito_steward::id(),
+            )
+            .0,
+            basket: *basket,
+            basket_contribution: Pubkey::find_program_address(
+                &[
+                    DirectedStakeBasketContribution::SEED,
+                    ticket_account.as_ref(),
+                    basket.as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
+            signer: fixture.keypair.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::ClearDirectedStakeTicketBasket {}.data(),
+    };
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_crank_directed_stake_ticket_basket() {
+    let fixture = setup_directed_stake_fixture().await;
+    let directed_stake_meta_address = initialize_directed_stake_meta(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    let validator2 = fixture
+        .get_validator_from_list(1)
+        .await
+        .expect("Validator list should have at least two validators");
+    let validator3 = fixture
+        .get_validator_from_list(2)
+        .await
+        .expect("Validator list should have at least three validators");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+    add_validator_to_whitelist(&fixture, &validator2).await;
+    add_validator_to_whitelist(&fixture, &validator3).await;
+
+    let basket = initialize_directed_stake_basket(
+        &fixture,
+        vec![
+            DirectedStakeBasketEntry::new(validator1, 7_500),
+            DirectedStakeBasketEntry::new(validator2, 2_500),
+        ],
+    )
+    .await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![
+            DirectedStakePreference::basket(basket, 6_000),
+            DirectedStakePreference::new(validator3, 4_000),
+        ],
+    )
+    .await;
+
+    // 10 pool tokens at 2 SOL per token
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+    set_average_balance(&fixture, &ticket_account, 10_000_000_000).await;
+    crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+
+    // The basket share is split across the basket validators by weight
+    let basket_contribution =
+        crank_directed_stake_ticket_basket(&fixture, &ticket_account, &basket).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(9_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(3_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator3), Some(8_000_000_000));
+    let contribution: DirectedStakeBasketContribution =
+        fixture.load_and_deserialize(&basket_contribution).await;
+    assert_eq!(contribution.total_lamports(), 12_000_000_000);
+
+    // A curator update re-targets the ticket without the holder signing anything
+    update_directed_stake_basket(
+        &fixture,
+        &basket,
+        vec![DirectedStakeBasketEntry::new(validator2, 10_000)],
+    )
+    .await;
+    crank_directed_stake_ticket_basket(&fixture, &ticket_account, &basket).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(0));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(12_000_000_000));
+
+    // The contribution can't be cleared while the ticket still points at the basket
+    let tx =
+        clear_directed_stake_ticket_basket_transaction(&fixture, &ticket_account, &basket).await;
+    fixture
+        .submit_transaction_assert_error(tx, "DirectedStakeBasketStillReferenced")
+        .await;
+
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator3, 10_000)],
+    )
+    .await;
+
+    // Leaving the basket removes what the ticket contributed through it right away
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator2), Some(0));
+    let contribution: DirectedStakeBasketContribution =
+        fixture.load_and_deserialize(&basket_contribution).await;
+    assert_eq!(contribution.total_lamports(), 0);
+
+    // The emptied contribution account is then closed
+    let tx =
+        clear_directed_stake_ticket_basket_transaction(&fixture, &ticket_account, &basket).await;
+    fixture.submit_transaction_assert_success(tx).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator2), Some(0));
+    assert!(!fixture.account_exists(&basket_contribution).await);
+}
+
+/// Helper function to set the consent of `vote_pubkey` directly
+fn set_validator_consent(
+    fixture: &TestFixture,
+    vote_pubkey: &Pubkey,
+    opted_out: bool,
+    max_directed_stake_lamports: u64,
+) {
+    let address = Pubkey::find_program_address(
+        &[
+            DirectedStakeValidatorConsent::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+            vote_pubkey.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let consent = DirectedStakeValidatorConsent {
+        config: fixture.steward_config.pubkey(),
+        vote_account: *vote_pubkey,
+        max_directed_stake_lamports,
+        opted_out: opted_out.into(),
+        _padding0: [0; 63],
+    };
+
+    let mut account_data = Vec::new();
+    account_data.extend_from_slice(DirectedStakeValidatorConsent::DISCRIMINATOR);
+    account_data.extend_from_slice(&borsh::to_vec(&consent).unwrap());
+    fixture.ctx.borrow_mut().set_account(
+        &address,
+        &solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data: account_data,
+            owner: jito_steward::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+#[tokio::test]
+async fn test_apply_scheduled_preferences_respects_validator_consent() {
+    let fixture = setup_directed_stake_fixture().await;
+    let directed_stake_meta_address = initialize_directed_stake_meta(&fixture).await;
+
+    let validator1 = fixture
+        .get_validator_from_list(0)
+        .await
+        .expect("Validator list should have at least one validator");
+    let validator2 = fixture
+        .get_validator_from_list(1)
+        .await
+        .expect("Validator list should have at least two validators");
+    add_validator_to_whitelist(&fixture, &validator1).await;
+    add_validator_to_whitelist(&fixture, &validator2).await;
+
+    let holder = fixture.keypair.pubkey();
+    let ticket_account =
+        initialize_directed_stake_ticket(&fixture, &fixture.keypair, holder, false).await;
+    update_directed_stake_ticket(
+        &fixture,
+        &ticket_account,
+        &fixture.keypair,
+        vec![DirectedStakePreference::new(validator1, 10_000)],
+    )
+    .await;
+
+    // Move half of the ticket's stake to validator2 next epoch
+    let preference_schedule = Pubkey::find_program_address(
+        &[
+            jito_steward::DirectedStakePreferenceSchedule::SEED,
+            ticket_account.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let whitelist_account = Pubkey::find_program_address(
+        &[
+            DirectedStakeWhitelist::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0;
+    let clock: solana_sdk::clock::Clock = fixture.get_sysvar().await;
+    let scheduled_preferences = vec![
+        DirectedStakePreference::new(validator1, 5_000),
+        DirectedStakePreference::new(validator2, 5_000),
+    ];
+    let ixs = vec![
+        Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::InitializeDirectedStakePreferenceSchedule {
+                config: fixture.steward_config.pubkey(),
+                ticket_account,
+                preference_schedule,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                authority: fixture.keypair.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakePreferenceSchedule {}.data(),
+        },
+        Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::ScheduleDirectedStakePreferences {
+                config: fixture.steward_config.pubkey(),
+                whitelist_account,
+                ticket_account,
+                preference_schedule,
+                signer: fixture.keypair.pubkey(),
+            }
+            .to_account_metas(None)
+            .into_iter()
+            .chain(validator_consent_metas(&fixture, &scheduled_preferences))
+            .collect(),
+            data: jito_steward::instruction::ScheduleDirectedStakePreferences {
+                activation_epoch: clock.epoch + 1,
+                preferences: scheduled_preferences.clone(),
+            }
+            .data(),
+        },
+    ];
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    fixture.advance_num_epochs(1, 10).await;
+
+    // 10 pool tokens at 2 SOL per token
+    let holder_token_account = set_holder_pool_tokens(
+        &fixture,
+        &holder,
+        10_000_000_000,
+        200_000_000_000,
+        100_000_000_000,
+    )
+    .await;
+    set_balance_sampling(&fixture, true).await;
+    migrate_directed_targets_to_tickets(&fixture).await;
+    set_average_balance(&fixture, &ticket_account, 10_000_000_000).await;
+    let contribution_account =
+        crank_directed_stake_ticket(&fixture, &ticket_account, &holder_token_account).await;
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(20_000_000_000));
+
+    // validator2 opts out after the preferences were scheduled
+    set_validator_consent(&fixture, &validator2, true, 0);
+
+    let validator_list: jito_steward::stake_pool_utils::ValidatorList = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.validator_list)
+        .await;
+    let validator_list_indices = scheduled_preferences
+        .iter()
+        .map(|preference| {
+            validator_list
+                .validators
+                .iter()
+                .position(|v| v.vote_account_address == preference.vote_pubkey)
+                .map_or(u32::MAX, |index| index as u32)
+        })
+        .collect();
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::ApplyScheduledDirectedStakePreferences {
+            config: fixture.steward_config.pubkey(),
+            whitelist_account,
+            ticket_account,
+            preference_schedule,
+            directed_stake_meta: directed_stake_meta_address,
+            contribution_account,
+            stake_pool: fixture.stake_pool_meta.stake_pool,
+            validator_list: fixture.stake_pool_meta.validator_list,
+        }
+        .to_account_metas(None)
+        .into_iter()
+        .chain(validator_consent_metas(&fixture, &scheduled_preferences))
+        .collect(),
+        data: jito_steward::instruction::ApplyScheduledDirectedStakePreferences {
+            validator_list_indices,
+        }
+        .data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture
+            .ctx
+            .borrow_mut()
+            .get_new_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    // The new preferences apply immediately from the balance of this epoch's crank, without the
+    // share of the validator that opted out
+    let ticket: DirectedStakeTicket = fixture.load_and_deserialize(&ticket_account).await;
+    assert_eq!(ticket.num_preferences, 2);
+    let meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&directed_stake_meta_address)
+        .await;
+    assert_eq!(meta.get_target_lamports(&validator1), Some(10_000_000_000));
+    assert_eq!(meta.get_target_lamports(&validator2), Some(0));
+    let contribution: DirectedStakeContribution =
+        fixture.load_and_deserialize(&contribution_account).await;
+    assert_eq!(contribution.num_contributions, 1);
+    assert_eq!(contribution.total_lamports(), 10_000_000_000);
+}
@@ -123,6 +123,22 @@ async fn _epoch_maintenance_setup() -> (
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
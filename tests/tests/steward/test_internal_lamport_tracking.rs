@@ -9,7 +9,7 @@ use anchor_lang::{
 use jito_steward::state::directed_stake::DirectedStakeMeta;
 use jito_steward::{
     stake_pool_utils::{StakePool, ValidatorList},
-    StewardStateAccount, StewardStateAccountV2, UpdateParametersArgs,
+    Config, StewardStateAccount, StewardStateAccountV2, UpdateParametersArgs,
 };
 use solana_program_test::*;
 #[allow(deprecated)]
@@ -17,14 +17,20 @@ use solana_sdk::{
     signature::Keypair, signer::Signer, stake::state::StakeStateV2, transaction::Transaction,
 };
 use spl_associated_token_account::get_associated_token_address;
-use spl_stake_pool::{find_withdraw_authority_program_address, minimum_delegation};
+use spl_stake_pool::{
+    find_withdraw_authority_program_address, minimum_delegation,
+    state::{
+        AccountType, ValidatorList as SPLValidatorList, ValidatorListHeader, ValidatorStakeInfo,
+    },
+};
+use tests::stake_pool_utils::serialized_validator_list_account;
 use tests::steward_fixtures::{
     auto_add_validator, crank_compute_delegations, crank_compute_instant_unstake,
     crank_compute_score, crank_copy_directed_stake_targets, crank_directed_stake_permissions,
     crank_epoch_maintenance, crank_idle, crank_rebalance, crank_rebalance_directed,
     crank_stake_pool, crank_validator_history_accounts_no_credits, initialize_directed_stake_meta,
-    ExtraValidatorAccounts, FixtureDefaultAccounts, StateMachineFixtures, TestFixture,
-    ValidatorEntry,
+    serialized_steward_state_account, ExtraValidatorAccounts, FixtureDefaultAccounts,
+    StateMachineFixtures, TestFixture, ValidatorEntry,
 };
 use validator_history::ValidatorHistory;
 
@@ -283,6 +289,22 @@ async fn test_internal_lamport_tracking_basic() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -508,6 +530,22 @@ async fn test_internal_lamport_tracking_with_withdraw() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -730,6 +768,22 @@ async fn test_internal_lamport_tracking_with_withdraw_remainder() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1134,6 +1188,22 @@ async fn test_internal_lamport_tracking_with_deposit() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1358,6 +1428,22 @@ async fn test_internal_lamport_tracking_with_deposit_meeting_target() {
                 directed_stake_unstake_cap_bps: Some(10_000),
                 jito_bam_minimum_epochs: Some(0),
                 jito_bam_window_epochs: Some(0),
+                circuit_breaker_instant_unstake_count: None,
+                circuit_breaker_unstake_bps: None,
+                directed_stake_sample_interval_slots: None,
+                directed_stake_target_expiry_epochs: None,
+                directed_stake_target_decay_bps: None,
+                directed_stake_permissionless_ticket_min_balance: None,
+                reserve_liquidity_target_lamports: None,
+                reserve_liquidity_target_bps: None,
+                auto_preferred_validators: None,
+                instant_unstake_max_fraction_bps: None,
+                score_warning_filters: None,
+                validator_exit_cooldown_epochs: None,
+                commission_pledge_cooldown_epochs: None,
+                minimum_rebalance_lamports: None,
+                unstake_carryover_ceiling_bps: None,
+                cycle_reports_enabled: None,
             }),
             None,
         )
@@ -1510,3 +1596,128 @@ async fn test_internal_lamport_tracking_with_deposit_meeting_target() {
 
     drop(fixture);
 }
+
+async fn set_directed_stake_meta(fixture: &TestFixture, directed_stake_meta: &DirectedStakeMeta) {
+    let account_info = fixture.get_account(&fixture.directed_stake_meta).await;
+    let mut account_data = Vec::new();
+    account_data.extend_from_slice(&account_info.data[..8]);
+    account_data.extend_from_slice(bytemuck::bytes_of(directed_stake_meta));
+
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.directed_stake_meta,
+        &solana_sdk::account::Account {
+            data: account_data,
+            ..account_info
+        }
+        .into(),
+    );
+}
+
+fn _verify_invariants_tx(
+    fixture: &TestFixture,
+    end_index: u64,
+    pause_on_drift: bool,
+) -> Transaction {
+    let ix = Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::VerifyInvariants {
+            config: fixture.steward_config.pubkey(),
+            state_account: fixture.steward_state,
+            directed_stake_meta: fixture.directed_stake_meta,
+            validator_list: fixture.stake_pool_meta.validator_list,
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::VerifyInvariants {
+            start_index: 0,
+            end_index,
+            pause_on_drift,
+        }
+        .data(),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&fixture.keypair.pubkey()),
+        &[&fixture.keypair],
+        fixture.ctx.borrow().last_blockhash,
+    )
+}
+
+#[tokio::test]
+async fn test_verify_invariants() {
+    let mut fixture = TestFixture::new().await;
+    fixture.initialize_stake_pool().await;
+    fixture.initialize_steward(None, None).await;
+    fixture.directed_stake_meta = initialize_directed_stake_meta(&fixture).await;
+    fixture.realloc_directed_stake_meta().await;
+
+    let stake_rent = fixture.fetch_stake_rent().await;
+    let vote_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let validator_list = SPLValidatorList {
+        header: ValidatorListHeader {
+            account_type: AccountType::ValidatorList,
+            max_validators: 2,
+        },
+        validators: vote_accounts
+            .iter()
+            .map(|vote_account| ValidatorStakeInfo {
+                vote_account_address: *vote_account,
+                active_stake_lamports: (stake_rent + 1_000_000_000).into(),
+                ..ValidatorStakeInfo::default()
+            })
+            .collect(),
+    };
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.stake_pool_meta.validator_list,
+        &serialized_validator_list_account(validator_list, None).into(),
+    );
+
+    let mut steward_state_account: StewardStateAccountV2 =
+        fixture.load_and_deserialize(&fixture.steward_state).await;
+    steward_state_account.state.validator_lamport_balances[0] = stake_rent + 1_000_000_000;
+    steward_state_account.state.validator_lamport_balances[1] = stake_rent + 1_000_000_000;
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.steward_state,
+        &serialized_steward_state_account(steward_state_account).into(),
+    );
+
+    // Validator 0 has directed stake, validator 1 has none
+    let mut directed_stake_meta: DirectedStakeMeta = fixture
+        .load_and_deserialize(&fixture.directed_stake_meta)
+        .await;
+    directed_stake_meta.total_stake_targets = 1;
+    directed_stake_meta.targets[0].vote_pubkey = vote_accounts[0];
+    directed_stake_meta.targets[0].total_staked_lamports = 600_000_000;
+    directed_stake_meta.directed_stake_lamports[0] = 600_000_000;
+    directed_stake_meta.directed_stake_meta_indices[0] = 0;
+    set_directed_stake_meta(&fixture, &directed_stake_meta).await;
+
+    let tx = _verify_invariants_tx(&fixture, 2, true);
+    fixture.submit_transaction_assert_success(tx).await;
+    let config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    assert!(!config.is_paused());
+
+    // The directed stake mirrors disagree
+    directed_stake_meta.directed_stake_lamports[0] = 500_000_000;
+    set_directed_stake_meta(&fixture, &directed_stake_meta).await;
+
+    // Only reported unless pausing was requested
+    let tx = _verify_invariants_tx(&fixture, 2, false);
+    fixture.submit_transaction_assert_success(tx).await;
+    let config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    assert!(!config.is_paused());
+
+    // end_index is clamped to the validator list length
+    let tx = _verify_invariants_tx(&fixture, 3, true);
+    fixture.submit_transaction_assert_success(tx).await;
+    let config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    assert!(config.is_paused());
+
+    drop(fixture);
+}
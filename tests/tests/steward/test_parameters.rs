@@ -3,8 +3,9 @@ use anchor_lang::{solana_program::instruction::Instruction, InstructionData, ToA
 use jito_steward::{
     constants::{
         BASIS_POINTS_MAX, COMMISSION_MAX, COMPUTE_SCORE_SLOT_RANGE_MIN, EPOCH_PROGRESS_MAX,
-        MAX_VALIDATORS, NUM_EPOCHS_BETWEEN_SCORING_MAX,
+        MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH, MAX_VALIDATORS, NUM_EPOCHS_BETWEEN_SCORING_MAX,
     },
+    score::{SCORE_FILTER_BLACKLIST, SCORE_FILTER_COMMISSION, SCORE_FILTER_RUNNING_BAM},
     Config, Parameters, UpdateParametersArgs,
 };
 use solana_program_test::*;
@@ -217,6 +218,22 @@ async fn test_update_parameters() {
             directed_stake_unstake_cap_bps: Some(10_000),
             jito_bam_minimum_epochs: Some(0),
             jito_bam_window_epochs: Some(0),
+            circuit_breaker_instant_unstake_count: None,
+            circuit_breaker_unstake_bps: None,
+            directed_stake_sample_interval_slots: None,
+            directed_stake_target_expiry_epochs: None,
+            directed_stake_target_decay_bps: None,
+            directed_stake_permissionless_ticket_min_balance: None,
+            reserve_liquidity_target_lamports: None,
+            reserve_liquidity_target_bps: None,
+            auto_preferred_validators: None,
+            instant_unstake_max_fraction_bps: None,
+            score_warning_filters: None,
+            validator_exit_cooldown_epochs: None,
+            commission_pledge_cooldown_epochs: None,
+            minimum_rebalance_lamports: None,
+            unstake_carryover_ceiling_bps: None,
+            cycle_reports_enabled: None,
         },
         &fixture.keypair,
     )
@@ -260,8 +277,26 @@ fn _test_parameter(
         undirected_stake_ceiling_lamports: (10_000_000u64 * 1_000_000_000u64).to_le_bytes(),
         jito_bam_minimum_epochs: 0,
         jito_bam_window_epochs: 0,
+        circuit_breaker_instant_unstake_count: 0,
+        circuit_breaker_unstake_bps: 0,
         _padding_0: [0; 4],
-        _padding_1: [0; 28],
+        _padding_3: [0; 2],
+        directed_stake_sample_interval_slots: 0,
+        directed_stake_target_expiry_epochs: 0,
+        directed_stake_target_decay_bps: 0,
+        directed_stake_permissionless_ticket_min_balance: 0,
+        reserve_liquidity_target_lamports: 0,
+        reserve_liquidity_target_bps: 0,
+        instant_unstake_max_fraction_bps: 0,
+        score_warning_filters: 0,
+        auto_preferred_validators: 0,
+        cycle_reports_enabled: 0,
+        validator_exit_cooldown_epochs: 0,
+        commission_pledge_cooldown_epochs: 0,
+        minimum_rebalance_lamports: 0,
+        unstake_carryover_ceiling_bps: 0,
+        _padding_5: [0; 6],
+        _padding_1: [0; 18],
         _padding_2: [0; 6],
     });
 
@@ -912,3 +947,302 @@ fn test_num_epochs_between_scoring() {
         assert_eq!(result.unwrap().num_epochs_between_scoring, new_value);
     }
 }
+
+#[test]
+fn test_circuit_breaker_instant_unstake_count() {
+    {
+        // Cannot be above MAX_VALIDATORS
+        let new_value = MAX_VALIDATORS as u16 + 1;
+        let update_parameters = UpdateParametersArgs {
+            circuit_breaker_instant_unstake_count: Some(new_value),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_err());
+    }
+
+    {
+        // In range
+        let new_value = 50;
+        let update_parameters = UpdateParametersArgs {
+            circuit_breaker_instant_unstake_count: Some(new_value),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap().circuit_breaker_instant_unstake_count,
+            new_value
+        );
+    }
+}
+
+#[test]
+fn test_circuit_breaker_unstake_bps() {
+    {
+        // Cannot be above 10_000
+        let new_value = BASIS_POINTS_MAX + 1;
+        let update_parameters = UpdateParametersArgs {
+            circuit_breaker_unstake_bps: Some(new_value),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_err());
+    }
+
+    {
+        // In range
+        let new_value = 2_000;
+        let update_parameters = UpdateParametersArgs {
+            circuit_breaker_unstake_bps: Some(new_value),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().circuit_breaker_unstake_bps, new_value);
+    }
+}
+
+#[test]
+fn test_directed_stake_sample_interval_slots() {
+    let slots_per_epoch = 432_000;
+
+    {
+        // Cannot be above slots_per_epoch
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_sample_interval_slots: Some(slots_per_epoch + 1),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, Some(slots_per_epoch), None);
+        assert!(result.is_err());
+    }
+
+    {
+        // Cannot split the epoch into more than MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH buckets
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_sample_interval_slots: Some(
+                slots_per_epoch / MAX_DIRECTED_STAKE_SAMPLES_PER_EPOCH - 1,
+            ),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, Some(slots_per_epoch), None);
+        assert!(result.is_err());
+    }
+
+    {
+        // In range
+        let new_value = 10_000;
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_sample_interval_slots: Some(new_value),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, Some(slots_per_epoch), None);
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap().directed_stake_sample_interval_slots,
+            new_value
+        );
+    }
+
+    {
+        // 0 disables sampling
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_sample_interval_slots: Some(0),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, Some(slots_per_epoch), None);
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+fn test_directed_stake_target_decay_bps() {
+    {
+        // Cannot be above BASIS_POINTS_MAX
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_target_decay_bps: Some(BASIS_POINTS_MAX + 1),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_err());
+    }
+
+    {
+        // In range
+        let new_value = 5_000;
+        let update_parameters = UpdateParametersArgs {
+            directed_stake_target_decay_bps: Some(new_value),
+            directed_stake_target_expiry_epochs: Some(3),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_ok());
+        let parameters = result.unwrap();
+        assert_eq!(parameters.directed_stake_target_decay_bps, new_value);
+        assert_eq!(parameters.directed_stake_target_expiry_epochs, 3);
+    }
+}
+
+#[test]
+fn test_directed_stake_permissionless_ticket_min_balance() {
+    let new_value = 100 * 1_000_000_000;
+    let update_parameters = UpdateParametersArgs {
+        directed_stake_permissionless_ticket_min_balance: Some(new_value),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    let parameters = result.unwrap();
+    assert_eq!(
+        parameters.directed_stake_permissionless_ticket_min_balance,
+        new_value
+    );
+}
+
+#[test]
+fn test_reserve_liquidity_target() {
+    {
+        let update_parameters = UpdateParametersArgs {
+            reserve_liquidity_target_lamports: Some(1_000 * 1_000_000_000),
+            reserve_liquidity_target_bps: Some(50),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_ok());
+        let parameters = result.unwrap();
+        assert_eq!(
+            parameters.reserve_liquidity_target_lamports,
+            1_000 * 1_000_000_000
+        );
+        assert_eq!(parameters.reserve_liquidity_target_bps, 50);
+    }
+
+    {
+        // Above 10_000 bps
+        let update_parameters = UpdateParametersArgs {
+            reserve_liquidity_target_bps: Some(10_001),
+            ..UpdateParametersArgs::default()
+        };
+        let result = _test_parameter(&update_parameters, None, None, None);
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn test_auto_preferred_validators() {
+    let update_parameters = UpdateParametersArgs {
+        auto_preferred_validators: Some(true),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    let parameters = result.unwrap();
+    assert_eq!(parameters.auto_preferred_validators, 1);
+    assert!(parameters.auto_preferred_validators_enabled());
+
+    let update_parameters = UpdateParametersArgs {
+        auto_preferred_validators: Some(false),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    assert!(!result.unwrap().auto_preferred_validators_enabled());
+}
+
+#[test]
+fn test_instant_unstake_max_fraction_bps() {
+    let update_parameters = UpdateParametersArgs {
+        instant_unstake_max_fraction_bps: Some(5_000),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    let parameters = result.unwrap();
+    assert_eq!(parameters.instant_unstake_max_fraction_bps, 5_000);
+    assert!(parameters.graded_instant_unstake_enabled());
+
+    let update_parameters = UpdateParametersArgs {
+        instant_unstake_max_fraction_bps: Some(0),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    assert!(!result.unwrap().graded_instant_unstake_enabled());
+
+    let update_parameters = UpdateParametersArgs {
+        instant_unstake_max_fraction_bps: Some(BASIS_POINTS_MAX + 1),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_score_warning_filters() {
+    let update_parameters = UpdateParametersArgs {
+        score_warning_filters: Some(SCORE_FILTER_COMMISSION | SCORE_FILTER_RUNNING_BAM),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    let parameters = result.unwrap();
+    assert_eq!(
+        parameters.score_warning_filters,
+        SCORE_FILTER_COMMISSION | SCORE_FILTER_RUNNING_BAM
+    );
+    assert!(parameters.score_warnings_enabled());
+
+    // Blacklisting always applies immediately
+    let update_parameters = UpdateParametersArgs {
+        score_warning_filters: Some(SCORE_FILTER_BLACKLIST),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_err());
+
+    // Unknown filter
+    let update_parameters = UpdateParametersArgs {
+        score_warning_filters: Some(1 << 15),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_unstake_carryover_ceiling_bps() {
+    let update_parameters = UpdateParametersArgs {
+        unstake_carryover_ceiling_bps: Some(500),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().unstake_carryover_ceiling_bps, 500);
+
+    let update_parameters = UpdateParametersArgs {
+        unstake_carryover_ceiling_bps: Some(BASIS_POINTS_MAX + 1),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cycle_reports_enabled() {
+    let update_parameters = UpdateParametersArgs {
+        cycle_reports_enabled: Some(true),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(result.is_ok());
+    let parameters = result.unwrap();
+    assert_eq!(parameters.cycle_reports_enabled, 1);
+    assert!(parameters.cycle_reports_enabled());
+
+    let update_parameters = UpdateParametersArgs {
+        cycle_reports_enabled: Some(false),
+        ..UpdateParametersArgs::default()
+    };
+    let result = _test_parameter(&update_parameters, None, None, None);
+    assert!(!result.unwrap().cycle_reports_enabled());
+}
@@ -201,8 +201,26 @@ fn test_priority_parameter_validation() {
         undirected_stake_ceiling_lamports: (10_000_000u64 * 1_000_000_000u64).to_le_bytes(),
         jito_bam_minimum_epochs: 0,
         jito_bam_window_epochs: 0,
+        circuit_breaker_instant_unstake_count: 0,
+        circuit_breaker_unstake_bps: 0,
         _padding_0: [0; 4],
-        _padding_1: [0; 28],
+        _padding_3: [0; 2],
+        directed_stake_sample_interval_slots: 0,
+        directed_stake_target_expiry_epochs: 0,
+        directed_stake_target_decay_bps: 0,
+        directed_stake_permissionless_ticket_min_balance: 0,
+        reserve_liquidity_target_lamports: 0,
+        reserve_liquidity_target_bps: 0,
+        instant_unstake_max_fraction_bps: 0,
+        score_warning_filters: 0,
+        auto_preferred_validators: 0,
+        cycle_reports_enabled: 0,
+        validator_exit_cooldown_epochs: 0,
+        commission_pledge_cooldown_epochs: 0,
+        minimum_rebalance_lamports: 0,
+        unstake_carryover_ceiling_bps: 0,
+        _padding_5: [0; 6],
+        _padding_1: [0; 18],
         _padding_2: [0; 6],
     };
 
@@ -1,4 +1,6 @@
-use jito_steward::{score::*, Config, LargeBitMask, Parameters};
+use jito_steward::{
+    score::*, CircuitBreakerBaseline, Config, LargeBitMask, Parameters, UnstakeCarryover,
+};
 use solana_sdk::pubkey::Pubkey;
 use validator_history::{CircBuf, MerkleRootUploadAuthority, ValidatorHistory};
 
@@ -37,7 +39,9 @@ fn create_config(
         directed_stake_meta_upload_authority: Pubkey::new_unique(),
         directed_stake_whitelist_authority: Pubkey::new_unique(),
         directed_stake_ticket_override_authority: Pubkey::new_unique(),
-        _padding: [0; 888],
+        unstake_carryover: UnstakeCarryover::default(),
+        circuit_breaker_baseline: CircuitBreakerBaseline::default(),
+        _padding: [0; 800],
     }
 }
 
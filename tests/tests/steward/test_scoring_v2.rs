@@ -1,6 +1,9 @@
 use jito_steward::{
     constants::VOTE_CREDITS_RATIO_MAX,
-    score::{calculate_avg_mev_commission, encode_validator_score},
+    score::{
+        calculate_avg_mev_commission, encode_validator_score, ValidatorScoreComponents,
+        COMMISSION_PLEDGE_SCORE_BIT,
+    },
 };
 use solana_sdk::pubkey::Pubkey;
 use validator_history::{CircBuf, ValidatorHistory, ValidatorHistoryEntry};
@@ -25,14 +28,14 @@ fn create_validator_history() -> ValidatorHistory {
 #[test]
 fn test_encode_validator_score_perfect_validator() {
     // Perfect validator: 0% commissions, max age, max credits
-    let score = encode_validator_score(0, 0, 131071, 33554431).unwrap();
+    let score = encode_validator_score(0, 0, 65535, 33554431).unwrap();
 
     // Should have maximum possible score
     // Inflation: 100 (inverted from 0)
     // MEV: 10000 (inverted from 0)
-    // Age: 131071 (max 17-bit value)
+    // Age: 65535 (max 16-bit value)
     // Credits: 33554431 (max 25-bit value)
-    let expected = (100u64 << 56) | (10000u64 << 42) | (131071u64 << 25) | 33554431u64;
+    let expected = (100u64 << 56) | (10000u64 << 42) | (65535u64 << 25) | 33554431u64;
     assert_eq!(score, expected);
 
     // Verify top byte is 100 (0x64)
@@ -130,18 +133,18 @@ fn test_encode_validator_score_mev_commission_inversion() {
 #[test]
 fn test_encode_validator_score_bit_boundaries() {
     // Test that values are properly contained in their bit ranges
-    let score = encode_validator_score(50, 5000, 65536, 16777216).unwrap();
+    let score = encode_validator_score(50, 5000, 32768, 16777216).unwrap();
 
     // Extract each component
     let inflation_bits = (score >> 56) & 0xFF; // 8 bits
     let mev_bits = (score >> 42) & 0x3FFF; // 14 bits
-    let age_bits = (score >> 25) & 0x1FFFF; // 17 bits
+    let age_bits = (score >> 25) & 0xFFFF; // 16 bits
     let credit_bits = score & 0x1FFFFFF; // 25 bits
 
     // Verify inversions
     assert_eq!(inflation_bits, 50, "Inflation: 100 - 50 = 50");
     assert_eq!(mev_bits, 5000, "MEV: 10000 - 5000 = 5000");
-    assert_eq!(age_bits, 65536, "Age: Direct (not inverted)");
+    assert_eq!(age_bits, 32768, "Age: Direct (not inverted)");
     assert_eq!(credit_bits, 16777216, "Credits: Direct");
 }
 
@@ -153,12 +156,12 @@ fn test_encode_validator_score_caps() {
     // Extract components - should be capped at their maximums
     let inflation_bits = (score >> 56) & 0xFF;
     let mev_bits = (score >> 42) & 0x3FFF;
-    let age_bits = (score >> 25) & 0x1FFFF;
+    let age_bits = (score >> 25) & 0xFFFF;
     let credit_bits = score & 0x1FFFFFF;
 
     assert_eq!(inflation_bits, 0, "100 - min(200, 100) = 0");
     assert_eq!(mev_bits, 0, "10000 - min(20000, 10000) = 0");
-    assert_eq!(age_bits, 131071, "Capped at (1 << 17) - 1");
+    assert_eq!(age_bits, 65535, "Capped at (1 << 16) - 1");
     assert_eq!(credit_bits, 33554431, "Capped at (1 << 25) - 1");
 }
 
@@ -258,7 +261,7 @@ fn test_validator_age_impact() {
     // Same commissions and credits, different ages
     let young_validator = encode_validator_score(10, 1000, 10, 10000).unwrap();
     let old_validator = encode_validator_score(10, 1000, 1000, 10000).unwrap();
-    let ancient_validator = encode_validator_score(10, 1000, 100000, 10000).unwrap();
+    let ancient_validator = encode_validator_score(10, 1000, 50000, 10000).unwrap();
 
     assert!(
         old_validator > young_validator,
@@ -270,9 +273,34 @@ fn test_validator_age_impact() {
     );
 
     // Extract age components to verify
-    assert_eq!((young_validator >> 25) & 0x1FFFF, 10);
-    assert_eq!((old_validator >> 25) & 0x1FFFF, 1000);
-    assert_eq!((ancient_validator >> 25) & 0x1FFFF, 100000);
+    assert_eq!((young_validator >> 25) & 0xFFFF, 10);
+    assert_eq!((old_validator >> 25) & 0xFFFF, 1000);
+    assert_eq!((ancient_validator >> 25) & 0xFFFF, 50000);
+}
+
+#[test]
+fn test_commission_pledge_tier() {
+    // Pledge ranks above any age, below the commission tiers
+    let pledged = encode_validator_score(10, 1000, 10, 1000).unwrap() | COMMISSION_PLEDGE_SCORE_BIT;
+    let oldest = encode_validator_score(10, 1000, 65535, 33554431).unwrap();
+    let lower_mev_commission = encode_validator_score(10, 999, 0, 0).unwrap();
+
+    assert!(pledged > oldest, "Pledge should outrank age");
+    assert!(
+        lower_mev_commission > pledged,
+        "MEV commission should outrank pledge"
+    );
+
+    // Pledge decodes separately and leaves the age intact
+    let components = ValidatorScoreComponents::decode(pledged);
+    assert!(components.commission_pledge);
+    assert_eq!(components.validator_age, 10);
+    assert_eq!(components.vote_credits, 1000);
+    assert!(!ValidatorScoreComponents::decode(oldest).commission_pledge);
+    assert_eq!(
+        ValidatorScoreComponents::decode(oldest).validator_age,
+        65535
+    );
 }
 
 #[test]
@@ -303,7 +331,10 @@ fn test_vote_credits_impact() {
 #[cfg(test)]
 mod validator_score_integration_tests {
     use super::*;
-    use jito_steward::{score::validator_score, Config, LargeBitMask, Parameters};
+    use jito_steward::{
+        score::validator_score, CircuitBreakerBaseline, Config, LargeBitMask, Parameters,
+        UnstakeCarryover,
+    };
     use solana_sdk::pubkey::Pubkey;
     use validator_history::{
         constants::TVC_MULTIPLIER, CircBufCluster, ClusterHistory, ClusterHistoryEntry,
@@ -331,7 +362,9 @@ mod validator_score_integration_tests {
             paused: false.into(),
             _padding_0: [0u8; 7],
             priority_fee_parameters_authority: Pubkey::new_unique(),
-            _padding: [0; 888],
+            unstake_carryover: UnstakeCarryover::default(),
+            circuit_breaker_baseline: CircuitBreakerBaseline::default(),
+            _padding: [0; 800],
             directed_stake_whitelist_authority: Pubkey::new_unique(),
             directed_stake_meta_upload_authority: Pubkey::new_unique(),
             directed_stake_ticket_override_authority: Pubkey::new_unique(),
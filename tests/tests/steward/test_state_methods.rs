@@ -10,13 +10,22 @@ use anchor_lang::error::Error;
 use jito_steward::state::directed_stake::DirectedStakeMeta;
 use jito_steward::{
     constants::{LAMPORT_BALANCE_DEFAULT, MAX_VALIDATORS, SORTED_INDEX_DEFAULT},
-    delegation::RebalanceType,
+    delegation::{
+        preferred_validators_calculation, PreferredValidators, RebalanceType,
+        UndirectedPoolLamports,
+    },
     errors::StewardError,
-    Delegation, StewardStateEnum, StewardStateV2,
+    score::{
+        ValidatorScoreComponents, COMMISSION_PLEDGE_SCORE_BIT, SCORE_FILTER_COMMISSION,
+        SCORE_FILTER_MEV_COMMISSION, SCORE_WARNING_FILTERS_ALLOWED,
+    },
+    CircuitBreakerBaseline, CommissionPledge, Delegation, InstantUnstakeFractions, ScoreWarnings,
+    StewardStateEnum, StewardStateV2, UnstakeCarryover, COMPUTE_DELEGATIONS, COMPUTE_SCORE,
+    RECOMPUTE_DELEGATIONS,
 };
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::pubkey::Pubkey;
-use spl_stake_pool::big_vec::BigVec;
+use spl_stake_pool::{big_vec::BigVec, state::StakeStatus};
 use tests::steward_fixtures::StateMachineFixtures;
 use validator_history::ValidatorHistoryEntry;
 
@@ -55,6 +64,8 @@ fn test_compute_scores() {
             cluster_history,
             config,
             state.num_pool_validators,
+            None,
+            None,
         );
         assert!(res.is_ok());
         assert!(matches!(state.state_tag, StewardStateEnum::ComputeScores));
@@ -88,6 +99,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert_eq!(res, Err(Error::from(StewardError::InvalidState)));
 
@@ -108,6 +121,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert_eq!(
         res,
@@ -128,6 +143,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert_eq!(
         res,
@@ -146,6 +163,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert_eq!(
         res,
@@ -169,6 +188,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert!(res.is_ok());
     // validator would not have a score of 0 if it was not blacklisted
@@ -191,6 +212,8 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert!(res.is_ok());
     // assert!(state.start_computing_scores_slot == clock.slot);
@@ -212,6 +235,7 @@ fn test_compute_scores() {
     //     cluster_history,
     //     config,
     //     state.num_pool_validators,
+    //     None,
     // );
     // assert!(res.is_ok());
     // assert!(state.current_epoch == current_epoch);
@@ -233,11 +257,481 @@ fn test_compute_scores() {
         cluster_history,
         config,
         state.num_pool_validators,
+        None,
+        None,
     );
     assert!(res.is_ok());
     //assert!(state.start_computing_scores_slot == clock.slot);
 }
 
+#[test]
+fn test_compute_score_warnings() {
+    /*
+    - [X]  first failure of a warning filter keeps the raw score
+    - [X]  still failing in a later cycle is unscored
+    - [X]  failures outside the warning filters are unscored
+    */
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.score_warning_filters = SCORE_WARNING_FILTERS_ALLOWED;
+
+    let clock = &fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &fixtures.validators;
+    let cluster_history = &fixtures.cluster_history;
+    let config = &fixtures.config;
+    let state = &mut fixtures.state;
+    state.state_tag = StewardStateEnum::ComputeScores;
+
+    let mut score_warnings: Box<ScoreWarnings> = Box::new(bytemuck::Zeroable::zeroed());
+
+    for validator in validators.iter() {
+        let res = state.compute_score(
+            clock,
+            epoch_schedule,
+            validator,
+            validator.index as usize,
+            cluster_history,
+            config,
+            state.num_pool_validators,
+            Some(&mut score_warnings),
+            None,
+        );
+        assert!(res.is_ok());
+    }
+
+    // Validator 1 fails the commission filters, so it is warned and keeps its raw score
+    assert_eq!(state.scores[0..3], state.raw_scores[0..3]);
+    assert!(state.scores[1] > 0);
+    assert!(score_warnings
+        .warned
+        .get(validators[1].index as usize)
+        .unwrap());
+    assert!(!score_warnings
+        .warned
+        .get(validators[0].index as usize)
+        .unwrap());
+    assert_eq!(score_warnings.cycle_end_epoch, state.next_cycle_epoch);
+
+    // Warnings were issued in an earlier cycle, so validator 1 is unscored
+    score_warnings.cycle_end_epoch = 0;
+    state.progress.reset();
+    let res = state.compute_score(
+        clock,
+        epoch_schedule,
+        &validators[1],
+        validators[1].index as usize,
+        cluster_history,
+        config,
+        state.num_pool_validators,
+        Some(&mut score_warnings),
+        None,
+    );
+    assert!(res.is_ok());
+    assert_eq!(state.scores[1], 0);
+    assert!(score_warnings
+        .warned
+        .get(validators[1].index as usize)
+        .unwrap());
+
+    // Validator 1 also fails filters without warnings, so it is never warned
+    let mut score_warnings: Box<ScoreWarnings> = Box::new(bytemuck::Zeroable::zeroed());
+    let mut config = Box::new(*config);
+    config.parameters.score_warning_filters = SCORE_FILTER_COMMISSION;
+    state.progress.reset();
+    let res = state.compute_score(
+        clock,
+        epoch_schedule,
+        &validators[1],
+        validators[1].index as usize,
+        cluster_history,
+        &config,
+        state.num_pool_validators,
+        Some(&mut score_warnings),
+        None,
+    );
+    assert!(res.is_ok());
+    assert_eq!(state.scores[1], 0);
+    assert!(!score_warnings
+        .warned
+        .get(validators[1].index as usize)
+        .unwrap());
+}
+
+#[test]
+fn test_compute_score_added_validator() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+
+    let clock = &mut fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &mut fixtures.validators;
+    let cluster_history = &mut fixtures.cluster_history;
+    let config = &mut fixtures.config;
+    let state = &mut fixtures.state;
+
+    // Run a cycle with only the first two validators in the pool
+    state.state_tag = StewardStateEnum::ComputeScores;
+    state.num_pool_validators = 2;
+    for validator in validators[0..2].iter() {
+        state
+            .compute_score(
+                clock,
+                epoch_schedule,
+                validator,
+                validator.index as usize,
+                cluster_history,
+                config,
+                2,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+    state.set_flag(COMPUTE_SCORE);
+    state.state_tag = StewardStateEnum::ComputeDelegations;
+    state.compute_delegations(clock.epoch, config).unwrap();
+    state.state_tag = StewardStateEnum::Idle;
+    assert!(state.delegations[0..2] == [Delegation::new(1, 1), Delegation::default()]);
+
+    // Third validator is added mid-cycle
+    state.increment_validator_to_add().unwrap();
+
+    // Only the first added validator can be scored
+    let res = state.compute_score_added_validator(
+        clock,
+        epoch_schedule,
+        &validators[2],
+        3,
+        cluster_history,
+        config,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(Error::from(StewardError::ValidatorIndexOutOfBounds))
+    );
+
+    let score = state
+        .compute_score_added_validator(
+            clock,
+            epoch_schedule,
+            &validators[2],
+            2,
+            cluster_history,
+            config,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(score.score, 6887252875468641920);
+    assert_eq!(state.num_pool_validators, 3);
+    assert_eq!(state.validators_added, 0);
+    assert!(matches!(state.state_tag, StewardStateEnum::Idle));
+    assert!(state.sorted_score_indices[0..3] == [0, 2, 1]);
+    assert!(state.sorted_raw_score_indices[0..3] == [0, 2, 1]);
+
+    // Delegations are recomputed to include the newcomer
+    assert!(
+        state.delegations[0..3]
+            == [
+                Delegation::new(1, 2),
+                Delegation::default(),
+                Delegation::new(1, 2)
+            ]
+    );
+
+    // No validators left to score
+    let res = state.compute_score_added_validator(
+        clock,
+        epoch_schedule,
+        &validators[2],
+        3,
+        cluster_history,
+        config,
+        None,
+        None,
+    );
+    assert_eq!(
+        res,
+        Err(Error::from(StewardError::ValidatorIndexOutOfBounds))
+    );
+
+    // Not allowed while the regular scoring run is in progress
+    state.increment_validator_to_add().unwrap();
+    state.state_tag = StewardStateEnum::ComputeScores;
+    let res = state.compute_score_added_validator(
+        clock,
+        epoch_schedule,
+        &validators[2],
+        3,
+        cluster_history,
+        config,
+        None,
+        None,
+    );
+    assert_eq!(res, Err(Error::from(StewardError::InvalidState)));
+}
+
+#[test]
+fn test_compute_score_added_validator_during_rebalance() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+
+    let clock = &mut fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &mut fixtures.validators;
+    let cluster_history = &mut fixtures.cluster_history;
+    let config = &mut fixtures.config;
+    let state = &mut fixtures.state;
+
+    state.state_tag = StewardStateEnum::ComputeScores;
+    state.num_pool_validators = 2;
+    for validator in validators[0..2].iter() {
+        state
+            .compute_score(
+                clock,
+                epoch_schedule,
+                validator,
+                validator.index as usize,
+                cluster_history,
+                config,
+                2,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+    state.set_flag(COMPUTE_SCORE);
+    state.state_tag = StewardStateEnum::ComputeDelegations;
+    state.compute_delegations(clock.epoch, config).unwrap();
+
+    // Third validator is scored after this epoch's instant unstake check
+    state.state_tag = StewardStateEnum::Rebalance;
+    state.increment_validator_to_add().unwrap();
+    state
+        .compute_score_added_validator(
+            clock,
+            epoch_schedule,
+            &validators[2],
+            2,
+            cluster_history,
+            config,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(state.num_pool_validators, 3);
+    assert!(state.has_flag(RECOMPUTE_DELEGATIONS));
+    assert!(state.delegations[0..2] == [Delegation::new(1, 1), Delegation::default()]);
+    assert_eq!(state.delegations[2].numerator, 0);
+
+    // Delegations include the newcomer once the next ComputeInstantUnstake has checked it
+    state.state_tag = StewardStateEnum::ComputeInstantUnstake;
+    for index in 0..3 {
+        state.progress.set(index, true).unwrap();
+    }
+    state
+        .transition(clock, &config.parameters, epoch_schedule)
+        .unwrap();
+    assert!(matches!(state.state_tag, StewardStateEnum::Rebalance));
+    assert!(!state.has_flag(RECOMPUTE_DELEGATIONS));
+    assert!(
+        state.delegations[0..3]
+            == [
+                Delegation::new(1, 2),
+                Delegation::default(),
+                Delegation::new(1, 2)
+            ]
+    );
+}
+
+#[test]
+fn test_compute_score_added_validator_after_instant_unstake() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.scoring_unstake_cap_bps = 10000;
+    fixtures.config.parameters.instant_unstake_cap_bps = 10000;
+    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 10000;
+
+    let clock = &fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &fixtures.validators;
+    let cluster_history = &fixtures.cluster_history;
+    let config = &fixtures.config;
+    let state = &mut fixtures.state;
+
+    // Cycle with the first two validators in the pool, both delegated to
+    state.state_tag = StewardStateEnum::Rebalance;
+    state.num_pool_validators = 2;
+    state.scores[0..2].copy_from_slice(&[1_000_000_000, 500_000_000]);
+    state.raw_scores[0..2].copy_from_slice(&[1_000_000_000, 500_000_000]);
+    state.sorted_score_indices[0..2].copy_from_slice(&[0, 1]);
+    state.sorted_raw_score_indices[0..2].copy_from_slice(&[0, 1]);
+    state.delegations[0..2].copy_from_slice(&[Delegation::new(1, 2), Delegation::new(1, 2)]);
+    state.set_flag(COMPUTE_SCORE | COMPUTE_DELEGATIONS);
+
+    // Second validator is instant unstaked, and its share goes to the first
+    state.instant_unstake.set(1, true).unwrap();
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        1,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        0,
+        0,
+        &config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(matches!(res, Ok(RebalanceType::Decrease(_))));
+    assert!(state.delegations[0..2] == [Delegation::new(1, 1), Delegation::new(0, 1)]);
+
+    // Third validator is added mid-cycle and gets its share, the instant unstaked validator gets none
+    state.state_tag = StewardStateEnum::Idle;
+    state.increment_validator_to_add().unwrap();
+    state
+        .compute_score_added_validator(
+            clock,
+            epoch_schedule,
+            &validators[2],
+            2,
+            cluster_history,
+            config,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(
+        state.delegations[0..3]
+            == [
+                Delegation::new(1, 2),
+                Delegation::new(0, 1),
+                Delegation::new(1, 2)
+            ]
+    );
+}
+
+#[test]
+fn test_compute_score_added_validator_warnings() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.score_warning_filters = SCORE_WARNING_FILTERS_ALLOWED;
+
+    let clock = &fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &fixtures.validators;
+    let cluster_history = &fixtures.cluster_history;
+    let config = &fixtures.config;
+    let state = &mut fixtures.state;
+
+    let mut score_warnings: Box<ScoreWarnings> = Box::new(bytemuck::Zeroable::zeroed());
+
+    // Run a cycle with only the first validator in the pool
+    state.state_tag = StewardStateEnum::ComputeScores;
+    state.num_pool_validators = 1;
+    state
+        .compute_score(
+            clock,
+            epoch_schedule,
+            &validators[0],
+            0,
+            cluster_history,
+            config,
+            1,
+            Some(&mut score_warnings),
+            None,
+        )
+        .unwrap();
+    state.set_flag(COMPUTE_SCORE);
+    state.state_tag = StewardStateEnum::Idle;
+
+    // Validator failing the commission filters is added mid-cycle, and is warned like in ComputeScores
+    state.increment_validator_to_add().unwrap();
+    let score = state
+        .compute_score_added_validator(
+            clock,
+            epoch_schedule,
+            &validators[1],
+            1,
+            cluster_history,
+            config,
+            Some(&mut score_warnings),
+            None,
+        )
+        .unwrap();
+    assert!(score.score > 0);
+    assert_eq!(state.scores[1], state.raw_scores[1]);
+    assert!(score_warnings
+        .is_in_grace(state.next_cycle_epoch, validators[1].index as usize)
+        .unwrap());
+}
+
+#[test]
+fn test_compute_score_commission_pledge() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+
+    let current_epoch = fixtures.current_epoch;
+    let clock = &mut fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let validators = &mut fixtures.validators;
+    let cluster_history = &mut fixtures.cluster_history;
+    let config = &mut fixtures.config;
+    let state = &mut fixtures.state;
+    state.state_tag = StewardStateEnum::ComputeScores;
+
+    let mut commission_pledge: CommissionPledge = bytemuck::Zeroable::zeroed();
+    commission_pledge.start_epoch = current_epoch;
+    commission_pledge.end_epoch = current_epoch + config.parameters.num_epochs_between_scoring;
+
+    // Validator 0 keeps its pledge, validator 1 fails the binary filters, validator 2 broke its pledge
+    let mut broken_pledge = commission_pledge;
+    broken_pledge.mark_broken(current_epoch, 0);
+    let pledges = [
+        Some(&commission_pledge),
+        Some(&commission_pledge),
+        Some(&broken_pledge),
+    ];
+
+    for (validator, pledge) in validators.iter().zip(pledges) {
+        state
+            .compute_score(
+                clock,
+                epoch_schedule,
+                validator,
+                validator.index as usize,
+                cluster_history,
+                config,
+                state.num_pool_validators,
+                None,
+                pledge,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(
+        state.scores[0..3],
+        [
+            7249739868913833600 | COMMISSION_PLEDGE_SCORE_BIT,
+            0,
+            6887252875468641920
+        ]
+    );
+    assert_eq!(
+        state.raw_scores[0..3],
+        [
+            7249739868913833600 | COMMISSION_PLEDGE_SCORE_BIT,
+            72057594039927936 | COMMISSION_PLEDGE_SCORE_BIT,
+            6887252875468641920
+        ]
+    );
+    assert!(ValidatorScoreComponents::decode(state.raw_scores[0]).commission_pledge);
+}
+
 #[test]
 fn test_compute_delegations() {
     // - [ ]  `compute_delegations`
@@ -326,6 +820,7 @@ fn test_compute_instant_unstake_errors() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::InstantUnstakeNotReady)));
 
@@ -338,6 +833,7 @@ fn test_compute_instant_unstake_errors() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::InvalidState)));
 
@@ -350,6 +846,7 @@ fn test_compute_instant_unstake_errors() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::InvalidState)));
 
@@ -369,6 +866,7 @@ fn test_compute_instant_unstake_errors() {
         validator.index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::VoteHistoryNotRecentEnough)));
 
@@ -385,6 +883,7 @@ fn test_compute_instant_unstake_errors() {
         validator.index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::VoteHistoryNotRecentEnough)));
 
@@ -399,6 +898,7 @@ fn test_compute_instant_unstake_errors() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res == Err(Error::from(StewardError::ClusterHistoryNotRecentEnough)));
 }
@@ -426,6 +926,7 @@ fn test_compute_instant_unstake_success() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res.is_ok());
     assert!(matches!(
@@ -445,6 +946,7 @@ fn test_compute_instant_unstake_success() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res.is_ok());
 
@@ -463,6 +965,7 @@ fn test_compute_instant_unstake_success() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res.is_ok());
     assert!(state
@@ -481,6 +984,7 @@ fn test_compute_instant_unstake_success() {
         validators[0].index as usize,
         cluster_history,
         config,
+        None,
     );
     assert!(res.is_ok());
     assert!(state
@@ -490,21 +994,120 @@ fn test_compute_instant_unstake_success() {
 }
 
 #[test]
-fn test_rebalance() {
-    /*
-    - [ ]  `rebalance`
-        - [X]  InvalidState error
-        - [X]  positive rebalance nonzero
-        - [X]  negative rebalance + instant unstake: test rebalancing of `self.delegations`
-        - [X]  negative rebalance zero
-    */
+fn test_compute_instant_unstake_score_warnings() {
     let mut fixtures = Box::<StateMachineFixtures>::default();
-    fixtures.config.parameters.scoring_unstake_cap_bps = 10000;
-    fixtures.config.parameters.instant_unstake_cap_bps = 10000;
-    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 10000;
-
+    fixtures.config.parameters.score_warning_filters = SCORE_FILTER_COMMISSION;
     let state = &mut fixtures.state;
-
+    let clock = &mut fixtures.clock;
+    let epoch_schedule = &fixtures.epoch_schedule;
+    let current_epoch = &fixtures.current_epoch;
+    let validators = &fixtures.validators;
+    let cluster_history = &fixtures.cluster_history;
+    let config = &mut fixtures.config;
+
+    state.state_tag = StewardStateEnum::ComputeInstantUnstake;
+    clock.slot = epoch_schedule.get_last_slot_in_epoch(*current_epoch);
+
+    // Validator 1 is in its grace period for the commission filter
+    let index = validators[1].index as usize;
+    let mut score_warnings: Box<ScoreWarnings> = Box::new(bytemuck::Zeroable::zeroed());
+    score_warnings.cycle_end_epoch = state.next_cycle_epoch;
+    score_warnings.warned.set(index, true).unwrap();
+    score_warnings.in_grace.set(index, true).unwrap();
+    score_warnings.warned_filters[index] = SCORE_FILTER_COMMISSION;
+
+    // Commission check is skipped, but the MEV commission check still applies
+    let res = state
+        .compute_instant_unstake(
+            clock,
+            epoch_schedule,
+            &validators[1],
+            index,
+            cluster_history,
+            config,
+            Some(&score_warnings),
+        )
+        .unwrap()
+        .unwrap();
+    assert!(!res.commission_check);
+    assert!(res.mev_commission_check);
+    assert!(state.instant_unstake.get(index).unwrap());
+
+    // MEV commission is also a warning filter, but the validator was only warned for commission
+    config.parameters.score_warning_filters = SCORE_FILTER_COMMISSION | SCORE_FILTER_MEV_COMMISSION;
+    state.progress.reset();
+    state.instant_unstake.reset();
+    let res = state
+        .compute_instant_unstake(
+            clock,
+            epoch_schedule,
+            &validators[1],
+            index,
+            cluster_history,
+            config,
+            Some(&score_warnings),
+        )
+        .unwrap()
+        .unwrap();
+    assert!(!res.commission_check);
+    assert!(res.mev_commission_check);
+    assert!(state.instant_unstake.get(index).unwrap());
+
+    // Warned for both commission filters, the validator keeps its delegation this cycle
+    score_warnings.warned_filters[index] = SCORE_FILTER_COMMISSION | SCORE_FILTER_MEV_COMMISSION;
+    state.progress.reset();
+    state.instant_unstake.reset();
+    let res = state
+        .compute_instant_unstake(
+            clock,
+            epoch_schedule,
+            &validators[1],
+            index,
+            cluster_history,
+            config,
+            Some(&score_warnings),
+        )
+        .unwrap()
+        .unwrap();
+    assert!(!res.instant_unstake);
+    assert_eq!(res.instant_unstake_fraction_bps, 0);
+    assert!(!state.instant_unstake.get(index).unwrap());
+
+    // Grace from an earlier cycle no longer applies
+    score_warnings.cycle_end_epoch = 0;
+    state.progress.reset();
+    let res = state
+        .compute_instant_unstake(
+            clock,
+            epoch_schedule,
+            &validators[1],
+            index,
+            cluster_history,
+            config,
+            Some(&score_warnings),
+        )
+        .unwrap()
+        .unwrap();
+    assert!(res.commission_check && res.mev_commission_check);
+    assert!(state.instant_unstake.get(index).unwrap());
+}
+
+#[test]
+fn test_rebalance() {
+    /*
+    - [ ]  `rebalance`
+        - [X]  InvalidState error
+        - [X]  positive rebalance nonzero
+        - [X]  negative rebalance + instant unstake: test rebalancing of `self.delegations`
+        - [X]  negative rebalance zero
+    */
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.scoring_unstake_cap_bps = 10000;
+    fixtures.config.parameters.instant_unstake_cap_bps = 10000;
+    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 10000;
+
+    let state = &mut fixtures.state;
+
     // Increase stake
     // validator_list: all validators have 1000 SOL
     // reserve_stake: 1000 SOL
@@ -534,6 +1137,9 @@ fn test_rebalance() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -571,6 +1177,9 @@ fn test_rebalance() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
 
     assert!(res.is_ok());
@@ -602,168 +1211,678 @@ fn test_rebalance() {
     // Test that rebalance will be skipped if validator has already been run
     let res = state.rebalance(
         &DirectedStakeMeta::default(),
-        fixtures.current_epoch,
-        1,
+        fixtures.current_epoch,
+        1,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::None => {}
+        _ => panic!("Expected RebalanceType::None"),
+    }
+
+    // Instant unstake validator, but no delegation, so other delegations are not affected
+    // Same scenario as above but out-of-band validator
+    state.delegations[0..3].copy_from_slice(&[
+        Delegation::new(1, 2),
+        Delegation::new(0, 1),
+        Delegation::new(1, 2),
+    ]);
+    state.scores[0..3].copy_from_slice(&[1_000_000_000, 500_000_000, 0]);
+    state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    // Second validator is instant unstakeable
+    state.instant_unstake.set(1, true).unwrap();
+    state.validator_lamport_balances[1] = 1000 * LAMPORTS_PER_SOL;
+
+    // Validator index 0: 1000 SOL, 1 score, 1 delegation -> Keeps its stake
+    // Validator index 1: 1000 SOL, 0.5 score, 0 delegation, -> Decrease stake, from "instant unstake" category, and set delegation to 0
+    // Validator index 2: 1000 SOL, 0 score, 0 delegation -> Decrease stake, from "regular unstake" category
+
+    state.progress.reset();
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        1,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::Decrease(decrease_components) => {
+            assert_eq!(
+                decrease_components.total_unstake_lamports,
+                1000 * LAMPORTS_PER_SOL
+            );
+            assert_eq!(
+                decrease_components.instant_unstake_lamports,
+                1000 * LAMPORTS_PER_SOL
+            );
+            assert_eq!(decrease_components.scoring_unstake_lamports, 0);
+            assert_eq!(decrease_components.stake_deposit_unstake_lamports, 0);
+
+            assert!(
+                state.delegations[0..3]
+                    == [
+                        Delegation::new(1, 2),
+                        Delegation::new(0, 1),
+                        Delegation::new(1, 2)
+                    ]
+            );
+        }
+        _ => panic!("Expected RebalanceType::Decrease"),
+    }
+
+    // Decrease, instant unstake on the last eligible validator
+    state.instant_unstake_total = 0;
+    state.scoring_unstake_total = 0;
+    state.stake_deposit_unstake_total = 0;
+    state.delegations[0..3].copy_from_slice(&[
+        Delegation::new(0, 1),
+        Delegation::default(),
+        Delegation::default(),
+    ]);
+    state.scores[0..3].copy_from_slice(&[1_000_000_000, 0, 0]);
+    state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.instant_unstake.reset();
+    state.instant_unstake.set(0, true).unwrap();
+
+    state.progress.reset();
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        0,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[0].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::Decrease(decrease_components) => {
+            assert!(decrease_components.total_unstake_lamports == 1000 * LAMPORTS_PER_SOL);
+            assert!(decrease_components.scoring_unstake_lamports == 0);
+            assert!(decrease_components.instant_unstake_lamports == 1000 * LAMPORTS_PER_SOL);
+            assert!(decrease_components.stake_deposit_unstake_lamports == 0);
+            assert!(state.delegations[0..3] == [Delegation::default(); 3]);
+        }
+        _ => panic!("Expected RebalanceType::Decrease"),
+    }
+
+    // No rebalance
+    state.instant_unstake_total = 0;
+    state.scoring_unstake_total = 0;
+    state.stake_deposit_unstake_total = 0;
+    state.instant_unstake.reset();
+    state.delegations[0..3].copy_from_slice(&[Delegation::new(1, 3); 3]);
+    state.scores[0..3].copy_from_slice(&[1_000_000_000, 1_000_000_000, 1_000_000_000]);
+    state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.progress.reset();
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        0,
+        &validator_list_bigvec,
+        3000 * LAMPORTS_PER_SOL,
+        0,
+        u64::from(fixtures.validator_list[0].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::None => {}
+        _ => panic!("Expected RebalanceType::None"),
+    }
+
+    // Invalid State
+    state.state_tag = StewardStateEnum::Idle;
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        0,
+        &validator_list_bigvec,
+        3000 * LAMPORTS_PER_SOL,
+        0,
+        u64::from(fixtures.validator_list[0].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
+    );
+    match res {
+        Ok(_) => panic!("Expected StewardError::InvalidState"),
+        Err(e) => {
+            assert_eq!(e, Error::from(StewardError::InvalidState));
+        }
+    }
+}
+
+#[test]
+fn test_rebalance_graded_instant_unstake() {
+    /*
+    - [X]  partial fraction: only the fraction is instant unstaked, delegation is kept
+    - [X]  no recorded fraction: fully unstaked and delegation is redistributed
+    */
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.scoring_unstake_cap_bps = 10000;
+    fixtures.config.parameters.instant_unstake_cap_bps = 10000;
+    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 10000;
+    fixtures.config.parameters.instant_unstake_max_fraction_bps = 5_000;
+
+    let mut instant_unstake_fractions: Box<InstantUnstakeFractions> =
+        Box::new(bytemuck::Zeroable::zeroed());
+    assert!(instant_unstake_fractions.record(
+        fixtures.current_epoch,
+        &fixtures.validator_list[1].vote_account_address,
+        2_500,
+    ));
+
+    let state = &mut fixtures.state;
+    state.state_tag = StewardStateEnum::Rebalance;
+    state.delegations[0..3].copy_from_slice(&[
+        Delegation::new(1, 2),
+        Delegation::new(1, 2),
+        Delegation::new(0, 1),
+    ]);
+    state.scores[0..3].copy_from_slice(&[1_000_000_000, 500_000_000, 0]);
+    state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
+    state.instant_unstake.set(1, true).unwrap();
+
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
+
+    // Validator index 1: 1000 SOL, 2000 SOL target, 25% fraction -> 250 SOL instant unstaked, delegation kept
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        1,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        Some(&instant_unstake_fractions),
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::Decrease(decrease_components) => {
+            assert_eq!(
+                decrease_components.total_unstake_lamports,
+                250 * LAMPORTS_PER_SOL
+            );
+            assert_eq!(
+                decrease_components.instant_unstake_lamports,
+                250 * LAMPORTS_PER_SOL
+            );
+            assert_eq!(decrease_components.scoring_unstake_lamports, 0);
+            assert_eq!(decrease_components.stake_deposit_unstake_lamports, 0);
+            assert!(
+                state.delegations[0..3]
+                    == [
+                        Delegation::new(1, 2),
+                        Delegation::new(1, 2),
+                        Delegation::new(0, 1)
+                    ]
+            );
+        }
+        _ => panic!("Expected RebalanceType::Decrease"),
+    }
+
+    // Without a recorded fraction the validator is fully unstaked
+    state.instant_unstake_total = 0;
+    state.scoring_unstake_total = 0;
+    state.stake_deposit_unstake_total = 0;
+    state.progress.reset();
+    state.validator_lamport_balances[1] = 1000 * LAMPORTS_PER_SOL;
+    instant_unstake_fractions.record(
+        fixtures.current_epoch,
+        &fixtures.validator_list[1].vote_account_address,
+        0,
+    );
+
+    let res = state.rebalance(
+        &DirectedStakeMeta::default(),
+        fixtures.current_epoch,
+        1,
+        &validator_list_bigvec,
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        0,
+        0,
+        &fixtures.config.parameters,
+        Some(&instant_unstake_fractions),
+        &UnstakeCarryover::default(),
+        None,
+    );
+    assert!(res.is_ok());
+    match res.unwrap() {
+        RebalanceType::Decrease(decrease_components) => {
+            assert_eq!(
+                decrease_components.instant_unstake_lamports,
+                1000 * LAMPORTS_PER_SOL
+            );
+            assert!(
+                state.delegations[0..3]
+                    == [
+                        Delegation::new(1, 1),
+                        Delegation::new(0, 1),
+                        Delegation::new(0, 1)
+                    ]
+            );
+        }
+        _ => panic!("Expected RebalanceType::Decrease"),
+    }
+}
+
+#[test]
+fn test_preferred_validators_calculation() {
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+
+    // validator_list: all validators have 1000 SOL
+    // Validator index 0: 0.5 delegation -> 500 SOL below target
+    // Validator index 1: 0.5 delegation, highest score -> 500 SOL below target
+    // Validator index 2: 0 delegation, lowest score -> 1000 SOL above target
+    let state = &mut fixtures.state;
+    state.delegations[0..3].copy_from_slice(&[
+        Delegation::new(1, 2),
+        Delegation::new(1, 2),
+        Delegation::new(0, 1),
+    ]);
+    state.scores[0..3].copy_from_slice(&[500_000_000, 1_000_000_000, 0]);
+    state.sorted_score_indices[0..3].copy_from_slice(&[1, 0, 2]);
+    state.sorted_raw_score_indices[0..3].copy_from_slice(&[1, 0, 2]);
+
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
+
+    let preferred = preferred_validators_calculation(
+        state,
+        &DirectedStakeMeta::default(),
+        3000 * LAMPORTS_PER_SOL,
+        &validator_list_bigvec,
+        0,
+        0,
+    )
+    .unwrap();
+    // Equal distance below target goes to the higher score
+    assert_eq!(
+        preferred,
+        PreferredValidators {
+            deposit: Some(1),
+            withdraw: Some(2),
+        }
+    );
+
+    // Instant unstake validators have a target of 0 and can't receive deposits.
+    // Equal distance above target goes to the lower score
+    state.instant_unstake.set(1, true).unwrap();
+    let preferred = preferred_validators_calculation(
+        state,
+        &DirectedStakeMeta::default(),
+        3000 * LAMPORTS_PER_SOL,
+        &validator_list_bigvec,
+        0,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        preferred,
+        PreferredValidators {
+            deposit: Some(0),
+            withdraw: Some(2),
+        }
+    );
+
+    // Validators marked for removal are never picked
+    state.validators_to_remove.set(2, true).unwrap();
+    let preferred = preferred_validators_calculation(
+        state,
+        &DirectedStakeMeta::default(),
+        3000 * LAMPORTS_PER_SOL,
+        &validator_list_bigvec,
+        0,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        preferred,
+        PreferredValidators {
+            deposit: Some(0),
+            withdraw: Some(1),
+        }
+    );
+
+    // Validators that are not active in the validator list are never picked
+    fixtures.validator_list[0].status = StakeStatus::DeactivatingValidator.into();
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
+    let preferred = preferred_validators_calculation(
+        &fixtures.state,
+        &DirectedStakeMeta::default(),
+        3000 * LAMPORTS_PER_SOL,
         &validator_list_bigvec,
-        4000 * LAMPORTS_PER_SOL,
-        1000 * LAMPORTS_PER_SOL,
-        u64::from(fixtures.validator_list[1].active_stake_lamports),
         0,
         0,
-        &fixtures.config.parameters,
+    )
+    .unwrap();
+    assert_eq!(
+        preferred,
+        PreferredValidators {
+            deposit: None,
+            withdraw: Some(1),
+        }
     );
+}
 
-    assert!(res.is_ok());
-    match res.unwrap() {
-        RebalanceType::None => {}
-        _ => panic!("Expected RebalanceType::None"),
-    }
+#[test]
+fn test_undirected_pool_lamports() {
+    let mut parameters = StateMachineFixtures::default().config.parameters;
+    parameters.reserve_liquidity_target_lamports = 100 * LAMPORTS_PER_SOL;
+    parameters.reserve_liquidity_target_bps = 0;
+
+    // 1000 SOL pool with 200 SOL directed and 3 validators at 1 SOL of fixed costs each
+    let pool_lamports = UndirectedPoolLamports::new(
+        &parameters,
+        1000 * LAMPORTS_PER_SOL,
+        200 * LAMPORTS_PER_SOL,
+        3,
+        LAMPORTS_PER_SOL / 2,
+        LAMPORTS_PER_SOL / 2,
+    );
+    assert_eq!(
+        pool_lamports,
+        UndirectedPoolLamports {
+            undirected_pool_lamports: 800 * LAMPORTS_PER_SOL,
+            reserve_liquidity_target: 100 * LAMPORTS_PER_SOL,
+            base_lamports: 3 * LAMPORTS_PER_SOL,
+        }
+    );
+    assert_eq!(pool_lamports.stake_pool_lamports(), 700 * LAMPORTS_PER_SOL);
+    assert_eq!(pool_lamports.delegable_lamports(), 697 * LAMPORTS_PER_SOL);
 
-    // Instant unstake validator, but no delegation, so other delegations are not affected
-    // Same scenario as above but out-of-band validator
+    // The liquidity buffer never eats into the fixed costs
+    let pool_lamports = UndirectedPoolLamports::new(
+        &parameters,
+        1000 * LAMPORTS_PER_SOL,
+        950 * LAMPORTS_PER_SOL,
+        3,
+        LAMPORTS_PER_SOL / 2,
+        LAMPORTS_PER_SOL / 2,
+    );
+    assert_eq!(
+        pool_lamports.reserve_liquidity_target,
+        47 * LAMPORTS_PER_SOL
+    );
+    assert_eq!(pool_lamports.stake_pool_lamports(), 3 * LAMPORTS_PER_SOL);
+    assert_eq!(pool_lamports.delegable_lamports(), 0);
+}
+
+#[test]
+fn test_rebalance_unstake_carryover() {
+    /*
+    - [X]  carried over lamports are added to the unstake caps
+    - [X]  scoring unstake is limited by the carried over budget
+    - [X]  unused budget is carried into the next cycle, up to the ceiling
+    */
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.scoring_unstake_cap_bps = 0;
+    fixtures.config.parameters.instant_unstake_cap_bps = 0;
+    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 0;
+
+    let mut unstake_carryover = UnstakeCarryover::default();
+    unstake_carryover.scoring_unused_lamports = 100 * LAMPORTS_PER_SOL;
+    unstake_carryover.instant_unused_lamports = 500 * LAMPORTS_PER_SOL;
+
+    // Ceiling of 5% of 4000 SOL caps the carryover at 200 SOL
+    unstake_carryover
+        .start_cycle(
+            fixtures.state.next_cycle_epoch,
+            500,
+            4000 * LAMPORTS_PER_SOL,
+        )
+        .unwrap();
+    assert_eq!(
+        unstake_carryover.cycle_end_epoch,
+        fixtures.state.next_cycle_epoch
+    );
+    assert_eq!(
+        unstake_carryover.scoring_carryover_lamports,
+        100 * LAMPORTS_PER_SOL
+    );
+    assert_eq!(
+        unstake_carryover.instant_carryover_lamports,
+        200 * LAMPORTS_PER_SOL
+    );
+    assert_eq!(unstake_carryover.stake_deposit_carryover_lamports, 0);
+    assert_eq!(unstake_carryover.scoring_unused_lamports, 0);
+    assert_eq!(unstake_carryover.instant_unused_lamports, 0);
+
+    // Starting the same cycle again does nothing
+    unstake_carryover
+        .start_cycle(fixtures.state.next_cycle_epoch, 0, 4000 * LAMPORTS_PER_SOL)
+        .unwrap();
+    assert_eq!(
+        unstake_carryover.scoring_carryover_lamports,
+        100 * LAMPORTS_PER_SOL
+    );
+
+    let state = &mut fixtures.state;
+    let unstake_state = state
+        .unstake_state(
+            &fixtures.config.parameters,
+            4000 * LAMPORTS_PER_SOL,
+            &unstake_carryover,
+        )
+        .unwrap();
+    assert_eq!(unstake_state.scoring_unstake_cap, 100 * LAMPORTS_PER_SOL);
+    assert_eq!(unstake_state.instant_unstake_cap, 200 * LAMPORTS_PER_SOL);
+    assert_eq!(unstake_state.stake_deposit_unstake_cap, 0);
+
+    state.state_tag = StewardStateEnum::Rebalance;
     state.delegations[0..3].copy_from_slice(&[
-        Delegation::new(1, 2),
-        Delegation::new(0, 1),
-        Delegation::new(1, 2),
+        Delegation::new(1, 1),
+        Delegation::default(),
+        Delegation::default(),
     ]);
-    state.scores[0..3].copy_from_slice(&[1_000_000_000, 500_000_000, 0]);
+    state.scores[0..3].copy_from_slice(&[1_000_000_000, 0, 0]);
     state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
     state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
-    // Second validator is instant unstakeable
-    state.instant_unstake.set(1, true).unwrap();
-    state.validator_lamport_balances[1] = 1000 * LAMPORTS_PER_SOL;
 
-    // Validator index 0: 1000 SOL, 1 score, 1 delegation -> Keeps its stake
-    // Validator index 1: 1000 SOL, 0.5 score, 0 delegation, -> Decrease stake, from "instant unstake" category, and set delegation to 0
-    // Validator index 2: 1000 SOL, 0 score, 0 delegation -> Decrease stake, from "regular unstake" category
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
 
-    state.progress.reset();
+    // Validator index 2: 1000 SOL, 0 delegation -> only the carried over 100 SOL can be unstaked
     let res = state.rebalance(
         &DirectedStakeMeta::default(),
         fixtures.current_epoch,
-        1,
+        2,
         &validator_list_bigvec,
         4000 * LAMPORTS_PER_SOL,
         1000 * LAMPORTS_PER_SOL,
-        u64::from(fixtures.validator_list[1].active_stake_lamports),
+        u64::from(fixtures.validator_list[2].active_stake_lamports),
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &unstake_carryover,
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
         RebalanceType::Decrease(decrease_components) => {
             assert_eq!(
-                decrease_components.total_unstake_lamports,
-                1000 * LAMPORTS_PER_SOL
+                decrease_components.scoring_unstake_lamports,
+                100 * LAMPORTS_PER_SOL
             );
             assert_eq!(
-                decrease_components.instant_unstake_lamports,
-                1000 * LAMPORTS_PER_SOL
-            );
-            assert_eq!(decrease_components.scoring_unstake_lamports, 0);
-            assert_eq!(decrease_components.stake_deposit_unstake_lamports, 0);
-
-            assert!(
-                state.delegations[0..3]
-                    == [
-                        Delegation::new(1, 2),
-                        Delegation::new(0, 1),
-                        Delegation::new(1, 2)
-                    ]
+                decrease_components.total_unstake_lamports,
+                100 * LAMPORTS_PER_SOL
             );
         }
         _ => panic!("Expected RebalanceType::Decrease"),
     }
 
-    // Decrease, instant unstake on the last eligible validator
-    state.instant_unstake_total = 0;
-    state.scoring_unstake_total = 0;
-    state.stake_deposit_unstake_total = 0;
+    // The scoring budget is used up, the instant budget is carried into the next cycle
+    let unstake_state = state
+        .unstake_state(
+            &fixtures.config.parameters,
+            4000 * LAMPORTS_PER_SOL,
+            &unstake_carryover,
+        )
+        .unwrap();
+    unstake_carryover.record_unused(&unstake_state);
+    assert_eq!(unstake_carryover.scoring_unused_lamports, 0);
+    assert_eq!(
+        unstake_carryover.instant_unused_lamports,
+        200 * LAMPORTS_PER_SOL
+    );
+
+    unstake_carryover
+        .start_cycle(state.next_cycle_epoch + 10, 10_000, 4000 * LAMPORTS_PER_SOL)
+        .unwrap();
+    assert_eq!(unstake_carryover.scoring_carryover_lamports, 0);
+    assert_eq!(
+        unstake_carryover.instant_carryover_lamports,
+        200 * LAMPORTS_PER_SOL
+    );
+
+    // A zero ceiling disables the carryover
+    unstake_carryover.record_unused(&unstake_state);
+    unstake_carryover
+        .start_cycle(state.next_cycle_epoch + 20, 0, 4000 * LAMPORTS_PER_SOL)
+        .unwrap();
+    assert_eq!(
+        unstake_carryover,
+        UnstakeCarryover {
+            cycle_end_epoch: state.next_cycle_epoch + 20,
+            ..UnstakeCarryover::default()
+        }
+    );
+}
+
+#[test]
+fn test_rebalance_unstake_circuit_breaker() {
+    /*
+    - [X]  a decrease that would exceed the unstake limit is not issued and leaves the state unchanged
+    - [X]  the same decrease goes through once the limit allows it
+    - [X]  the limit is taken from the same stake pool lamports as the unstake caps
+    */
+    let mut fixtures = Box::<StateMachineFixtures>::default();
+    fixtures.config.parameters.scoring_unstake_cap_bps = 10000;
+    fixtures.config.parameters.instant_unstake_cap_bps = 10000;
+    fixtures.config.parameters.stake_deposit_unstake_cap_bps = 10000;
+    // 500 SOL of the 4000 SOL pool
+    fixtures.config.parameters.circuit_breaker_unstake_bps = 1250;
+    let circuit_breaker_baseline = CircuitBreakerBaseline::default();
+
+    let state = &mut fixtures.state;
+    state.state_tag = StewardStateEnum::Rebalance;
     state.delegations[0..3].copy_from_slice(&[
-        Delegation::new(0, 1),
+        Delegation::new(1, 1),
         Delegation::default(),
         Delegation::default(),
     ]);
     state.scores[0..3].copy_from_slice(&[1_000_000_000, 0, 0]);
     state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
     state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
-    state.instant_unstake.reset();
-    state.instant_unstake.set(0, true).unwrap();
+    let lamport_balance = state.validator_lamport_balances[2];
 
-    state.progress.reset();
+    let mut serialized_data = serialize_validator_list(&fixtures.validator_list);
+    let validator_list_bigvec = BigVec {
+        data: &mut serialized_data,
+    };
+
+    // Validator index 2: 1000 SOL, 0 delegation -> decrease over the 500 SOL limit
     let res = state.rebalance(
         &DirectedStakeMeta::default(),
         fixtures.current_epoch,
-        0,
+        2,
         &validator_list_bigvec,
         4000 * LAMPORTS_PER_SOL,
         1000 * LAMPORTS_PER_SOL,
-        u64::from(fixtures.validator_list[0].active_stake_lamports),
+        u64::from(fixtures.validator_list[2].active_stake_lamports),
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        Some(&circuit_breaker_baseline),
     );
-    assert!(res.is_ok());
-    match res.unwrap() {
-        RebalanceType::Decrease(decrease_components) => {
-            assert!(decrease_components.total_unstake_lamports == 1000 * LAMPORTS_PER_SOL);
-            assert!(decrease_components.scoring_unstake_lamports == 0);
-            assert!(decrease_components.instant_unstake_lamports == 1000 * LAMPORTS_PER_SOL);
-            assert!(decrease_components.stake_deposit_unstake_lamports == 0);
-            assert!(state.delegations[0..3] == [Delegation::default(); 3]);
-        }
-        _ => panic!("Expected RebalanceType::Decrease"),
-    }
-
-    // No rebalance
-    state.instant_unstake_total = 0;
-    state.scoring_unstake_total = 0;
-    state.stake_deposit_unstake_total = 0;
-    state.instant_unstake.reset();
-    state.delegations[0..3].copy_from_slice(&[Delegation::new(1, 3); 3]);
-    state.scores[0..3].copy_from_slice(&[1_000_000_000, 1_000_000_000, 1_000_000_000]);
-    state.sorted_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
-    state.sorted_raw_score_indices[0..3].copy_from_slice(&[0, 1, 2]);
-    state.progress.reset();
-    let res = state.rebalance(
-        &DirectedStakeMeta::default(),
-        fixtures.current_epoch,
-        0,
-        &validator_list_bigvec,
-        3000 * LAMPORTS_PER_SOL,
-        0,
-        u64::from(fixtures.validator_list[0].active_stake_lamports),
-        0,
-        0,
-        &fixtures.config.parameters,
+    assert_eq!(
+        res.unwrap_err(),
+        Error::from(StewardError::UnstakeCircuitBreakerTripped)
     );
-    assert!(res.is_ok());
-    match res.unwrap() {
-        RebalanceType::None => {}
-        _ => panic!("Expected RebalanceType::None"),
-    }
+    assert_eq!(state.unstake_total().unwrap(), 0);
+    assert_eq!(state.validator_lamport_balances[2], lamport_balance);
+    assert!(!state.progress.get(2).unwrap());
 
-    // Invalid State
-    state.state_tag = StewardStateEnum::Idle;
+    // 2000 SOL of the 4000 SOL pool
+    fixtures.config.parameters.circuit_breaker_unstake_bps = 5000;
     let res = state.rebalance(
         &DirectedStakeMeta::default(),
         fixtures.current_epoch,
-        0,
+        2,
         &validator_list_bigvec,
-        3000 * LAMPORTS_PER_SOL,
-        0,
-        u64::from(fixtures.validator_list[0].active_stake_lamports),
+        4000 * LAMPORTS_PER_SOL,
+        1000 * LAMPORTS_PER_SOL,
+        u64::from(fixtures.validator_list[2].active_stake_lamports),
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        Some(&circuit_breaker_baseline),
     );
-    match res {
-        Ok(_) => panic!("Expected StewardError::InvalidState"),
-        Err(e) => {
-            assert_eq!(e, Error::from(StewardError::InvalidState));
-        }
-    }
+    assert!(matches!(res, Ok(RebalanceType::Decrease(_))));
+    assert!(state.unstake_total().unwrap() > 0);
+    assert!(state.progress.get(2).unwrap());
 }
 
 #[test]
@@ -801,6 +1920,9 @@ fn test_rebalance_default_lamports() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
 
     assert!(res.is_ok());
@@ -844,6 +1966,9 @@ fn test_rebalance_default_lamports() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
 
     assert!(res.is_ok());
@@ -937,6 +2062,9 @@ fn test_rebalance_undirected_stake_ceiling() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -973,6 +2101,9 @@ fn test_rebalance_undirected_stake_ceiling() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -1006,6 +2137,9 @@ fn test_rebalance_undirected_stake_ceiling() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -1038,6 +2172,9 @@ fn test_rebalance_undirected_stake_ceiling() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -1069,6 +2206,9 @@ fn test_rebalance_undirected_stake_ceiling() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
     assert!(res.is_ok());
     match res.unwrap() {
@@ -1110,6 +2250,7 @@ fn test_remove_validator() {
     let res = state.remove_validator(1, &mut DirectedStakeMeta::default());
     assert!(res.is_ok());
     assert_eq!(state.num_pool_validators, 2);
+    assert_eq!(state.validators_removed, 1);
     // Assert that values were shifted left
     assert_eq!(state.raw_scores[1], 2);
     assert_eq!(state.scores[1], 2);
@@ -1140,6 +2281,8 @@ fn test_remove_validator() {
     assert!(res.is_ok());
 
     assert_eq!(state.num_pool_validators, 3);
+    assert_eq!(state.validators_added, 1);
+    assert_eq!(state.validators_removed, 1);
     assert!(state.validators_for_immediate_removal.get(3).unwrap());
     assert!(!state.validators_for_immediate_removal.get(4).unwrap());
 }
@@ -1285,6 +2428,9 @@ fn test_rebalance_max_lamports() {
         0,
         0,
         &fixtures.config.parameters,
+        None,
+        &UnstakeCarryover::default(),
+        None,
     );
 
     assert!(res.is_ok());
@@ -1301,11 +2447,14 @@ fn test_directed_stake_preferences_valid() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 4000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: Pubkey::default(),
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
     ticket.staker_preferences[1].stake_share_bps = 3000;
     ticket.staker_preferences[2].stake_share_bps = 3000;
@@ -1349,11 +2498,14 @@ fn test_directed_stake_get_allocations() {
         staker_preferences: [DirectedStakePreference {
             vote_pubkey: Pubkey::default(),
             stake_share_bps: 4000,
-            _padding0: [0; 94],
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
         }; MAX_PREFERENCES_PER_TICKET],
         ticket_update_authority: Pubkey::default(),
         ticket_holder_is_protocol: U8Bool::from(false),
-        _padding0: [0; 125],
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
     };
     let pk1 = Pubkey::new_unique();
     let pk2 = Pubkey::new_unique();
@@ -1420,6 +2572,65 @@ fn test_directed_stake_get_allocations() {
                                              // Total allocated: 5,000 out of 10,000 (50%)
 }
 
+#[test]
+fn test_directed_stake_ticket_extension() {
+    use jito_steward::utils::U8Bool;
+    use jito_steward::{
+        DirectedStakeExtensionPreference, DirectedStakePreference, DirectedStakeTicket,
+        DirectedStakeTicketExtension, MAX_PREFERENCES_PER_TICKET,
+        MAX_PREFERENCES_PER_TICKET_EXTENSION,
+    };
+
+    let pk1 = Pubkey::new_unique();
+    let mut ticket = DirectedStakeTicket {
+        num_preferences: 1,
+        staker_preferences: [DirectedStakePreference {
+            vote_pubkey: pk1,
+            stake_share_bps: 2000,
+            is_basket: U8Bool::from(false),
+            _padding0: [0; 93],
+        }; MAX_PREFERENCES_PER_TICKET],
+        ticket_update_authority: Pubkey::default(),
+        ticket_holder_is_protocol: U8Bool::from(false),
+        is_permissionless: U8Bool::from(false),
+        extension_stake_share_bps: 0,
+        _padding0: [0; 122],
+    };
+
+    let mut extension: Box<DirectedStakeTicketExtension> = Box::new(bytemuck::Zeroable::zeroed());
+    let extension_pubkeys: Vec<Pubkey> = (0..MAX_PREFERENCES_PER_TICKET_EXTENSION)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    for (i, vote_pubkey) in extension_pubkeys.iter().enumerate() {
+        extension.staker_preferences[i] = DirectedStakeExtensionPreference::new(*vote_pubkey, 40);
+    }
+    extension.num_preferences = MAX_PREFERENCES_PER_TICKET_EXTENSION as u64;
+    assert_eq!(extension.stake_share_bps(), 8000);
+
+    // Ticket and extension shares are validated together
+    ticket.extension_stake_share_bps = extension.stake_share_bps() as u16;
+    assert!(ticket.preferences_valid());
+    ticket.staker_preferences[0].stake_share_bps = 2001;
+    assert!(!ticket.preferences_valid());
+    ticket.staker_preferences[0].stake_share_bps = 2000;
+
+    let allocations = ticket.get_allocations_with_extension(Some(&extension), 1_000_000);
+    assert_eq!(allocations.len(), MAX_PREFERENCES_PER_TICKET_EXTENSION + 1);
+    assert_eq!(allocations[0], (pk1, 200_000));
+    assert_eq!(allocations[1], (extension_pubkeys[0], 4_000));
+    let total: u128 = allocations.iter().map(|(_, lamports)| lamports).sum();
+    assert_eq!(total, 1_000_000);
+
+    // Preferences past num_preferences are ignored
+    extension.num_preferences = 10;
+    let allocations = ticket.get_allocations_with_extension(Some(&extension), 1_000_000);
+    assert_eq!(allocations.len(), 11);
+    assert_eq!(
+        ticket.get_allocations_with_extension(None, 1_000_000).len(),
+        1
+    );
+}
+
 #[test]
 fn test_directed_stake_whitelist_operations() {
     use jito_steward::{DirectedStakeWhitelist, MAX_PERMISSIONED_DIRECTED_STAKERS};
@@ -1710,3 +2921,158 @@ fn test_directed_stake_whitelist_capacity_limits() {
     let result = whitelist.add_validator(extra_validator);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_directed_stake_validator_consent() {
+    use jito_steward::utils::U8Bool;
+    use jito_steward::DirectedStakeValidatorConsent;
+
+    let mut consent = DirectedStakeValidatorConsent {
+        config: Pubkey::new_unique(),
+        vote_account: Pubkey::new_unique(),
+        max_directed_stake_lamports: 0,
+        opted_out: U8Bool::from(false),
+        _padding0: [0; 63],
+    };
+
+    // No maximum accepts everything
+    assert_eq!(consent.accepted_lamports(1_000, 5_000), 1_000);
+
+    // Maximum clamps to the remaining room
+    consent.max_directed_stake_lamports = 5_500;
+    assert_eq!(consent.accepted_lamports(1_000, 5_000), 500);
+    assert_eq!(consent.accepted_lamports(1_000, 0), 1_000);
+    assert_eq!(consent.accepted_lamports(1_000, 6_000), 0);
+
+    // Opted out accepts nothing
+    consent.opted_out = U8Bool::from(true);
+    consent.max_directed_stake_lamports = 0;
+    assert_eq!(consent.accepted_lamports(1_000, 0), 0);
+}
+
+#[test]
+fn test_directed_stake_basket_allocations() {
+    use jito_steward::{DirectedStakeBasket, DirectedStakeBasketEntry};
+
+    let validator1 = Pubkey::new_unique();
+    let validator2 = Pubkey::new_unique();
+    let mut basket: Box<DirectedStakeBasket> = Box::new(bytemuck::Zeroable::zeroed());
+    basket.validators[0] = DirectedStakeBasketEntry::new(validator1, 7_500);
+    basket.validators[1] = DirectedStakeBasketEntry::new(validator2, 2_500);
+    // Entries past num_validators are ignored
+    basket.validators[2] = DirectedStakeBasketEntry::new(Pubkey::new_unique(), 10_000);
+    basket.num_validators = 2;
+
+    assert_eq!(basket.validators().len(), 2);
+    assert_eq!(
+        basket.get_allocations(1_000),
+        vec![(validator1, 750), (validator2, 250)]
+    );
+}
+
+#[test]
+fn test_directed_stake_basket_ticket_stake_share() {
+    use jito_steward::{
+        DirectedStakeBasket, DirectedStakeExtensionPreference, DirectedStakePreference,
+        DirectedStakeTicket, DirectedStakeTicketExtension,
+    };
+
+    let basket = Pubkey::new_unique();
+    let mut ticket: Box<DirectedStakeTicket> = Box::new(bytemuck::Zeroable::zeroed());
+    ticket.staker_preferences[0] = DirectedStakePreference::basket(basket, 2_000);
+    ticket.staker_preferences[1] = DirectedStakePreference::new(Pubkey::new_unique(), 3_000);
+    // A validator preference at the basket address is not a basket preference
+    ticket.staker_preferences[2] = DirectedStakePreference::new(basket, 1_000);
+    ticket.num_preferences = 3;
+
+    assert_eq!(
+        DirectedStakeBasket::ticket_stake_share_bps(&basket, &ticket, None),
+        2_000
+    );
+
+    let mut extension: Box<DirectedStakeTicketExtension> = Box::new(bytemuck::Zeroable::zeroed());
+    extension.staker_preferences[0] = DirectedStakeExtensionPreference::basket(basket, 500);
+    extension.staker_preferences[1] =
+        DirectedStakeExtensionPreference::basket(Pubkey::new_unique(), 700);
+    // Preferences past num_preferences are ignored
+    extension.staker_preferences[2] = DirectedStakeExtensionPreference::basket(basket, 900);
+    extension.num_preferences = 2;
+
+    assert_eq!(
+        DirectedStakeBasket::ticket_stake_share_bps(&basket, &ticket, Some(&extension)),
+        2_500
+    );
+}
+
+#[test]
+fn test_directed_stake_preference_schedule() {
+    use jito_steward::{
+        DirectedStakePreference, DirectedStakePreferenceSchedule, MAX_SCHEDULED_PREFERENCE_SETS,
+    };
+
+    let validator1 = Pubkey::new_unique();
+    let validator2 = Pubkey::new_unique();
+    let mut schedule: Box<DirectedStakePreferenceSchedule> = Box::new(bytemuck::Zeroable::zeroed());
+
+    // Sets are kept ordered by activation epoch
+    schedule
+        .schedule(30, &[DirectedStakePreference::new(validator2, 10_000)])
+        .unwrap();
+    schedule
+        .schedule(10, &[DirectedStakePreference::new(validator1, 5_000)])
+        .unwrap();
+    schedule.schedule(20, &[]).unwrap();
+    let epochs: Vec<u64> = schedule
+        .scheduled()
+        .iter()
+        .map(|scheduled| scheduled.activation_epoch)
+        .collect();
+    assert_eq!(epochs, vec![10, 20, 30]);
+
+    // Scheduling the same epoch again replaces the set
+    schedule
+        .schedule(10, &[DirectedStakePreference::new(validator1, 7_000)])
+        .unwrap();
+    assert_eq!(schedule.scheduled().len(), 3);
+    assert_eq!(
+        schedule.scheduled()[0].preferences()[0].stake_share_bps,
+        7_000
+    );
+
+    schedule.schedule(40, &[]).unwrap();
+    assert_eq!(schedule.scheduled().len(), MAX_SCHEDULED_PREFERENCE_SETS);
+    assert_eq!(
+        schedule.schedule(50, &[]),
+        Err(Error::from(
+            StewardError::DirectedStakePreferenceScheduleFull
+        ))
+    );
+
+    schedule.cancel(40).unwrap();
+    assert_eq!(
+        schedule.cancel(40),
+        Err(Error::from(
+            StewardError::ScheduledDirectedStakePreferencesNotFound
+        ))
+    );
+
+    // Nothing is due before the first activation epoch
+    assert!(schedule.take_due(9).is_none());
+    assert_eq!(schedule.scheduled().len(), 3);
+
+    // Only the latest due set is returned, superseded sets are removed with it
+    let due = schedule.take_due(25).unwrap();
+    assert_eq!(due.activation_epoch, 20);
+    assert!(due.preferences().is_empty());
+    assert_eq!(schedule.scheduled().len(), 1);
+    assert_eq!(schedule.scheduled()[0].activation_epoch, 30);
+    assert_eq!(
+        schedule.scheduled()[0].preferences()[0].vote_pubkey,
+        validator2
+    );
+
+    let due = schedule.take_due(30).unwrap();
+    assert_eq!(due.preferences()[0].vote_pubkey, validator2);
+    assert!(schedule.scheduled().is_empty());
+    assert!(schedule.take_due(100).is_none());
+}
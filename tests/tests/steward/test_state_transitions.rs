@@ -33,6 +33,8 @@ pub fn test_compute_scores_to_compute_delegations() {
                 cluster_history,
                 config,
                 state.num_pool_validators,
+                None,
+                None,
             )
             .unwrap();
         assert!(matches!(state.state_tag, StewardStateEnum::ComputeScores));
@@ -79,6 +81,8 @@ pub fn test_compute_scores_to_new_compute_scores() {
             cluster_history,
             config,
             state.num_pool_validators,
+            None,
+            None,
         )
         .unwrap();
     assert!(matches!(state.state_tag, StewardStateEnum::ComputeScores));
@@ -265,6 +269,7 @@ pub fn test_compute_instant_unstake_to_rebalance() {
                 validator.index as usize,
                 cluster_history,
                 config,
+                None,
             )
             .unwrap();
         assert!(matches!(
@@ -280,6 +285,7 @@ pub fn test_compute_instant_unstake_to_rebalance() {
             validators[0].index as usize,
             cluster_history,
             config,
+            None,
         )
         .unwrap();
     assert!(state
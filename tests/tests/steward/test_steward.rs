@@ -3,15 +3,16 @@
 #[allow(deprecated)]
 use anchor_lang::{
     solana_program::{instruction::Instruction, pubkey::Pubkey, stake, sysvar},
-    InstructionData, ToAccountMetas,
+    Discriminator, InstructionData, ToAccountMetas,
 };
 use jito_steward::{
     instructions::AuthorityType,
     stake_pool_utils::{StakePool, ValidatorList},
-    Config, StewardStateAccountV2,
+    CommissionPledge, Config, PledgeCommissionArgs, StewardStateAccountV2, ValidatorExit,
 };
 use solana_program_test::*;
 use solana_sdk::{
+    account::Account,
     clock::Clock,
     signature::Keypair,
     signer::Signer,
@@ -25,10 +26,11 @@ use spl_stake_pool::state::StakeStatus;
 use tests::{
     stake_pool_utils::serialized_validator_list_account,
     steward_fixtures::{
-        closed_vote_account, crank_epoch_maintenance, crank_stake_pool,
+        closed_vote_account, commission_pledge_address, crank_epoch_maintenance, crank_stake_pool,
         initialize_directed_stake_meta, manual_remove_validator, new_vote_account,
-        serialized_stake_account, serialized_validator_history_account, system_account,
-        validator_history_default, TestFixture,
+        new_vote_account_with_authorized_withdrawer, serialized_config, serialized_stake_account,
+        serialized_validator_history_account, system_account, validator_history_default,
+        TestFixture,
     },
 };
 use validator_history::{ValidatorHistory, ValidatorHistoryEntry};
@@ -78,6 +80,15 @@ async fn _auto_add_validator_to_pool(fixture: &TestFixture, vote_account: &Pubke
             stake_config: stake::config::ID,
             stake_program: stake::program::id(),
             system_program: solana_program::system_program::id(),
+            validator_exit: Pubkey::find_program_address(
+                &[
+                    ValidatorExit::SEED,
+                    fixture.steward_config.pubkey().as_ref(),
+                    vote_account.as_ref(),
+                ],
+                &jito_steward::id(),
+            )
+            .0,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::AutoAddValidatorToPool {}.data(),
@@ -677,6 +688,404 @@ async fn test_instant_remove_validator() {
     drop(fixture);
 }
 
+fn _request_validator_exit_tx(
+    fixture: &TestFixture,
+    vote_account: Pubkey,
+    validator_list_index: u64,
+    authorized_withdrawer: &Keypair,
+) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::RequestValidatorExit {
+                config: fixture.steward_config.pubkey(),
+                state_account: fixture.steward_state,
+                validator_list: fixture.stake_pool_meta.validator_list,
+                vote_account,
+                validator_exit: _validator_exit_address(fixture, &vote_account),
+                system_program: solana_program::system_program::id(),
+                authorized_withdrawer: authorized_withdrawer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::RequestValidatorExit {
+                validator_list_index,
+            }
+            .data(),
+        }],
+        Some(&authorized_withdrawer.pubkey()),
+        &[authorized_withdrawer],
+        fixture.ctx.borrow().last_blockhash,
+    )
+}
+
+fn _validator_exit_address(fixture: &TestFixture, vote_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ValidatorExit::SEED,
+            fixture.steward_config.pubkey().as_ref(),
+            vote_account.as_ref(),
+        ],
+        &jito_steward::id(),
+    )
+    .0
+}
+
+async fn _setup_request_validator_exit_test() -> (TestFixture, Pubkey, Keypair) {
+    let (fixture, vote_account) = _setup_auto_remove_validator_test().await;
+
+    let mut config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    config.parameters.validator_exit_cooldown_epochs = 10;
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.steward_config.pubkey(),
+        &serialized_config(config).into(),
+    );
+
+    let authorized_withdrawer = Keypair::new();
+    fixture.ctx.borrow_mut().set_account(
+        &authorized_withdrawer.pubkey(),
+        &system_account(100_000_000_000).into(),
+    );
+    fixture.ctx.borrow_mut().set_account(
+        &vote_account,
+        &new_vote_account_with_authorized_withdrawer(
+            Pubkey::new_unique(),
+            vote_account,
+            authorized_withdrawer.pubkey(),
+            1,
+            None,
+        )
+        .into(),
+    );
+
+    (fixture, vote_account, authorized_withdrawer)
+}
+
+#[tokio::test]
+async fn test_request_validator_exit() {
+    let (fixture, vote_account, authorized_withdrawer) = _setup_request_validator_exit_test().await;
+
+    // Only the authorized withdrawer can request the exit
+    let tx = _request_validator_exit_tx(&fixture, vote_account, 0, &fixture.keypair);
+    fixture
+        .submit_transaction_assert_error(tx, "Unauthorized")
+        .await;
+
+    let tx = _request_validator_exit_tx(&fixture, vote_account, 0, &authorized_withdrawer);
+    fixture.submit_transaction_assert_success(tx).await;
+
+    // The validator is only marked, it stays in the pool until it is auto removed
+    let validator_list: ValidatorList = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.validator_list)
+        .await;
+    let steward_state_account: StewardStateAccountV2 =
+        fixture.load_and_deserialize(&fixture.steward_state).await;
+    assert!(validator_list.validators[0].status == StakeStatus::Active.into());
+    assert!(steward_state_account
+        .state
+        .validators_to_remove
+        .get(0)
+        .unwrap());
+    assert_eq!(
+        steward_state_account
+            .state
+            .validators_for_immediate_removal
+            .count(),
+        0
+    );
+
+    // Its stake is still active, but the exit request makes it removable
+    fixture
+        .submit_transaction_assert_success(
+            _auto_remove_validator_tx(&fixture, vote_account, 0).await,
+        )
+        .await;
+
+    let validator_list: ValidatorList = fixture
+        .load_and_deserialize(&fixture.stake_pool_meta.validator_list)
+        .await;
+    let steward_state_account: StewardStateAccountV2 =
+        fixture.load_and_deserialize(&fixture.steward_state).await;
+    assert!(validator_list.validators[0].status != StakeStatus::Active.into());
+    assert_eq!(
+        steward_state_account.state.validators_to_remove.count()
+            + steward_state_account
+                .state
+                .validators_for_immediate_removal
+                .count(),
+        1
+    );
+
+    let current_epoch = fixture.get_sysvar::<Clock>().await.epoch;
+    let validator_exit_address = _validator_exit_address(&fixture, &vote_account);
+    let validator_exit: ValidatorExit = fixture.load_and_deserialize(&validator_exit_address).await;
+    assert_eq!(validator_exit.vote_account, vote_account);
+    assert_eq!(validator_exit.exit_epoch, current_epoch);
+    assert_eq!(validator_exit.rejoin_epoch, current_epoch + 10);
+    assert!(validator_exit.is_blocked(current_epoch + 9));
+    assert!(!validator_exit.is_blocked(current_epoch + 10));
+
+    // Rejoining closes the exit account, lifting the block
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::RequestValidatorRejoin {
+                config: fixture.steward_config.pubkey(),
+                vote_account,
+                validator_exit: validator_exit_address,
+                authorized_withdrawer: authorized_withdrawer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::RequestValidatorRejoin {}.data(),
+        }],
+        Some(&authorized_withdrawer.pubkey()),
+        &[&authorized_withdrawer],
+        fixture.ctx.borrow().last_blockhash,
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+    assert!(!fixture.account_exists(&validator_exit_address).await);
+
+    drop(fixture);
+}
+
+#[tokio::test]
+async fn test_request_validator_exit_window_closed() {
+    let (fixture, vote_account, authorized_withdrawer) = _setup_request_validator_exit_test().await;
+
+    // Exits requested past the instant unstake mark could not be removed before the epoch ends
+    let mut config: Config = fixture
+        .load_and_deserialize(&fixture.steward_config.pubkey())
+        .await;
+    config.parameters.instant_unstake_epoch_progress = 0.0;
+    fixture.ctx.borrow_mut().set_account(
+        &fixture.steward_config.pubkey(),
+        &serialized_config(config).into(),
+    );
+
+    let tx = _request_validator_exit_tx(&fixture, vote_account, 0, &authorized_withdrawer);
+    fixture
+        .submit_transaction_assert_error(tx, "ValidatorExitWindowClosed")
+        .await;
+
+    let steward_state_account: StewardStateAccountV2 =
+        fixture.load_and_deserialize(&fixture.steward_state).await;
+    assert!(steward_state_account.state.validators_to_remove.is_empty());
+    assert!(
+        !fixture
+            .account_exists(&_validator_exit_address(&fixture, &vote_account))
+            .await
+    );
+
+    drop(fixture);
+}
+
+#[tokio::test]
+async fn test_request_validator_exit_after_rejoin() {
+    let (fixture, vote_account, authorized_withdrawer) = _setup_request_validator_exit_test().await;
+
+    // Exit account left over from an earlier exit whose cooldown ended, after which
+    // `auto_add_validator_to_pool` re-added the validator
+    let validator_exit_address = _validator_exit_address(&fixture, &vote_account);
+    let stale_validator_exit = ValidatorExit {
+        config: fixture.steward_config.pubkey(),
+        vote_account,
+        exit_epoch: 0,
+        rejoin_epoch: 1,
+        _padding0: [0; 64],
+    };
+    let mut data = ValidatorExit::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&stale_validator_exit));
+    fixture.ctx.borrow_mut().set_account(
+        &validator_exit_address,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: jito_steward::id(),
+            ..Account::default()
+        }
+        .into(),
+    );
+
+    // Exiting again overwrites the exit account and restarts the cooldown
+    let tx = _request_validator_exit_tx(&fixture, vote_account, 0, &authorized_withdrawer);
+    fixture.submit_transaction_assert_success(tx).await;
+
+    let current_epoch = fixture.get_sysvar::<Clock>().await.epoch;
+    let validator_exit: ValidatorExit = fixture.load_and_deserialize(&validator_exit_address).await;
+    assert_eq!(validator_exit.vote_account, vote_account);
+    assert_eq!(validator_exit.exit_epoch, current_epoch);
+    assert_eq!(validator_exit.rejoin_epoch, current_epoch + 10);
+    assert!(validator_exit.is_blocked(current_epoch));
+
+    drop(fixture);
+}
+
+fn _pledge_commission_tx(
+    fixture: &TestFixture,
+    vote_account: Pubkey,
+    authorized_withdrawer: &Keypair,
+    args: PledgeCommissionArgs,
+    initialize: bool,
+) -> Transaction {
+    let commission_pledge = commission_pledge_address(fixture, &vote_account);
+    let mut instructions = vec![];
+    if initialize {
+        instructions.push(Instruction {
+            program_id: jito_steward::id(),
+            accounts: jito_steward::accounts::InitializeCommissionPledge {
+                config: fixture.steward_config.pubkey(),
+                vote_account,
+                commission_pledge,
+                system_program: solana_program::system_program::id(),
+                authorized_withdrawer: authorized_withdrawer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeCommissionPledge {}.data(),
+        });
+    }
+    instructions.push(Instruction {
+        program_id: jito_steward::id(),
+        accounts: jito_steward::accounts::PledgeCommission {
+            config: fixture.steward_config.pubkey(),
+            vote_account,
+            commission_pledge,
+            authorized_withdrawer: authorized_withdrawer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: jito_steward::instruction::PledgeCommission { args }.data(),
+    });
+
+    Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&authorized_withdrawer.pubkey()),
+        &[authorized_withdrawer],
+        fixture.ctx.borrow().last_blockhash,
+    )
+}
+
+#[tokio::test]
+async fn test_commission_pledge() {
+    let fixture = TestFixture::new().await;
+    fixture.initialize_stake_pool().await;
+    fixture.initialize_steward(None, None).await;
+
+    let vote_account = Pubkey::new_unique();
+    let authorized_withdrawer = Keypair::new();
+    fixture.ctx.borrow_mut().set_account(
+        &authorized_withdrawer.pubkey(),
+        &system_account(100_000_000_000).into(),
+    );
+    fixture.ctx.borrow_mut().set_account(
+        &vote_account,
+        &new_vote_account_with_authorized_withdrawer(
+            Pubkey::new_unique(),
+            vote_account,
+            authorized_withdrawer.pubkey(),
+            1,
+            None,
+        )
+        .into(),
+    );
+
+    let current_epoch = fixture.get_sysvar::<Clock>().await.epoch;
+    let args = PledgeCommissionArgs {
+        max_commission: 5,
+        max_mev_commission_bps: 800,
+        max_priority_fee_commission_bps: 5000,
+        end_epoch: current_epoch + 10,
+    };
+
+    // Only the authorized withdrawer can pledge
+    let tx = _pledge_commission_tx(&fixture, vote_account, &fixture.keypair, args, true);
+    fixture
+        .submit_transaction_assert_error(tx, "Unauthorized")
+        .await;
+
+    let tx = _pledge_commission_tx(&fixture, vote_account, &authorized_withdrawer, args, true);
+    fixture.submit_transaction_assert_success(tx).await;
+
+    let commission_pledge_address = commission_pledge_address(&fixture, &vote_account);
+    let commission_pledge: CommissionPledge = fixture
+        .load_and_deserialize(&commission_pledge_address)
+        .await;
+    assert_eq!(commission_pledge.vote_account, vote_account);
+    assert_eq!(commission_pledge.start_epoch, current_epoch);
+    assert_eq!(commission_pledge.end_epoch, current_epoch + 10);
+    assert_eq!(commission_pledge.max_commission, 5);
+    assert!(commission_pledge.is_active(current_epoch));
+
+    // An active pledge can't be loosened
+    let loosened = PledgeCommissionArgs {
+        max_commission: 6,
+        ..args
+    };
+    let tx = _pledge_commission_tx(
+        &fixture,
+        vote_account,
+        &authorized_withdrawer,
+        loosened,
+        false,
+    );
+    fixture
+        .submit_transaction_assert_error(tx, "InvalidCommissionPledgeTerms")
+        .await;
+
+    // But it can be tightened and extended
+    let tightened = PledgeCommissionArgs {
+        max_commission: 4,
+        end_epoch: current_epoch + 20,
+        ..args
+    };
+    let tx = _pledge_commission_tx(
+        &fixture,
+        vote_account,
+        &authorized_withdrawer,
+        tightened,
+        false,
+    );
+    fixture.submit_transaction_assert_success(tx).await;
+
+    let mut commission_pledge: CommissionPledge = fixture
+        .load_and_deserialize(&commission_pledge_address)
+        .await;
+    assert_eq!(commission_pledge.start_epoch, current_epoch);
+    assert_eq!(commission_pledge.end_epoch, current_epoch + 20);
+    assert_eq!(commission_pledge.max_commission, 4);
+
+    // A broken pledge can't be renewed until the cooldown ends
+    commission_pledge.mark_broken(current_epoch, 10);
+    let mut data = CommissionPledge::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&commission_pledge));
+    fixture.ctx.borrow_mut().set_account(
+        &commission_pledge_address,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: jito_steward::id(),
+            ..Account::default()
+        }
+        .into(),
+    );
+
+    let renewed = PledgeCommissionArgs {
+        end_epoch: current_epoch + 30,
+        ..tightened
+    };
+    let tx = _pledge_commission_tx(
+        &fixture,
+        vote_account,
+        &authorized_withdrawer,
+        renewed,
+        false,
+    );
+    fixture
+        .submit_transaction_assert_error(tx, "CommissionPledgeCooldown")
+        .await;
+
+    drop(fixture);
+}
+
 #[tokio::test]
 async fn test_pause() {
     let fixture = TestFixture::new().await;
@@ -732,6 +1141,7 @@ async fn test_pause() {
         .load_and_deserialize(&fixture.steward_config.pubkey())
         .await;
     assert!(!config.is_paused());
+    assert!(bool::from(config.circuit_breaker_baseline.resumed));
 
     drop(fixture);
 }
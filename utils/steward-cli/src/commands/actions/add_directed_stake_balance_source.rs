@@ -0,0 +1,116 @@
+//! Directed Stake Balance Source
+//!
+//! Attributes the JitoSOL held in a token or position account to a directed stake ticket.
+//! Both the ticket update authority and the owner of the position sign the transaction.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::{get_directed_stake_balance_sources_address, get_directed_stake_ticket_address},
+    instructions::add_directed_stake_balance_source,
+};
+
+use crate::{
+    commands::command_args::PermissionedParameters, utils::transactions::configure_instruction,
+};
+
+#[derive(Parser)]
+#[command(about = "Attributes a token or position account to a directed stake ticket")]
+pub struct AddDirectedStakeBalanceSource {
+    /// Steward config and ticket update authority keypair
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Token or position account holding JitoSOL
+    #[arg(long)]
+    source_account: Pubkey,
+
+    /// Source type selecting the balance adapter (0 = token account)
+    #[arg(long, default_value_t = 0)]
+    source_type: u16,
+
+    /// Keypair of the position owner, defaults to the ticket update authority
+    #[arg(long)]
+    source_owner_keypair_path: Option<std::path::PathBuf>,
+}
+
+pub(crate) async fn command_add_directed_stake_balance_source(
+    args: AddDirectedStakeBalanceSource,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+    let source_owner = match &args.source_owner_keypair_path {
+        Some(path) => {
+            Some(read_keypair_file(path).map_err(|e| anyhow!("Failed to read keypair file: {e}"))?)
+        }
+        None => None,
+    };
+    let source_owner_pubkey = source_owner
+        .as_ref()
+        .map(|keypair| keypair.pubkey())
+        .unwrap_or(authority.pubkey());
+
+    let ticket_account =
+        get_directed_stake_ticket_address(&steward_config, &authority.pubkey(), &program_id);
+    let initialize = client
+        .get_account(&get_directed_stake_balance_sources_address(
+            &ticket_account,
+            &program_id,
+        ))
+        .await
+        .is_err();
+
+    let ixs = add_directed_stake_balance_source(
+        &program_id,
+        &steward_config,
+        &authority.pubkey(),
+        &args.source_account,
+        &source_owner_pubkey,
+        args.source_type,
+        initialize,
+    );
+
+    let configured_ix = configure_instruction(
+        &ixs,
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    let mut signers = vec![&authority];
+    if let Some(source_owner) = source_owner.as_ref() {
+        signers.push(source_owner);
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &signers,
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
@@ -12,7 +12,9 @@ use solana_sdk::{
 };
 use spl_stake_pool::find_stake_program_address;
 use stakenet_sdk::utils::{
-    accounts::{get_all_steward_accounts, get_validator_history_address},
+    accounts::{
+        get_all_steward_accounts, get_validator_exit_address, get_validator_history_address,
+    },
     transactions::{configure_instruction, print_base58_tx},
 };
 use validator_history::id as validator_history_id;
@@ -65,6 +67,7 @@ pub async fn command_auto_add_validator_from_pool(
             stake_config: stake::config::ID,
             system_program: system_program::id(),
             stake_program: stake::program::id(),
+            validator_exit: get_validator_exit_address(&steward_config, &vote_account, &program_id),
         }
         .to_account_metas(None),
         data: jito_steward::instruction::AutoAddValidatorToPool {}.data(),
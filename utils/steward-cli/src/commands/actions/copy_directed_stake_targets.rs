@@ -15,8 +15,8 @@ use solana_sdk::{
     signer::Signer, transaction::Transaction,
 };
 use stakenet_sdk::utils::accounts::{
-    get_directed_stake_meta_address, get_stake_pool_account, get_steward_config_account,
-    get_validator_list_account,
+    get_directed_stake_meta_address, get_directed_stake_validator_consent_address,
+    get_stake_pool_account, get_steward_config_account, get_validator_list_account,
 };
 
 use crate::{
@@ -117,6 +117,11 @@ pub(crate) async fn command_copy_directed_stake_targets(
                 authority: authority_pubkey,
                 clock: solana_sdk::sysvar::clock::id(),
                 validator_list: validator_list_address,
+                validator_consent: get_directed_stake_validator_consent_address(
+                    &steward_config,
+                    vote_pubkey,
+                    &program_id,
+                ),
             }
             .to_account_metas(None),
             data: jito_steward::instruction::CopyDirectedStakeTargets {
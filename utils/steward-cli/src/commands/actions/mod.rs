@@ -1,3 +1,4 @@
+pub mod add_directed_stake_balance_source;
 pub mod add_to_blacklist;
 pub mod add_to_directed_stake_whitelist;
 pub mod admin_mark_for_removal;
@@ -12,20 +13,28 @@ pub mod instant_remove_validator;
 pub mod manually_copy_all_vote_accounts;
 pub mod manually_copy_vote_accounts;
 pub mod manually_remove_validator;
+pub mod migrate_directed_targets_to_tickets;
 pub mod migrate_directed_to_algorithmic;
 pub mod migrate_state_to_v2;
 pub mod pause;
+pub mod pledge_commission;
 pub mod remove_bad_validators;
 pub mod remove_from_blacklist;
 pub mod remove_from_directed_stake_whitelist;
+pub mod request_validator_exit;
+pub mod request_validator_rejoin;
 pub mod reset_state;
 pub mod reset_validator_lamport_balances;
 pub mod resume;
 pub mod revert_staker;
+pub mod schedule_directed_stake_preferences;
 pub mod set_staker;
 pub mod sync_directed_stake_lamports;
 pub mod update_authority;
 pub mod update_config;
+pub mod update_directed_stake_basket;
 pub mod update_directed_stake_ticket;
+pub mod update_directed_stake_validator_consent;
 pub mod update_priority_fee_config;
 pub mod update_validator_list_balance;
+pub mod verify_invariants;
@@ -0,0 +1,116 @@
+//! Commission Pledge
+//!
+//! This module provides functionality for a validator to pledge maximum inflation, MEV and
+//! priority fee commissions until an end epoch. Validators keeping a pledge that covers the next
+//! cycle are ranked above unpledged validators with the same commission scores, and breaking it
+//! instantly unstakes them. The signer must be the vote account's authorized withdrawer.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use jito_steward::PledgeCommissionArgs;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_commission_pledge_address, instructions::pledge_commission,
+};
+
+use crate::{
+    commands::command_args::{parse_pubkey, parse_u64, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Pledges maximum commissions until an end epoch")]
+pub struct PledgeCommission {
+    /// Signer must be the vote account's authorized withdrawer
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Vote account of the validator
+    #[arg(long, env, value_parser = parse_pubkey)]
+    vote_pubkey: Pubkey,
+
+    /// Maximum inflation commission, in percent
+    #[arg(long)]
+    max_commission: u8,
+
+    /// Maximum MEV commission, in basis points
+    #[arg(long)]
+    max_mev_commission_bps: u16,
+
+    /// Maximum priority fee commission, in basis points
+    #[arg(long)]
+    max_priority_fee_commission_bps: u16,
+
+    /// Last epoch covered by the pledge
+    #[arg(long, value_parser = parse_u64)]
+    end_epoch: u64,
+}
+
+pub(crate) async fn command_pledge_commission(
+    args: PledgeCommission,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let commission_pledge =
+        get_commission_pledge_address(&steward_config, &args.vote_pubkey, &program_id);
+    let initialize = client.get_account(&commission_pledge).await.is_err();
+
+    let ixs = pledge_commission(
+        &program_id,
+        &steward_config,
+        &args.vote_pubkey,
+        &authority.pubkey(),
+        PledgeCommissionArgs {
+            max_commission: args.max_commission,
+            max_mev_commission_bps: args.max_mev_commission_bps,
+            max_priority_fee_commission_bps: args.max_priority_fee_commission_bps,
+            end_epoch: args.end_epoch,
+        },
+        initialize,
+    );
+
+    let configured_ix = configure_instruction(
+        &ixs,
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
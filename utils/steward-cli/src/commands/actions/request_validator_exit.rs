@@ -0,0 +1,97 @@
+//! Validator Exit Request
+//!
+//! This module provides functionality for a validator to mark itself for removal from the pool,
+//! which the keeper carries out with `auto_remove_validator_from_pool`. The validator is not added
+//! back by `auto_add_validator_to_pool` for `validator_exit_cooldown_epochs` epochs, unless it calls
+//! `request-validator-rejoin`. The signer must be the vote account's authorized withdrawer.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_all_steward_accounts, instructions::request_validator_exit,
+};
+
+use crate::{
+    commands::command_args::{parse_pubkey, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Marks a validator for removal from the pool at its own request")]
+pub struct RequestValidatorExit {
+    /// Signer must be the vote account's authorized withdrawer
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Vote account of the validator
+    #[arg(long, env, value_parser = parse_pubkey)]
+    vote_pubkey: Pubkey,
+}
+
+pub(crate) async fn command_request_validator_exit(
+    args: RequestValidatorExit,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let steward_accounts = get_all_steward_accounts(&client, &program_id, &steward_config).await?;
+
+    let validator_list_index = steward_accounts
+        .validator_list_account
+        .validators
+        .iter()
+        .position(|validator| validator.vote_account_address == args.vote_pubkey)
+        .ok_or_else(|| anyhow!("Validator {} is not in the pool", args.vote_pubkey))?;
+
+    let ix = request_validator_exit(
+        &program_id,
+        &steward_accounts,
+        validator_list_index,
+        &authority.pubkey(),
+    )?;
+
+    let configured_ix = configure_instruction(
+        &[ix],
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
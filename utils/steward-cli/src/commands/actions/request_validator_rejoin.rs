@@ -0,0 +1,85 @@
+//! Validator Rejoin Request
+//!
+//! This module provides functionality for a validator that exited the pool to lift the block
+//! preventing `auto_add_validator_to_pool` from adding it back. The signer must be the vote
+//! account's authorized withdrawer.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::instructions::request_validator_rejoin;
+
+use crate::{
+    commands::command_args::{parse_pubkey, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Allows a validator that exited the pool to be added back")]
+pub struct RequestValidatorRejoin {
+    /// Signer must be the vote account's authorized withdrawer
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Vote account of the validator
+    #[arg(long, env, value_parser = parse_pubkey)]
+    vote_pubkey: Pubkey,
+}
+
+pub(crate) async fn command_request_validator_rejoin(
+    args: RequestValidatorRejoin,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let ix = request_validator_rejoin(
+        &program_id,
+        &steward_config,
+        &args.vote_pubkey,
+        &authority.pubkey(),
+    );
+
+    let configured_ix = configure_instruction(
+        &[ix],
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
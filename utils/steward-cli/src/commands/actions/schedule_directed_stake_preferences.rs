@@ -0,0 +1,163 @@
+//! Scheduled Directed Stake Preferences
+//!
+//! This module provides functionality to schedule a future change of a directed stake ticket's
+//! preferences. Once the activation epoch is reached, the scheduled preferences replace the
+//! ticket's preferences when `apply_scheduled_directed_stake_preferences` is cranked, and are
+//! already counted by `compute_directed_stake_meta`. A scheduled change can also be cancelled.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use jito_steward::DirectedStakePreference;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::{get_directed_stake_preference_schedule_address, get_directed_stake_ticket_address},
+    instructions::{
+        cancel_scheduled_directed_stake_preferences, schedule_directed_stake_preferences,
+    },
+};
+
+use crate::{
+    commands::command_args::{parse_pubkey, parse_u16, parse_u64, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Schedules or cancels a future change of a directed stake ticket's preferences")]
+pub struct ScheduleDirectedStakePreferences {
+    /// Signer must be the ticket update authority or the ticket override authority
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Ticket update authority pubkey
+    #[arg(long, env)]
+    ticket_update_authority: Pubkey,
+
+    /// Epoch at which the preferences replace the ticket's preferences
+    #[arg(long, value_parser = parse_u64)]
+    activation_epoch: u64,
+
+    /// Vote accounts of validators to direct stake to (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_pubkey)]
+    vote_pubkey: Vec<Pubkey>,
+
+    /// Stake share allocations in basis points for each validator (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_u16)]
+    stake_share_bps: Vec<u16>,
+
+    /// Directed stake baskets to direct stake to (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_pubkey)]
+    basket: Vec<Pubkey>,
+
+    /// Stake share allocations in basis points for each basket (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_u16)]
+    basket_stake_share_bps: Vec<u16>,
+
+    /// Cancel the preferences scheduled for the activation epoch instead
+    #[arg(long, default_value_t = false)]
+    cancel: bool,
+}
+
+pub(crate) async fn command_schedule_directed_stake_preferences(
+    args: ScheduleDirectedStakePreferences,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let ixs = if args.cancel {
+        vec![cancel_scheduled_directed_stake_preferences(
+            &program_id,
+            &steward_config,
+            &args.ticket_update_authority,
+            &authority.pubkey(),
+            args.activation_epoch,
+        )]
+    } else {
+        if args.vote_pubkey.len().ne(&args.stake_share_bps.len()) {
+            return Err(anyhow!(
+                "Vote pubkeys and stake share bps should be same length"
+            ));
+        }
+        if args.basket.len().ne(&args.basket_stake_share_bps.len()) {
+            return Err(anyhow!(
+                "Baskets and basket stake share bps should be same length"
+            ));
+        }
+
+        let preferences: Vec<DirectedStakePreference> = args
+            .basket
+            .iter()
+            .zip(args.basket_stake_share_bps)
+            .map(|(basket, stake_share_bps)| {
+                DirectedStakePreference::basket(*basket, stake_share_bps)
+            })
+            .chain(args.vote_pubkey.iter().zip(args.stake_share_bps).map(
+                |(vote_pubkey, stake_share_bps)| {
+                    DirectedStakePreference::new(*vote_pubkey, stake_share_bps)
+                },
+            ))
+            .collect();
+
+        let ticket_address = get_directed_stake_ticket_address(
+            &steward_config,
+            &args.ticket_update_authority,
+            &program_id,
+        );
+        let preference_schedule =
+            get_directed_stake_preference_schedule_address(&ticket_address, &program_id);
+        let initialize = client.get_account(&preference_schedule).await.is_err();
+
+        schedule_directed_stake_preferences(
+            &program_id,
+            &steward_config,
+            &args.ticket_update_authority,
+            &authority.pubkey(),
+            args.activation_epoch,
+            preferences,
+            initialize,
+        )
+    };
+
+    let configured_ix = configure_instruction(
+        &ixs,
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Directed Stake Basket Update
+//!
+//! This module provides functionality for a basket curator to replace the validators and
+//! weights of a [`jito_steward::DirectedStakeBasket`]. Every ticket pointing at the basket
+//! is re-targeted the next time directed stake targets are computed.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use jito_steward::DirectedStakeBasketEntry;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::instructions::update_directed_stake_basket;
+
+use crate::{
+    commands::command_args::{parse_basket_name, parse_pubkey, parse_u16, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Updates the validators of a directed stake basket")]
+pub struct UpdateDirectedStakeBasket {
+    /// Authority must be the basket curator
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Basket name, at most 32 bytes
+    #[arg(long, value_parser = parse_basket_name)]
+    name: [u8; 32],
+
+    /// Vote accounts of the basket validators (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_pubkey)]
+    vote_pubkey: Vec<Pubkey>,
+
+    /// Weights of the basket validators in basis points (comma-separated), must sum to 10_000
+    #[arg(long, value_delimiter = ',', value_parser = parse_u16)]
+    weight_bps: Vec<u16>,
+}
+
+pub(crate) async fn command_update_directed_stake_basket(
+    args: UpdateDirectedStakeBasket,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    if args.vote_pubkey.len() != args.weight_bps.len() {
+        return Err(anyhow!("Vote pubkeys and weight bps should be same length"));
+    }
+    let curator = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let validators: Vec<DirectedStakeBasketEntry> = args
+        .vote_pubkey
+        .iter()
+        .zip(args.weight_bps.iter())
+        .map(|(vote_pubkey, weight_bps)| DirectedStakeBasketEntry::new(*vote_pubkey, *weight_bps))
+        .collect();
+
+    let configured_ix = configure_instruction(
+        &[update_directed_stake_basket(
+            &program_id,
+            &args.permissioned_parameters.steward_config,
+            args.name,
+            &curator.pubkey(),
+            validators,
+        )],
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&curator.pubkey()),
+        &[&curator],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
@@ -2,18 +2,29 @@
 //!
 //! This module provides functionality to update the directed stake ticket account in the
 //! `jito_steward` program. The ticket allows specifying stake preferences across multiple
-//! validators with custom stake share allocations.
+//! validators with custom stake share allocations. Preferences beyond the
+//! `MAX_PREFERENCES_PER_TICKET` held by the ticket are written to its extension.
 
 use std::sync::Arc;
 
 use anyhow::anyhow;
 use clap::Parser;
-use jito_steward::DirectedStakePreference;
+use jito_steward::{
+    DirectedStakeExtensionPreference, DirectedStakePreference, MAX_PREFERENCES_PER_TICKET,
+};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
-    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+    instruction::Instruction, pubkey::Pubkey, signature::read_keypair_file, signer::Signer,
+    transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::{
+        get_directed_stake_ticket, get_directed_stake_ticket_address,
+        get_directed_stake_ticket_extension,
+    },
+    helpers::left_directed_stake_baskets,
+    instructions::{update_directed_stake_ticket, update_directed_stake_ticket_extension},
 };
-use stakenet_sdk::utils::instructions::update_directed_stake_ticket;
 
 use crate::{
     commands::command_args::{parse_pubkey, parse_u16, PermissionedParameters},
@@ -44,6 +55,15 @@ pub struct UpdateDirectedStakeTicket {
     #[arg(long, value_delimiter = ',', value_parser = parse_u16)]
     stake_share_bps: Vec<u16>,
 
+    /// Directed stake baskets to direct stake to (comma-separated). Baskets are placed ahead of
+    /// validators.
+    #[arg(long, value_delimiter = ',', value_parser = parse_pubkey)]
+    basket: Vec<Pubkey>,
+
+    /// Stake share allocations in basis points for each basket (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_u16)]
+    basket_stake_share_bps: Vec<u16>,
+
     /// Ticket update authority pubkey
     #[arg(long, env)]
     ticket_update_authority: Pubkey,
@@ -82,62 +102,151 @@ pub(crate) async fn command_update_directed_stake_ticket(
         ));
     }
 
-    let preferences = args
-        .vote_pubkey
+    if args.basket.len().ne(&args.basket_stake_share_bps.len()) {
+        return Err(anyhow!(
+            "Baskets and basket stake share bps should be same length"
+        ));
+    }
+    let mut preferences: Vec<DirectedStakePreference> = args
+        .basket
         .iter()
-        .zip(args.stake_share_bps)
-        .map(|(vote_pubkey, stake_share_bps)| {
-            DirectedStakePreference::new(*vote_pubkey, stake_share_bps)
+        .zip(args.basket_stake_share_bps)
+        .map(|(basket, stake_share_bps)| DirectedStakePreference::basket(*basket, stake_share_bps))
+        .chain(args.vote_pubkey.iter().zip(args.stake_share_bps).map(
+            |(vote_pubkey, stake_share_bps)| {
+                DirectedStakePreference::new(*vote_pubkey, stake_share_bps)
+            },
+        ))
+        .collect();
+    let extension_preferences: Vec<DirectedStakeExtensionPreference> = preferences
+        .split_off(preferences.len().min(MAX_PREFERENCES_PER_TICKET))
+        .into_iter()
+        .map(|preference| {
+            if preference.is_basket.into() {
+                DirectedStakeExtensionPreference::basket(
+                    preference.vote_pubkey,
+                    preference.stake_share_bps,
+                )
+            } else {
+                DirectedStakeExtensionPreference::new(
+                    preference.vote_pubkey,
+                    preference.stake_share_bps,
+                )
+            }
         })
         .collect();
 
-    let ix = update_directed_stake_ticket(
+    let ticket_address = get_directed_stake_ticket_address(
+        &steward_config,
+        &args.ticket_update_authority,
+        &program_id,
+    );
+    let has_extension =
+        get_directed_stake_ticket_extension(client.clone(), &ticket_address, &program_id)
+            .await?
+            .is_some();
+    // The extension is cleared before the ticket is updated
+    let ticket = get_directed_stake_ticket(
+        client.clone(),
+        &steward_config,
+        &args.ticket_update_authority,
+        &program_id,
+    )
+    .await?;
+    let left_baskets = left_directed_stake_baskets(&ticket, None, &preferences);
+
+    // Each batch of instructions is sent in its own transaction. An existing extension is cleared
+    // first so the combined stake share never exceeds 10_000 bps in between.
+    let mut batches: Vec<Vec<Instruction>> = Vec::new();
+    if has_extension {
+        batches.extend(
+            update_directed_stake_ticket_extension(
+                &program_id,
+                &steward_config,
+                &args.ticket_update_authority,
+                &signer,
+                vec![],
+                false,
+            )
+            .into_iter()
+            .map(|ix| vec![ix]),
+        );
+    }
+    batches.push(vec![update_directed_stake_ticket(
         &program_id,
         &steward_config,
         &args.ticket_update_authority,
         &signer,
         preferences,
-    );
+        &left_baskets,
+    )]);
+    if !extension_preferences.is_empty() {
+        let mut extension_ixs = update_directed_stake_ticket_extension(
+            &program_id,
+            &steward_config,
+            &args.ticket_update_authority,
+            &signer,
+            extension_preferences,
+            !has_extension,
+        );
+        if !has_extension {
+            // Initialization is sent together with the first update
+            let init_ix = extension_ixs.remove(0);
+            batches.push(vec![init_ix, extension_ixs.remove(0)]);
+        }
+        batches.extend(extension_ixs.into_iter().map(|ix| vec![ix]));
+    }
 
-    let configured_ix = configure_instruction(
-        &[ix],
-        args.permissioned_parameters
-            .transaction_parameters
-            .priority_fee,
-        args.permissioned_parameters
-            .transaction_parameters
-            .compute_limit,
-        args.permissioned_parameters
-            .transaction_parameters
-            .heap_size,
-    );
+    let configured_batches: Vec<Vec<Instruction>> = batches
+        .iter()
+        .map(|ixs| {
+            configure_instruction(
+                ixs,
+                args.permissioned_parameters
+                    .transaction_parameters
+                    .priority_fee,
+                args.permissioned_parameters
+                    .transaction_parameters
+                    .compute_limit,
+                args.permissioned_parameters
+                    .transaction_parameters
+                    .heap_size,
+            )
+        })
+        .collect();
 
     // If we are printing, do so and return early without requiring the authority keypair
-    if maybe_print_tx(
-        &configured_ix,
-        &args.permissioned_parameters.transaction_parameters,
-    ) {
+    let mut printed = false;
+    for configured_ix in configured_batches.iter() {
+        printed |= maybe_print_tx(
+            configured_ix,
+            &args.permissioned_parameters.transaction_parameters,
+        );
+    }
+    if printed {
         return Ok(());
     }
 
-    // Otherwise, send transaction signed by the authority
+    // Otherwise, send transactions signed by the authority
     let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
         .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
 
-    let blockhash = client.get_latest_blockhash().await?;
+    for configured_ix in configured_batches.iter() {
+        let blockhash = client.get_latest_blockhash().await?;
 
-    let transaction = Transaction::new_signed_with_payer(
-        &configured_ix,
-        Some(&authority.pubkey()),
-        &[&authority],
-        blockhash,
-    );
+        let transaction = Transaction::new_signed_with_payer(
+            configured_ix,
+            Some(&authority.pubkey()),
+            &[&authority],
+            blockhash,
+        );
 
-    let signature = client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await?;
+        let signature = client
+            .send_and_confirm_transaction_with_spinner(&transaction)
+            .await?;
 
-    println!("Signature: {signature}");
+        println!("Signature: {signature}");
+    }
 
     Ok(())
 }
@@ -0,0 +1,107 @@
+//! Directed Stake Validator Consent Update
+//!
+//! This module provides functionality for a validator to control the directed stake it
+//! receives. A validator can opt out of directed stake entirely or cap its directed stake
+//! target. The signer must be the validator identity or the vote account's authorized
+//! withdrawer.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_directed_stake_validator_consent_address,
+    instructions::update_directed_stake_validator_consent,
+};
+
+use crate::{
+    commands::command_args::{parse_pubkey, parse_u64, PermissionedParameters},
+    utils::transactions::{configure_instruction, maybe_print_tx},
+};
+
+#[derive(Parser)]
+#[command(about = "Sets whether a validator accepts directed stake and how much")]
+pub struct UpdateDirectedStakeValidatorConsent {
+    /// Signer must be the validator identity or the vote account's authorized withdrawer
+    #[command(flatten)]
+    pub permissioned_parameters: PermissionedParameters,
+
+    /// Vote account of the validator
+    #[arg(long, env, value_parser = parse_pubkey)]
+    vote_pubkey: Pubkey,
+
+    /// Opt out of receiving directed stake
+    #[arg(long, default_value_t = false)]
+    opted_out: bool,
+
+    /// Maximum directed stake target in lamports, 0 for no maximum
+    #[arg(long, default_value_t = 0, value_parser = parse_u64)]
+    max_directed_stake_lamports: u64,
+}
+
+pub(crate) async fn command_update_directed_stake_validator_consent(
+    args: UpdateDirectedStakeValidatorConsent,
+    client: Arc<RpcClient>,
+    program_id: Pubkey,
+) -> anyhow::Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair file: {e}"))?;
+
+    let validator_consent = get_directed_stake_validator_consent_address(
+        &steward_config,
+        &args.vote_pubkey,
+        &program_id,
+    );
+    let initialize = client.get_account(&validator_consent).await.is_err();
+
+    let ixs = update_directed_stake_validator_consent(
+        &program_id,
+        &steward_config,
+        &args.vote_pubkey,
+        &authority.pubkey(),
+        args.opted_out,
+        args.max_directed_stake_lamports,
+        initialize,
+    );
+
+    let configured_ix = configure_instruction(
+        &ixs,
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if maybe_print_tx(
+        &configured_ix,
+        &args.permissioned_parameters.transaction_parameters,
+    ) {
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("Signature: {signature}");
+
+    Ok(())
+}
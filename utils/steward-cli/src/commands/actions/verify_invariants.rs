@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use jito_steward::{
+    events::{AccountingDriftEvent, VerifyInvariantsEvent},
+    instructions::verify_invariants::{
+        ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE, ACCOUNTING_DRIFT_DIRECTED_ABOVE_VALIDATOR_LIST,
+        ACCOUNTING_DRIFT_DIRECTED_TARGET,
+    },
+};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_all_steward_accounts,
+    events::{decode_event, program_data_from_logs},
+    instructions::verify_invariants,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(
+    about = "Checks the steward's lamport accounting against the ValidatorList and DirectedStakeMeta"
+)]
+pub struct VerifyInvariants {
+    #[command(flatten)]
+    permissionless_parameters: PermissionlessParameters,
+
+    /// Number of validator list indices checked per transaction
+    #[arg(long, default_value_t = 500)]
+    chunk_size: u64,
+
+    /// Pause the steward if its directed stake accounting drifted. Otherwise the check is only simulated
+    #[arg(long, default_value_t = false)]
+    pause_on_drift: bool,
+}
+
+pub async fn command_verify_invariants(
+    args: VerifyInvariants,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let chunk_size = args.chunk_size.max(1);
+    let pause_on_drift = args.pause_on_drift;
+    let args = args.permissionless_parameters;
+    let payer = read_keypair_file(&args.payer_keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair: {e}"))?;
+
+    let all_steward_accounts =
+        get_all_steward_accounts(client, &program_id, &args.steward_config).await?;
+    let num_validators = all_steward_accounts.validator_list_account.validators.len() as u64;
+
+    let mut validators_with_drift = 0;
+    for start_index in (0..num_validators).step_by(chunk_size as usize) {
+        let end_index = start_index.saturating_add(chunk_size).min(num_validators);
+
+        let configured_ix = configure_instruction(
+            &[verify_invariants(
+                &program_id,
+                &all_steward_accounts.config_address,
+                &all_steward_accounts.validator_list_address,
+                start_index,
+                end_index,
+                pause_on_drift,
+            )],
+            args.transaction_parameters.priority_fee,
+            args.transaction_parameters.compute_limit,
+            args.transaction_parameters.heap_size,
+        );
+
+        if args.transaction_parameters.print_tx {
+            print_base58_tx(&configured_ix);
+            continue;
+        }
+
+        let blockhash = client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &configured_ix,
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        );
+
+        let result = client
+            .simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await?
+            .value;
+        if let Some(err) = result.err {
+            return Err(anyhow::anyhow!(
+                "Simulation failed for indices {start_index}..{end_index}: {err:?}"
+            ));
+        }
+        let logs = result.logs.unwrap_or_default();
+
+        let mut paused = false;
+        for data in program_data_from_logs(&logs) {
+            if let Some(event) = decode_event::<AccountingDriftEvent>(&data) {
+                validators_with_drift += 1;
+                println!(
+                    "Index {} ({}): {}",
+                    event.validator_list_index,
+                    event.vote_account,
+                    drift_description(event.drift_flags)
+                );
+                println!(
+                    "  Lamport Balance: {}, Validator List Active: {}, Directed: {}, Target Staked: {}",
+                    event.validator_lamport_balance,
+                    event.validator_list_active_lamports,
+                    event.directed_stake_lamports,
+                    event.target_total_staked_lamports
+                );
+            } else if let Some(event) = decode_event::<VerifyInvariantsEvent>(&data) {
+                paused |= event.paused;
+            }
+        }
+
+        if paused {
+            let signature = client
+                .send_and_confirm_transaction_with_spinner(&transaction)
+                .await?;
+            println!("Steward paused on drift in indices {start_index}..{end_index}");
+            println!("Signature: {signature}");
+        }
+    }
+
+    println!("\nValidators checked: {num_validators}");
+    println!("Validators with drift: {validators_with_drift}");
+
+    Ok(())
+}
+
+fn drift_description(drift_flags: u8) -> String {
+    [
+        (
+            ACCOUNTING_DRIFT_DIRECTED_TARGET,
+            "directed stake target mismatch",
+        ),
+        (
+            ACCOUNTING_DRIFT_DIRECTED_ABOVE_BALANCE,
+            "directed stake above lamport balance",
+        ),
+        (
+            ACCOUNTING_DRIFT_DIRECTED_ABOVE_VALIDATOR_LIST,
+            "directed stake above validator list active stake",
+        ),
+    ]
+    .iter()
+    .filter(|(flag, _)| drift_flags & flag != 0)
+    .map(|(_, description)| *description)
+    .collect::<Vec<_>>()
+    .join(", ")
+}
@@ -24,29 +24,44 @@ impl From<CommitmentLevel> for CommitmentConfig {
 
 use crate::commands::{
     actions::{
+        add_directed_stake_balance_source::AddDirectedStakeBalanceSource,
         add_to_directed_stake_whitelist::AddToDirectedStakeWhitelist,
         admin_mark_for_removal::AdminMarkForRemoval,
         close_directed_stake_meta::CloseDirectedStakeMeta,
         close_directed_stake_ticket::CloseDirectedStakeTicket,
         close_directed_stake_whitelist::CloseDirectedStakeWhitelist,
         copy_directed_stake_targets::CopyDirectedStakeTargets,
+        migrate_directed_targets_to_tickets::MigrateDirectedTargetsToTickets,
         migrate_directed_to_algorithmic::MigrateDirectedToAlgorithmic,
-        migrate_state_to_v2::MigrateStateToV2,
+        migrate_state_to_v2::MigrateStateToV2, pledge_commission::PledgeCommission,
         remove_from_directed_stake_whitelist::RemoveFromDirectedStakeWhitelist,
+        request_validator_exit::RequestValidatorExit,
+        request_validator_rejoin::RequestValidatorRejoin,
+        schedule_directed_stake_preferences::ScheduleDirectedStakePreferences,
         sync_directed_stake_lamports::SyncDirectedStakeLamports,
+        update_directed_stake_basket::UpdateDirectedStakeBasket,
         update_directed_stake_ticket::UpdateDirectedStakeTicket,
+        update_directed_stake_validator_consent::UpdateDirectedStakeValidatorConsent,
+        verify_invariants::VerifyInvariants,
     },
     cranks::{
         compute_directed_stake_meta::ComputeDirectedStakeMeta,
+        directed_stake_balance_samples::CrankDirectedStakeBalanceSamples,
+        directed_stake_tickets::CrankDirectedStakeTickets,
         instant_remove_validators::CrankInstantRemoveValidators,
         rebalance_directed::CrankRebalanceDirected,
     },
     info::{
-        view_blacklist::ViewBlacklist, view_directed_stake_meta::ViewDirectedStakeMeta,
+        view_blacklist::ViewBlacklist, view_cycle_report::ViewCycleReport,
+        view_directed_stake_meta::ViewDirectedStakeMeta,
         view_directed_stake_ticket::ViewDirectedStakeTicket,
+        view_rebalance_journal::ViewRebalanceJournal,
     },
     init::{
+        init_directed_stake_basket::InitDirectedStakeBasket,
         init_directed_stake_ticket::InitDirectedStakeTicket,
+        init_instant_unstake_fractions::InitInstantUnstakeFractions,
+        init_rebalance_journal::InitRebalanceJournal, init_score_warnings::InitScoreWarnings,
         realloc_directed_stake_meta::ReallocDirectedStakeMeta,
         realloc_directed_stake_whitelist::ReallocDirectedStakeWhitelist,
     },
@@ -196,6 +211,78 @@ pub struct ConfigParameters {
     /// Window size (in epochs) over which to check BAM connectivity.
     #[arg(long, env)]
     pub jito_bam_window_epochs: Option<u8>,
+
+    /// Maximum number of validators that can be marked for instant unstake in a cycle before
+    /// the steward pauses itself (0 disables)
+    #[arg(long, env)]
+    pub circuit_breaker_instant_unstake_count: Option<u16>,
+
+    /// Maximum amount of the pool that can be unstaked in a cycle before the steward pauses
+    /// itself, in basis points (0 disables)
+    #[arg(long, env)]
+    pub circuit_breaker_unstake_bps: Option<u16>,
+
+    /// Slots per bucket when sampling directed stake ticket balances for a time-weighted
+    /// average (0 disables)
+    #[arg(long, env)]
+    pub directed_stake_sample_interval_slots: Option<u64>,
+
+    /// Number of epochs a directed stake target can go without being refreshed before it decays
+    /// during rebalancing (0 disables)
+    #[arg(long, env)]
+    pub directed_stake_target_expiry_epochs: Option<u64>,
+
+    /// Basis points of an expired directed stake target removed each epoch (0 resets the target
+    /// to zero at once)
+    #[arg(long, env)]
+    pub directed_stake_target_decay_bps: Option<u16>,
+
+    /// Minimum JitoSOL balance (in pool token base units) to open a directed stake ticket without
+    /// a whitelist entry (0 disables permissionless tickets)
+    #[arg(long, env)]
+    pub directed_stake_permissionless_ticket_min_balance: Option<u64>,
+
+    /// Lamports kept in the reserve as a liquidity buffer for SOL withdrawals
+    #[arg(long, env)]
+    pub reserve_liquidity_target_lamports: Option<u64>,
+
+    /// Share of the pool TVL kept in the reserve as a liquidity buffer (in basis points). The
+    /// larger of this and `reserve_liquidity_target_lamports` is targeted.
+    #[arg(long, env)]
+    pub reserve_liquidity_target_bps: Option<u16>,
+
+    /// Let the steward manage the pool's preferred deposit and withdraw validators
+    #[arg(long, env)]
+    pub auto_preferred_validators: Option<bool>,
+
+    /// Share of a flagged validator's stake instant unstaked at full severity (in basis points).
+    /// Lower severities unstake proportionally less (0 disables graded instant unstake)
+    #[arg(long, env)]
+    pub instant_unstake_max_fraction_bps: Option<u16>,
+    /// SCORE_FILTER_* flags of the binary filters that warn a validator for one cycle
+    /// before unscoring it (0 disables warnings)
+    #[arg(long, env)]
+    pub score_warning_filters: Option<u16>,
+
+    /// Epochs during which a validator that requested to exit is not re-added to the pool
+    #[arg(long, env)]
+    pub validator_exit_cooldown_epochs: Option<u64>,
+
+    /// Epochs after breaking a commission pledge during which a validator cannot pledge again
+    #[arg(long, env)]
+    pub commission_pledge_cooldown_epochs: Option<u64>,
+
+    /// Minimum lamports moved by a single rebalance increase or decrease
+    #[arg(long, env)]
+    pub minimum_rebalance_lamports: Option<u64>,
+
+    /// Maximum unused unstake cap budget carried into the next cycle, per cap (in basis points)
+    #[arg(long, env)]
+    pub unstake_carryover_ceiling_bps: Option<u16>,
+
+    /// Require a cycle report for every cycle, passed to every crank that fills it in
+    #[arg(long, env)]
+    pub cycle_reports_enabled: Option<bool>,
 }
 
 impl From<ConfigParameters> for UpdateParametersArgs {
@@ -225,6 +312,23 @@ impl From<ConfigParameters> for UpdateParametersArgs {
             directed_stake_unstake_cap_bps: config.directed_stake_unstake_cap_bps,
             jito_bam_minimum_epochs: config.jito_bam_minimum_epochs,
             jito_bam_window_epochs: config.jito_bam_window_epochs,
+            circuit_breaker_instant_unstake_count: config.circuit_breaker_instant_unstake_count,
+            circuit_breaker_unstake_bps: config.circuit_breaker_unstake_bps,
+            directed_stake_sample_interval_slots: config.directed_stake_sample_interval_slots,
+            directed_stake_target_expiry_epochs: config.directed_stake_target_expiry_epochs,
+            directed_stake_target_decay_bps: config.directed_stake_target_decay_bps,
+            directed_stake_permissionless_ticket_min_balance: config
+                .directed_stake_permissionless_ticket_min_balance,
+            reserve_liquidity_target_lamports: config.reserve_liquidity_target_lamports,
+            reserve_liquidity_target_bps: config.reserve_liquidity_target_bps,
+            auto_preferred_validators: config.auto_preferred_validators,
+            instant_unstake_max_fraction_bps: config.instant_unstake_max_fraction_bps,
+            score_warning_filters: config.score_warning_filters,
+            validator_exit_cooldown_epochs: config.validator_exit_cooldown_epochs,
+            commission_pledge_cooldown_epochs: config.commission_pledge_cooldown_epochs,
+            minimum_rebalance_lamports: config.minimum_rebalance_lamports,
+            unstake_carryover_ceiling_bps: config.unstake_carryover_ceiling_bps,
+            cycle_reports_enabled: config.cycle_reports_enabled,
         }
     }
 }
@@ -351,6 +455,8 @@ pub enum Commands {
     ViewDirectedStakeTicket(ViewDirectedStakeTicket),
     ViewDirectedStakeWhitelist(ViewDirectedStakeWhitelist),
     ViewDirectedStakeMeta(ViewDirectedStakeMeta),
+    ViewCycleReport(ViewCycleReport),
+    ViewRebalanceJournal(ViewRebalanceJournal),
     GetJitosolBalance(GetJitosolBalance),
 
     // Actions
@@ -389,16 +495,29 @@ pub enum Commands {
     InitDirectedStakeWhitelist(InitDirectedStakeWhitelist),
     ReallocDirectedStakeWhitelist(ReallocDirectedStakeWhitelist),
     InitDirectedStakeTicket(InitDirectedStakeTicket),
+    InitDirectedStakeBasket(InitDirectedStakeBasket),
+    InitInstantUnstakeFractions(InitInstantUnstakeFractions),
+    InitScoreWarnings(InitScoreWarnings),
+    InitRebalanceJournal(InitRebalanceJournal),
     AddToDirectedStakeWhitelist(AddToDirectedStakeWhitelist),
     UpdateDirectedStakeTicket(UpdateDirectedStakeTicket),
+    UpdateDirectedStakeValidatorConsent(UpdateDirectedStakeValidatorConsent),
+    RequestValidatorExit(RequestValidatorExit),
+    RequestValidatorRejoin(RequestValidatorRejoin),
+    PledgeCommission(PledgeCommission),
+    UpdateDirectedStakeBasket(UpdateDirectedStakeBasket),
+    ScheduleDirectedStakePreferences(ScheduleDirectedStakePreferences),
+    AddDirectedStakeBalanceSource(AddDirectedStakeBalanceSource),
     CopyDirectedStakeTargets(CopyDirectedStakeTargets),
     ComputeDirectedStakeMeta(ComputeDirectedStakeMeta),
     RemoveFromDirectedStakeWhitelist(RemoveFromDirectedStakeWhitelist),
     SyncDirectedStakeLamports(SyncDirectedStakeLamports),
+    VerifyInvariants(VerifyInvariants),
     CloseDirectedStakeTicket(CloseDirectedStakeTicket),
     CloseDirectedStakeWhitelist(CloseDirectedStakeWhitelist),
     CloseDirectedStakeMeta(CloseDirectedStakeMeta),
     MigrateDirectedToAlgorithmic(MigrateDirectedToAlgorithmic),
+    MigrateDirectedTargetsToTickets(MigrateDirectedTargetsToTickets),
 
     // Cranks
     CrankSteward(CrankSteward),
@@ -409,8 +528,11 @@ pub enum Commands {
     CrankComputeInstantUnstake(CrankComputeInstantUnstake),
     CrankRebalance(CrankRebalance),
     CrankRebalanceDirected(CrankRebalanceDirected),
+    CrankDirectedStakeTickets(CrankDirectedStakeTickets),
+    CrankDirectedStakeBalanceSamples(CrankDirectedStakeBalanceSamples),
     CrankUpdateStakePool(CrankUpdateStakePool),
     CrankInstantRemoveValidators(CrankInstantRemoveValidators),
+    CrankUpdatePreferredValidators(CrankUpdatePreferredValidators),
 }
 
 // ---------- VIEWS ------------
@@ -653,6 +775,16 @@ pub(crate) fn parse_u64(s: &str) -> Result<u64, std::num::ParseIntError> {
     s.parse()
 }
 
+// Zero pads a directed stake basket name to the 32 bytes used in its seeds
+pub(crate) fn parse_basket_name(s: &str) -> Result<[u8; 32], String> {
+    if s.len() > 32 {
+        return Err(format!("Basket name is longer than 32 bytes: {s}"));
+    }
+    let mut name = [0u8; 32];
+    name[..s.len()].copy_from_slice(s.as_bytes());
+    Ok(name)
+}
+
 // Add helper to parse a Pubkey from string
 pub(crate) fn parse_pubkey(s: &str) -> Result<Pubkey, solana_sdk::pubkey::ParsePubkeyError> {
     use std::str::FromStr;
@@ -879,6 +1011,13 @@ pub struct CrankRebalance {
     pub permissionless_parameters: PermissionlessParameters,
 }
 
+#[derive(Parser)]
+#[command(about = "Crank `update_preferred_validators`")]
+pub struct CrankUpdatePreferredValidators {
+    #[command(flatten)]
+    pub permissionless_parameters: PermissionlessParameters,
+}
+
 #[derive(Parser)]
 #[command(
     about = "Update stake pool - runs update_validator_list_balance, update_stake_pool_balance, and cleanup_removed_validator_entries"
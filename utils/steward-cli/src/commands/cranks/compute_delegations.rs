@@ -12,7 +12,7 @@ use solana_sdk::{
 
 use crate::commands::command_args::CrankComputeDelegations;
 use stakenet_sdk::utils::{
-    accounts::get_all_steward_accounts,
+    accounts::{get_all_steward_accounts, get_required_cycle_report_address},
     transactions::{configure_instruction, print_base58_tx},
 };
 
@@ -30,6 +30,8 @@ pub async fn command_crank_compute_delegations(
     let steward_config = args.steward_config;
 
     let steward_accounts = get_all_steward_accounts(client, &program_id, &steward_config).await?;
+    let cycle_report =
+        get_required_cycle_report_address(client, &steward_accounts, &program_id).await?;
 
     match steward_accounts.state_account.state.state_tag {
         StewardStateEnum::ComputeDelegations => { /* Continue */ }
@@ -48,6 +50,7 @@ pub async fn command_crank_compute_delegations(
             config: steward_config,
             state_account: steward_accounts.state_address,
             validator_list: steward_accounts.validator_list_address,
+            cycle_report,
         }
         .to_account_metas(None),
         data: jito_steward::instruction::ComputeDelegations {}.data(),
@@ -12,7 +12,9 @@ use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
 use crate::commands::command_args::CrankComputeInstantUnstake;
 use stakenet_sdk::utils::{
     accounts::{
-        get_all_steward_accounts, get_cluster_history_address, get_validator_history_address,
+        get_all_steward_accounts, get_cluster_history_address, get_commission_pledge_address,
+        get_instant_unstake_fractions_address, get_required_cycle_report_address,
+        get_score_warnings_address, get_validator_history_address,
     },
     transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
 };
@@ -32,6 +34,19 @@ pub async fn command_crank_compute_instant_unstake(
     let steward_config = args.permissionless_parameters.steward_config;
 
     let steward_accounts = get_all_steward_accounts(client, &program_id, &steward_config).await?;
+    let cycle_report =
+        get_required_cycle_report_address(client, &steward_accounts, &program_id).await?;
+
+    let instant_unstake_fractions = steward_accounts
+        .config_account
+        .parameters
+        .graded_instant_unstake_enabled()
+        .then(|| get_instant_unstake_fractions_address(&steward_config, &program_id));
+    let score_warnings = steward_accounts
+        .config_account
+        .parameters
+        .score_warnings_enabled()
+        .then(|| get_score_warnings_address(&steward_config, &program_id));
 
     match steward_accounts.state_account.state.state_tag {
         StewardStateEnum::ComputeInstantUnstake => { /* Continue */ }
@@ -70,21 +85,31 @@ pub async fn command_crank_compute_instant_unstake(
 
     let ixs_to_run = validators_to_run
         .iter()
-        .map(|(validator_index, _, history_account)| Instruction {
-            program_id,
-            accounts: jito_steward::accounts::ComputeInstantUnstake {
-                config: steward_config,
-                state_account: steward_accounts.state_address,
-                validator_history: *history_account,
-                validator_list: steward_accounts.validator_list_address,
-                cluster_history,
-            }
-            .to_account_metas(None),
-            data: jito_steward::instruction::ComputeInstantUnstake {
-                validator_list_index: *validator_index as u64,
-            }
-            .data(),
-        })
+        .map(
+            |(validator_index, vote_account, history_account)| Instruction {
+                program_id,
+                accounts: jito_steward::accounts::ComputeInstantUnstake {
+                    config: steward_config,
+                    state_account: steward_accounts.state_address,
+                    validator_history: *history_account,
+                    validator_list: steward_accounts.validator_list_address,
+                    cluster_history,
+                    commission_pledge: get_commission_pledge_address(
+                        &steward_config,
+                        vote_account,
+                        &program_id,
+                    ),
+                    cycle_report,
+                    instant_unstake_fractions,
+                    score_warnings,
+                }
+                .to_account_metas(None),
+                data: jito_steward::instruction::ComputeInstantUnstake {
+                    validator_list_index: *validator_index as u64,
+                }
+                .data(),
+            },
+        )
         .collect::<Vec<Instruction>>();
 
     let txs_to_run = package_instructions(
@@ -10,7 +10,9 @@ use crate::commands::command_args::CrankComputeScore;
 use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
 use stakenet_sdk::utils::{
     accounts::{
-        get_all_steward_accounts, get_cluster_history_address, get_validator_history_address,
+        get_all_steward_accounts, get_cluster_history_address, get_commission_pledge_address,
+        get_required_cycle_report_address, get_score_warnings_address,
+        get_validator_history_address,
     },
     transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
 };
@@ -30,6 +32,14 @@ pub async fn command_crank_compute_score(
     let steward_config = args.permissionless_parameters.steward_config;
 
     let steward_accounts = get_all_steward_accounts(client, &program_id, &steward_config).await?;
+    let cycle_report =
+        get_required_cycle_report_address(client, &steward_accounts, &program_id).await?;
+
+    let score_warnings = steward_accounts
+        .config_account
+        .parameters
+        .score_warnings_enabled()
+        .then(|| get_score_warnings_address(&steward_config, &program_id));
 
     let validators_to_run = (0..steward_accounts.state_account.state.num_pool_validators)
         .filter_map(|validator_index| {
@@ -70,6 +80,13 @@ pub async fn command_crank_compute_score(
                     validator_history: *history_account,
                     validator_list: steward_accounts.validator_list_address,
                     cluster_history,
+                    commission_pledge: get_commission_pledge_address(
+                        &steward_config,
+                        vote_account,
+                        &program_id,
+                    ),
+                    cycle_report,
+                    score_warnings,
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::ComputeScore {
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer};
+use stakenet_sdk::utils::{
+    instructions::compute_directed_stake_balance_samples,
+    transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(about = "Crank `sample_directed_stake_balance` for every directed stake ticket")]
+pub struct CrankDirectedStakeBalanceSamples {
+    #[command(flatten)]
+    pub permissionless_parameters: PermissionlessParameters,
+}
+
+pub async fn command_crank_directed_stake_balance_samples(
+    args: CrankDirectedStakeBalanceSamples,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let payer = Arc::new(
+        read_keypair_file(args.permissionless_parameters.payer_keypair_path)
+            .expect("Failed reading keypair file ( Payer )"),
+    );
+
+    let ixs_to_run = compute_directed_stake_balance_samples(
+        client.clone(),
+        &args.permissionless_parameters.steward_config,
+        &payer.pubkey(),
+        &program_id,
+    )
+    .await
+    .map_err(|e| anyhow!(e.to_string()))?;
+
+    let txs_to_run = package_instructions(
+        &ixs_to_run,
+        args.permissionless_parameters
+            .transaction_parameters
+            .chunk_size
+            .unwrap_or(8),
+        args.permissionless_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissionless_parameters
+            .transaction_parameters
+            .compute_limit
+            .or(Some(400_000)),
+        None,
+    );
+
+    if args
+        .permissionless_parameters
+        .transaction_parameters
+        .print_tx
+    {
+        txs_to_run.iter().for_each(|tx| print_base58_tx(tx));
+    } else {
+        println!("Submitting {} instructions", ixs_to_run.len());
+        println!("Submitting {} transactions", txs_to_run.len());
+
+        let submit_stats =
+            submit_packaged_transactions(client, txs_to_run, &payer, None, None).await?;
+
+        println!("Submit stats: {submit_stats:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+use stakenet_sdk::utils::{
+    instructions::{
+        compute_directed_stake_ticket_cranks,
+        compute_scheduled_directed_stake_preference_applications,
+        compute_underfunded_directed_stake_ticket_closures,
+    },
+    transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(about = "Crank `crank_directed_stake_ticket` for every directed stake ticket")]
+pub struct CrankDirectedStakeTickets {
+    #[command(flatten)]
+    pub permissionless_parameters: PermissionlessParameters,
+}
+
+pub async fn command_crank_directed_stake_tickets(
+    args: CrankDirectedStakeTickets,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let payer = Arc::new(
+        read_keypair_file(args.permissionless_parameters.payer_keypair_path)
+            .expect("Failed reading keypair file ( Payer )"),
+    );
+
+    // Due scheduled preferences are applied first so tickets are cranked with them
+    let applications = compute_scheduled_directed_stake_preference_applications(
+        client.clone(),
+        &args.permissionless_parameters.steward_config,
+        &program_id,
+    )
+    .await
+    .map_err(|e| anyhow!(e.to_string()))?;
+    if !applications.is_empty() {
+        submit_or_print(&applications, &args, client, &payer).await?;
+    }
+
+    // Tickets whose holders fell below the permissionless minimum are closed before cranking
+    let closures = compute_underfunded_directed_stake_ticket_closures(
+        client.clone(),
+        &args.permissionless_parameters.steward_config,
+        &payer.pubkey(),
+        &program_id,
+    )
+    .await
+    .map_err(|e| anyhow!(e.to_string()))?;
+    if !closures.is_empty() {
+        submit_or_print(&closures, &args, client, &payer).await?;
+    }
+
+    let ixs_to_run = compute_directed_stake_ticket_cranks(
+        client.clone(),
+        &args.permissionless_parameters.steward_config,
+        &payer.pubkey(),
+        &program_id,
+    )
+    .await
+    .map_err(|e| anyhow!(e.to_string()))?;
+
+    submit_or_print(&ixs_to_run, &args, client, &payer).await
+}
+
+async fn submit_or_print(
+    ixs_to_run: &[Instruction],
+    args: &CrankDirectedStakeTickets,
+    client: &Arc<RpcClient>,
+    payer: &Arc<Keypair>,
+) -> Result<()> {
+    let txs_to_run = package_instructions(
+        ixs_to_run,
+        args.permissionless_parameters
+            .transaction_parameters
+            .chunk_size
+            .unwrap_or(4),
+        args.permissionless_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissionless_parameters
+            .transaction_parameters
+            .compute_limit
+            .or(Some(1_400_000)),
+        None,
+    );
+
+    if args
+        .permissionless_parameters
+        .transaction_parameters
+        .print_tx
+    {
+        txs_to_run.iter().for_each(|tx| print_base58_tx(tx));
+    } else {
+        println!("Submitting {} instructions", ixs_to_run.len());
+        println!("Submitting {} transactions", txs_to_run.len());
+
+        let submit_stats =
+            submit_packaged_transactions(client, txs_to_run, payer, None, None).await?;
+
+        println!("Submit stats: {submit_stats:?}");
+    }
+
+    Ok(())
+}
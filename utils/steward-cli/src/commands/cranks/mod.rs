@@ -2,10 +2,13 @@ pub mod compute_delegations;
 pub mod compute_directed_stake_meta;
 pub mod compute_instant_unstake;
 pub mod compute_score;
+pub mod directed_stake_balance_samples;
+pub mod directed_stake_tickets;
 pub mod epoch_maintenance;
 pub mod idle;
 pub mod instant_remove_validators;
 pub mod rebalance;
 pub mod rebalance_directed;
 pub mod steward;
+pub mod update_preferred_validators;
 pub mod update_stake_pool;
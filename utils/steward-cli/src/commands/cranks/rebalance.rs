@@ -15,7 +15,9 @@ use spl_stake_pool::{
 };
 use stakenet_sdk::utils::{
     accounts::{
-        get_all_steward_accounts, get_directed_stake_meta_address, get_validator_history_address,
+        get_all_steward_accounts, get_directed_stake_meta_address,
+        get_instant_unstake_fractions_address, get_rebalance_journal_address,
+        get_required_cycle_report_address, get_validator_history_address,
     },
     transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
 };
@@ -36,6 +38,14 @@ pub async fn command_crank_rebalance(
     let steward_config = args.permissionless_parameters.steward_config;
 
     let steward_accounts = get_all_steward_accounts(client, &program_id, &steward_config).await?;
+    let cycle_report =
+        get_required_cycle_report_address(client, &steward_accounts, &program_id).await?;
+
+    let instant_unstake_fractions = steward_accounts
+        .config_account
+        .parameters
+        .graded_instant_unstake_enabled()
+        .then(|| get_instant_unstake_fractions_address(&steward_config, &program_id));
     let directed_stake_meta = get_directed_stake_meta_address(&steward_config, &program_id);
 
     match steward_accounts.state_account.state.state_tag {
@@ -136,6 +146,13 @@ pub async fn command_crank_rebalance(
                     stake_history: solana_sdk::sysvar::stake_history::id(),
                     stake_config: stake::config::ID,
                     directed_stake_meta,
+                    cycle_report,
+                    instant_unstake_fractions,
+                    rebalance_journal: get_rebalance_journal_address(
+                        &steward_config,
+                        vote_account,
+                        &program_id,
+                    ),
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::Rebalance {
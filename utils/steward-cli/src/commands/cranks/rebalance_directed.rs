@@ -11,7 +11,7 @@ use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, stake, system_pro
 use stakenet_sdk::utils::{
     accounts::{
         get_all_steward_accounts, get_directed_stake_meta, get_directed_stake_meta_address,
-        get_stake_address, get_transient_stake_address,
+        get_rebalance_journal_address, get_stake_address, get_transient_stake_address,
     },
     helpers::DirectedRebalanceProgressionInfo,
     transactions::{package_instructions, print_base58_tx, submit_packaged_transactions},
@@ -101,6 +101,11 @@ pub async fn command_crank_rebalance_directed(
                     stake_history: solana_sdk::sysvar::stake_history::id(),
                     stake_config: stake::config::ID,
                     directed_stake_meta: directed_stake_meta_address,
+                    rebalance_journal: get_rebalance_journal_address(
+                        &steward_accounts.config_address,
+                        vote_account,
+                        &program_id,
+                    ),
                 }
                 .to_account_metas(None),
                 data: jito_steward::instruction::RebalanceDirected {
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use jito_steward::StewardStateEnum;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+
+use crate::commands::command_args::CrankUpdatePreferredValidators;
+use stakenet_sdk::utils::{
+    accounts::get_all_steward_accounts,
+    instructions::update_preferred_validators,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+pub async fn command_crank_update_preferred_validators(
+    args: CrankUpdatePreferredValidators,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let args = args.permissionless_parameters;
+
+    let payer =
+        read_keypair_file(args.payer_keypair_path).expect("Failed reading keypair file ( Payer )");
+
+    let steward_config = args.steward_config;
+
+    let steward_accounts = get_all_steward_accounts(client, &program_id, &steward_config).await?;
+
+    if !steward_accounts
+        .config_account
+        .parameters
+        .auto_preferred_validators_enabled()
+    {
+        println!("Automatic preferred validators are disabled");
+        return Ok(());
+    }
+
+    match steward_accounts.state_account.state.state_tag {
+        StewardStateEnum::Rebalance | StewardStateEnum::Idle => { /* Continue */ }
+        _ => {
+            println!(
+                "State account is not in Rebalance or Idle state: {}",
+                steward_accounts.state_account.state.state_tag
+            );
+            return Ok(());
+        }
+    }
+
+    let ix = update_preferred_validators(
+        &program_id,
+        &steward_config,
+        &steward_accounts.stake_pool_address,
+        &steward_accounts.validator_list_address,
+    );
+
+    let blockhash = client.get_latest_blockhash().await?;
+
+    let configured_ix = configure_instruction(
+        &[ix],
+        args.transaction_parameters.priority_fee,
+        args.transaction_parameters.compute_limit,
+        args.transaction_parameters.heap_size,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    if args.transaction_parameters.print_tx {
+        print_base58_tx(&configured_ix)
+    } else {
+        let signature = client
+            .send_and_confirm_transaction_with_spinner(&transaction)
+            .await?;
+
+        println!("Signature: {signature}");
+    }
+
+    Ok(())
+}
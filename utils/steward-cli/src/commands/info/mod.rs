@@ -1,10 +1,12 @@
 pub mod get_jitosol_balance;
 pub mod view_blacklist;
 pub mod view_config;
+pub mod view_cycle_report;
 pub mod view_directed_stake_meta;
 pub mod view_directed_stake_ticket;
 pub mod view_directed_stake_tickets;
 pub mod view_directed_stake_whitelist;
 pub mod view_next_index_to_remove;
 pub mod view_priority_fee_config;
+pub mod view_rebalance_journal;
 pub mod view_state;
@@ -195,6 +195,82 @@ fn _print_default_config(
             .parameters
             .undirected_stake_ceiling_lamports()
     );
+    formatted_string += "\n⚙️ Circuit Breaker Parameters ⚙️\n";
+    formatted_string += &format!(
+        "Circuit Breaker Instant Unstake Count:  {:?}\n",
+        config_account
+            .parameters
+            .circuit_breaker_instant_unstake_count
+    );
+    formatted_string += &format!(
+        "Circuit Breaker Unstake BPS:  {:?}\n",
+        config_account.parameters.circuit_breaker_unstake_bps
+    );
+    formatted_string += "\n⚙️ Directed Stake Parameters ⚙️\n";
+    formatted_string += &format!(
+        "Directed Stake Sample Interval Slots:  {:?}\n",
+        config_account
+            .parameters
+            .directed_stake_sample_interval_slots
+    );
+    formatted_string += &format!(
+        "Directed Stake Target Expiry Epochs:  {:?}\n",
+        config_account
+            .parameters
+            .directed_stake_target_expiry_epochs
+    );
+    formatted_string += &format!(
+        "Directed Stake Target Decay BPS:  {:?}\n",
+        config_account.parameters.directed_stake_target_decay_bps
+    );
+    formatted_string += &format!(
+        "Directed Stake Permissionless Ticket Min Balance:  {:?}\n",
+        config_account
+            .parameters
+            .directed_stake_permissionless_ticket_min_balance
+    );
+    formatted_string += &format!(
+        "Reserve Liquidity Target Lamports:  {:?}\n",
+        config_account.parameters.reserve_liquidity_target_lamports
+    );
+    formatted_string += &format!(
+        "Reserve Liquidity Target BPS:  {:?}\n",
+        config_account.parameters.reserve_liquidity_target_bps
+    );
+    formatted_string += &format!(
+        "Instant Unstake Max Fraction BPS:  {:?}\n",
+        config_account.parameters.instant_unstake_max_fraction_bps
+    );
+    formatted_string += &format!(
+        "Score Warning Filters:  {:#012b}\n",
+        config_account.parameters.score_warning_filters
+    );
+    formatted_string += &format!(
+        "Auto Preferred Validators:  {:?}\n",
+        config_account
+            .parameters
+            .auto_preferred_validators_enabled()
+    );
+    formatted_string += &format!(
+        "Validator Exit Cooldown Epochs:  {:?}\n",
+        config_account.parameters.validator_exit_cooldown_epochs
+    );
+    formatted_string += &format!(
+        "Commission Pledge Cooldown Epochs: {:?}\n",
+        config_account.parameters.commission_pledge_cooldown_epochs
+    );
+    formatted_string += &format!(
+        "Minimum Rebalance Lamports:      {:?}\n",
+        config_account.parameters.minimum_rebalance_lamports
+    );
+    formatted_string += &format!(
+        "Unstake Carryover Ceiling Bps:   {:?}\n",
+        config_account.parameters.unstake_carryover_ceiling_bps
+    );
+    formatted_string += &format!(
+        "Cycle Reports Enabled:           {:?}\n",
+        config_account.parameters.cycle_reports_enabled()
+    );
     formatted_string += "---------------------";
 
     println!("{formatted_string}")
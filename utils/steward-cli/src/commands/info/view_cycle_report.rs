@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use stakenet_sdk::utils::accounts::{
+    get_cycle_report, get_cycle_report_address, get_steward_state_account,
+};
+
+#[derive(Parser)]
+#[command(about = "View CycleReport account contents")]
+pub struct ViewCycleReport {
+    /// Steward config account
+    #[arg(long, env)]
+    steward_config: Pubkey,
+
+    /// Epoch the cycle started in. Defaults to the cycle currently tracked by the steward state
+    #[arg(long)]
+    cycle_start_epoch: Option<u64>,
+}
+
+pub async fn command_view_cycle_report(
+    args: ViewCycleReport,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let cycle_start_epoch = match args.cycle_start_epoch {
+        Some(epoch) => epoch,
+        None => {
+            let state_account =
+                get_steward_state_account(client, &program_id, &args.steward_config).await?;
+            client
+                .get_epoch_schedule()
+                .await?
+                .get_epoch(state_account.state.start_computing_scores_slot)
+        }
+    };
+
+    let cycle_report_address =
+        get_cycle_report_address(&args.steward_config, cycle_start_epoch, &program_id);
+    let report = get_cycle_report(
+        client.clone(),
+        &args.steward_config,
+        cycle_start_epoch,
+        &program_id,
+    )
+    .await?;
+
+    println!("\n📊 CycleReport Information:");
+    println!("\nCycleReport Account: {cycle_report_address}");
+    println!("Cycle Start Epoch: {}", report.cycle_start_epoch);
+    println!(
+        "Start Computing Scores Slot: {}",
+        report.start_computing_scores_slot
+    );
+    println!("Pool Validators At Start: {}", report.num_pool_validators);
+
+    println!("\n🧮 Compute Scores:");
+    println!("  Validators Scored: {}", report.validators_scored);
+    println!(
+        "  Validators With Non-Zero Score: {}",
+        report.validators_with_nonzero_score
+    );
+    println!("  Passed Filters:");
+    println!("    MEV Commission: {}", report.mev_commission_passed);
+    println!("    Commission: {}", report.commission_passed);
+    println!(
+        "    Historical Commission: {}",
+        report.historical_commission_passed
+    );
+    println!("    Blacklist: {}", report.blacklisted_passed);
+    println!("    Superminority: {}", report.superminority_passed);
+    println!("    Delinquency: {}", report.delinquency_passed);
+    println!("    Running BAM: {}", report.running_bam_passed);
+    println!(
+        "    Merkle Root Upload Authority: {}",
+        report.merkle_root_upload_authority_passed
+    );
+    println!(
+        "    Priority Fee Commission: {}",
+        report.priority_fee_commission_passed
+    );
+    println!(
+        "    Priority Fee Merkle Root Upload Authority: {}",
+        report.priority_fee_merkle_root_upload_authority_passed
+    );
+
+    println!("\n🎯 Compute Delegations:");
+    println!("  Validators Delegated: {}", report.validators_delegated);
+
+    println!("\n🚨 Compute Instant Unstake:");
+    println!(
+        "  Validators Instant Unstaked: {}",
+        report.validators_instant_unstaked
+    );
+
+    println!("\n⚖️  Rebalance:");
+    println!("  None: {}", report.rebalance_none_count);
+    println!(
+        "  Increase: {} ({} lamports)",
+        report.rebalance_increase_count, report.increase_lamports
+    );
+    println!(
+        "  Decrease: {} ({} lamports)",
+        report.rebalance_decrease_count, report.decrease_lamports
+    );
+    println!(
+        "    Scoring Unstake Lamports: {}",
+        report.scoring_unstake_lamports
+    );
+    println!(
+        "    Instant Unstake Lamports: {}",
+        report.instant_unstake_lamports
+    );
+    println!(
+        "    Stake Deposit Unstake Lamports: {}",
+        report.stake_deposit_unstake_lamports
+    );
+
+    println!("\n👥 Pool Membership:");
+    println!("  Validators Added: {}", report.validators_added);
+    println!("  Validators Removed: {}", report.validators_removed);
+
+    Ok(())
+}
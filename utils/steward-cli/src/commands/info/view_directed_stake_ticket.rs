@@ -4,7 +4,10 @@ use anyhow::Result;
 use clap::Parser;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use stakenet_sdk::utils::accounts::get_directed_stake_ticket;
+use stakenet_sdk::utils::accounts::{
+    get_directed_stake_ticket, get_directed_stake_ticket_address,
+    get_directed_stake_ticket_extension,
+};
 
 #[derive(Parser)]
 #[command(about = "View DirectedStakeTicket account")]
@@ -37,6 +40,7 @@ pub async fn command_view_directed_stake_ticket(
     for preference in ticket.staker_preferences {
         println!("  Vote pubkey: {:?}", preference.vote_pubkey);
         println!("  Stake share bps: {:?}", preference.stake_share_bps);
+        println!("  Is basket: {:?}", bool::from(preference.is_basket));
     }
 
     println!(
@@ -48,5 +52,26 @@ pub async fn command_view_directed_stake_ticket(
         ticket.ticket_holder_is_protocol.value
     );
 
+    let ticket_address =
+        get_directed_stake_ticket_address(&args.steward_config, &args.ticket_signer, &program_id);
+    if let Some(extension) =
+        get_directed_stake_ticket_extension(client.clone(), &ticket_address, &program_id).await?
+    {
+        println!(
+            "extension_stake_share_bps: {:?}",
+            ticket.extension_stake_share_bps
+        );
+        println!("extension num_preferences: {:?}", extension.num_preferences);
+        println!("extension staker_preferences:");
+        for preference in extension
+            .staker_preferences
+            .iter()
+            .take(extension.num_preferences as usize)
+        {
+            println!("  Vote pubkey: {:?}", preference.vote_pubkey);
+            println!("  Stake share bps: {:?}", preference.stake_share_bps);
+        }
+    }
+
     Ok(())
 }
@@ -50,6 +50,10 @@ pub async fn command_view_directed_stake_tickets(
                     "vote_pubkey".to_string(),
                     serde_json::Value::String(pref.vote_pubkey.to_string()),
                 );
+                pref_obj.insert(
+                    "is_basket".to_string(),
+                    serde_json::Value::Bool(pref.is_basket.into()),
+                );
                 pref_obj.insert(
                     "stake_share_bps".to_string(),
                     serde_json::Value::Number(serde_json::Number::from(pref.stake_share_bps)),
@@ -112,8 +116,13 @@ pub async fn command_view_directed_stake_tickets(
                     let pref = &ticket.staker_preferences[i];
                     total_bps += pref.stake_share_bps as u32;
                     println!(
-                        "    [{}] Validator: {} - {:.2}% ({} bps)",
+                        "    [{}] {}: {} - {:.2}% ({} bps)",
                         i + 1,
+                        if pref.is_basket.into() {
+                            "Basket"
+                        } else {
+                            "Validator"
+                        },
                         pref.vote_pubkey,
                         pref.stake_share_bps as f64 / 100.0,
                         pref.stake_share_bps
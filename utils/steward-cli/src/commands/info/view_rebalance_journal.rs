@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use jito_steward::{
+    REBALANCE_REASON_DIRECTED, REBALANCE_REASON_INSTANT_UNSTAKE, REBALANCE_REASON_SCORING,
+    REBALANCE_REASON_STAKE_DEPOSIT,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use stakenet_sdk::utils::accounts::{get_rebalance_journal, get_rebalance_journal_address};
+
+#[derive(Parser)]
+#[command(about = "View RebalanceJournal account contents")]
+pub struct ViewRebalanceJournal {
+    /// Steward config account
+    #[arg(long, env)]
+    steward_config: Pubkey,
+
+    /// Vote account of the validator
+    #[arg(long)]
+    vote_account: Pubkey,
+}
+
+pub async fn command_view_rebalance_journal(
+    args: ViewRebalanceJournal,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let rebalance_journal_address =
+        get_rebalance_journal_address(&args.steward_config, &args.vote_account, &program_id);
+    let journal = get_rebalance_journal(
+        client.clone(),
+        &args.steward_config,
+        &args.vote_account,
+        &program_id,
+    )
+    .await?;
+
+    println!("\n📒 RebalanceJournal Information:");
+    println!("\nRebalanceJournal Account: {rebalance_journal_address}");
+    println!("Vote Account: {}", journal.vote_account);
+    println!("Entries Recorded: {}", journal.num_recorded);
+
+    println!("\n⚖️  Entries (oldest first):");
+    for entry in journal.entries() {
+        let direction = if bool::from(entry.increase) {
+            "Increase"
+        } else {
+            "Decrease"
+        };
+        let reason = match entry.reason {
+            REBALANCE_REASON_SCORING => "Scoring",
+            REBALANCE_REASON_INSTANT_UNSTAKE => "Instant Unstake",
+            REBALANCE_REASON_STAKE_DEPOSIT => "Stake Deposit",
+            REBALANCE_REASON_DIRECTED => "Directed",
+            _ => "Unknown",
+        };
+        println!(
+            "  Epoch {}: {direction} {} lamports ({reason})",
+            entry.epoch, entry.lamports
+        );
+    }
+
+    Ok(())
+}
@@ -145,6 +145,15 @@ pub struct StateInfo {
     /// Tracks deposits that need to be withdrawn
     stake_deposit_unstake_total: u64,
 
+    /// Unused scoring unstake cap carried over from the previous cycle
+    scoring_carryover_lamports: u64,
+
+    /// Unused instant unstake cap carried over from the previous cycle
+    instant_carryover_lamports: u64,
+
+    /// Unused stake deposit unstake cap carried over from the previous cycle
+    stake_deposit_carryover_lamports: u64,
+
     /// Count of validators marked for removal from the pool
     /// Cleaned up in the next epoch after removal
     validators_to_remove_count: usize,
@@ -444,6 +453,7 @@ pub async fn command_view_state(
             &steward_config,
             &all_steward_accounts.state_address,
             &all_steward_accounts.state_account,
+            &all_steward_accounts.config_account,
             &all_steward_accounts.validator_list_account,
             &all_steward_accounts.reserve_stake_account,
             directed_stake_meta.as_deref(),
@@ -463,6 +473,7 @@ fn build_default_state_output(
     steward_config: &Pubkey,
     steward_state: &Pubkey,
     state_account: &StewardStateAccountV2,
+    config_account: &Config,
     validator_list_account: &ValidatorList,
     reserve_stake_account: &Account,
     directed_stake_meta: Option<&DirectedStakeMeta>,
@@ -552,6 +563,11 @@ fn build_default_state_output(
             scoring_unstake_total: state.scoring_unstake_total,
             instant_unstake_total: state.instant_unstake_total,
             stake_deposit_unstake_total: state.stake_deposit_unstake_total,
+            scoring_carryover_lamports: config_account.unstake_carryover.scoring_carryover_lamports,
+            instant_carryover_lamports: config_account.unstake_carryover.instant_carryover_lamports,
+            stake_deposit_carryover_lamports: config_account
+                .unstake_carryover
+                .stake_deposit_carryover_lamports,
             validators_to_remove_count: state.validators_to_remove.count(),
             validators_for_immediate_removal_count: state.validators_for_immediate_removal.count(),
             validators_added: state.validators_added,
@@ -572,10 +588,12 @@ fn build_default_state_output(
 }
 
 /// Display the information of [`DefaultStateOutput`]
+#[allow(clippy::too_many_arguments)]
 fn _print_default_state(
     steward_config: &Pubkey,
     steward_state: &Pubkey,
     state_account: &StewardStateAccountV2,
+    config_account: &Config,
     validator_list_account: &ValidatorList,
     reserve_stake_account: &Account,
     directed_stake_meta: Option<&DirectedStakeMeta>,
@@ -586,6 +604,7 @@ fn _print_default_state(
             steward_config,
             steward_state,
             state_account,
+            config_account,
             validator_list_account,
             reserve_stake_account,
             directed_stake_meta,
@@ -598,6 +617,7 @@ fn _print_default_state(
             steward_config,
             steward_state,
             state_account,
+            config_account,
             validator_list_account,
             reserve_stake_account,
             directed_stake_meta,
@@ -669,6 +689,18 @@ fn _print_default_state(
             "Stake Deposit Unstake Total: {}\n",
             output.state.stake_deposit_unstake_total
         );
+        formatted_string += &format!(
+            "Scoring Unstake Carryover: {}\n",
+            output.state.scoring_carryover_lamports
+        );
+        formatted_string += &format!(
+            "Instant Unstake Carryover: {}\n",
+            output.state.instant_carryover_lamports
+        );
+        formatted_string += &format!(
+            "Stake Deposit Unstake Carryover: {}\n",
+            output.state.stake_deposit_carryover_lamports
+        );
         formatted_string += &format!("Padding0 Length: {}\n", state._padding0.len());
         formatted_string += "\n";
         formatted_string += &format!(
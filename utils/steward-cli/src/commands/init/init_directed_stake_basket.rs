@@ -0,0 +1,92 @@
+//! Directed Stake Basket Initialization
+//!
+//! This command creates an empty [`DirectedStakeBasket`] account in the `jito_steward`
+//! program. A basket is a named, weighted list of validators managed by a curator that
+//! directed stake tickets can point at.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_directed_stake_basket_address,
+    instructions::initialize_directed_stake_basket,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+use crate::commands::command_args::{parse_basket_name, PermissionedParameters};
+
+#[derive(Parser)]
+#[command(about = "Initialize DirectedStakeBasket account")]
+pub struct InitDirectedStakeBasket {
+    /// Authority must be the directed stake whitelist authority
+    #[command(flatten)]
+    permissioned_parameters: PermissionedParameters,
+
+    /// Basket name, at most 32 bytes
+    #[arg(long, value_parser = parse_basket_name)]
+    name: [u8; 32],
+
+    /// Authority that manages the basket validators
+    #[arg(long)]
+    curator: Pubkey,
+}
+
+pub async fn command_init_directed_stake_basket(
+    args: InitDirectedStakeBasket,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let steward_config = args.permissioned_parameters.steward_config;
+    let authority_keypair = read_keypair_file(&args.permissioned_parameters.authority_keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair: {e}"))?;
+
+    let basket = get_directed_stake_basket_address(&steward_config, &args.name, &program_id);
+    println!("Initializing DirectedStakeBasket...");
+    println!("  Curator: {}", args.curator);
+    println!("  DirectedStakeBasket PDA: {basket}");
+
+    let configured_ix = configure_instruction(
+        &[initialize_directed_stake_basket(
+            &program_id,
+            &steward_config,
+            args.name,
+            &args.curator,
+            &authority_keypair.pubkey(),
+        )],
+        args.permissioned_parameters
+            .transaction_parameters
+            .priority_fee,
+        args.permissioned_parameters
+            .transaction_parameters
+            .compute_limit,
+        args.permissioned_parameters
+            .transaction_parameters
+            .heap_size,
+    );
+
+    if args.permissioned_parameters.transaction_parameters.print_tx {
+        print_base58_tx(&configured_ix);
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&authority_keypair.pubkey()),
+        &[&authority_keypair],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("  Transaction signature: {signature}");
+
+    Ok(())
+}
@@ -16,7 +16,11 @@ use solana_sdk::signature::read_keypair_file;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
 use stakenet_sdk::utils::{
-    accounts::{get_directed_stake_ticket_address, get_directed_stake_whitelist_address},
+    accounts::{
+        get_directed_stake_ticket_address, get_directed_stake_whitelist_address,
+        get_stake_pool_account,
+    },
+    instructions::initialize_permissionless_directed_stake_ticket,
     transactions::{configure_instruction, print_base58_tx},
 };
 
@@ -37,6 +41,11 @@ pub struct InitDirectedStakeTicket {
     /// Whether the ticket holder is a protocol
     #[arg(long, env)]
     ticket_holder_is_protocol: bool,
+
+    /// Open the ticket without a whitelist entry by holding the minimum JitoSOL balance. The
+    /// authority keypair must be the ticket update authority.
+    #[arg(long, default_value_t = false)]
+    permissionless: bool,
 }
 
 pub async fn command_init_directed_stake_ticket(
@@ -77,21 +86,39 @@ pub async fn command_init_directed_stake_ticket(
     );
     println!("  DirectedStakeTicket PDA: {directed_stake_ticket_pda}");
 
-    let instruction = Instruction {
-        program_id,
-        accounts: jito_steward::accounts::InitializeDirectedStakeTicket {
-            config: steward_config_pubkey,
-            whitelist_account: directed_stake_whitelist_pda,
-            ticket_account: directed_stake_ticket_pda,
-            system_program: solana_sdk::system_program::ID,
-            signer: authority_pubkey,
+    let instruction = if args.permissionless {
+        if args.ticket_update_authority != authority_pubkey {
+            return Err(anyhow::anyhow!(
+                "Permissionless tickets must be opened by the ticket update authority"
+            ));
         }
-        .to_account_metas(None),
-        data: jito_steward::instruction::InitializeDirectedStakeTicket {
-            ticket_update_authority: args.ticket_update_authority,
-            ticket_holder_is_protocol: args.ticket_holder_is_protocol,
+        let config = get_steward_config_account(client, &steward_config_pubkey).await?;
+        let stake_pool = get_stake_pool_account(client, &config.stake_pool).await?;
+        initialize_permissionless_directed_stake_ticket(
+            &program_id,
+            &steward_config_pubkey,
+            &config.stake_pool,
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+            &authority_pubkey,
+        )
+    } else {
+        Instruction {
+            program_id,
+            accounts: jito_steward::accounts::InitializeDirectedStakeTicket {
+                config: steward_config_pubkey,
+                whitelist_account: directed_stake_whitelist_pda,
+                ticket_account: directed_stake_ticket_pda,
+                system_program: solana_sdk::system_program::ID,
+                signer: authority_pubkey,
+            }
+            .to_account_metas(None),
+            data: jito_steward::instruction::InitializeDirectedStakeTicket {
+                ticket_update_authority: args.ticket_update_authority,
+                ticket_holder_is_protocol: args.ticket_holder_is_protocol,
+            }
+            .data(),
         }
-        .data(),
     };
 
     let configured_ix = configure_instruction(
@@ -0,0 +1,77 @@
+//! Instant Unstake Fractions Initialization
+//!
+//! This command creates the [`InstantUnstakeFractions`] account in the `jito_steward`
+//! program. It records the graded instant unstake fraction of each flagged validator, and
+//! is required by `compute_instant_unstake` and `rebalance` once
+//! `instant_unstake_max_fraction_bps` is set.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_instant_unstake_fractions_address,
+    instructions::initialize_instant_unstake_fractions,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(about = "Initialize InstantUnstakeFractions account")]
+pub struct InitInstantUnstakeFractions {
+    #[command(flatten)]
+    permissionless_parameters: PermissionlessParameters,
+}
+
+pub async fn command_init_instant_unstake_fractions(
+    args: InitInstantUnstakeFractions,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let args = args.permissionless_parameters;
+    let steward_config = args.steward_config;
+    let payer = read_keypair_file(&args.payer_keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair: {e}"))?;
+
+    let instant_unstake_fractions =
+        get_instant_unstake_fractions_address(&steward_config, &program_id);
+    println!("Initializing InstantUnstakeFractions...");
+    println!("  InstantUnstakeFractions PDA: {instant_unstake_fractions}");
+
+    let configured_ix = configure_instruction(
+        &[initialize_instant_unstake_fractions(
+            &program_id,
+            &steward_config,
+            &payer.pubkey(),
+        )],
+        args.transaction_parameters.priority_fee,
+        args.transaction_parameters.compute_limit,
+        args.transaction_parameters.heap_size,
+    );
+
+    if args.transaction_parameters.print_tx {
+        print_base58_tx(&configured_ix);
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("  Transaction signature: {signature}");
+
+    Ok(())
+}
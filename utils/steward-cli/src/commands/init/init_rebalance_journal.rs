@@ -0,0 +1,83 @@
+//! Rebalance Journal Initialization
+//!
+//! This command creates the [`RebalanceJournal`] account of a validator in the
+//! `jito_steward` program. Once it exists, `rebalance` and `rebalance_directed`
+//! record every stake increase and decrease applied to the validator in it.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_rebalance_journal_address,
+    instructions::initialize_rebalance_journal,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(about = "Initialize RebalanceJournal account for a validator")]
+pub struct InitRebalanceJournal {
+    #[command(flatten)]
+    permissionless_parameters: PermissionlessParameters,
+
+    /// Vote account of the validator
+    #[arg(long)]
+    vote_account: Pubkey,
+}
+
+pub async fn command_init_rebalance_journal(
+    args: InitRebalanceJournal,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let vote_account = args.vote_account;
+    let args = args.permissionless_parameters;
+    let steward_config = args.steward_config;
+    let payer = read_keypair_file(&args.payer_keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair: {e}"))?;
+
+    let rebalance_journal =
+        get_rebalance_journal_address(&steward_config, &vote_account, &program_id);
+    println!("Initializing RebalanceJournal...");
+    println!("  Vote Account: {vote_account}");
+    println!("  RebalanceJournal PDA: {rebalance_journal}");
+
+    let configured_ix = configure_instruction(
+        &[initialize_rebalance_journal(
+            &program_id,
+            &steward_config,
+            &vote_account,
+            &payer.pubkey(),
+        )],
+        args.transaction_parameters.priority_fee,
+        args.transaction_parameters.compute_limit,
+        args.transaction_parameters.heap_size,
+    );
+
+    if args.transaction_parameters.print_tx {
+        print_base58_tx(&configured_ix);
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("  Transaction signature: {signature}");
+
+    Ok(())
+}
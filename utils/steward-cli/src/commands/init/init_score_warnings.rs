@@ -0,0 +1,71 @@
+//! Score Warnings Initialization
+//!
+//! This command creates the [`ScoreWarnings`] account in the `jito_steward`
+//! program. It tracks validators warned before being unscored, and is required
+//! by `compute_score` once `score_warning_filters` is set.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey, signature::read_keypair_file, signer::Signer, transaction::Transaction,
+};
+use stakenet_sdk::utils::{
+    accounts::get_score_warnings_address,
+    instructions::initialize_score_warnings,
+    transactions::{configure_instruction, print_base58_tx},
+};
+
+use crate::commands::command_args::PermissionlessParameters;
+
+#[derive(Parser)]
+#[command(about = "Initialize ScoreWarnings account")]
+pub struct InitScoreWarnings {
+    #[command(flatten)]
+    permissionless_parameters: PermissionlessParameters,
+}
+
+pub async fn command_init_score_warnings(
+    args: InitScoreWarnings,
+    client: &Arc<RpcClient>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let args = args.permissionless_parameters;
+    let steward_config = args.steward_config;
+    let payer = read_keypair_file(&args.payer_keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair: {e}"))?;
+
+    let score_warnings = get_score_warnings_address(&steward_config, &program_id);
+    println!("Initializing ScoreWarnings...");
+    println!("  ScoreWarnings PDA: {score_warnings}");
+
+    let configured_ix = configure_instruction(
+        &initialize_score_warnings(&program_id, &steward_config, &payer.pubkey()),
+        args.transaction_parameters.priority_fee,
+        args.transaction_parameters.compute_limit,
+        args.transaction_parameters.heap_size,
+    );
+
+    if args.transaction_parameters.print_tx {
+        print_base58_tx(&configured_ix);
+        return Ok(());
+    }
+
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &configured_ix,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .await?;
+
+    println!("  Transaction signature: {signature}");
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
+pub mod init_directed_stake_basket;
 pub mod init_directed_stake_meta;
 pub mod init_directed_stake_ticket;
 pub mod init_directed_stake_whitelist;
+pub mod init_instant_unstake_fractions;
+pub mod init_rebalance_journal;
+pub mod init_score_warnings;
 pub mod init_steward;
 pub mod realloc_directed_stake_meta;
 pub mod realloc_directed_stake_whitelist;
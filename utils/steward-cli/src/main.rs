@@ -31,11 +31,13 @@ use commands::{
     },
     info::{
         get_jitosol_balance::command_get_jitosol_balance, view_config::command_view_config,
+        view_cycle_report::command_view_cycle_report,
         view_directed_stake_meta::command_view_directed_stake_meta,
         view_directed_stake_tickets::command_view_directed_stake_tickets,
         view_directed_stake_whitelist::command_view_directed_stake_whitelist,
         view_next_index_to_remove::command_view_next_index_to_remove,
-        view_priority_fee_config::command_view_priority_fee_config, view_state::command_view_state,
+        view_priority_fee_config::command_view_priority_fee_config,
+        view_rebalance_journal::command_view_rebalance_journal, view_state::command_view_state,
     },
     init::{init_steward::command_init_steward, realloc_state::command_realloc_state},
 };
@@ -46,6 +48,7 @@ use crate::{
     cli_signer::CliSigner,
     commands::{
         actions::{
+            add_directed_stake_balance_source::command_add_directed_stake_balance_source,
             add_to_directed_stake_whitelist::command_add_to_directed_stake_whitelist,
             admin_mark_for_removal::command_admin_mark_for_removal,
             close_directed_stake_meta::command_close_directed_stake_meta,
@@ -53,25 +56,40 @@ use crate::{
             close_directed_stake_whitelist::command_close_directed_stake_whitelist,
             close_steward::command_close_steward,
             copy_directed_stake_targets::command_copy_directed_stake_targets,
+            migrate_directed_targets_to_tickets::command_migrate_directed_targets_to_tickets,
             migrate_directed_to_algorithmic::command_migrate_directed_to_algorithmic,
             migrate_state_to_v2::command_migrate_state_to_v2,
+            pledge_commission::command_pledge_commission,
             remove_from_directed_stake_whitelist::command_remove_from_directed_stake_whitelist,
+            request_validator_exit::command_request_validator_exit,
+            request_validator_rejoin::command_request_validator_rejoin,
+            schedule_directed_stake_preferences::command_schedule_directed_stake_preferences,
             sync_directed_stake_lamports::command_sync_directed_stake_lamports,
+            update_directed_stake_basket::command_update_directed_stake_basket,
             update_directed_stake_ticket::command_update_directed_stake_ticket,
+            update_directed_stake_validator_consent::command_update_directed_stake_validator_consent,
+            verify_invariants::command_verify_invariants,
         },
         cranks::{
             compute_directed_stake_meta::command_crank_compute_directed_stake_meta,
+            directed_stake_balance_samples::command_crank_directed_stake_balance_samples,
+            directed_stake_tickets::command_crank_directed_stake_tickets,
             instant_remove_validators::command_crank_instant_remove_validators,
             rebalance_directed::command_crank_rebalance_directed,
+            update_preferred_validators::command_crank_update_preferred_validators,
         },
         info::{
             view_blacklist::command_view_blacklist,
             view_directed_stake_ticket::command_view_directed_stake_ticket,
         },
         init::{
+            init_directed_stake_basket::command_init_directed_stake_basket,
             init_directed_stake_meta::command_init_directed_stake_meta,
             init_directed_stake_ticket::command_init_directed_stake_ticket,
             init_directed_stake_whitelist::command_init_directed_stake_whitelist,
+            init_instant_unstake_fractions::command_init_instant_unstake_fractions,
+            init_rebalance_journal::command_init_rebalance_journal,
+            init_score_warnings::command_init_score_warnings,
             realloc_directed_stake_meta::command_realloc_directed_stake_meta,
             realloc_directed_stake_whitelist::command_realloc_directed_stake_whitelist,
         },
@@ -135,6 +153,12 @@ async fn main() -> Result<()> {
         Commands::ViewDirectedStakeMeta(args) => {
             command_view_directed_stake_meta(args, &client, steward_program_id).await
         }
+        Commands::ViewCycleReport(args) => {
+            command_view_cycle_report(args, &client, steward_program_id).await
+        }
+        Commands::ViewRebalanceJournal(args) => {
+            command_view_rebalance_journal(args, &client, steward_program_id).await
+        }
         Commands::GetJitosolBalance(args) => {
             command_get_jitosol_balance(args, &client, steward_program_id).await
         }
@@ -247,12 +271,51 @@ async fn main() -> Result<()> {
         Commands::InitDirectedStakeTicket(args) => {
             command_init_directed_stake_ticket(args, &client, steward_program_id).await
         }
+        Commands::InitDirectedStakeBasket(args) => {
+            command_init_directed_stake_basket(args, &client, steward_program_id).await
+        }
+        Commands::InitInstantUnstakeFractions(args) => {
+            command_init_instant_unstake_fractions(args, &client, steward_program_id).await
+        }
+        Commands::InitScoreWarnings(args) => {
+            command_init_score_warnings(args, &client, steward_program_id).await
+        }
+        Commands::InitRebalanceJournal(args) => {
+            command_init_rebalance_journal(args, &client, steward_program_id).await
+        }
         Commands::AddToDirectedStakeWhitelist(args) => {
             command_add_to_directed_stake_whitelist(args, &client, steward_program_id).await
         }
         Commands::UpdateDirectedStakeTicket(args) => {
             command_update_directed_stake_ticket(args, client.clone(), steward_program_id).await
         }
+        Commands::UpdateDirectedStakeValidatorConsent(args) => {
+            command_update_directed_stake_validator_consent(
+                args,
+                client.clone(),
+                steward_program_id,
+            )
+            .await
+        }
+        Commands::RequestValidatorExit(args) => {
+            command_request_validator_exit(args, client.clone(), steward_program_id).await
+        }
+        Commands::RequestValidatorRejoin(args) => {
+            command_request_validator_rejoin(args, client.clone(), steward_program_id).await
+        }
+        Commands::PledgeCommission(args) => {
+            command_pledge_commission(args, client.clone(), steward_program_id).await
+        }
+        Commands::UpdateDirectedStakeBasket(args) => {
+            command_update_directed_stake_basket(args, client.clone(), steward_program_id).await
+        }
+        Commands::ScheduleDirectedStakePreferences(args) => {
+            command_schedule_directed_stake_preferences(args, client.clone(), steward_program_id)
+                .await
+        }
+        Commands::AddDirectedStakeBalanceSource(args) => {
+            command_add_directed_stake_balance_source(args, &client, steward_program_id).await
+        }
         Commands::CopyDirectedStakeTargets(args) => {
             command_copy_directed_stake_targets(args, client.clone(), steward_program_id).await
         }
@@ -262,6 +325,9 @@ async fn main() -> Result<()> {
         Commands::SyncDirectedStakeLamports(args) => {
             command_sync_directed_stake_lamports(args, &client, steward_program_id).await
         }
+        Commands::VerifyInvariants(args) => {
+            command_verify_invariants(args, &client, steward_program_id).await
+        }
         Commands::CloseDirectedStakeTicket(args) => {
             command_close_directed_stake_ticket(args, &client, steward_program_id).await
         }
@@ -274,6 +340,9 @@ async fn main() -> Result<()> {
         Commands::MigrateDirectedToAlgorithmic(args) => {
             command_migrate_directed_to_algorithmic(args, &client, steward_program_id).await
         }
+        Commands::MigrateDirectedTargetsToTickets(args) => {
+            command_migrate_directed_targets_to_tickets(args, &client, steward_program_id).await
+        }
 
         // --- Cranks ---
         Commands::CrankSteward(args) => {
@@ -301,12 +370,21 @@ async fn main() -> Result<()> {
         Commands::CrankRebalanceDirected(args) => {
             command_crank_rebalance_directed(args, &client, steward_program_id).await
         }
+        Commands::CrankDirectedStakeTickets(args) => {
+            command_crank_directed_stake_tickets(args, &client, steward_program_id).await
+        }
+        Commands::CrankDirectedStakeBalanceSamples(args) => {
+            command_crank_directed_stake_balance_samples(args, &client, steward_program_id).await
+        }
         Commands::CrankUpdateStakePool(args) => {
             command_crank_update_stake_pool(args, &client, steward_program_id).await
         }
         Commands::CrankInstantRemoveValidators(args) => {
             command_crank_instant_remove_validators(args, &client, steward_program_id).await
         }
+        Commands::CrankUpdatePreferredValidators(args) => {
+            command_crank_update_preferred_validators(args, &client, steward_program_id).await
+        }
     };
 
     if let Err(e) = result {
@@ -430,6 +430,28 @@ impl VoteStateVersions {
             .map_err(|_| ErrorCode::VoteAccountDataNotValid.into())
     }
 
+    pub fn deserialize_authorized_withdrawer(account_info: &AccountInfo) -> Result<Pubkey> {
+        if account_info.owner != &vote::program::ID.key() {
+            return Err(ConstraintOwner.into());
+        }
+
+        let data = account_info.data.borrow();
+        let authorized_withdrawer_idx = match Self::enum_value_at_index(&data, 0)? {
+            // Enum index + Pubkey + Pubkey + Epoch + CircBuf
+            0 => Self::VOTE_STATE_0_23_5_COMMISSION_INDEX - Self::PUBKEY_BYTES,
+            // Enum index + Pubkey
+            1..=3 => Self::ENUM_LEN_BYTES + Self::PUBKEY_BYTES,
+            _ => return Err(ErrorCode::VoteAccountDataNotValid.into()),
+        };
+        if data.len() < authorized_withdrawer_idx + Self::PUBKEY_BYTES {
+            return Err(ErrorCode::VoteAccountDataNotValid.into());
+        }
+        let authorized_withdrawer_bytes =
+            &data[authorized_withdrawer_idx..authorized_withdrawer_idx + Self::PUBKEY_BYTES];
+        bincode::deserialize(authorized_withdrawer_bytes)
+            .map_err(|_| ErrorCode::VoteAccountDataNotValid.into())
+    }
+
     fn collection_length_at_index(bincode_data: &[u8], index: usize) -> Result<usize> {
         bincode::deserialize::<u64>(&bincode_data[index..index + Self::COLLECTION_LEN_BYTES])
             .map(|x| x as usize)
@@ -1047,4 +1069,72 @@ mod tests {
             VoteStateVersions::deserialize_node_pubkey(&account_current).unwrap();
         assert_eq!(node_pubkey_result, node_pubkey);
     }
+
+    #[test]
+    fn test_deserialize_authorized_withdrawer() {
+        let authorized_withdrawer = Pubkey::new_unique();
+        let mut lamports: u64 = 0;
+        let key = Pubkey::new_unique();
+        let owner = vote::program::ID.key();
+
+        let vote_state_0_23_5 = VoteStateVersions::V0_23_5(Box::new(VoteState0_23_5 {
+            node_pubkey: Pubkey::new_unique(),
+            authorized_voter: Pubkey::new_unique(),
+            authorized_voter_epoch: 0,
+            prior_voters: CircBuf::default(),
+            authorized_withdrawer,
+            commission: 69,
+            votes: VecDeque::new(),
+            root_slot: None,
+            epoch_credits: Vec::new(),
+            last_timestamp: BlockTimestamp::default(),
+        }));
+        let mut ser_0_23_5 = bincode::serialize(&vote_state_0_23_5).unwrap();
+        let account_0_23_5 = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            ser_0_23_5.as_mut_slice(),
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            VoteStateVersions::deserialize_authorized_withdrawer(&account_0_23_5).unwrap(),
+            authorized_withdrawer
+        );
+
+        let mut lamports: u64 = 0;
+        let vote_state_current = VoteStateVersions::Current(Box::new(crate::VoteState {
+            node_pubkey: Pubkey::new_unique(),
+            authorized_withdrawer,
+            inflation_rewards_collector: Pubkey::new_unique(),
+            block_revenue_collector: Pubkey::new_unique(),
+            inflation_rewards_commission_bps: 99,
+            block_revenue_commission_bps: 99,
+            pending_delegator_rewards: 0,
+            bls_pubkey_compressed: None,
+            votes: VecDeque::new(),
+            root_slot: None,
+            authorized_voters: AuthorizedVoters::default(),
+            epoch_credits: Vec::new(),
+            last_timestamp: BlockTimestamp::default(),
+        }));
+        let mut ser_current = bincode::serialize(&vote_state_current).unwrap();
+        let account_current = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            ser_current.as_mut_slice(),
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            VoteStateVersions::deserialize_authorized_withdrawer(&account_current).unwrap(),
+            authorized_withdrawer
+        );
+    }
 }